@@ -8,8 +8,16 @@
 //! # Design
 //!
 //! Alurl is a pure function library: given an input string and an `AliasMap`,
-//! it produces a deterministic output string. No I/O, no side effects, no
-//! external dependencies beyond [`unicode-ident`] for alias name validation.
+//! it produces a deterministic output string. No I/O, no side effects, and
+//! by default no external dependencies beyond [`unicode-ident`] for alias
+//! name validation; the optional `tracing` feature below adds `tracing`.
+//!
+//! # Features
+//!
+//! - `tracing` (off by default) — emits `tracing` spans and events for each resolution step (alias
+//!   hit, fallback, expansion, recursion depth, cycle detection) so embedding services can see why
+//!   a URL resolved the way it did. Diagnostics only: resolution results are identical with the
+//!   feature on or off.
 //!
 //! # Examples
 //!
@@ -23,8 +31,18 @@
 //! assert_eq!(result.url(), "github.com/owner/repo");
 //! ```
 
+use std::borrow::Cow;
 use std::collections::HashMap;
 
+/// Forward to a `tracing` event macro when the `tracing` feature is enabled,
+/// compile to nothing otherwise.
+macro_rules! trace_event {
+    ($level:ident, $($arg:tt)+) => {
+        #[cfg(feature = "tracing")]
+        tracing::$level!($($arg)+);
+    };
+}
+
 mod parse;
 
 // ============================================================================
@@ -39,9 +57,38 @@ mod parse;
 ///
 /// All resolution logic (lookup, recursive expansion, cycle detection) is
 /// owned by this type via [`AliasMap::resolve`].
+///
+/// A catch-all entry keyed by [`AliasMap::FALLBACK`] (`*`) is consulted when
+/// a `+name` has no explicit entry. Its value may contain the
+/// [`AliasMap::FALLBACK_NAME`] placeholder (`{name}`), which is replaced with
+/// the unknown alias name — e.g. `* = "proxy.example.com/{name}"` routes
+/// `+anything/repo` to `proxy.example.com/anything/repo`.
 #[derive(Debug, Clone)]
 pub struct AliasMap(HashMap<String, String>);
 
+/// Conflict-detecting builder for an [`AliasMap`].
+///
+/// [`AliasMap::insert`] is last-write-wins, which silently hides merge bugs
+/// when aliases are assembled from several configuration layers. The
+/// builder instead records every distinct value seen per alias name and
+/// [`build`](AliasMapBuilder::build) fails with the complete list of
+/// conflicts. Re-defining an alias with an identical value is not a
+/// conflict.
+#[derive(Debug, Clone, Default)]
+pub struct AliasMapBuilder {
+    /// Distinct values per alias name, in insertion order.
+    entries: HashMap<String, Vec<String>>,
+}
+
+/// A single alias defined more than once with different values.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AliasConflict {
+    /// The alias name that was defined more than once.
+    pub name: String,
+    /// Every distinct value given for the alias, in insertion order.
+    pub values: Vec<String>,
+}
+
 /// Result of alias resolution.
 ///
 /// Either the input contained a `+`-prefixed alias at a valid host position
@@ -76,6 +123,13 @@ pub enum ResolveError {
     },
 }
 
+/// Error returned by [`AliasMapBuilder::build`] when aliases conflict.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConflictError {
+    /// Every conflicting alias, sorted by name.
+    pub conflicts: Vec<AliasConflict>,
+}
+
 // ============================================================================
 // Traits
 // ============================================================================
@@ -112,11 +166,24 @@ impl AliasedUrl {
 // ============================================================================
 
 impl AliasMap {
+    /// Key of the catch-all alias, consulted when a `+name` has no explicit
+    /// entry. Not a valid UAX #31 identifier, so it can never be typed as
+    /// `+*` by a user.
+    pub const FALLBACK: &'static str = "*";
+    /// Placeholder in the fallback value that is replaced with the unknown
+    /// alias name.
+    pub const FALLBACK_NAME: &'static str = "{name}";
+
     /// Creates an empty alias map.
     pub fn new() -> Self {
         Self(HashMap::new())
     }
 
+    /// Creates a conflict-detecting [`AliasMapBuilder`].
+    pub fn builder() -> AliasMapBuilder {
+        AliasMapBuilder::default()
+    }
+
     /// Creates an alias map with pre-allocated capacity.
     pub fn with_capacity(capacity: usize) -> Self {
         Self(HashMap::with_capacity(capacity))
@@ -127,6 +194,15 @@ impl AliasMap {
         self.0.insert(name.into(), value.into());
     }
 
+    /// Sets the catch-all value used when a `+name` has no explicit entry.
+    ///
+    /// Equivalent to inserting under [`AliasMap::FALLBACK`]. Any
+    /// [`AliasMap::FALLBACK_NAME`] placeholder in `value` is replaced with the
+    /// unknown alias name at resolution time.
+    pub fn set_fallback(&mut self, value: impl Into<String>) {
+        self.insert(Self::FALLBACK, value);
+    }
+
     /// Resolves aliases in the input string.
     ///
     /// Detects a `+`-prefixed alias at a valid host position, expands it
@@ -138,18 +214,26 @@ impl AliasMap {
     ///
     /// # Errors
     ///
-    /// - [`ResolveError::AliasNotFound`] — `+` at host position but alias name not in map.
+    /// - [`ResolveError::AliasNotFound`] — `+` at host position but alias name not in map and no
+    ///   fallback applies.
     /// - [`ResolveError::InvalidAliasName`] — alias name fails UAX #31.
     /// - [`ResolveError::CycleDetected`] — recursive resolution loops.
     // @spec-compliance[sigil-required]
     // Mechanism: Parses input using the `parse::classify` helper to require a '+' prefix at the
     // host position for alias detection, returning Raw if absent. Verified-By:
     // alurl/src/tests.rs:sigil_present_bare
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "alurl::resolve", level = "debug", skip(self), ret, err)
+    )]
     pub fn resolve(&self, input: &str) -> Result<AliasedUrl, ResolveError> {
         let classified = parse::classify(input)?;
 
         match classified {
-            parse::Classification::Raw => Ok(AliasedUrl::Raw(input.to_string())),
+            parse::Classification::Raw => {
+                trace_event!(trace, "no alias at host position");
+                Ok(AliasedUrl::Raw(input.to_string()))
+            },
             parse::Classification::Aliased {
                 prefix,
                 alias_name,
@@ -157,24 +241,70 @@ impl AliasMap {
             } => {
                 let original_alias = alias_name.to_string();
                 let mut chain = vec![original_alias.clone()];
+                let mut fallback_used = false;
 
-                let value = self
-                    .0
-                    .get(alias_name)
-                    .ok_or_else(|| ResolveError::AliasNotFound(alias_name.to_string()))?;
+                let value = self.lookup(alias_name, &mut fallback_used)?;
 
-                let expanded = reconstruct(prefix, value, suffix);
-                self.resolve_recursive(&expanded, &original_alias, &mut chain)
+                let expanded = reconstruct(prefix, &value, suffix);
+                trace_event!(trace, depth = 1, %expanded, "expanded");
+                self.resolve_recursive(&expanded, &original_alias, &mut chain, &mut fallback_used)
             },
         }
     }
 
+    /// Reverse lookup: the shortest `+alias` spelling of `url`, if any
+    /// alias covers its host.
+    ///
+    /// An alias covers `url` when its fully expanded value appears at the
+    /// host position and is followed by `/`, `:` or the end of input; the
+    /// prefix (scheme, credentials) and suffix are kept. Every candidate is
+    /// checked by resolving it back to exactly `url`, so the result always
+    /// round-trips. Ties on length go to the alphabetically first alias
+    /// name; the fallback entry is never used.
+    ///
+    /// Returns `None` when no alias applies, including for inputs that
+    /// already contain one.
+    #[must_use]
+    pub fn compress(&self, url: &str) -> Option<String> {
+        if !matches!(parse::classify(url), Ok(parse::Classification::Raw)) {
+            return None;
+        }
+        let host = parse::host_position(url)?;
+        let (prefix, rest) = url.split_at(host);
+
+        let mut best: Option<(&str, String)> = None;
+        for name in self.0.keys().filter(|name| *name != Self::FALLBACK) {
+            let Ok(AliasedUrl::Expanded { url: value, .. }) = self.resolve(&format!("+{name}"))
+            else {
+                continue;
+            };
+            let Some(after) = rest.strip_prefix(value.as_str()) else {
+                continue;
+            };
+            if !(after.is_empty() || after.starts_with(['/', ':'])) {
+                continue;
+            }
+            let candidate = format!("{prefix}+{name}{after}");
+            if self.resolve(&candidate).ok().as_ref().map(AliasedUrl::url) != Some(url) {
+                continue;
+            }
+            let better = best.as_ref().is_none_or(|(best_name, best_url)| {
+                (candidate.len(), name.as_str()) < (best_url.len(), *best_name)
+            });
+            if better {
+                best = Some((name, candidate));
+            }
+        }
+        best.map(|(_, compressed)| compressed)
+    }
+
     /// Recursive resolution with cycle detection.
     fn resolve_recursive(
         &self,
         input: &str,
         original_alias: &str,
         chain: &mut Vec<String>,
+        fallback_used: &mut bool,
     ) -> Result<AliasedUrl, ResolveError> {
         let classified = parse::classify(input)?;
 
@@ -194,19 +324,48 @@ impl AliasMap {
                 // Verified-By: alurl/src/tests.rs:cycle_detected_two_aliases
                 if chain.iter().any(|n| n == alias_name) {
                     chain.push(alias_name.to_string());
+                    trace_event!(debug, ?chain, "alias cycle detected");
                     return Err(ResolveError::CycleDetected {
                         chain: chain.clone(),
                     });
                 }
                 chain.push(alias_name.to_string());
+                trace_event!(trace, depth = chain.len(), alias = alias_name, "recursing");
 
-                let value = self
-                    .0
-                    .get(alias_name)
-                    .ok_or_else(|| ResolveError::AliasNotFound(alias_name.to_string()))?;
+                let value = self.lookup(alias_name, fallback_used)?;
 
-                let expanded = reconstruct(prefix, value, suffix);
-                self.resolve_recursive(&expanded, original_alias, chain)
+                let expanded = reconstruct(prefix, &value, suffix);
+                trace_event!(trace, depth = chain.len(), %expanded, "expanded");
+                self.resolve_recursive(&expanded, original_alias, chain, fallback_used)
+            },
+        }
+    }
+
+    /// Look up an alias value, falling back to the catch-all entry.
+    ///
+    /// The fallback applies at most once per resolution chain: a fallback
+    /// value that expands to yet another unknown alias would otherwise mint
+    /// a fresh name on every step and never revisit one, escaping cycle
+    /// detection.
+    // @spec-compliance[fallback-alias]
+    // Mechanism: Explicit entries win; the `*` entry is templated with the unknown name and
+    // consumed at most once per chain via `fallback_used`. Verified-By:
+    // alurl/src/tests.rs:fallback_applies_once_per_chain
+    fn lookup(&self, name: &str, fallback_used: &mut bool) -> Result<Cow<'_, str>, ResolveError> {
+        if let Some(value) = self.0.get(name) {
+            trace_event!(debug, alias = name, %value, "alias hit");
+            return Ok(Cow::Borrowed(value));
+        }
+        match self.0.get(Self::FALLBACK) {
+            Some(template) if !*fallback_used => {
+                *fallback_used = true;
+                let value = template.replace(Self::FALLBACK_NAME, name);
+                trace_event!(debug, alias = name, %value, "fallback alias applied");
+                Ok(Cow::Owned(value))
+            },
+            _ => {
+                trace_event!(debug, alias = name, "alias not found");
+                Err(ResolveError::AliasNotFound(name.to_string()))
             },
         }
     }
@@ -238,6 +397,92 @@ where
     }
 }
 
+// ============================================================================
+// Impls — AliasMapBuilder
+// ============================================================================
+
+impl AliasMapBuilder {
+    /// Creates an empty builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records an alias definition.
+    ///
+    /// Never fails on its own; conflicts are collected and reported together
+    /// by [`build`](AliasMapBuilder::build).
+    pub fn insert(&mut self, name: impl Into<String>, value: impl Into<String>) -> &mut Self {
+        let value = value.into();
+        let values = self.entries.entry(name.into()).or_default();
+        if !values.contains(&value) {
+            values.push(value);
+        }
+        self
+    }
+
+    /// Records every definition from an iterator of `(name, value)` pairs.
+    pub fn extend<I, S1, S2>(&mut self, iter: I) -> &mut Self
+    where
+        I: IntoIterator<Item = (S1, S2)>,
+        S1: Into<String>,
+        S2: Into<String>,
+    {
+        for (name, value) in iter {
+            self.insert(name, value);
+        }
+        self
+    }
+
+    /// Builds the [`AliasMap`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConflictError`] listing every alias that was defined with
+    /// more than one distinct value.
+    // @spec-compliance[no-silent-override]
+    // Mechanism: Distinct values are accumulated per name; build fails with all conflicts sorted
+    // by name rather than keeping the last write. Verified-By:
+    // alurl/src/tests.rs:builder_reports_all_conflicts
+    pub fn build(&self) -> Result<AliasMap, ConflictError> {
+        let mut conflicts: Vec<AliasConflict> = self
+            .entries
+            .iter()
+            .filter(|(_, values)| values.len() > 1)
+            .map(|(name, values)| AliasConflict {
+                name: name.clone(),
+                values: values.clone(),
+            })
+            .collect();
+
+        if !conflicts.is_empty() {
+            conflicts.sort_by(|a, b| a.name.cmp(&b.name));
+            return Err(ConflictError { conflicts });
+        }
+
+        Ok(self
+            .entries
+            .iter()
+            .map(|(name, values)| (name.clone(), values[0].clone()))
+            .collect())
+    }
+}
+
+// ============================================================================
+// Impls — ConflictError
+// ============================================================================
+
+impl std::fmt::Display for ConflictError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "conflicting alias definitions:")?;
+        for conflict in &self.conflicts {
+            write!(f, " {} = [{}];", conflict.name, conflict.values.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ConflictError {}
+
 // ============================================================================
 // Impls — ResolveError
 // ============================================================================
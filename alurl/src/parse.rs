@@ -32,13 +32,14 @@ pub(crate) enum Classification<'a> {
 /// Classify an input string for alias presence.
 ///
 /// Implements the host position detection algorithm:
+/// 0. Windows drive-letter and UNC paths have no host position; they are raw.
 /// 1. Check for scheme (`://`) and skip past it.
 /// 2. Determine authority boundary (first `/` for scheme URLs, first `/` or `:` for bare/SCP).
 /// 3. Find last `@` within authority to skip credentials.
 /// 4. Check for `+` at the resulting host position.
 /// 5. If `+` found, extract and validate the alias name (UAX #31).
 pub(crate) fn classify(input: &str) -> Result<Classification<'_>, ResolveError> {
-    if input.is_empty() {
+    if input.is_empty() || is_windows_path(input) {
         return Ok(Classification::Raw);
     }
 
@@ -75,6 +76,12 @@ pub(crate) fn classify(input: &str) -> Result<Classification<'_>, ResolveError>
     })
 }
 
+/// The host position of `input`, or `None` for a Windows local path,
+/// which has none.
+pub(crate) fn host_position(input: &str) -> Option<usize> {
+    (!is_windows_path(input)).then(|| find_host_position(input))
+}
+
 /// Locate the host position in an input string.
 ///
 /// Per spec `[host-position-only]`:
@@ -106,6 +113,20 @@ fn find_host_position(input: &str) -> usize {
     }
 }
 
+/// Whether `input` is a Windows local path: a drive letter followed by a
+/// separator (`C:\`, `C:/`) or a UNC path (`\\server\share`).
+///
+/// Without this check `C:\atoms` reads as an SCP-style `host:path` with
+/// host `C`, and backslashes are never authority boundaries.
+fn is_windows_path(input: &str) -> bool {
+    let bytes = input.as_bytes();
+    let drive = bytes.len() >= 3
+        && bytes[0].is_ascii_alphabetic()
+        && bytes[1] == b':'
+        && matches!(bytes[2], b'\\' | b'/');
+    drive || input.starts_with(r"\\")
+}
+
 /// Find the end of a valid URI scheme (`://`).
 ///
 /// Returns `(position_after_separator, true)` if a valid scheme is found,
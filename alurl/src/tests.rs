@@ -1,4 +1,4 @@
-//! Tests covering all 19 normative spec constraints.
+//! Tests covering all 21 normative spec constraints.
 //!
 //! Test vectors are derived from the resolution examples table in
 //! `docs/specs/aliased-url-resolution.md`.
@@ -73,6 +73,22 @@ fn plus_in_credentials_not_alias() {
     );
 }
 
+#[test]
+fn windows_drive_path_raw() {
+    let map = aliases(&[("gh", "github.com")]);
+    for input in [r"C:\atoms\set", "d:/atoms/set", r"C:\Users\me@+gh\set"] {
+        assert_eq!(map.resolve(input).unwrap(), AliasedUrl::Raw(input.into()));
+    }
+}
+
+#[test]
+fn windows_unc_path_raw() {
+    let map = aliases(&[("gh", "github.com")]);
+    for input in [r"\\server\share\atoms", r"\\user@+gh\share"] {
+        assert_eq!(map.resolve(input).unwrap(), AliasedUrl::Raw(input.into()));
+    }
+}
+
 // ============================================================================
 // [alias-name-validated]: UAX #31 validation, InvalidAliasName error
 // ============================================================================
@@ -231,6 +247,17 @@ fn raw_preserves_exact_input() {
     assert_eq!(result, AliasedUrl::Raw(input.into()));
 }
 
+#[test]
+fn unicode_hosts_pass_through() {
+    let map = aliases(&[("de", "bücher.example")]);
+    let input = "https://bücher.example/repo";
+    assert_eq!(map.resolve(input).unwrap(), AliasedUrl::Raw(input.into()));
+    assert_eq!(
+        map.resolve("git@+de:repo").unwrap().url(),
+        "git@bücher.example:repo"
+    );
+}
+
 // ============================================================================
 // [expanded-preserves-alias]: original alias name preserved
 // ============================================================================
@@ -311,6 +338,133 @@ fn error_cycle_includes_chain() {
     }
 }
 
+// ============================================================================
+// [fallback-alias]: `*` entry catches unknown aliases, at most once per chain
+// ============================================================================
+
+#[test]
+fn fallback_used_for_unknown_alias() {
+    let map = aliases(&[("*", "proxy.example.com/{name}")]);
+    let result = map.resolve("+anything/owner/repo").unwrap();
+    assert_eq!(
+        result,
+        AliasedUrl::Expanded {
+            alias: "anything".into(),
+            url: "proxy.example.com/anything/owner/repo".into(),
+        }
+    );
+}
+
+#[test]
+fn fallback_explicit_entry_wins() {
+    let mut map = aliases(&[("gh", "github.com")]);
+    map.set_fallback("proxy.example.com/{name}");
+    assert_eq!(map.resolve("+gh/repo").unwrap().url(), "github.com/repo");
+    assert_eq!(
+        map.resolve("+gl/repo").unwrap().url(),
+        "proxy.example.com/gl/repo"
+    );
+}
+
+#[test]
+fn fallback_without_placeholder() {
+    let mut map = AliasMap::new();
+    map.set_fallback("mirror.example.com");
+    assert_eq!(
+        map.resolve("git@+any:owner/repo").unwrap().url(),
+        "git@mirror.example.com:owner/repo"
+    );
+}
+
+#[test]
+fn fallback_recurses_into_known_alias() {
+    let map = aliases(&[("*", "+gh/{name}"), ("gh", "github.com")]);
+    assert_eq!(
+        map.resolve("+myorg/repo").unwrap().url(),
+        "github.com/myorg/repo"
+    );
+}
+
+#[test]
+fn fallback_applies_once_per_chain() {
+    // Each fallback step would mint a fresh name (`xa`, `xxa`, ...) that
+    // cycle detection never sees twice — the second miss must error.
+    let map = aliases(&[("*", "+x{name}")]);
+    match map.resolve("+a") {
+        Err(ResolveError::AliasNotFound(name)) => assert_eq!(name, "xa"),
+        other => panic!("expected AliasNotFound, got {other:?}"),
+    }
+}
+
+#[test]
+fn fallback_does_not_bypass_name_validation() {
+    let map = aliases(&[("*", "proxy.example.com/{name}")]);
+    assert!(matches!(
+        map.resolve("+123/repo"),
+        Err(ResolveError::InvalidAliasName(_))
+    ));
+    assert!(matches!(
+        map.resolve("+*/repo"),
+        Err(ResolveError::InvalidAliasName(_))
+    ));
+}
+
+// ============================================================================
+// [no-silent-override]: builder rejects conflicting redefinitions
+// ============================================================================
+
+#[test]
+fn builder_builds_without_conflicts() {
+    let map = AliasMap::builder()
+        .insert("gh", "github.com")
+        .insert("work", "+gh/myorg")
+        .build()
+        .unwrap();
+    assert_eq!(
+        map.resolve("+work/repo").unwrap().url(),
+        "github.com/myorg/repo"
+    );
+}
+
+#[test]
+fn builder_identical_redefinition_is_not_conflict() {
+    let mut builder = AliasMapBuilder::new();
+    builder.insert("gh", "github.com");
+    builder.insert("gh", "github.com");
+    let map = builder.build().unwrap();
+    assert_eq!(map.resolve("+gh").unwrap().url(), "github.com");
+}
+
+#[test]
+fn builder_reports_all_conflicts() {
+    let mut builder = AliasMapBuilder::new();
+    builder.extend([
+        ("gl", "gitlab.com"),
+        ("gh", "github.com"),
+        ("ok", "example.com"),
+        ("gh", "github.example.com"),
+        ("gl", "gitlab.example.com"),
+        ("gh", "github.com"),
+    ]);
+    let err = builder.build().unwrap_err();
+    assert_eq!(
+        err.conflicts,
+        vec![
+            AliasConflict {
+                name: "gh".into(),
+                values: vec!["github.com".into(), "github.example.com".into()],
+            },
+            AliasConflict {
+                name: "gl".into(),
+                values: vec!["gitlab.com".into(), "gitlab.example.com".into()],
+            },
+        ]
+    );
+    let msg = err.to_string();
+    assert!(msg.contains("gh = [github.com, github.example.com]"));
+    assert!(msg.contains("gl = [gitlab.com, gitlab.example.com]"));
+}
+
 // ============================================================================
 // Spec resolution examples table (integration)
 // ============================================================================
@@ -531,3 +685,43 @@ fn self_referencing_alias_detected() {
         other => panic!("expected CycleDetected, got {other:?}"),
     }
 }
+
+// ============================================================================
+// Reverse lookup
+// ============================================================================
+
+#[test]
+fn compress_picks_shortest_alias() {
+    let map = aliases(&[
+        ("gh", "github.com"),
+        ("github", "github.com"),
+        ("work", "+gh/myorg"),
+    ]);
+    assert_eq!(
+        map.compress("github.com/owner/repo").as_deref(),
+        Some("+gh/owner/repo")
+    );
+    assert_eq!(
+        map.compress("github.com/myorg/project").as_deref(),
+        Some("+work/project")
+    );
+    assert_eq!(
+        map.compress("git@github.com:owner/repo").as_deref(),
+        Some("git@+gh:owner/repo")
+    );
+    assert_eq!(
+        map.compress("https://github.com/owner/repo").as_deref(),
+        Some("https://+gh/owner/repo")
+    );
+}
+
+#[test]
+fn compress_requires_a_whole_host() {
+    let mut map = aliases(&[("gh", "github.com")]);
+    map.set_fallback("proxy.example.com/{name}");
+    assert_eq!(map.compress("github.company.com/repo"), None);
+    assert_eq!(map.compress("proxy.example.com/foo/repo"), None);
+    assert_eq!(map.compress("example.com/github.com/repo"), None);
+    assert_eq!(map.compress("+gh/owner/repo"), None);
+    assert_eq!(map.compress(r"C:\github.com"), None);
+}
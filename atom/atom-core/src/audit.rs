@@ -0,0 +1,276 @@
+//! An audit trail of registry writes: [`AuditSink`] and
+//! [`AuditedRegistry`].
+
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::{
+    AtomContent, AtomId, AtomRegistry, AtomSource, Changes, Checkpoint, ContentEntry, Cursor, Czd,
+    DiscoverPage, OwnerRef, RawVersion, VersionMatch, VersionScheme,
+};
+
+/// A registry write, as an [`AuditedRegistry`] reports it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuditRecord {
+    /// When the write finished, in seconds since the Unix epoch.
+    pub at: u64,
+    /// Who asked for it, as the registry's host identified them.
+    pub caller: String,
+    /// What was asked for.
+    pub op: AuditOp,
+    /// The czd of the payload the write signed; `None` if it failed, and
+    /// for a publish, whose czd the registry does not return.
+    pub czd: Option<Czd>,
+    /// Why the write failed, if it did.
+    pub error: Option<String>,
+}
+
+/// The write an [`AuditRecord`] is about, with its arguments.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AuditOp {
+    /// [`AtomRegistry::claim`].
+    Claim {
+        /// The atom claimed.
+        id: AtomId,
+        /// The owner named.
+        owner: OwnerRef,
+    },
+    /// [`AtomRegistry::publish`].
+    Publish {
+        /// The atom.
+        id: AtomId,
+        /// The version published.
+        version: RawVersion,
+        /// The czd of the authorizing claim.
+        claim: Czd,
+        /// The published content's digest.
+        dig: Vec<u8>,
+    },
+    /// [`AtomRegistry::yank`].
+    Yank {
+        /// The atom.
+        id: AtomId,
+        /// The version yanked.
+        version: RawVersion,
+        /// The czd of the claim the version chains to.
+        claim: Czd,
+    },
+    /// [`AtomRegistry::charter`]: founding an atom-set, or with a `prior`,
+    /// transferring it.
+    Charter {
+        /// The owners named.
+        owner: Vec<OwnerRef>,
+        /// The charter succeeded, for a transfer.
+        prior: Option<Czd>,
+    },
+}
+
+/// Where an [`AuditedRegistry`] sends its records.
+///
+/// Called once per write, after the write, on the writing thread. Recording
+/// cannot fail the write, which has already happened: a sink that must not
+/// lose records makes itself durable.
+pub trait AuditSink: Send + Sync + 'static {
+    /// Keep `record`.
+    fn record(&self, record: &AuditRecord);
+}
+
+/// An in-memory audit log.
+impl AuditSink for Mutex<Vec<AuditRecord>> {
+    fn record(&self, record: &AuditRecord) {
+        self.lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(record.clone());
+    }
+}
+
+impl<A: AuditSink + ?Sized> AuditSink for Arc<A> {
+    fn record(&self, record: &AuditRecord) {
+        (**self).record(record);
+    }
+}
+
+/// A registry that reports every claim, publish, yank and charter to an
+/// [`AuditSink`], successful or not.
+///
+/// Reads pass straight through. The caller identity is fixed per wrapper:
+/// a hosted registry serving many callers wraps its registry once per
+/// authenticated caller.
+///
+/// ```
+/// use std::sync::Mutex;
+///
+/// use atom_core::{AtomRegistry, AuditRecord, AuditedRegistry};
+///
+/// fn audited<R: AtomRegistry>(registry: R) -> impl AtomRegistry {
+///     AuditedRegistry::new(registry, Mutex::new(Vec::<AuditRecord>::new()), "ci")
+/// }
+/// ```
+#[derive(Debug)]
+pub struct AuditedRegistry<R, A> {
+    registry: R,
+    sink: A,
+    caller: String,
+}
+
+impl<R, A: AuditSink> AuditedRegistry<R, A> {
+    /// Report `registry`'s writes, made for `caller`, to `sink`.
+    pub fn new(registry: R, sink: A, caller: impl Into<String>) -> Self {
+        Self {
+            registry,
+            sink,
+            caller: caller.into(),
+        }
+    }
+
+    /// The registry behind the wrapper.
+    pub fn registry(&self) -> &R {
+        &self.registry
+    }
+
+    /// The sink records go to.
+    pub fn sink(&self) -> &A {
+        &self.sink
+    }
+
+    /// The registry, unwrapped.
+    pub fn into_inner(self) -> R {
+        self.registry
+    }
+
+    fn report<T, E: std::fmt::Display>(
+        &self,
+        op: AuditOp,
+        result: Result<T, E>,
+        czd: impl FnOnce(&T) -> Option<Czd>,
+    ) -> Result<T, E> {
+        let at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        let (czd, error) = match &result {
+            Ok(value) => (czd(value), None),
+            Err(e) => (None, Some(e.to_string())),
+        };
+        self.sink.record(&AuditRecord {
+            at,
+            caller: self.caller.clone(),
+            op,
+            czd,
+            error,
+        });
+        result
+    }
+}
+
+impl<R: AtomSource, A: AuditSink> AtomSource for AuditedRegistry<R, A> {
+    type Entry = R::Entry;
+    type Error = R::Error;
+
+    async fn resolve(&self, id: &AtomId) -> Result<Option<Self::Entry>, Self::Error> {
+        self.registry.resolve(id).await
+    }
+
+    async fn resolve_many(&self, ids: &[AtomId]) -> Result<Vec<Option<Self::Entry>>, Self::Error>
+    where
+        Self::Entry: Send,
+    {
+        self.registry.resolve_many(ids).await
+    }
+
+    async fn versions(&self, id: &AtomId) -> Result<Vec<RawVersion>, Self::Error> {
+        self.registry.versions(id).await
+    }
+
+    async fn resolve_version<V>(
+        &self,
+        id: &AtomId,
+        scheme: &V,
+        req: &V::Requirement,
+    ) -> Result<Option<VersionMatch<Self::Entry>>, Self::Error>
+    where
+        V: VersionScheme + Sync,
+        V::Requirement: Sync,
+    {
+        self.registry.resolve_version(id, scheme, req).await
+    }
+
+    async fn discover(&self, query: &str) -> Result<Vec<AtomId>, Self::Error> {
+        self.registry.discover(query).await
+    }
+
+    async fn discover_page(
+        &self,
+        query: &str,
+        cursor: Option<&Cursor>,
+        limit: usize,
+    ) -> Result<DiscoverPage, Self::Error> {
+        self.registry.discover_page(query, cursor, limit).await
+    }
+
+    async fn since(&self, checkpoint: Option<&Checkpoint>) -> Result<Changes, Self::Error> {
+        self.registry.since(checkpoint).await
+    }
+}
+
+impl<R: AtomContent, A: AuditSink> AtomContent for AuditedRegistry<R, A> {
+    async fn content(
+        &self,
+        id: &AtomId,
+        dig: &[u8],
+    ) -> Result<Option<Vec<ContentEntry>>, Self::Error> {
+        self.registry.content(id, dig).await
+    }
+}
+
+impl<R: AtomRegistry, A: AuditSink> AtomRegistry for AuditedRegistry<R, A> {
+    fn claim(&self, id: &AtomId, owner: &OwnerRef) -> Result<Czd, Self::Error> {
+        let op = AuditOp::Claim {
+            id: id.clone(),
+            owner: owner.clone(),
+        };
+        self.report(op, self.registry.claim(id, owner), |czd| Some(czd.clone()))
+    }
+
+    fn publish(
+        &self,
+        id: &AtomId,
+        claim: &Czd,
+        version: &RawVersion,
+        dig: &[u8],
+        src: &[u8],
+        path: &str,
+    ) -> Result<(), Self::Error> {
+        let op = AuditOp::Publish {
+            id: id.clone(),
+            version: version.clone(),
+            claim: claim.clone(),
+            dig: dig.to_vec(),
+        };
+        let result = self.registry.publish(id, claim, version, dig, src, path);
+        self.report(op, result, |()| None)
+    }
+
+    fn yank(&self, id: &AtomId, version: &RawVersion, claim: &Czd) -> Result<Czd, Self::Error> {
+        let op = AuditOp::Yank {
+            id: id.clone(),
+            version: version.clone(),
+            claim: claim.clone(),
+        };
+        let result = self.registry.yank(id, version, claim);
+        self.report(op, result, |czd| Some(czd.clone()))
+    }
+
+    fn charter(
+        &self,
+        owner: &[OwnerRef],
+        src: &[u8],
+        prior: Option<&Czd>,
+    ) -> Result<Czd, Self::Error> {
+        let op = AuditOp::Charter {
+            owner: owner.to_vec(),
+            prior: prior.cloned(),
+        };
+        let result = self.registry.charter(owner, src, prior);
+        self.report(op, result, |czd| Some(czd.clone()))
+    }
+}
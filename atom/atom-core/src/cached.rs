@@ -0,0 +1,374 @@
+//! Memoized lookups against a slow source: [`CachedSource`].
+
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+
+use crate::{
+    AtomContent, AtomEntry, AtomId, AtomSource, AtomVersion, Changes, Checkpoint, ContentEntry,
+    Cursor, DiscoverPage, RawVersion, VersionMatch, VersionScheme,
+};
+
+/// A source that remembers its [`resolve`](AtomSource::resolve) and
+/// [`discover`](AtomSource::discover) answers for a while, for tools that
+/// look up the same atoms many times per command.
+///
+/// An answer is reused until it is `ttl` old; an answer that the atom is
+/// not there, until it is [`negative_ttl`](Self::negative_ttl) old, which
+/// defaults to `ttl`. Errors are never cached. Content is always fetched
+/// from the source, since a digest already names it exactly.
+///
+/// [`resolve_many`](AtomSource::resolve_many) answers what it can from the
+/// cache and asks the source for the rest in one batch, caching those
+/// answers too. [`versions`](AtomSource::versions) and
+/// [`resolve_version`](AtomSource::resolve_version) answer from a cached
+/// entry when there is one, and are passed to the source uncached when
+/// there is not. Pages and change lists are never cached:
+/// [`discover_page`](AtomSource::discover_page) and
+/// [`since`](AtomSource::since) always reach the source.
+///
+/// Each cache holds at most [`capacity`](Self::capacity) answers —
+/// unbounded by default. Expired answers are dropped when a cache is full,
+/// and otherwise each time it has doubled since they last were, so caching
+/// an answer costs amortized constant time; if dropping them leaves no
+/// room, the oldest answer is evicted.
+///
+/// A cached answer can be out of date by up to its TTL. A caller that has
+/// just changed the source — published, yanked, ingested — should
+/// [`invalidate`](Self::invalidate) what it changed, or
+/// [`clear`](Self::clear) the cache, rather than wait.
+pub struct CachedSource<S: AtomSource> {
+    source: S,
+    ttl: Duration,
+    negative_ttl: Duration,
+    capacity: usize,
+    resolved: Cache<AtomId, Option<S::Entry>>,
+    discovered: Cache<String, Vec<AtomId>>,
+}
+
+impl<S: AtomSource> CachedSource<S> {
+    /// Cache `source`'s answers for `ttl`.
+    #[must_use]
+    pub fn new(source: S, ttl: Duration) -> Self {
+        Self {
+            source,
+            ttl,
+            negative_ttl: ttl,
+            capacity: usize::MAX,
+            resolved: Cache::default(),
+            discovered: Cache::default(),
+        }
+    }
+
+    /// Cache answers that an atom is not there for `ttl` instead —
+    /// [`Duration::ZERO`] not to cache them at all.
+    #[must_use]
+    pub fn negative_ttl(mut self, ttl: Duration) -> Self {
+        self.negative_ttl = ttl;
+        self
+    }
+
+    /// Hold at most `capacity` [`resolve`](AtomSource::resolve) answers,
+    /// and as many [`discover`](AtomSource::discover) answers — `0` not to
+    /// cache at all.
+    #[must_use]
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    /// The source behind the cache.
+    #[must_use]
+    pub fn source(&self) -> &S {
+        &self.source
+    }
+
+    /// The source, dropping the cache.
+    #[must_use]
+    pub fn into_source(self) -> S {
+        self.source
+    }
+
+    /// Forget the cached [`resolve`](AtomSource::resolve) answer for `id`,
+    /// and every cached [`discover`](AtomSource::discover) answer, since
+    /// the change may have added `id` to the source or removed it.
+    pub fn invalidate(&self, id: &AtomId) {
+        self.resolved.remove(id);
+        self.discovered.clear();
+    }
+
+    /// Forget every cached answer.
+    pub fn clear(&self) {
+        self.resolved.clear();
+        self.discovered.clear();
+    }
+
+    /// How long a [`resolve`](AtomSource::resolve) answer is reused.
+    fn resolved_ttl(&self, entry: &Option<S::Entry>) -> Duration {
+        if entry.is_some() {
+            self.ttl
+        } else {
+            self.negative_ttl
+        }
+    }
+}
+
+impl<S: AtomSource> AtomSource for CachedSource<S>
+where
+    S::Entry: Clone + Send,
+{
+    type Entry = S::Entry;
+    type Error = S::Error;
+
+    async fn resolve(&self, id: &AtomId) -> Result<Option<Self::Entry>, Self::Error> {
+        let ttl = |entry: &Option<S::Entry>| self.resolved_ttl(entry);
+        if let Some(entry) = self.resolved.get(id, ttl) {
+            return Ok(entry);
+        }
+        let entry = self.source.resolve(id).await?;
+        self.resolved
+            .insert(id.clone(), entry.clone(), ttl, self.capacity);
+        Ok(entry)
+    }
+
+    async fn resolve_many(&self, ids: &[AtomId]) -> Result<Vec<Option<Self::Entry>>, Self::Error>
+    where
+        Self::Entry: Send,
+    {
+        let ttl = |entry: &Option<S::Entry>| self.resolved_ttl(entry);
+        let mut entries = Vec::with_capacity(ids.len());
+        let mut missed = Vec::new();
+        for (index, id) in ids.iter().enumerate() {
+            let cached = self.resolved.get(id, ttl);
+            if cached.is_none() {
+                missed.push(index);
+            }
+            entries.push(cached.flatten());
+        }
+        if missed.is_empty() {
+            return Ok(entries);
+        }
+        let asked: Vec<AtomId> = missed.iter().map(|&index| ids[index].clone()).collect();
+        let answers = self.source.resolve_many(&asked).await?;
+        for (index, entry) in missed.into_iter().zip(answers) {
+            self.resolved
+                .insert(ids[index].clone(), entry.clone(), ttl, self.capacity);
+            entries[index] = entry;
+        }
+        Ok(entries)
+    }
+
+    async fn versions(&self, id: &AtomId) -> Result<Vec<RawVersion>, Self::Error> {
+        let ttl = |entry: &Option<S::Entry>| self.resolved_ttl(entry);
+        let Some(entry) = self.resolved.get(id, ttl) else {
+            return self.source.versions(id).await;
+        };
+        Ok(entry
+            .map(|entry| entry.versions().map(|v| v.version().clone()).collect())
+            .unwrap_or_default())
+    }
+
+    async fn resolve_version<V>(
+        &self,
+        id: &AtomId,
+        scheme: &V,
+        req: &V::Requirement,
+    ) -> Result<Option<VersionMatch<Self::Entry>>, Self::Error>
+    where
+        V: VersionScheme + Sync,
+        V::Requirement: Sync,
+    {
+        let ttl = |entry: &Option<S::Entry>| self.resolved_ttl(entry);
+        let Some(entry) = self.resolved.get(id, ttl) else {
+            return self.source.resolve_version(id, scheme, req).await;
+        };
+        Ok(entry.and_then(|entry| VersionMatch::pick(entry, scheme, req)))
+    }
+
+    async fn discover(&self, query: &str) -> Result<Vec<AtomId>, Self::Error> {
+        let ttl = |_: &Vec<AtomId>| self.ttl;
+        if let Some(ids) = self.discovered.get(query, ttl) {
+            return Ok(ids);
+        }
+        let ids = self.source.discover(query).await?;
+        self.discovered
+            .insert(query.to_owned(), ids.clone(), ttl, self.capacity);
+        Ok(ids)
+    }
+
+    async fn discover_page(
+        &self,
+        query: &str,
+        cursor: Option<&Cursor>,
+        limit: usize,
+    ) -> Result<DiscoverPage, Self::Error> {
+        self.source.discover_page(query, cursor, limit).await
+    }
+
+    async fn since(&self, checkpoint: Option<&Checkpoint>) -> Result<Changes, Self::Error> {
+        self.source.since(checkpoint).await
+    }
+}
+
+impl<S: AtomContent> AtomContent for CachedSource<S>
+where
+    S::Entry: Clone + Send,
+{
+    async fn content(
+        &self,
+        id: &AtomId,
+        dig: &[u8],
+    ) -> Result<Option<Vec<ContentEntry>>, Self::Error> {
+        self.source.content(id, dig).await
+    }
+}
+
+impl<S: AtomSource + std::fmt::Debug> std::fmt::Debug for CachedSource<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CachedSource")
+            .field("source", &self.source)
+            .field("ttl", &self.ttl)
+            .field("negative_ttl", &self.negative_ttl)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Answers by key, with when each was fetched.
+struct Cache<K, V>(Mutex<Answers<K, V>>);
+
+struct Answers<K, V> {
+    map: HashMap<K, (Instant, V)>,
+    /// How many answers the last prune kept; the next is due at twice as
+    /// many.
+    kept: usize,
+}
+
+/// The fewest answers a cache with room to spare is pruned at.
+const PRUNE_AT_LEAST: usize = 64;
+
+impl<K, V> Default for Cache<K, V> {
+    fn default() -> Self {
+        Self(Mutex::new(Answers {
+            map: HashMap::new(),
+            kept: 0,
+        }))
+    }
+}
+
+impl<K: Clone + Eq + Hash, V: Clone> Cache<K, V> {
+    /// The answer for `key`, if it is younger than its `ttl`.
+    fn get<Q>(&self, key: &Q, ttl: impl Fn(&V) -> Duration) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        let cache = self.lock();
+        let (at, value) = cache.map.get(key)?;
+        (at.elapsed() < ttl(value)).then(|| value.clone())
+    }
+
+    /// Cache `value` for `key`, first dropping every expired answer if the
+    /// cache is full or due a prune and, if that leaves no room, the
+    /// oldest.
+    fn insert(&self, key: K, value: V, ttl: impl Fn(&V) -> Duration, capacity: usize) {
+        if capacity == 0 {
+            return;
+        }
+        let mut cache = self.lock();
+        let full =
+            |cache: &Answers<K, V>| cache.map.len() >= capacity && !cache.map.contains_key(&key);
+        if full(&cache) || cache.map.len() >= (cache.kept * 2).max(PRUNE_AT_LEAST) {
+            cache.map.retain(|_, (at, value)| at.elapsed() < ttl(value));
+            cache.kept = cache.map.len();
+        }
+        if full(&cache) {
+            let oldest = cache
+                .map
+                .iter()
+                .min_by_key(|(_, (at, _))| *at)
+                .map(|(oldest, _)| oldest.clone());
+            if let Some(oldest) = oldest {
+                cache.map.remove(&oldest);
+            }
+        }
+        cache.map.insert(key, (Instant::now(), value));
+    }
+}
+
+impl<K: Eq + Hash, V> Cache<K, V> {
+    fn remove<Q>(&self, key: &Q)
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.lock().map.remove(key);
+    }
+
+    fn clear(&self) {
+        let mut cache = self.lock();
+        cache.map.clear();
+        cache.kept = 0;
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Answers<K, V>> {
+        // A cache is only ever replaced whole, never left half-written.
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOUR: Duration = Duration::from_secs(3600);
+
+    #[test]
+    fn insert_prunes_expired_answers_when_full_or_due() {
+        let cache: Cache<&str, u32> = Cache::default();
+        let ttl = |&value: &u32| if value == 0 { Duration::ZERO } else { HOUR };
+        cache.insert("stale", 0, ttl, 2);
+        cache.insert("fresh", 1, ttl, 2);
+        cache.insert("newer", 2, ttl, 2);
+        let held = cache.lock();
+        assert!(!held.map.contains_key("stale"), "pruned, not evicted");
+        assert_eq!(held.map.len(), 2);
+        drop(held);
+
+        let cache: Cache<usize, u32> = Cache::default();
+        for key in 0..PRUNE_AT_LEAST {
+            cache.insert(key, 0, ttl, usize::MAX);
+        }
+        assert_eq!(cache.lock().map.len(), PRUNE_AT_LEAST, "not yet due");
+        cache.insert(PRUNE_AT_LEAST, 1, ttl, usize::MAX);
+        assert_eq!(cache.lock().map.len(), 1);
+    }
+
+    #[test]
+    fn insert_past_capacity_evicts_the_oldest() {
+        let cache: Cache<&str, u32> = Cache::default();
+        let ttl = |_: &u32| HOUR;
+        // Fetch times must differ for "oldest" to be well defined.
+        let tick = || std::thread::sleep(Duration::from_millis(2));
+        cache.insert("a", 1, ttl, 2);
+        tick();
+        cache.insert("b", 2, ttl, 2);
+        tick();
+        cache.insert("a", 3, ttl, 2);
+        assert_eq!(cache.lock().map.len(), 2, "replacing a key needs no room");
+
+        tick();
+        cache.insert("c", 4, ttl, 2);
+        assert_eq!(cache.get("a", ttl), Some(3));
+        assert_eq!(cache.get("b", ttl), None);
+        assert_eq!(cache.get("c", ttl), Some(4));
+
+        cache.insert("d", 5, ttl, 0);
+        assert_eq!(cache.get("d", ttl), None);
+    }
+}
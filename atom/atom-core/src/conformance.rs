@@ -0,0 +1,315 @@
+//! The coalgebra laws of `models/publishing-stack-layers.md` §2, as
+//! generic checks a backend's own tests run against it.
+//!
+//! Two implementations of a trait are interchangeable if their
+//! observations agree pointwise; these checks are how a backend shows it
+//! observes like any other, instead of each author re-reading the model.
+//! Each check is `async`, since the traits are — run it on the test's own
+//! executor — and panics, naming the law, at the first violation. A
+//! backend error is a violation too: the laws are about a working
+//! backend.
+//!
+//! ```
+//! use atom_core::conformance::{
+//!     assert_content_laws, assert_source_laws, assert_store_accumulation,
+//! };
+//! use atom_core::{AtomContent, AtomStore};
+//!
+//! async fn conforms(store: &impl AtomStore, upstream: &impl AtomContent) {
+//!     assert_source_laws(upstream).await;
+//!     assert_content_laws(upstream).await;
+//!     assert_store_accumulation(store, upstream).await;
+//! }
+//! ```
+
+use std::fmt::Display;
+use std::io::Read;
+
+use crate::{
+    AtomBlobs, AtomContent, AtomEntry, AtomId, AtomSource, AtomStore, AtomVersion, ContentEntry,
+    Czd, RawVersion,
+};
+
+/// The §2.1 laws of a single source:
+///
+/// - [`discover`](AtomSource::discover) lists each atom once;
+/// - every atom it lists [`resolve`](AtomSource::resolve)s, to an entry with that id, and to the
+///   same versions each time;
+/// - [`versions`](AtomSource::versions) lists exactly the entry's versions, in its order;
+/// - walking [`discover_page`](AtomSource::discover_page) finds what `discover` does, in the same
+///   order;
+/// - [`since`](AtomSource::since) with no checkpoint lists every atom.
+pub async fn assert_source_laws<S: AtomSource>(source: &S) {
+    let ids = ok(source.discover("").await, "discover(\"\")");
+    for (i, id) in ids.iter().enumerate() {
+        assert!(!ids[..i].contains(id), "discover lists {id} more than once");
+    }
+    for id in &ids {
+        let first = resolved(source, id).await;
+        let again = resolved(source, id).await;
+        assert!(first == again, "resolve({id}) answered differently twice");
+        let versions = ok(source.versions(id).await, format_args!("versions({id})"));
+        let listed: Vec<&RawVersion> = first.iter().map(|v| &v.version).collect();
+        assert!(
+            versions.iter().eq(listed),
+            "versions({id}) disagrees with the versions resolve({id}) lists"
+        );
+    }
+    let mut paged = Vec::new();
+    let mut cursor = None;
+    // Every page but the last holds an atom, so there are at most as many
+    // pages as atoms, and one more.
+    for _ in 0..=ids.len() {
+        let (page, next) = ok(
+            source.discover_page("", cursor.as_ref(), 2).await,
+            "discover_page(\"\")",
+        );
+        assert!(
+            page.len() <= 2,
+            "discover_page returned more than its limit"
+        );
+        paged.extend(page);
+        cursor = next;
+        if cursor.is_none() {
+            break;
+        }
+    }
+    assert!(
+        cursor.is_none(),
+        "discover_page never reached its last page"
+    );
+    assert!(paged == ids, "discover_page's pages disagree with discover");
+    let (changed, _) = ok(source.since(None).await, "since(None)");
+    for id in &ids {
+        assert!(changed.contains(id), "since(None) leaves out {id}");
+    }
+}
+
+/// The §2.1a laws of a source's content: every version the source lists
+/// has content, the same tree each time, ordered children before parents.
+/// Run [`assert_source_laws`] for the source's own.
+pub async fn assert_content_laws<C: AtomContent>(source: &C) {
+    for id in ok(source.discover("").await, "discover(\"\")") {
+        for version in resolved(source, &id).await {
+            let tree = content(source, &id, &version).await;
+            let again = content(source, &id, &version).await;
+            assert!(
+                tree == again,
+                "content of {id} {} answered differently twice",
+                version.version
+            );
+            for (i, entry) in tree.iter().enumerate() {
+                let ContentEntry::Directory { path: dir } = entry else {
+                    continue;
+                };
+                let late = tree[i + 1..].iter().find(|child| {
+                    path_of(child)
+                        .strip_prefix(dir.as_str())
+                        .is_some_and(|rest| rest.starts_with('/'))
+                });
+                assert!(
+                    late.is_none(),
+                    "content of {id} {} lists {} after its parent {dir}",
+                    version.version,
+                    late.map_or("", path_of)
+                );
+            }
+        }
+    }
+}
+
+/// §2.1 bisimulation: `a` and `b` [`discover`](AtomSource::discover) the
+/// same atoms, and [`resolve`](AtomSource::resolve) each to the same
+/// versions — with the same content digests, czds and yanks.
+pub async fn assert_sources_bisimilar<A: AtomSource, B: AtomSource>(a: &A, b: &B) {
+    let mut a_ids = ok(a.discover("").await, "discover(\"\") on the first source");
+    let mut b_ids = ok(b.discover("").await, "discover(\"\") on the second source");
+    a_ids.sort_by_key(ToString::to_string);
+    b_ids.sort_by_key(ToString::to_string);
+    assert!(a_ids == b_ids, "the sources discover different atoms");
+    for id in &a_ids {
+        assert!(
+            resolved(a, id).await == resolved(b, id).await,
+            "the sources resolve {id} differently"
+        );
+    }
+}
+
+/// §2.1a bisimulation: [`assert_sources_bisimilar`], and the same tree for
+/// every version's content.
+pub async fn assert_content_bisimilar<A: AtomContent, B: AtomContent>(a: &A, b: &B) {
+    assert_sources_bisimilar(a, b).await;
+    for id in ok(a.discover("").await, "discover(\"\")") {
+        for version in resolved(a, &id).await {
+            assert!(
+                same_tree(
+                    content(a, &id, &version).await,
+                    content(b, &id, &version).await
+                ),
+                "the sources' content of {id} {} differs",
+                version.version
+            );
+        }
+    }
+}
+
+/// The §2.3 ingest homomorphism: after `store` ingests `source`,
+///
+/// - every version `store` had before is still there (accumulation);
+/// - every version of `source` is there, with the same content digest and czd (⊇),
+///   [`contains`](AtomStore::contains) reports its atom, and its content is the same tree;
+/// - ingesting `source` again stores nothing and changes nothing.
+pub async fn assert_store_accumulation<S: AtomStore, T: AtomContent>(store: &S, source: &T) {
+    let before = observe(store).await;
+    ok(store.ingest(source).await, "ingest");
+    let after = observe(store).await;
+    for (id, versions) in &before {
+        let kept = versions_of(&after, id);
+        for version in versions {
+            assert!(
+                kept.iter().any(|v| v.same_version(version)),
+                "ingest lost {id} {} from the store",
+                version.version
+            );
+        }
+    }
+    for (id, versions) in observe(source).await {
+        assert!(
+            ok(store.contains(&id).await, format_args!("contains({id})")),
+            "the store does not contain {id} after ingesting it"
+        );
+        let stored = versions_of(&after, &id);
+        for version in &versions {
+            assert!(
+                stored.iter().any(|v| v.same_version(version)),
+                "the store lacks {id} {}, with its digest and czd, after ingesting it",
+                version.version
+            );
+            assert!(
+                same_tree(
+                    content(store, &id, version).await,
+                    content(source, &id, version).await
+                ),
+                "the store's content of {id} {} differs from the source's",
+                version.version
+            );
+        }
+    }
+    let counts = ok(
+        store.ingest_with_progress(source, &mut ()).await,
+        "a second ingest",
+    );
+    assert!(
+        counts.stored == 0,
+        "a second ingest of the same source stored versions"
+    );
+    assert!(
+        observe(store).await == after,
+        "a second ingest of the same source changed the store"
+    );
+}
+
+/// The §2.5 laws of a blob store, checked with `blob`: putting it gives
+/// the same digest twice, and the digest then names exactly its bytes.
+pub async fn assert_blob_round_trip<B: AtomBlobs>(blobs: &B, blob: &[u8]) {
+    let dig = ok(blobs.put_blob(&mut &blob[..]).await, "put_blob");
+    let again = ok(blobs.put_blob(&mut &blob[..]).await, "a second put_blob");
+    assert!(
+        dig == again,
+        "putting the same bytes twice gave two digests"
+    );
+    assert!(
+        ok(blobs.has_blob(&dig).await, "has_blob"),
+        "has_blob denies a blob just put"
+    );
+    let Some(mut reader) = ok(blobs.get_blob(&dig).await, "get_blob") else {
+        panic!("get_blob found nothing under a digest put_blob returned");
+    };
+    let mut bytes = Vec::new();
+    ok(reader.read_to_end(&mut bytes), "reading a blob");
+    assert!(
+        bytes == blob,
+        "get_blob gave back other bytes than were put"
+    );
+}
+
+/// What a source says about one version.
+#[derive(Debug, PartialEq)]
+struct Observed {
+    version: RawVersion,
+    dig: Vec<u8>,
+    czd: Option<Czd>,
+    yanked: bool,
+}
+
+impl Observed {
+    /// The same version, ignoring whether it has been yanked since.
+    fn same_version(&self, other: &Self) -> bool {
+        self.version == other.version && self.dig == other.dig && self.czd == other.czd
+    }
+}
+
+async fn resolved<S: AtomSource>(source: &S, id: &AtomId) -> Vec<Observed> {
+    let entry = ok(source.resolve(id).await, format_args!("resolve({id})"));
+    let Some(entry) = entry else {
+        panic!("resolve({id}) found nothing, though discover lists it");
+    };
+    assert!(
+        entry.id() == id,
+        "resolve({id}) answered for {}",
+        entry.id()
+    );
+    entry
+        .versions()
+        .map(|v| Observed {
+            version: v.version().clone(),
+            dig: v.dig().to_vec(),
+            czd: v.czd().cloned(),
+            yanked: v.is_yanked(),
+        })
+        .collect()
+}
+
+async fn observe<S: AtomSource>(source: &S) -> Vec<(AtomId, Vec<Observed>)> {
+    let mut atoms = Vec::new();
+    for id in ok(source.discover("").await, "discover(\"\")") {
+        let versions = resolved(source, &id).await;
+        atoms.push((id, versions));
+    }
+    atoms
+}
+
+fn versions_of<'a>(atoms: &'a [(AtomId, Vec<Observed>)], id: &AtomId) -> &'a [Observed] {
+    atoms
+        .iter()
+        .find(|(atom, _)| atom == id)
+        .map_or(&[], |(_, versions)| versions)
+}
+
+async fn content<C: AtomContent>(source: &C, id: &AtomId, version: &Observed) -> Vec<ContentEntry> {
+    let tree = ok(
+        source.content(id, &version.dig).await,
+        format_args!("content of {id} {}", version.version),
+    );
+    tree.unwrap_or_else(|| panic!("{id} {} lists a version with no content", version.version))
+}
+
+/// Whether two trees hold the same entries. Siblings' order is the
+/// backend's own, so it is not compared.
+fn same_tree(mut a: Vec<ContentEntry>, mut b: Vec<ContentEntry>) -> bool {
+    a.sort_by(|x, y| path_of(x).cmp(path_of(y)));
+    b.sort_by(|x, y| path_of(x).cmp(path_of(y)));
+    a == b
+}
+
+fn path_of(entry: &ContentEntry) -> &str {
+    match entry {
+        ContentEntry::Regular { path, .. }
+        | ContentEntry::Symlink { path, .. }
+        | ContentEntry::Directory { path } => path,
+    }
+}
+
+fn ok<T, E: Display>(result: Result<T, E>, what: impl Display) -> T {
+    result.unwrap_or_else(|e| panic!("{what} failed: {e}"))
+}
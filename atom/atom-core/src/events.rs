@@ -0,0 +1,189 @@
+//! Change notification for sources and stores: [`AtomEvents`].
+//!
+//! Caches, indexes and notifiers subscribe to the changes a registry or
+//! store makes instead of polling [`discover`](crate::AtomSource::discover)
+//! for them. [`Subscribers`] is the subscriber list a backend keeps and
+//! emits through.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+
+use crate::{AtomId, Czd, RawVersion};
+
+/// A change made by a registry or store.
+///
+/// Each event carries the czd of the signed payload behind it, so a
+/// subscriber can fetch and verify exactly what changed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AtomEvent {
+    /// A registry claimed `id`.
+    Claimed {
+        /// The claimed atom.
+        id: AtomId,
+        /// The claim's czd.
+        czd: Czd,
+    },
+    /// A registry published `version` of `id`.
+    Published {
+        /// The atom.
+        id: AtomId,
+        /// The version published.
+        version: RawVersion,
+        /// The publish's czd.
+        czd: Czd,
+    },
+    /// A store wrote `version` of `id`. Versions an ingest skips, being
+    /// already stored, are not reported.
+    Ingested {
+        /// The atom.
+        id: AtomId,
+        /// The version written.
+        version: RawVersion,
+        /// The publish's czd; `None` for an unsigned dev version.
+        czd: Option<Czd>,
+    },
+    /// `version` of `id` was yanked.
+    Yanked {
+        /// The atom.
+        id: AtomId,
+        /// The version yanked.
+        version: RawVersion,
+        /// The yank's czd.
+        czd: Czd,
+    },
+}
+
+impl AtomEvent {
+    /// The atom the event is about.
+    #[must_use]
+    pub fn id(&self) -> &AtomId {
+        match self {
+            Self::Claimed { id, .. }
+            | Self::Published { id, .. }
+            | Self::Ingested { id, .. }
+            | Self::Yanked { id, .. } => id,
+        }
+    }
+}
+
+/// A subscription, for [`AtomEvents::unsubscribe`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Subscription(u64);
+
+/// A subscriber: called once per event, on the thread that made the
+/// change, after the change is written.
+pub type Subscriber = dyn Fn(&AtomEvent) + Send + Sync;
+
+/// A source or store that reports its changes as [`AtomEvent`]s.
+///
+/// Subscribers see only changes made through this value, after they are
+/// written, in the order they were made. A change made inside a
+/// transaction is reported when it is written, and not retracted if the
+/// transaction rolls back.
+pub trait AtomEvents {
+    /// Call `subscriber` with every event from now on.
+    fn subscribe(&self, subscriber: impl Fn(&AtomEvent) + Send + Sync + 'static) -> Subscription;
+
+    /// Stop a subscription. `false` if it was already stopped.
+    fn unsubscribe(&self, subscription: Subscription) -> bool;
+}
+
+/// The subscriber list behind an [`AtomEvents`] implementation.
+///
+/// [`emit`](Self::emit) calls the subscribers without holding the list's
+/// lock, so a subscriber may itself subscribe or unsubscribe.
+#[derive(Default)]
+pub struct Subscribers {
+    next: AtomicU64,
+    list: Mutex<Vec<(Subscription, Arc<Subscriber>)>>,
+}
+
+impl Subscribers {
+    /// An empty list.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Report `event` to every subscriber.
+    pub fn emit(&self, event: &AtomEvent) {
+        let subscribers: Vec<Arc<Subscriber>> = self
+            .lock()
+            .iter()
+            .map(|(_, subscriber)| Arc::clone(subscriber))
+            .collect();
+        for subscriber in subscribers {
+            subscriber(event);
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<(Subscription, Arc<Subscriber>)>> {
+        // A subscriber that panicked mid-emit left the list itself intact.
+        self.list.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl AtomEvents for Subscribers {
+    fn subscribe(&self, subscriber: impl Fn(&AtomEvent) + Send + Sync + 'static) -> Subscription {
+        let subscription = Subscription(self.next.fetch_add(1, Ordering::Relaxed));
+        self.lock().push((subscription, Arc::new(subscriber)));
+        subscription
+    }
+
+    fn unsubscribe(&self, subscription: Subscription) -> bool {
+        let mut list = self.lock();
+        let before = list.len();
+        list.retain(|(s, _)| *s != subscription);
+        list.len() != before
+    }
+}
+
+impl std::fmt::Debug for Subscribers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Subscribers")
+            .field("len", &self.lock().len())
+            .finish()
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Anchor, Label};
+
+    #[test]
+    fn subscribers_see_events_until_they_unsubscribe() {
+        let subscribers = Arc::new(Subscribers::new());
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let first = subscribers.subscribe({
+            let seen = Arc::clone(&seen);
+            move |event: &AtomEvent| seen.lock().unwrap().push(event.clone())
+        });
+        // Subscribing from inside a subscriber must not deadlock.
+        subscribers.subscribe({
+            let inner = Arc::clone(&subscribers);
+            move |_: &AtomEvent| {
+                inner.unsubscribe(inner.subscribe(|_: &AtomEvent| {}));
+            }
+        });
+
+        let id = AtomId::new(Anchor::new(vec![1; 32]), Label::try_from("pkg").unwrap());
+        let event = AtomEvent::Ingested {
+            id: id.clone(),
+            version: RawVersion::new("1.0.0".to_string()),
+            czd: None,
+        };
+        subscribers.emit(&event);
+        assert!(subscribers.unsubscribe(first));
+        assert!(!subscribers.unsubscribe(first));
+        subscribers.emit(&event);
+
+        let seen = seen.lock().unwrap();
+        assert_eq!(*seen, [event]);
+        assert_eq!(seen[0].id(), &id);
+    }
+}
@@ -0,0 +1,269 @@
+//! Metrics for trait operations, through the `metrics` facade:
+//! [`InstrumentedSource`].
+
+use std::io;
+use std::time::Instant;
+
+use metrics::{counter, histogram};
+
+use crate::{
+    AtomContent, AtomId, AtomIdKey, AtomSource, AtomStore, Changes, Checkpoint, ContentEntry,
+    Cursor, DiscoverPage, IngestCounts, IngestFilter, IngestObserver, RawVersion, SnapshotError,
+    VersionMatch, VersionScheme,
+};
+
+/// A source or store that reports what it does to whichever `metrics`
+/// recorder the program installed, so operators see it without each
+/// backend measuring itself.
+///
+/// Every metric carries a `source` label, the name given to
+/// [`new`](Self::new), to tell instrumented sources apart:
+///
+/// | Metric | Kind | Recorded |
+/// |---|---|---|
+/// | `atom_resolve_total` | counter | each atom resolved, alone or in a batch, labeled `outcome`: `hit`, `miss` or `error` |
+/// | `atom_resolve_seconds` | histogram | each resolve's latency, a version pick's included |
+/// | `atom_resolve_many_seconds` | histogram | each batched resolve's latency |
+/// | `atom_versions_seconds` | histogram | each version listing's latency |
+/// | `atom_discover_results` | histogram | how many ids each discover, or page of one, found |
+/// | `atom_discover_seconds` | histogram | each discover's latency, or each page's |
+/// | `atom_since_results` | histogram | how many changed ids each since listed |
+/// | `atom_since_seconds` | histogram | each since's latency |
+/// | `atom_content_seconds` | histogram | each content fetch's latency |
+/// | `atom_ingest_versions_total` | counter | each version ingested, labeled `stage`: `verified`, `stored` or `skipped` |
+/// | `atom_ingest_seconds` | histogram | each ingest's duration, errors included |
+///
+/// With no recorder installed, every metric is a no-op.
+#[derive(Debug)]
+pub struct InstrumentedSource<S> {
+    source: S,
+    name: String,
+}
+
+impl<S> InstrumentedSource<S> {
+    /// Report `source`'s operations, labeled `name`.
+    pub fn new(source: S, name: impl Into<String>) -> Self {
+        Self {
+            source,
+            name: name.into(),
+        }
+    }
+
+    /// The source being measured.
+    pub fn source(&self) -> &S {
+        &self.source
+    }
+
+    /// The source, unwrapped.
+    pub fn into_source(self) -> S {
+        self.source
+    }
+
+    fn seconds(&self, metric: &'static str, started: Instant) {
+        histogram!(metric, "source" => self.name.clone()).record(started.elapsed().as_secs_f64());
+    }
+
+    /// Record a resolve started at `started` that answered `result`.
+    fn resolved<T, E>(&self, started: Instant, result: &Result<Option<T>, E>) {
+        self.seconds("atom_resolve_seconds", started);
+        let outcome = match result {
+            Ok(Some(_)) => "hit",
+            Ok(None) => "miss",
+            Err(_) => "error",
+        };
+        self.outcome(outcome, 1);
+    }
+
+    fn outcome(&self, outcome: &'static str, n: u64) {
+        counter!("atom_resolve_total", "source" => self.name.clone(), "outcome" => outcome)
+            .increment(n);
+    }
+
+    fn results(&self, metric: &'static str, ids: &[AtomId]) {
+        histogram!(metric, "source" => self.name.clone()).record(ids.len() as f64);
+    }
+}
+
+impl<S: AtomSource> AtomSource for InstrumentedSource<S> {
+    type Entry = S::Entry;
+    type Error = S::Error;
+
+    async fn resolve(&self, id: &AtomId) -> Result<Option<Self::Entry>, Self::Error> {
+        let started = Instant::now();
+        let result = self.source.resolve(id).await;
+        self.resolved(started, &result);
+        result
+    }
+
+    async fn resolve_many(&self, ids: &[AtomId]) -> Result<Vec<Option<Self::Entry>>, Self::Error>
+    where
+        Self::Entry: Send,
+    {
+        let started = Instant::now();
+        let result = self.source.resolve_many(ids).await;
+        self.seconds("atom_resolve_many_seconds", started);
+        match &result {
+            Ok(entries) => {
+                let hits = entries.iter().filter(|e| e.is_some()).count() as u64;
+                self.outcome("hit", hits);
+                self.outcome("miss", entries.len() as u64 - hits);
+            },
+            Err(_) => self.outcome("error", 1),
+        }
+        result
+    }
+
+    async fn versions(&self, id: &AtomId) -> Result<Vec<RawVersion>, Self::Error> {
+        let started = Instant::now();
+        let result = self.source.versions(id).await;
+        self.seconds("atom_versions_seconds", started);
+        result
+    }
+
+    async fn resolve_version<V>(
+        &self,
+        id: &AtomId,
+        scheme: &V,
+        req: &V::Requirement,
+    ) -> Result<Option<VersionMatch<Self::Entry>>, Self::Error>
+    where
+        V: VersionScheme + Sync,
+        V::Requirement: Sync,
+    {
+        let started = Instant::now();
+        let result = self.source.resolve_version(id, scheme, req).await;
+        self.resolved(started, &result);
+        result
+    }
+
+    async fn discover(&self, query: &str) -> Result<Vec<AtomId>, Self::Error> {
+        let started = Instant::now();
+        let result = self.source.discover(query).await;
+        self.seconds("atom_discover_seconds", started);
+        if let Ok(ids) = &result {
+            self.results("atom_discover_results", ids);
+        }
+        result
+    }
+
+    async fn discover_page(
+        &self,
+        query: &str,
+        cursor: Option<&Cursor>,
+        limit: usize,
+    ) -> Result<DiscoverPage, Self::Error> {
+        let started = Instant::now();
+        let result = self.source.discover_page(query, cursor, limit).await;
+        self.seconds("atom_discover_seconds", started);
+        if let Ok((ids, _)) = &result {
+            self.results("atom_discover_results", ids);
+        }
+        result
+    }
+
+    async fn since(&self, checkpoint: Option<&Checkpoint>) -> Result<Changes, Self::Error> {
+        let started = Instant::now();
+        let result = self.source.since(checkpoint).await;
+        self.seconds("atom_since_seconds", started);
+        if let Ok((ids, _)) = &result {
+            self.results("atom_since_results", ids);
+        }
+        result
+    }
+}
+
+impl<S: AtomContent> AtomContent for InstrumentedSource<S> {
+    async fn content(
+        &self,
+        id: &AtomId,
+        dig: &[u8],
+    ) -> Result<Option<Vec<ContentEntry>>, Self::Error> {
+        let started = Instant::now();
+        let result = self.source.content(id, dig).await;
+        self.seconds("atom_content_seconds", started);
+        result
+    }
+}
+
+impl<S: AtomStore> AtomStore for InstrumentedSource<S> {
+    async fn ingest_with_progress<T: AtomContent>(
+        &self,
+        source: &T,
+        observer: &mut impl IngestObserver,
+    ) -> Result<IngestCounts, Self::Error> {
+        let started = Instant::now();
+        let mut metered = Metered {
+            observer,
+            name: &self.name,
+        };
+        let result = self.source.ingest_with_progress(source, &mut metered).await;
+        self.seconds("atom_ingest_seconds", started);
+        result
+    }
+
+    async fn export(
+        &self,
+        writer: &mut (impl io::Write + Send),
+    ) -> Result<(), SnapshotError<Self::Error>> {
+        self.source.export(writer).await
+    }
+
+    async fn ingest_filtered<T: AtomContent>(
+        &self,
+        source: &T,
+        filter: &IngestFilter,
+    ) -> Result<(), Self::Error> {
+        let started = Instant::now();
+        let result = self.source.ingest_filtered(source, filter).await;
+        self.seconds("atom_ingest_seconds", started);
+        result
+    }
+
+    async fn contains(&self, id: &(dyn AtomIdKey + Sync)) -> Result<bool, Self::Error> {
+        self.source.contains(id).await
+    }
+
+    async fn resolve_key(
+        &self,
+        id: &(dyn AtomIdKey + Sync),
+    ) -> Result<Option<Self::Entry>, Self::Error> {
+        let started = Instant::now();
+        let result = self.source.resolve_key(id).await;
+        self.resolved(started, &result);
+        result
+    }
+}
+
+/// Counts an ingest's versions as they go by, then passes them on.
+struct Metered<'a, O> {
+    observer: &'a mut O,
+    name: &'a str,
+}
+
+impl<O> Metered<'_, O> {
+    fn count(&self, stage: &'static str) {
+        counter!("atom_ingest_versions_total", "source" => self.name.to_owned(), "stage" => stage)
+            .increment(1);
+    }
+}
+
+impl<O: IngestObserver> IngestObserver for Metered<'_, O> {
+    fn started(&mut self, id: &AtomId, version: &RawVersion) {
+        self.observer.started(id, version);
+    }
+
+    fn verified(&mut self, id: &AtomId, version: &RawVersion) {
+        self.count("verified");
+        self.observer.verified(id, version);
+    }
+
+    fn stored(&mut self, id: &AtomId, version: &RawVersion) {
+        self.count("stored");
+        self.observer.stored(id, version);
+    }
+
+    fn skipped(&mut self, id: &AtomId, version: &RawVersion) {
+        self.count("skipped");
+        self.observer.skipped(id, version);
+    }
+}
@@ -10,11 +10,36 @@
 //! |:------------------|:--------|:----------------------------------|
 //! | [`AtomSource`]    | §2.1    | Read-only observation             |
 //! | [`AtomRegistry`]  | §2.2    | Claiming and publishing (source)  |
+//! | `AsyncAtomRegistry` | §2.2   | [`AtomRegistry`], awaitable       |
 //! | [`AtomStore`]     | §2.3    | Local accumulation (consumer)     |
+//! | [`AtomStoreMut`]  | §2.3    | Explicit removal from a store     |
+//! | [`AtomStoreTxn`]  | §2.3    | All-or-nothing store writes       |
+//! | [`AtomStoreResume`] | §2.3  | Ingests that survive interruption |
+//! | [`RegistryMirror`] | §2.3   | Verbatim replication of a source  |
+//! | [`AtomEvents`]    | —       | Change notification               |
+//! | [`AtomBlobs`]     | §2.5    | Content-addressed blob bytes      |
+//! | [`AuditSink`]     | —       | Audit trail of registry writes    |
+//! | [`VerifyPolicy`]  | §2.3    | Signatures a store accepts        |
 //! | [`Manifest`]      | §1      | Minimal package metadata          |
+//! | [`ManifestDeps`]  | §1      | Declared dependencies             |
 //!
 //! Two implementations of the same trait are interchangeable if their
 //! observations agree pointwise (bisimulation equivalence from the model).
+//! The [`conformance`] module checks a backend against the model's laws.
+//!
+//! ## Async
+//!
+//! Observation and ingestion are network-bound, so [`AtomSource`],
+//! [`AtomContent`] and [`AtomStore`] are async already: their methods return
+//! `Send` futures (return-position `impl Trait`, no `async_trait` boxing).
+//! [`AtomRegistry`]'s writes are synchronous, since a registry signs and
+//! writes locally; `AsyncAtomRegistry`, with the `async-registry`
+//! feature, is its awaitable form, implemented for every [`AtomRegistry`],
+//! for backends whose writes go over the network and for callers that
+//! drive every backend the same way. Its methods carry an `_async` suffix,
+//! so a registry implementing both traits is never ambiguous to call.
+//! There is no adapter the other way: blocking on a future takes an
+//! executor, and this crate carries none.
 //!
 //! ## `AtomDigest`
 //!
@@ -31,6 +56,24 @@
 //! `content_hash` field. It lives here, not `atom-id`, because it operates
 //! on [`ContentEntry`], a type `atom-id` has no access to.
 //!
+//! ## Composing sources
+//!
+//! [`TieredSource`] puts several sources behind one [`AtomSource`],
+//! consulted in priority order — a local store, then a team mirror, then
+//! upstream. [`CachedSource`] remembers a slow source's answers for a
+//! while, so repeated lookups stay local. [`RetryingSource`] retries a
+//! flaky source's failures with exponential backoff. [`RateLimited`] holds
+//! callers to per-operation rates, sparing the registry behind it.
+//! [`ReadThroughStore`] fills a local store from a remote source on demand,
+//! one atom at a time. [`LockedStore`] runs a store's writes alone, for a
+//! backend whose reads cannot run alongside them, or every call alone, for
+//! a backend that is single-threaded internally. A backend returning
+//! [`SignedEntry`]s carries each version's signed transactions byte for
+//! byte, and `VerifiedSource` checks them before answering, with the
+//! default `serde` feature. With the `metrics` feature,
+//! `InstrumentedSource` reports a source's or store's operations through
+//! the `metrics` facade.
+//!
 //! ## Design principles
 //!
 //! - **Backend-agnostic**: trait signatures contain no git types, no concrete version types, no
@@ -39,15 +82,19 @@
 //! - **Identity/signature crypto-free**: all identity and signature-verification logic lives in
 //!   `atom-id`. This crate consumes `atom-id`'s types and re-exported coz-rs primitives; its own
 //!   `blake3` dependency computes [`content_hash`] only, never identity or signatures.
-//! - **Minimal**: no gix, no semver, no tokio. Two dependencies: `atom-id`, `blake3`.
+//! - **Minimal**: no gix, no semver, no tokio. Two dependencies: `atom-id`, `blake3` — plus
+//!   `metrics`, optional and off by default, behind the `metrics` feature.
 
 #![warn(missing_docs)]
 #![warn(rust_2018_idioms)]
 #![forbid(unsafe_code)]
 
+use std::future::Future;
+use std::{fmt, io};
+
 pub use atom_id::{
-    Alg, Anchor, AtomDigest, AtomId, Cad, Czd, HashAlg, Label, OwnerRef, RawVersion, Thumbprint,
-    VersionScheme,
+    Alg, Anchor, AtomDigest, AtomId, AtomIdKey, AtomIdRef, Cad, Czd, HashAlg, Label, OwnerRef,
+    RawRequirement, RawVersion, Tag, Thumbprint, VersionScheme,
 };
 
 mod hash {
@@ -446,6 +493,73 @@ mod hash {
 
 pub use hash::{NulInFilename, content_hash};
 
+mod snapshot;
+
+pub use snapshot::{Snapshot, SnapshotEntry, SnapshotError, SnapshotVersion};
+
+mod events;
+
+pub use events::{AtomEvent, AtomEvents, Subscriber, Subscribers, Subscription};
+
+mod tiered;
+
+pub use tiered::{TierPolicy, TieredSource};
+
+mod cached;
+
+pub use cached::CachedSource;
+
+mod policy;
+
+pub use policy::{SignaturePolicy, VerifyPolicy};
+
+mod trust;
+
+pub use trust::{TrustCheck, TrustStore};
+
+mod audit;
+
+pub use audit::{AuditOp, AuditRecord, AuditSink, AuditedRegistry};
+
+mod retry;
+
+pub use retry::{Backoff, RetryingSource, Sleep};
+
+mod signed;
+
+pub use signed::{SignedEntry, SignedTxn, SignedVersion};
+
+#[cfg(feature = "serde")]
+mod verified;
+
+mod read_through;
+
+pub use read_through::ReadThroughStore;
+
+mod rate;
+
+pub use rate::{ErrorClass, Limit, RateLimited, RateOp};
+
+mod resume;
+
+pub use resume::IngestCheckpoint;
+
+mod locked;
+
+pub use locked::LockedStore;
+
+#[cfg(feature = "metrics")]
+mod instrumented;
+
+pub mod conformance;
+
+#[cfg(feature = "serde")]
+pub use atom_id::VerifyError;
+#[cfg(feature = "metrics")]
+pub use instrumented::InstrumentedSource;
+#[cfg(feature = "serde")]
+pub use verified::{VerifiedSource, VerifiedSourceError};
+
 // ============================================================================
 // Traits
 // ============================================================================
@@ -467,12 +581,39 @@ pub trait Manifest {
     fn version(&self) -> &RawVersion;
 }
 
-/// Read-only observation of an atom store or source.
+/// A [`Manifest`] that declares its dependencies, so generic resolution
+/// code can walk dependency graphs whatever the manifest format.
 ///
-/// The common interface shared by sources and stores (model §2.1).
-/// Two implementations are interchangeable if `resolve` and `discover`
-/// agree pointwise (bisimulation equivalence).
-/// Trait representing an observed entry in an atom source.
+/// Kept apart from [`Manifest`], which requires only what the protocol
+/// does (`[manifest-minimal]`).
+pub trait ManifestDeps: Manifest {
+    /// The declared dependencies, in the order the manifest gives them.
+    fn dependencies(&self) -> &[Dependency];
+}
+
+/// A dependency declared by a [`ManifestDeps`] manifest.
+///
+/// Every part is as the manifest writes it: the source is resolved to
+/// an anchor, and the requirement parsed by a [`VersionScheme`], by the
+/// resolver walking the graph.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dependency {
+    /// The depended-on atom's label.
+    pub label: Label,
+    /// Where to find it — an atom URI, a URL, or a name the manifest
+    /// format maps to one; `None` for the dependent's own source.
+    pub source: Option<String>,
+    /// The versions accepted.
+    pub requirement: RawRequirement,
+}
+
+/// Trait representing an observed entry in an atom source: an atom's
+/// identity and every version observed for it.
+///
+/// [`AtomSource::Entry`] is bound by this trait, so generic consumers can
+/// inspect what [`resolve`](AtomSource::resolve) returns — each version's
+/// string, content digest, claim and provenance ([`AtomVersion`]) —
+/// without knowing the backend.
 pub trait AtomEntry {
     /// Concrete version observation type.
     type Version: AtomVersion;
@@ -490,6 +631,11 @@ pub trait AtomEntry {
 }
 
 /// Trait representing an observed version of an atom.
+///
+/// The provenance accessors — [`src`](Self::src), [`path`](Self::path) and
+/// [`published_at`](Self::published_at) — come from the signed publish
+/// payload, so they default to `None` for backends that carry no payload
+/// or have not read it.
 pub trait AtomVersion {
     /// The unparsed version string.
     fn version(&self) -> &RawVersion;
@@ -505,6 +651,39 @@ pub trait AtomVersion {
 
     /// Raw publish Coz message envelope JSON string, if signed.
     fn publish_msg(&self) -> Option<&str>;
+
+    /// The source revision the version was published from.
+    fn src(&self) -> Option<&[u8]> {
+        None
+    }
+
+    /// The atom's subtree path within the source tree.
+    fn path(&self) -> Option<&str> {
+        None
+    }
+
+    /// When the version was published, in seconds since the Unix epoch.
+    fn published_at(&self) -> Option<u64> {
+        None
+    }
+
+    /// The channel or track the version was published to, for backends
+    /// that record one.
+    fn tag(&self) -> Option<&Tag> {
+        None
+    }
+
+    /// Raw yank Coz message envelope JSON string, if the version was
+    /// yanked.
+    fn yank_msg(&self) -> Option<&str> {
+        None
+    }
+
+    /// Whether the version was yanked: still resolvable, but not to be
+    /// picked for new resolutions (spec `[yank-authority]`).
+    fn is_yanked(&self) -> bool {
+        self.yank_msg().is_some()
+    }
 }
 
 /// Read-only observation of an atom store or source.
@@ -529,16 +708,258 @@ pub trait AtomSource: Send + Sync + 'static {
     fn resolve(
         &self,
         id: &AtomId,
-    ) -> impl std::future::Future<Output = Result<Option<Self::Entry>, Self::Error>> + Send;
+    ) -> impl Future<Output = Result<Option<Self::Entry>, Self::Error>> + Send;
+
+    /// [`resolve`](Self::resolve) every atom in `ids`, answering in the
+    /// same order — `None` for each atom not present.
+    ///
+    /// Resolving a lockfile's hundreds of atoms one round trip at a time
+    /// dominates a cold start. The default resolves them one by one and
+    /// stops at the first error; backends with a batched query override
+    /// it to answer in one. The entries must be `Send`, since the answers
+    /// are held until the last is in.
+    fn resolve_many(
+        &self,
+        ids: &[AtomId],
+    ) -> impl Future<Output = Result<Vec<Option<Self::Entry>>, Self::Error>> + Send
+    where
+        Self::Entry: Send,
+    {
+        async move {
+            let mut entries = Vec::with_capacity(ids.len());
+            for id in ids {
+                entries.push(self.resolve(id).await?);
+            }
+            Ok(entries)
+        }
+    }
+
+    /// The published versions of `id`, in the order the entry lists them;
+    /// empty if the atom is not present.
+    ///
+    /// Yanked versions are listed too: a yank withdraws a version, it does
+    /// not delete it. [`resolve`](Self::resolve) the entry to tell them
+    /// apart by [`AtomVersion::is_yanked`].
+    ///
+    /// The default resolves the whole entry and keeps only its version
+    /// strings; backends that can list versions without reading each
+    /// version's metadata override it.
+    fn versions(
+        &self,
+        id: &AtomId,
+    ) -> impl Future<Output = Result<Vec<RawVersion>, Self::Error>> + Send {
+        async move {
+            Ok(self
+                .resolve(id)
+                .await?
+                .map(|entry| entry.versions().map(|v| v.version().clone()).collect())
+                .unwrap_or_default())
+        }
+    }
+
+    /// The newest published version of `id` that satisfies `req` under
+    /// `scheme` — "the newest 1.x of this atom" — with the entry it
+    /// belongs to.
+    ///
+    /// Returns `Ok(None)` if the atom is not present or no version
+    /// matches. Published versions `scheme` cannot parse match nothing.
+    /// A yanked version is picked only if no version that is not yanked
+    /// matches, so an exact pin still finds it.
+    /// The default resolves the whole entry and filters it; backends that
+    /// index versions override it to answer in one query.
+    fn resolve_version<S>(
+        &self,
+        id: &AtomId,
+        scheme: &S,
+        req: &S::Requirement,
+    ) -> impl Future<Output = Result<Option<VersionMatch<Self::Entry>>, Self::Error>> + Send
+    where
+        S: VersionScheme + Sync,
+        S::Requirement: Sync,
+    {
+        async move {
+            Ok(self
+                .resolve(id)
+                .await?
+                .and_then(|entry| VersionMatch::pick(entry, scheme, req)))
+        }
+    }
 
     /// Search for atoms matching a query string.
     ///
     /// Returns atom identities, not full entries — use
-    /// [`resolve`](Self::resolve) for observation data.
+    /// [`resolve`](Self::resolve) for observation data. Callers taking
+    /// search syntax from users parse it as an `atom_uri::AtomPattern`,
+    /// which validates the label glob before it reaches a backend.
     fn discover(
         &self,
         query: &str,
-    ) -> impl std::future::Future<Output = Result<Vec<AtomId>, Self::Error>> + Send;
+    ) -> impl Future<Output = Result<Vec<AtomId>, Self::Error>> + Send;
+
+    /// Search like [`discover`](Self::discover), one page at a time: at
+    /// most `limit` matches after `cursor` (`None` for the first page),
+    /// and the cursor for the next page, `None` after the last.
+    ///
+    /// A registry with millions of atoms cannot return every match at
+    /// once; backends with a native listing override this to page through
+    /// it. The default pages over [`discover`](Self::discover)'s full
+    /// result, so it relies on that being in a stable order, and on the
+    /// caller passing back only cursors this source returned — any other
+    /// ends the search with an empty page. A `limit` of 0 is taken as 1,
+    /// so every page but the last makes progress.
+    fn discover_page(
+        &self,
+        query: &str,
+        cursor: Option<&Cursor>,
+        limit: usize,
+    ) -> impl Future<Output = Result<DiscoverPage, Self::Error>> + Send {
+        async move {
+            let Some(start) = cursor.map_or(Some(0), Cursor::offset) else {
+                return Ok((Vec::new(), None));
+            };
+            let mut matches = self.discover(query).await?;
+            let end = start.saturating_add(limit.max(1));
+            let next = (end < matches.len()).then(|| Cursor::from_offset(end));
+            matches.truncate(end);
+            Ok((matches.split_off(start.min(matches.len())), next))
+        }
+    }
+
+    /// The atoms that may have changed — gained or yanked a version — since
+    /// `checkpoint` (`None` for all of them), and the checkpoint to pass
+    /// next time.
+    ///
+    /// A store syncing from this source ingests only these atoms, through
+    /// [`IngestFilter::atoms`], and keeps the new checkpoint for its next
+    /// sync, instead of enumerating and verifying the whole source again.
+    /// The answer may list atoms that did not change, but never leaves out
+    /// one that did; an atom changed during the call may be listed again
+    /// next time. Checkpoints are the source's own tokens: pass back only
+    /// ones it returned.
+    ///
+    /// The default cannot tell what changed, so it lists every atom —
+    /// correct, never incremental. Backends that keep a change log
+    /// override it.
+    fn since(
+        &self,
+        checkpoint: Option<&Checkpoint>,
+    ) -> impl Future<Output = Result<Changes, Self::Error>> + Send {
+        let _ = checkpoint;
+        async move { Ok((self.discover("").await?, Checkpoint::new(""))) }
+    }
+}
+
+/// The version [`resolve_version`](AtomSource::resolve_version) picked,
+/// with the entry it was picked from.
+#[derive(Debug, Clone)]
+pub struct VersionMatch<E> {
+    entry: E,
+    index: usize,
+}
+
+impl<E: AtomEntry> VersionMatch<E> {
+    /// Pick from `entry` as the default
+    /// [`resolve_version`](AtomSource::resolve_version) does.
+    fn pick<S: VersionScheme>(entry: E, scheme: &S, req: &S::Requirement) -> Option<Self> {
+        let index = entry
+            .versions()
+            .enumerate()
+            .filter_map(|(index, v)| {
+                Some((
+                    index,
+                    v.is_yanked(),
+                    scheme.parse_version(v.version()).ok()?,
+                ))
+            })
+            .filter(|(_, _, version)| scheme.matches(version, req))
+            .max_by(|(_, a_yanked, a), (_, b_yanked, b)| {
+                b_yanked.cmp(a_yanked).then_with(|| a.cmp(b))
+            })
+            .map(|(index, ..)| index)?;
+        Some(Self { entry, index })
+    }
+
+    /// The picked version.
+    #[must_use]
+    pub fn version(&self) -> &E::Version {
+        self.entry
+            .versions()
+            .nth(self.index)
+            .expect("index taken from this entry's versions")
+    }
+
+    /// The atom's entry, every version included.
+    #[must_use]
+    pub fn entry(&self) -> &E {
+        &self.entry
+    }
+
+    /// The entry, giving up the pick.
+    #[must_use]
+    pub fn into_entry(self) -> E {
+        self.entry
+    }
+}
+
+/// One page of [`discover_page`](AtomSource::discover_page) matches, and the
+/// cursor for the next page, if there is one.
+pub type DiscoverPage = (Vec<AtomId>, Option<Cursor>);
+
+/// Where a [`discover_page`](AtomSource::discover_page) search left off.
+///
+/// Opaque to callers, and meaningful only to the source that returned it:
+/// a backend encodes whatever its listing resumes from — an offset, a key,
+/// a server token.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Cursor(String);
+
+impl Cursor {
+    /// A cursor carrying a backend's own resume token.
+    pub fn new(token: impl Into<String>) -> Self {
+        Self(token.into())
+    }
+
+    /// The resume token.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// The default [`discover_page`](AtomSource::discover_page)'s cursor:
+    /// an offset into the full result.
+    fn from_offset(offset: usize) -> Self {
+        Self(offset.to_string())
+    }
+
+    fn offset(&self) -> Option<usize> {
+        self.0.parse().ok()
+    }
+}
+
+/// The atoms [`since`](AtomSource::since) found changed, and the
+/// checkpoint to resume from.
+pub type Changes = (Vec<AtomId>, Checkpoint);
+
+/// How far a store has synced from a source, as of a
+/// [`since`](AtomSource::since).
+///
+/// Opaque to callers, and meaningful only to the source that returned it:
+/// a backend encodes whatever its change log resumes from — a sequence
+/// number, a time, a server token. A store keeps it between syncs.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Checkpoint(String);
+
+impl Checkpoint {
+    /// A checkpoint carrying a backend's own token.
+    pub fn new(token: impl Into<String>) -> Self {
+        Self(token.into())
+    }
+
+    /// The token.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
 }
 
 /// A single entry in an atom's content tree.
@@ -547,7 +968,7 @@ pub trait AtomSource: Send + Sync + 'static {
 /// [`AtomContent::content`]. Entries are ordered
 /// children-before-parents (leaves-to-root) to satisfy
 /// castore ingestion ordering requirements.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum ContentEntry {
     /// A regular file with content bytes.
     Regular {
@@ -602,7 +1023,45 @@ pub trait AtomContent: AtomSource {
         &self,
         id: &AtomId,
         dig: &[u8],
-    ) -> impl std::future::Future<Output = Result<Option<Vec<ContentEntry>>, Self::Error>> + Send;
+    ) -> impl Future<Output = Result<Option<Vec<ContentEntry>>, Self::Error>> + Send;
+}
+
+/// Content-addressed blob access (model §2.5): the bytes a digest names,
+/// for backends and build stores that move published snapshots rather
+/// than describe them.
+///
+/// Where [`AtomContent`] yields an atom version's whole tree, a blob store
+/// holds single byte strings under the digest of their bytes, in the
+/// backend's own addressing — a git blob id, say. Putting bytes is
+/// idempotent: the same bytes always land under the same digest, and
+/// [`get_blob`](Self::get_blob) gives back exactly what was put
+/// (`get(put(b)) = b`).
+pub trait AtomBlobs: Send + Sync + 'static {
+    /// A blob's bytes, read as they arrive.
+    type Blob: io::Read + Send;
+
+    /// Backend-specific error type.
+    type Error: std::error::Error + Send + Sync + 'static;
+
+    /// The blob under `dig`; `None` if there is none.
+    fn get_blob(
+        &self,
+        dig: &[u8],
+    ) -> impl Future<Output = Result<Option<Self::Blob>, Self::Error>> + Send;
+
+    /// Store the bytes `data` yields, returning their digest.
+    fn put_blob(
+        &self,
+        data: &mut (impl io::Read + Send),
+    ) -> impl Future<Output = Result<Vec<u8>, Self::Error>> + Send;
+
+    /// Whether there is a blob under `dig`.
+    ///
+    /// The default opens the blob with [`get_blob`](Self::get_blob);
+    /// backends that can check without reading override it.
+    fn has_blob(&self, dig: &[u8]) -> impl Future<Output = Result<bool, Self::Error>> + Send {
+        async move { Ok(self.get_blob(dig).await?.is_some()) }
+    }
 }
 
 /// Claiming and publishing interface (source-side).
@@ -643,6 +1102,15 @@ pub trait AtomRegistry: AtomSource {
         path: &str,
     ) -> Result<(), Self::Error>;
 
+    /// Yank a published version: sign a record withdrawing it, without
+    /// deleting the publish or its content.
+    ///
+    /// `claim` is the czd of the claim the version's publish chains to;
+    /// only that claim's owner may yank (`[yank-authority]`). Returns the
+    /// yank's [`Czd`]. The version stays resolvable and reports
+    /// [`AtomVersion::is_yanked`].
+    fn yank(&self, id: &AtomId, version: &RawVersion, claim: &Czd) -> Result<Czd, Self::Error>;
+
     /// Charter (found or succeed) an atom-set.
     ///
     /// `prior: None` founds a new atom-set: the returned [`Czd`] becomes
@@ -664,6 +1132,93 @@ pub trait AtomRegistry: AtomSource {
     ) -> Result<Czd, Self::Error>;
 }
 
+/// [`AtomRegistry`] with awaitable writes.
+///
+/// Same operations and contracts as [`AtomRegistry`]; see there. Every
+/// [`AtomRegistry`] is one, its futures completing on first poll, so
+/// callers can be written against this trait alone. A backend whose
+/// writes are network-bound implements it directly instead.
+#[cfg(feature = "async-registry")]
+pub trait AsyncAtomRegistry: AtomSource {
+    /// [`AtomRegistry::claim`], awaitable.
+    fn claim_async(
+        &self,
+        id: &AtomId,
+        owner: &OwnerRef,
+    ) -> impl Future<Output = Result<Czd, Self::Error>> + Send;
+
+    /// [`AtomRegistry::publish`], awaitable.
+    #[allow(clippy::too_many_arguments)]
+    fn publish_async(
+        &self,
+        id: &AtomId,
+        claim: &Czd,
+        version: &RawVersion,
+        dig: &[u8],
+        src: &[u8],
+        path: &str,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// [`AtomRegistry::yank`], awaitable.
+    fn yank_async(
+        &self,
+        id: &AtomId,
+        version: &RawVersion,
+        claim: &Czd,
+    ) -> impl Future<Output = Result<Czd, Self::Error>> + Send;
+
+    /// [`AtomRegistry::charter`], awaitable.
+    fn charter_async(
+        &self,
+        owner: &[OwnerRef],
+        src: &[u8],
+        prior: Option<&Czd>,
+    ) -> impl Future<Output = Result<Czd, Self::Error>> + Send;
+}
+
+#[cfg(feature = "async-registry")]
+impl<R: AtomRegistry> AsyncAtomRegistry for R {
+    fn claim_async(
+        &self,
+        id: &AtomId,
+        owner: &OwnerRef,
+    ) -> impl Future<Output = Result<Czd, Self::Error>> + Send {
+        std::future::ready(AtomRegistry::claim(self, id, owner))
+    }
+
+    fn publish_async(
+        &self,
+        id: &AtomId,
+        claim: &Czd,
+        version: &RawVersion,
+        dig: &[u8],
+        src: &[u8],
+        path: &str,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        std::future::ready(AtomRegistry::publish(
+            self, id, claim, version, dig, src, path,
+        ))
+    }
+
+    fn yank_async(
+        &self,
+        id: &AtomId,
+        version: &RawVersion,
+        claim: &Czd,
+    ) -> impl Future<Output = Result<Czd, Self::Error>> + Send {
+        std::future::ready(AtomRegistry::yank(self, id, version, claim))
+    }
+
+    fn charter_async(
+        &self,
+        owner: &[OwnerRef],
+        src: &[u8],
+        prior: Option<&Czd>,
+    ) -> impl Future<Output = Result<Czd, Self::Error>> + Send {
+        std::future::ready(AtomRegistry::charter(self, owner, src, prior))
+    }
+}
+
 /// Local accumulation interface (consumer-side).
 ///
 /// Extends [`AtomSource`] with ingestion from remote sources (model §2.3,
@@ -674,6 +1229,21 @@ pub trait AtomRegistry: AtomSource {
 /// [`resolve`](AtomSource::resolve) on this store MUST return at least
 /// what the source's `resolve` returns. The store accumulates — it never
 /// loses atoms through ingestion.
+///
+/// # Concurrency
+///
+/// Every method takes `&self`, and [`AtomSource`] requires `Send + Sync`:
+/// a store is shared, and any of its methods may be called from many tasks
+/// at once. Each version is atomic: a [`resolve`](AtomSource::resolve)
+/// concurrent with an ingest sees a version the ingest writes either whole,
+/// content included, or not at all. Nothing larger is: a resolve partway
+/// through an ingest may see some of its versions and not others, and two
+/// concurrent ingests of the same version store it once. A backend whose
+/// reads cannot run alongside its writes can be wrapped in a
+/// [`LockedStore`], which runs each write alone; one that is
+/// single-threaded internally, and cannot serve even two reads at once, in
+/// an [`exclusive`](LockedStore::exclusive) one, which runs every call
+/// alone.
 pub trait AtomStore: AtomContent {
     /// Import atoms from a source into this store.
     ///
@@ -682,11 +1252,1811 @@ pub trait AtomStore: AtomContent {
     fn ingest<S: AtomContent>(
         &self,
         source: &S,
-    ) -> impl std::future::Future<Output = Result<(), Self::Error>> + Send;
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        async move { self.ingest_with_progress(source, &mut ()).await.map(drop) }
+    }
+
+    /// [`ingest`](Self::ingest), telling `observer` about each atom version
+    /// as the store works through `source`.
+    ///
+    /// Returns how many versions were started, verified, stored and
+    /// skipped. On error the counts are lost, but `observer` has already
+    /// seen every event up to the failure.
+    fn ingest_with_progress<S: AtomContent>(
+        &self,
+        source: &S,
+        observer: &mut impl IngestObserver,
+    ) -> impl Future<Output = Result<IngestCounts, Self::Error>> + Send;
+
+    /// Write every atom version in this store, with its content, as a
+    /// portable [`Snapshot`] — for backup, for moving atoms to a store of
+    /// another backend, or for carrying them across an air gap.
+    ///
+    /// The default builds the snapshot in memory with [`Snapshot::of`].
+    fn export(
+        &self,
+        writer: &mut (impl io::Write + Send),
+    ) -> impl Future<Output = Result<(), SnapshotError<Self::Error>>> + Send {
+        async move {
+            let snapshot = Snapshot::of(self).await?;
+            snapshot.write_to(writer).map_err(SnapshotError::from_store)
+        }
+    }
+
+    /// Ingest a [`Snapshot`] written by [`export`](Self::export), from any
+    /// backend. Every version is verified as in any ingest: a snapshot
+    /// vouches for nothing.
+    fn import(
+        &self,
+        reader: &mut (impl io::Read + Send),
+    ) -> impl Future<Output = Result<IngestCounts, SnapshotError<Self::Error>>> + Send {
+        async move {
+            let snapshot = Snapshot::read_from(reader).map_err(SnapshotError::from_store)?;
+            self.ingest_with_progress(&snapshot, &mut ())
+                .await
+                .map_err(SnapshotError::Store)
+        }
+    }
+
+    /// [`ingest`](Self::ingest) only the atom versions `filter` admits.
+    ///
+    /// The ⊇ condition then holds for the admitted subset alone: a
+    /// consumer mirrors what it depends on, not the whole source.
+    fn ingest_filtered<S: AtomContent>(
+        &self,
+        source: &S,
+        filter: &IngestFilter,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
 
-    /// Check whether an atom is present in this store.
+    /// Check whether an atom is present in this store, by an owned
+    /// [`AtomId`] or a borrowed [`AtomIdRef`].
     fn contains(
+        &self,
+        id: &(dyn AtomIdKey + Sync),
+    ) -> impl Future<Output = Result<bool, Self::Error>> + Send;
+
+    /// [`resolve`](AtomSource::resolve) by an owned [`AtomId`] or a
+    /// borrowed [`AtomIdRef`], so a caller holding an anchor and label
+    /// probes the store without cloning them.
+    ///
+    /// The default builds an owned id to resolve; backends with an index
+    /// override it to probe the index with the borrowed one.
+    fn resolve_key(
+        &self,
+        id: &(dyn AtomIdKey + Sync),
+    ) -> impl Future<Output = Result<Option<Self::Entry>, Self::Error>> + Send {
+        let id = id.id_ref().to_atom_id();
+        async move { self.resolve(&id).await }
+    }
+
+    /// The atom-sets this store holds atoms of, each once — so one store
+    /// can host many without its callers keeping track of their anchors.
+    ///
+    /// The default collects the anchors of the atoms
+    /// [`discover`](AtomSource::discover) lists for the empty query, in
+    /// the order it lists them.
+    fn anchors(&self) -> impl Future<Output = Result<Vec<Anchor>, Self::Error>> + Send {
+        async move {
+            let mut anchors: Vec<Anchor> = Vec::new();
+            for id in self.discover("").await? {
+                if !anchors.contains(id.anchor()) {
+                    anchors.push(id.anchor().clone());
+                }
+            }
+            Ok(anchors)
+        }
+    }
+
+    /// [`resolve`](AtomSource::resolve) the atom labeled `label` in the
+    /// atom-set `anchor`.
+    ///
+    /// The default probes [`resolve_key`](Self::resolve_key) with the two
+    /// borrowed as an [`AtomIdRef`].
+    fn resolve_in(
+        &self,
+        anchor: &Anchor,
+        label: &Label,
+    ) -> impl Future<Output = Result<Option<Self::Entry>, Self::Error>> + Send {
+        async move { self.resolve_key(&AtomIdRef::new(anchor, label)).await }
+    }
+}
+
+/// Explicit removal from an [`AtomStore`].
+///
+/// The accumulation guarantee is about ingestion: ingest never takes an
+/// atom out, so removal only ever happens through these methods, called
+/// on purpose. A removed atom stays removed until a later ingest brings it
+/// back from a source that still has it.
+pub trait AtomStoreMut: AtomStore {
+    /// Remove `version` of `id`. Returns whether it was present.
+    fn remove_version(
         &self,
         id: &AtomId,
-    ) -> impl std::future::Future<Output = Result<bool, Self::Error>> + Send;
+        version: &RawVersion,
+    ) -> impl Future<Output = Result<bool, Self::Error>> + Send;
+
+    /// Remove every version of `id`. Returns whether any was present.
+    ///
+    /// The default removes each version the store resolves.
+    fn remove(&self, id: &AtomId) -> impl Future<Output = Result<bool, Self::Error>> + Send {
+        async move {
+            let mut removed = false;
+            for version in self.versions(id).await? {
+                removed |= self.remove_version(id, &version).await?;
+            }
+            Ok(removed)
+        }
+    }
+
+    /// Remove every atom but the retention `roots`, returning the atoms
+    /// removed. Nothing is retained implicitly: an atom the caller still
+    /// needs must be among the roots.
+    ///
+    /// The default removes each atom [`discover`](AtomSource::discover)
+    /// lists for the empty query; backends with store-internal garbage,
+    /// such as orphaned claims, override it to sweep that too.
+    fn gc(
+        &self,
+        roots: &[AtomId],
+    ) -> impl Future<Output = Result<Vec<AtomId>, Self::Error>> + Send {
+        async move {
+            let mut removed = Vec::new();
+            for id in self.discover("").await? {
+                if !roots.contains(&id) && self.remove(&id).await? {
+                    removed.push(id);
+                }
+            }
+            Ok(removed)
+        }
+    }
+}
+
+/// All-or-nothing writes to an [`AtomStore`].
+///
+/// A failure partway through a plain [`ingest`](AtomStore::ingest) leaves
+/// whatever was written so far in place. Writes made through a
+/// [`StoreTxn`] instead take effect together on
+/// [`commit`](StoreTxn::commit), or not at all.
+///
+/// A store runs one transaction at a time, and
+/// [`AtomEvents`] subscribers hear of a transaction's writes only once it
+/// commits. How a store keeps other writers out while one is open —
+/// making them wait, or refusing them — is backend-defined.
+pub trait AtomStoreTxn: AtomStore {
+    /// An open transaction on this store.
+    type Txn<'a>: StoreTxn<Error = Self::Error>
+    where
+        Self: 'a;
+
+    /// Open a transaction.
+    fn begin(&self) -> impl Future<Output = Result<Self::Txn<'_>, Self::Error>> + Send;
+}
+
+/// An open transaction, from [`AtomStoreTxn::begin`].
+///
+/// Dropping a transaction without committing it rolls it back, as far as
+/// a destructor can: errors are lost, so call
+/// [`rollback`](Self::rollback) to see them.
+pub trait StoreTxn: Send {
+    /// The store's error type.
+    type Error: std::error::Error + Send + Sync + 'static;
+
+    /// [`AtomStore::ingest`], within the transaction.
+    fn ingest<S: AtomContent>(
+        &mut self,
+        source: &S,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// [`AtomStore::ingest_filtered`], within the transaction.
+    fn ingest_filtered<S: AtomContent>(
+        &mut self,
+        source: &S,
+        filter: &IngestFilter,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// Make every write in the transaction permanent.
+    fn commit(self) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// Undo every write in the transaction.
+    fn rollback(self) -> impl Future<Output = Result<(), Self::Error>> + Send;
+}
+
+/// Ingests that pick up where an interrupted one stopped.
+///
+/// A plain [`ingest`](AtomStore::ingest) cut short keeps what it wrote, but
+/// the next one walks the source from the start again, resolving every atom
+/// to find nothing left to do. An [`IngestCheckpoint`] records the atoms
+/// finished, so a multi-hour mirror that loses its connection resumes with
+/// the first unfinished atom.
+pub trait AtomStoreResume: AtomStore {
+    /// [`ingest_with_progress`](AtomStore::ingest_with_progress), skipping
+    /// the atoms `checkpoint` has done and recording each atom in it as it
+    /// finishes. The counts are for this call's atoms alone.
+    ///
+    /// On error, `checkpoint` holds every atom finished before the failure:
+    /// persist it with [`write_to`](IngestCheckpoint::write_to) and pass it
+    /// back to resume. On success it is cleared, so reusing it starts over
+    /// rather than missing versions the source gains later.
+    fn ingest_resumable<S: AtomContent>(
+        &self,
+        source: &S,
+        checkpoint: &mut IngestCheckpoint,
+    ) -> impl Future<Output = Result<IngestCounts, Self::Error>> + Send;
+}
+
+/// Replication of one source by another, verbatim.
+///
+/// A mirror copies every claim, publish, yank and charter of its upstream
+/// exactly as signed, checking each as any ingest does, and serves them as
+/// a source of its own — so mirrors can chain, and a client can fail over
+/// from the upstream to any of its mirrors and verify the same signatures.
+/// Like a store, a mirror only accumulates: a sync never removes what an
+/// earlier one copied.
+pub trait RegistryMirror: AtomSource {
+    /// Copy everything in `upstream` not yet mirrored, returning what was
+    /// copied and skipped. The outcome is recorded for
+    /// [`sync_status`](Self::sync_status), failure included.
+    fn sync_from<S: AtomContent>(
+        &self,
+        upstream: &S,
+    ) -> impl Future<Output = Result<IngestCounts, Self::Error>> + Send;
+
+    /// How the mirror's syncs have gone.
+    fn sync_status(&self) -> SyncStatus;
+}
+
+/// The state of a [`RegistryMirror`], as of its latest sync.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SyncStatus {
+    /// When the last successful sync finished, in seconds since the Unix
+    /// epoch; `None` if no sync has succeeded.
+    pub synced_at: Option<u64>,
+    /// What the last successful sync copied and skipped.
+    pub last: IngestCounts,
+    /// Why the latest sync failed, if it did; cleared by the next success.
+    pub error: Option<String>,
+}
+
+/// Watches an [`AtomStore::ingest_with_progress`], one event per atom
+/// version, in order: [`started`](Self::started), then
+/// [`verified`](Self::verified) for a signed version, then either
+/// [`stored`](Self::stored) or [`skipped`](Self::skipped).
+///
+/// Every method does nothing by default; `()` is the observer that wants
+/// nothing, and [`IngestCounts`] only counts.
+pub trait IngestObserver: Send {
+    /// The store is about to check `version` of `id`.
+    fn started(&mut self, _id: &AtomId, _version: &RawVersion) {}
+
+    /// The version's claim and publish signatures checked out. Unsigned dev
+    /// versions have nothing to verify and skip this.
+    fn verified(&mut self, _id: &AtomId, _version: &RawVersion) {}
+
+    /// The version was written to the store.
+    fn stored(&mut self, _id: &AtomId, _version: &RawVersion) {}
+
+    /// The version was already in the store, so nothing was written.
+    fn skipped(&mut self, _id: &AtomId, _version: &RawVersion) {}
+}
+
+impl IngestObserver for () {}
+
+/// How many atom versions an ingest reported at each stage.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct IngestCounts {
+    /// Versions the store began checking.
+    pub started: usize,
+    /// Versions whose signatures verified.
+    pub verified: usize,
+    /// Versions written to the store.
+    pub stored: usize,
+    /// Versions already present, and left alone.
+    pub skipped: usize,
+}
+
+impl IngestObserver for IngestCounts {
+    fn started(&mut self, _id: &AtomId, _version: &RawVersion) {
+        self.started += 1;
+    }
+
+    fn verified(&mut self, _id: &AtomId, _version: &RawVersion) {
+        self.verified += 1;
+    }
+
+    fn stored(&mut self, _id: &AtomId, _version: &RawVersion) {
+        self.stored += 1;
+    }
+
+    fn skipped(&mut self, _id: &AtomId, _version: &RawVersion) {
+        self.skipped += 1;
+    }
+}
+
+/// Which atom versions an [`AtomStore::ingest_filtered`] takes from its
+/// source.
+///
+/// A version is admitted if it passes every predicate set; the empty
+/// filter admits everything.
+///
+/// ```
+/// use atom_core::{Anchor, IngestFilter};
+///
+/// let filter = IngestFilter::new()
+///     .anchor(Anchor::new(vec![7; 32]))
+///     .labels("serde-*");
+/// ```
+#[derive(Default)]
+pub struct IngestFilter {
+    atoms: Option<Vec<AtomId>>,
+    anchors: Vec<Anchor>,
+    labels: Option<String>,
+    tag: Option<Tag>,
+    versions: Option<VersionPredicate>,
+}
+
+type VersionPredicate = Box<dyn Fn(&RawVersion) -> bool + Send + Sync>;
+
+impl IngestFilter {
+    /// The filter that admits everything.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Admit only the atoms in `ids` — those [`since`](AtomSource::since)
+    /// reported changed, say. Given more than once, atoms in any of them.
+    #[must_use]
+    pub fn atoms(mut self, ids: impl IntoIterator<Item = AtomId>) -> Self {
+        self.atoms.get_or_insert_with(Vec::new).extend(ids);
+        self
+    }
+
+    /// Admit only atoms under `anchor`. Given more than once, atoms under
+    /// any of the anchors.
+    #[must_use]
+    pub fn anchor(mut self, anchor: Anchor) -> Self {
+        self.anchors.push(anchor);
+        self
+    }
+
+    /// Admit only atoms whose label matches `glob`, where `*` matches any
+    /// run of characters and `?` exactly one — matched by
+    /// [`Label::matches_glob`], as `atom_uri::AtomPattern` matches.
+    #[must_use]
+    pub fn labels(mut self, glob: &str) -> Self {
+        self.labels = Some(glob.to_owned());
+        self
+    }
+
+    /// Admit only versions published to `tag`. Versions of backends that
+    /// record no tag are then never admitted.
+    #[must_use]
+    pub fn tag(mut self, tag: Tag) -> Self {
+        self.tag = Some(tag);
+        self
+    }
+
+    /// Admit only versions that `scheme` parses and that satisfy `req`.
+    ///
+    /// # Errors
+    ///
+    /// The scheme's error if `req` does not parse.
+    pub fn versions<S>(mut self, scheme: S, req: &RawRequirement) -> Result<Self, S::Error>
+    where
+        S: VersionScheme + Send + Sync + 'static,
+        S::Requirement: Send + Sync + 'static,
+    {
+        let req = scheme.parse_requirement(req)?;
+        self.versions = Some(Box::new(move |raw| {
+            scheme
+                .parse_version(raw)
+                .is_ok_and(|version| scheme.matches(&version, &req))
+        }));
+        Ok(self)
+    }
+
+    /// Whether any version of `id` can be admitted: the atom, its anchor
+    /// and its label pass.
+    #[must_use]
+    pub fn admits_atom(&self, id: &AtomId) -> bool {
+        self.atoms.as_ref().is_none_or(|atoms| atoms.contains(id))
+            && (self.anchors.is_empty() || self.anchors.contains(id.anchor()))
+            && self
+                .labels
+                .as_ref()
+                .is_none_or(|glob| id.label().matches_glob(glob))
+    }
+
+    /// Whether `version` of an admitted atom is admitted: its tag and
+    /// version pass.
+    #[must_use]
+    pub fn admits_version(&self, version: &impl AtomVersion) -> bool {
+        self.tag
+            .as_ref()
+            .is_none_or(|tag| version.tag() == Some(tag))
+            && self
+                .versions
+                .as_ref()
+                .is_none_or(|admits| admits(version.version()))
+    }
+}
+
+impl fmt::Debug for IngestFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IngestFilter")
+            .field("atoms", &self.atoms)
+            .field("anchors", &self.anchors)
+            .field("labels", &self.labels)
+            .field("tag", &self.tag)
+            .field("versions", &self.versions.is_some())
+            .finish()
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+    use std::sync::{Arc, Mutex};
+    use std::task::{Context, Poll, Waker};
+    use std::time::Duration;
+
+    use atom_id::NaturalOrderScheme;
+
+    use super::*;
+
+    /// Drive a future that never waits — every test source answers on the
+    /// first poll — without an executor.
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = std::pin::pin!(future);
+        match future
+            .as_mut()
+            .poll(&mut Context::from_waker(Waker::noop()))
+        {
+            Poll::Ready(output) => output,
+            Poll::Pending => panic!("test sources never wait"),
+        }
+    }
+
+    fn id(label: &str) -> AtomId {
+        AtomId::new(Anchor::new(vec![7; 32]), Label::try_from(label).unwrap())
+    }
+
+    #[derive(Clone)]
+    struct Entry {
+        id: AtomId,
+        versions: Vec<Version>,
+    }
+
+    impl AtomEntry for Entry {
+        type Version = Version;
+        type VersionIter<'a> = std::slice::Iter<'a, Version>;
+
+        fn id(&self) -> &AtomId {
+            &self.id
+        }
+
+        fn versions(&self) -> Self::VersionIter<'_> {
+            self.versions.iter()
+        }
+    }
+
+    /// A version, and its yank message if it was yanked.
+    #[derive(Clone)]
+    struct Version(RawVersion, Option<String>);
+
+    impl AtomVersion for Version {
+        fn version(&self) -> &RawVersion {
+            &self.0
+        }
+
+        fn dig(&self) -> &[u8] {
+            &[]
+        }
+
+        fn czd(&self) -> Option<&Czd> {
+            None
+        }
+
+        fn claim_msg(&self) -> Option<&str> {
+            None
+        }
+
+        fn publish_msg(&self) -> Option<&str> {
+            None
+        }
+
+        fn yank_msg(&self) -> Option<&str> {
+            self.1.as_deref()
+        }
+    }
+
+    /// An in-memory source and store, listing its atoms in a fixed order.
+    struct Catalog(Mutex<Vec<Entry>>);
+
+    impl Catalog {
+        fn of(atoms: &[(&str, &[&str])]) -> Self {
+            Self(Mutex::new(
+                atoms
+                    .iter()
+                    .map(|(label, versions)| Entry {
+                        id: id(label),
+                        versions: versions
+                            .iter()
+                            .map(|v| Version(RawVersion::new((*v).to_owned()), None))
+                            .collect(),
+                    })
+                    .collect(),
+            ))
+        }
+
+        fn labels(&self) -> Vec<String> {
+            let entries = self.0.lock().unwrap();
+            entries.iter().map(|e| e.id.label().to_string()).collect()
+        }
+
+        /// Add `version` of `id`, unless it is already there.
+        fn take(&self, id: &AtomId, version: &RawVersion) -> bool {
+            let mut entries = self.0.lock().unwrap();
+            let index = match entries.iter().position(|e| e.id == *id) {
+                Some(index) => index,
+                None => {
+                    entries.push(Entry {
+                        id: id.clone(),
+                        versions: Vec::new(),
+                    });
+                    entries.len() - 1
+                },
+            };
+            let versions = &mut entries[index].versions;
+            if versions.iter().any(|v| v.0 == *version) {
+                return false;
+            }
+            versions.push(Version(version.clone(), None));
+            true
+        }
+
+        async fn ingest_where<S: AtomContent>(
+            &self,
+            source: &S,
+            filter: &IngestFilter,
+            observer: &mut impl IngestObserver,
+            mut checkpoint: Option<&mut IngestCheckpoint>,
+        ) -> Result<IngestCounts, io::Error> {
+            let mut counts = IngestCounts::default();
+            for id in source.discover("").await.map_err(io::Error::other)? {
+                if !filter.admits_atom(&id) || checkpoint.as_deref().is_some_and(|c| c.is_done(&id))
+                {
+                    continue;
+                }
+                let Some(entry) = source.resolve(&id).await.map_err(io::Error::other)? else {
+                    continue;
+                };
+                for version in entry.versions().filter(|v| filter.admits_version(*v)) {
+                    let version = version.version();
+                    observer.started(&id, version);
+                    counts.started(&id, version);
+                    if self.take(&id, version) {
+                        observer.stored(&id, version);
+                        counts.stored(&id, version);
+                    } else {
+                        observer.skipped(&id, version);
+                        counts.skipped(&id, version);
+                    }
+                }
+                if let Some(checkpoint) = checkpoint.as_deref_mut() {
+                    checkpoint.done(&id);
+                }
+            }
+            Ok(counts)
+        }
+    }
+
+    impl AtomSource for Catalog {
+        type Entry = Entry;
+        type Error = std::io::Error;
+
+        async fn resolve(&self, id: &AtomId) -> Result<Option<Entry>, Self::Error> {
+            let entries = self.0.lock().unwrap();
+            Ok(entries.iter().find(|e| e.id == *id).cloned())
+        }
+
+        async fn discover(&self, query: &str) -> Result<Vec<AtomId>, Self::Error> {
+            let entries = self.0.lock().unwrap();
+            Ok(entries
+                .iter()
+                .filter(|e| e.id.label().starts_with(query))
+                .map(|e| e.id.clone())
+                .collect())
+        }
+    }
+
+    impl AtomContent for Catalog {
+        /// Every version is empty.
+        async fn content(
+            &self,
+            id: &AtomId,
+            _dig: &[u8],
+        ) -> Result<Option<Vec<ContentEntry>>, Self::Error> {
+            let entries = self.0.lock().unwrap();
+            Ok(entries.iter().any(|e| e.id == *id).then(Vec::new))
+        }
+    }
+
+    impl AtomStore for Catalog {
+        async fn ingest_with_progress<S: AtomContent>(
+            &self,
+            source: &S,
+            observer: &mut impl IngestObserver,
+        ) -> Result<IngestCounts, Self::Error> {
+            self.ingest_where(source, &IngestFilter::new(), observer, None)
+                .await
+        }
+
+        async fn ingest_filtered<S: AtomContent>(
+            &self,
+            source: &S,
+            filter: &IngestFilter,
+        ) -> Result<(), Self::Error> {
+            self.ingest_where(source, filter, &mut (), None)
+                .await
+                .map(drop)
+        }
+
+        async fn contains(&self, id: &(dyn AtomIdKey + Sync)) -> Result<bool, Self::Error> {
+            let entries = self.0.lock().unwrap();
+            Ok(entries
+                .iter()
+                .any(|e| e.id == id.id_ref() && !e.versions.is_empty()))
+        }
+
+        /// Probes without building an owned id.
+        async fn resolve_key(
+            &self,
+            id: &(dyn AtomIdKey + Sync),
+        ) -> Result<Option<Entry>, Self::Error> {
+            let entries = self.0.lock().unwrap();
+            Ok(entries.iter().find(|e| e.id == id.id_ref()).cloned())
+        }
+    }
+
+    impl AtomStoreMut for Catalog {
+        async fn remove_version(
+            &self,
+            id: &AtomId,
+            version: &RawVersion,
+        ) -> Result<bool, Self::Error> {
+            let mut entries = self.0.lock().unwrap();
+            let Some(entry) = entries.iter_mut().find(|e| e.id == *id) else {
+                return Ok(false);
+            };
+            let before = entry.versions.len();
+            entry.versions.retain(|v| v.0 != *version);
+            let removed = entry.versions.len() < before;
+            entries.retain(|e| !e.versions.is_empty());
+            Ok(removed)
+        }
+    }
+
+    impl AtomStoreResume for Catalog {
+        async fn ingest_resumable<S: AtomContent>(
+            &self,
+            source: &S,
+            checkpoint: &mut IngestCheckpoint,
+        ) -> Result<IngestCounts, Self::Error> {
+            let counts = self
+                .ingest_where(source, &IngestFilter::new(), &mut (), Some(checkpoint))
+                .await?;
+            checkpoint.clear();
+            Ok(counts)
+        }
+    }
+
+    /// A [`Catalog`] transaction: the entries as they were at `begin`,
+    /// put back unless committed.
+    struct CatalogTxn<'a> {
+        catalog: &'a Catalog,
+        before: Option<Vec<Entry>>,
+    }
+
+    impl CatalogTxn<'_> {
+        fn restore(&mut self) {
+            if let Some(before) = self.before.take() {
+                *self.catalog.0.lock().unwrap() = before;
+            }
+        }
+    }
+
+    impl Drop for CatalogTxn<'_> {
+        fn drop(&mut self) {
+            self.restore();
+        }
+    }
+
+    impl StoreTxn for CatalogTxn<'_> {
+        type Error = io::Error;
+
+        async fn ingest<S: AtomContent>(&mut self, source: &S) -> Result<(), Self::Error> {
+            self.catalog.ingest(source).await
+        }
+
+        async fn ingest_filtered<S: AtomContent>(
+            &mut self,
+            source: &S,
+            filter: &IngestFilter,
+        ) -> Result<(), Self::Error> {
+            self.catalog.ingest_filtered(source, filter).await
+        }
+
+        async fn commit(mut self) -> Result<(), Self::Error> {
+            self.before = None;
+            Ok(())
+        }
+
+        async fn rollback(mut self) -> Result<(), Self::Error> {
+            self.restore();
+            Ok(())
+        }
+    }
+
+    impl AtomStoreTxn for Catalog {
+        type Txn<'a> = CatalogTxn<'a>;
+
+        async fn begin(&self) -> Result<CatalogTxn<'_>, Self::Error> {
+            let before = self.0.lock().unwrap().clone();
+            Ok(CatalogTxn {
+                catalog: self,
+                before: Some(before),
+            })
+        }
+    }
+
+    #[test]
+    fn default_discover_page_walks_the_whole_result() {
+        let labels = ["a1", "a2", "a3", "a4", "a5", "b1"];
+        let source = Catalog::of(&labels.map(|l| (l, &[][..])));
+        let mut seen = Vec::new();
+        let mut cursor = None;
+        loop {
+            let (page, next) = block_on(source.discover_page("a", cursor.as_ref(), 2)).unwrap();
+            assert!(page.len() <= 2);
+            seen.extend(page);
+            match next {
+                Some(next) => cursor = Some(next),
+                None => break,
+            }
+        }
+        assert_eq!(seen, block_on(source.discover("a")).unwrap());
+
+        let (page, next) = block_on(source.discover_page("a", None, 0)).unwrap();
+        assert_eq!((page.len(), next.is_some()), (1, true));
+        let foreign = Cursor::new("not-an-offset");
+        let (page, next) = block_on(source.discover_page("a", Some(&foreign), 2)).unwrap();
+        assert!(page.is_empty() && next.is_none());
+    }
+
+    #[test]
+    fn default_versions_lists_the_entry() {
+        let source = Catalog::of(&[("core", &["1.0", "1.1"])]);
+        let versions = block_on(source.versions(&id("core"))).unwrap();
+        let versions: Vec<&str> = versions.iter().map(RawVersion::as_str).collect();
+        assert_eq!(versions, ["1.0", "1.1"]);
+        assert!(block_on(source.versions(&id("absent"))).unwrap().is_empty());
+    }
+
+    #[test]
+    fn default_resolve_many_answers_in_the_order_asked() {
+        let source = Catalog::of(&[("core", &["1.0"]), ("util", &["0.1"])]);
+        let entries = block_on(source.resolve_many(&[id("util"), id("absent"), id("core")]));
+        let labels: Vec<Option<String>> = entries
+            .unwrap()
+            .iter()
+            .map(|e| e.as_ref().map(|e| e.id.label().to_string()))
+            .collect();
+        assert_eq!(labels, [Some("util".into()), None, Some("core".into())]);
+        assert!(block_on(source.resolve_many(&[])).unwrap().is_empty());
+    }
+
+    #[test]
+    fn default_since_lists_every_atom_for_a_filtered_ingest() {
+        let upstream = Catalog::of(&[("core", &["1.0"]), ("util", &["0.1"])]);
+        let (changed, checkpoint) = block_on(upstream.since(None)).unwrap();
+        assert_eq!(changed, [id("core"), id("util")]);
+        let (again, _) = block_on(upstream.since(Some(&checkpoint))).unwrap();
+        assert_eq!(again, changed, "the default is never incremental");
+
+        let store = Catalog::of(&[]);
+        let only_util = IngestFilter::new().atoms([id("util")]);
+        block_on(store.ingest_filtered(&upstream, &only_util)).unwrap();
+        assert_eq!(store.labels(), ["util"]);
+    }
+
+    #[test]
+    fn resumed_ingest_skips_what_the_checkpoint_has_done() {
+        let upstream = Catalog::of(&[("core", &["1.0"]), ("util", &["0.1"])]);
+        let store = Catalog::of(&[]);
+        // As an ingest interrupted after `core` would have left it, saved
+        // and read back.
+        let mut saved = Vec::new();
+        let mut interrupted = IngestCheckpoint::new();
+        interrupted.done(&id("core"));
+        interrupted.write_to(&mut saved).unwrap();
+        let mut checkpoint = IngestCheckpoint::read_from(&mut saved.as_slice()).unwrap();
+
+        let counts = block_on(store.ingest_resumable(&upstream, &mut checkpoint)).unwrap();
+        assert_eq!((counts.started, counts.stored), (1, 1));
+        assert_eq!(store.labels(), ["util"]);
+        assert!(
+            checkpoint.is_empty(),
+            "a finished ingest clears its checkpoint"
+        );
+
+        let counts = block_on(store.ingest_resumable(&upstream, &mut checkpoint)).unwrap();
+        assert_eq!((counts.stored, counts.skipped), (1, 1));
+        assert_eq!(store.labels(), ["util", "core"]);
+    }
+
+    #[test]
+    fn default_anchors_and_resolve_in_scope_a_store_by_atom_set() {
+        let store = Catalog::of(&[("core", &["1.0"]), ("util", &["0.1"])]);
+        let other = Anchor::new(vec![8; 32]);
+        let core = Label::try_from("core").unwrap();
+        let elsewhere = AtomId::new(other.clone(), core.clone());
+        store.take(&elsewhere, &RawVersion::new("9.0".to_owned()));
+
+        let anchors = block_on(store.anchors()).unwrap();
+        assert_eq!(anchors, [id("core").anchor().clone(), other.clone()]);
+        let entry = block_on(store.resolve_in(&other, &core)).unwrap().unwrap();
+        assert_eq!(entry.id(), &elsewhere);
+        let util = Label::try_from("util").unwrap();
+        assert!(block_on(store.resolve_in(&other, &util)).unwrap().is_none());
+        assert!(block_on(store.contains(&AtomIdRef::new(&other, &core))).unwrap());
+        assert!(!block_on(store.contains(&AtomIdRef::new(&other, &util))).unwrap());
+    }
+
+    /// A catalog whose lookup of `util` waits once before answering.
+    struct Stalling(Catalog, Mutex<bool>);
+
+    impl AtomSource for Stalling {
+        type Entry = Entry;
+        type Error = io::Error;
+
+        async fn resolve(&self, id: &AtomId) -> Result<Option<Entry>, Self::Error> {
+            if &**id.label() == "util" {
+                let mut stalled = false;
+                std::future::poll_fn(|cx| {
+                    if stalled || !std::mem::replace(&mut *self.1.lock().unwrap(), false) {
+                        return Poll::Ready(());
+                    }
+                    stalled = true;
+                    cx.waker().wake_by_ref();
+                    Poll::Pending
+                })
+                .await;
+            }
+            self.0.resolve(id).await
+        }
+
+        async fn discover(&self, query: &str) -> Result<Vec<AtomId>, Self::Error> {
+            self.0.discover(query).await
+        }
+    }
+
+    impl AtomContent for Stalling {
+        async fn content(
+            &self,
+            id: &AtomId,
+            dig: &[u8],
+        ) -> Result<Option<Vec<ContentEntry>>, Self::Error> {
+            self.0.content(id, dig).await
+        }
+    }
+
+    #[test]
+    fn locked_store_keeps_reads_out_of_an_ingest_under_way() {
+        let upstream = Catalog::of(&[("core", &["1.0"]), ("util", &["0.1"])]);
+        let store = LockedStore::new(Catalog::of(&[]));
+        let source = Stalling(upstream, Mutex::new(true));
+        let cx = &mut Context::from_waker(Waker::noop());
+
+        let mut ingest = std::pin::pin!(store.ingest(&source));
+        assert!(ingest.as_mut().poll(cx).is_pending());
+        assert_eq!(store.store().labels(), ["core"], "stalled after `core`");
+
+        let core = id("core");
+        let mut read = std::pin::pin!(store.resolve(&core));
+        assert!(
+            read.as_mut().poll(cx).is_pending(),
+            "reads wait out the ingest"
+        );
+
+        assert!(matches!(ingest.as_mut().poll(cx), Poll::Ready(Ok(()))));
+        let Poll::Ready(entry) = read.as_mut().poll(cx) else {
+            panic!("the ingest finished");
+        };
+        assert!(entry.unwrap().is_some());
+        assert_eq!(block_on(store.discover("")).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn default_resolve_version_picks_the_newest_match() {
+        let source = Catalog::of(&[("core", &["1.2", "1.10", "2.0", "...", "1.9"])]);
+        let scheme = NaturalOrderScheme;
+        let pick = |label: &str, req: &str| {
+            let req = scheme
+                .parse_requirement(&RawRequirement::new(req.to_owned()))
+                .unwrap();
+            block_on(source.resolve_version(&id(label), &scheme, &req))
+                .unwrap()
+                .map(|m| m.version().version().as_str().to_owned())
+        };
+        assert_eq!(pick("core", ">=1, <2").as_deref(), Some("1.10"));
+        assert_eq!(pick("core", "*").as_deref(), Some("2.0"));
+        assert_eq!(pick("core", ">3"), None);
+        assert_eq!(pick("absent", "*"), None);
+    }
+
+    #[test]
+    fn default_resolve_version_passes_over_yanked_versions() {
+        let source = Catalog::of(&[("core", &["1.0", "1.1", "2.0"])]);
+        for yanked in [1, 2] {
+            source.0.lock().unwrap()[0].versions[yanked].1 = Some("{}".to_owned());
+        }
+        let scheme = NaturalOrderScheme;
+        let pick = |req: &str| {
+            let req = scheme
+                .parse_requirement(&RawRequirement::new(req.to_owned()))
+                .unwrap();
+            block_on(source.resolve_version(&id("core"), &scheme, &req))
+                .unwrap()
+                .map(|m| {
+                    (
+                        m.version().version().as_str().to_owned(),
+                        m.version().is_yanked(),
+                    )
+                })
+        };
+        assert_eq!(pick("*"), Some(("1.0".to_owned(), false)));
+        assert_eq!(
+            pick(">=1.1"),
+            Some(("2.0".to_owned(), true)),
+            "only yanked ones match"
+        );
+        assert_eq!(block_on(source.versions(&id("core"))).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn ingest_filter_applies_every_predicate() {
+        let version = |v: &str| Version(RawVersion::new(v.to_owned()), None);
+        assert!(IngestFilter::new().admits_atom(&id("anything")));
+        assert!(IngestFilter::new().admits_version(&version("...")));
+
+        let filter = IngestFilter::new()
+            .labels("serde-?*")
+            .versions(
+                NaturalOrderScheme,
+                &RawRequirement::new(">=1, <2".to_owned()),
+            )
+            .unwrap();
+        assert!(filter.admits_atom(&id("serde-json")));
+        assert!(!filter.admits_atom(&id("serde-")));
+        assert!(!filter.admits_atom(&id("tokio")));
+        assert!(filter.admits_version(&version("1.4")));
+        assert!(!filter.admits_version(&version("2.0")));
+        assert!(!filter.admits_version(&version("...")));
+
+        let elsewhere = IngestFilter::new().anchor(Anchor::new(vec![9; 32]));
+        assert!(!elsewhere.admits_atom(&id("core")));
+        assert!(
+            elsewhere
+                .anchor(Anchor::new(vec![7; 32]))
+                .admits_atom(&id("core"))
+        );
+
+        let tagged = IngestFilter::new().tag(Tag::try_from("stable").unwrap());
+        assert!(!tagged.admits_version(&version("1.0")));
+    }
+
+    #[test]
+    fn removal_is_explicit_and_gc_keeps_only_roots() {
+        let store = Catalog::of(&[
+            ("core", &["1.0", "1.1"]),
+            ("util", &["0.1"]),
+            ("extra", &["2.0"]),
+        ]);
+        let v = |v: &str| RawVersion::new(v.to_owned());
+
+        assert!(block_on(store.remove_version(&id("core"), &v("1.0"))).unwrap());
+        assert!(!block_on(store.remove_version(&id("core"), &v("1.0"))).unwrap());
+        assert!(block_on(store.contains(&id("core"))).unwrap());
+        assert!(block_on(store.remove(&id("core"))).unwrap());
+        assert!(!block_on(store.contains(&id("core"))).unwrap());
+        assert!(!block_on(store.remove(&id("core"))).unwrap());
+
+        let removed = block_on(store.gc(&[id("util")])).unwrap();
+        assert_eq!(removed, [id("extra")]);
+        assert_eq!(store.labels(), ["util"]);
+    }
+
+    #[test]
+    fn transactions_commit_or_leave_no_trace() {
+        let upstream = Catalog::of(&[("core", &["1.0"]), ("util", &["0.1"])]);
+        let store = Catalog::of(&[("local", &["0.0"])]);
+
+        let mut txn = block_on(store.begin()).unwrap();
+        block_on(txn.ingest(&upstream)).unwrap();
+        assert_eq!(store.labels(), ["local", "core", "util"]);
+        block_on(txn.rollback()).unwrap();
+        assert_eq!(store.labels(), ["local"]);
+
+        let mut txn = block_on(store.begin()).unwrap();
+        block_on(txn.ingest(&upstream)).unwrap();
+        drop(txn);
+        assert_eq!(store.labels(), ["local"]);
+
+        let mut txn = block_on(store.begin()).unwrap();
+        let only_core = IngestFilter::new().labels("core");
+        block_on(txn.ingest_filtered(&upstream, &only_core)).unwrap();
+        block_on(txn.commit()).unwrap();
+        assert_eq!(store.labels(), ["local", "core"]);
+    }
+
+    #[test]
+    fn export_then_import_moves_every_version() {
+        let from = Catalog::of(&[("core", &["1.0", "1.1"]), ("util", &["0.1"])]);
+        let mut snapshot = Vec::new();
+        block_on(from.export(&mut snapshot)).unwrap();
+
+        let into = Catalog::of(&[("util", &["0.1"])]);
+        let counts = block_on(into.import(&mut snapshot.as_slice())).unwrap();
+        assert_eq!((counts.stored, counts.skipped), (2, 1));
+        assert_eq!(into.labels(), ["util", "core"]);
+        let versions = block_on(into.versions(&id("core"))).unwrap();
+        assert_eq!(versions.len(), 2);
+    }
+
+    /// A tier that is up, or one that fails every lookup.
+    struct Tier(Option<Catalog>);
+
+    impl AtomSource for Tier {
+        type Entry = Entry;
+        type Error = io::Error;
+
+        async fn resolve(&self, id: &AtomId) -> Result<Option<Entry>, Self::Error> {
+            match &self.0 {
+                Some(catalog) => catalog.resolve(id).await,
+                None => Err(io::ErrorKind::NotConnected.into()),
+            }
+        }
+
+        async fn discover(&self, query: &str) -> Result<Vec<AtomId>, Self::Error> {
+            match &self.0 {
+                Some(catalog) => catalog.discover(query).await,
+                None => Err(io::ErrorKind::NotConnected.into()),
+            }
+        }
+    }
+
+    #[test]
+    fn tiered_source_answers_from_the_highest_tier_with_the_atom() {
+        let newest = |sources: &TieredSource<Tier>, label: &str| {
+            block_on(sources.resolve(&id(label)))
+                .unwrap()
+                .map(|entry| entry.versions[0].0.as_str().to_owned())
+        };
+        let local = || Tier(Some(Catalog::of(&[("core", &["1.0"])])));
+        let upstream = Tier(Some(Catalog::of(&[("util", &["0.1"]), ("core", &["2.0"])])));
+        let sources = TieredSource::new()
+            .tier(local(), TierPolicy::FailFast)
+            .tier(Tier(None), TierPolicy::Skip)
+            .tier(upstream, TierPolicy::FailFast);
+        assert_eq!(newest(&sources, "core").as_deref(), Some("1.0"));
+        assert_eq!(newest(&sources, "util").as_deref(), Some("0.1"));
+        assert_eq!(newest(&sources, "absent"), None);
+        assert_eq!(
+            block_on(sources.discover("")).unwrap(),
+            [id("core"), id("util")]
+        );
+        let many = block_on(sources.resolve_many(&[id("util"), id("absent"), id("core")]));
+        let many: Vec<Option<&str>> = many
+            .as_ref()
+            .unwrap()
+            .iter()
+            .map(|e| e.as_ref().map(|e| e.versions[0].0.as_str()))
+            .collect();
+        assert_eq!(many, [Some("0.1"), None, Some("1.0")]);
+
+        let mut paged = Vec::new();
+        let mut cursor = None;
+        loop {
+            let (page, next) = block_on(sources.discover_page("", cursor.as_ref(), 1)).unwrap();
+            paged.extend(page);
+            match next {
+                Some(next) => cursor = Some(next),
+                None => break,
+            }
+        }
+        assert_eq!(
+            paged,
+            [id("core"), id("util")],
+            "upstream's core is passed over"
+        );
+        let (changed, checkpoint) = block_on(sources.since(None)).unwrap();
+        assert_eq!(changed, [id("core"), id("util")]);
+        let (again, _) = block_on(sources.since(Some(&checkpoint))).unwrap();
+        assert_eq!(again, changed);
+
+        let strict = TieredSource::new()
+            .tier(local(), TierPolicy::FailFast)
+            .tier(Tier(None), TierPolicy::FailFast);
+        assert_eq!(newest(&strict, "core").as_deref(), Some("1.0"));
+        assert!(block_on(strict.resolve(&id("util"))).is_err());
+        assert!(block_on(strict.discover("")).is_err());
+    }
+
+    #[test]
+    fn cached_source_reuses_answers_until_invalidated() {
+        let count = |cached: &CachedSource<Catalog>, label: &str| {
+            block_on(cached.resolve(&id(label)))
+                .unwrap()
+                .map(|entry| entry.versions.len())
+        };
+        let hour = Duration::from_secs(3600);
+        let cached = CachedSource::new(Catalog::of(&[("core", &["1.0"])]), hour);
+        assert_eq!(count(&cached, "core"), Some(1));
+        assert_eq!(count(&cached, "util"), None);
+        assert_eq!(block_on(cached.discover("")).unwrap(), [id("core")]);
+
+        let v = |s: &str| RawVersion::new(s.to_owned());
+        cached.source().take(&id("core"), &v("1.1"));
+        cached.source().take(&id("util"), &v("0.1"));
+        assert_eq!(count(&cached, "core"), Some(1));
+        assert_eq!(count(&cached, "util"), None);
+
+        cached.invalidate(&id("core"));
+        assert_eq!(count(&cached, "core"), Some(2));
+        assert_eq!(count(&cached, "util"), None);
+        assert_eq!(
+            block_on(cached.discover("")).unwrap(),
+            [id("core"), id("util")],
+            "an added atom is listed once anything is invalidated"
+        );
+        cached.clear();
+        assert_eq!(count(&cached, "util"), Some(1));
+
+        let positive_only = CachedSource::new(Catalog::of(&[]), hour).negative_ttl(Duration::ZERO);
+        assert_eq!(count(&positive_only, "core"), None);
+        positive_only.source().take(&id("core"), &v("1.0"));
+        assert_eq!(count(&positive_only, "core"), Some(1));
+
+        let uncached = CachedSource::new(Catalog::of(&[("core", &["1.0"])]), hour).capacity(0);
+        assert_eq!(count(&uncached, "core"), Some(1));
+        uncached.source().take(&id("core"), &v("1.1"));
+        assert_eq!(count(&uncached, "core"), Some(2));
+    }
+
+    /// A catalog whose next lookups time out, as many as are left.
+    struct Flaky(Catalog, Mutex<u32>);
+
+    impl AtomSource for Flaky {
+        type Entry = Entry;
+        type Error = io::Error;
+
+        async fn resolve(&self, id: &AtomId) -> Result<Option<Entry>, Self::Error> {
+            {
+                let mut failures = self.1.lock().unwrap();
+                if *failures > 0 {
+                    *failures -= 1;
+                    return Err(io::ErrorKind::TimedOut.into());
+                }
+            }
+            self.0.resolve(id).await
+        }
+
+        async fn discover(&self, query: &str) -> Result<Vec<AtomId>, Self::Error> {
+            self.0.discover(query).await
+        }
+    }
+
+    #[test]
+    fn retrying_source_backs_off_and_gives_up() {
+        let flaky = |failures| Flaky(Catalog::of(&[("core", &["1.0"])]), Mutex::new(failures));
+        let backoff = Backoff {
+            attempts: 3,
+            base: Duration::from_millis(10),
+            max: Duration::from_secs(1),
+            jitter: false,
+        };
+        let waits = Arc::new(Mutex::new(Vec::new()));
+        let sleep = {
+            let waits = Arc::clone(&waits);
+            move |wait: Duration| {
+                waits.lock().unwrap().push(wait);
+                std::future::ready(())
+            }
+        };
+
+        let retrying = RetryingSource::new(flaky(2), sleep).backoff(backoff);
+        assert!(block_on(retrying.resolve(&id("core"))).unwrap().is_some());
+        let ms = |n| Duration::from_millis(n);
+        assert_eq!(*waits.lock().unwrap(), [ms(10), ms(20)]);
+
+        let no_wait = |_: Duration| std::future::ready(());
+        let exhausted = RetryingSource::new(flaky(3), no_wait).backoff(backoff);
+        assert!(block_on(exhausted.resolve(&id("core"))).is_err());
+        let permanent = RetryingSource::new(flaky(1), no_wait)
+            .retry_if(|e: &io::Error| e.kind() != io::ErrorKind::TimedOut);
+        assert!(block_on(permanent.resolve(&id("core"))).is_err());
+        assert!(block_on(permanent.resolve(&id("core"))).unwrap().is_some());
+    }
+
+    /// Asks its callers to hold off for a while.
+    #[derive(Debug)]
+    struct SlowDown(Duration);
+
+    impl fmt::Display for SlowDown {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "slow down for {:?}", self.0)
+        }
+    }
+
+    impl std::error::Error for SlowDown {}
+
+    impl ErrorClass for SlowDown {
+        fn retry_after(&self) -> Option<Duration> {
+            Some(self.0)
+        }
+    }
+
+    /// A catalog that answers its first lookup with [`SlowDown`].
+    struct Overloaded(Catalog, Mutex<bool>);
+
+    impl AtomSource for Overloaded {
+        type Entry = Entry;
+        type Error = SlowDown;
+
+        async fn resolve(&self, id: &AtomId) -> Result<Option<Entry>, Self::Error> {
+            if std::mem::replace(&mut *self.1.lock().unwrap(), false) {
+                return Err(SlowDown(Duration::from_secs(30)));
+            }
+            Ok(self.0.resolve(id).await.unwrap())
+        }
+
+        async fn discover(&self, query: &str) -> Result<Vec<AtomId>, Self::Error> {
+            Ok(self.0.discover(query).await.unwrap())
+        }
+    }
+
+    #[test]
+    fn rate_limited_source_waits_for_its_bucket_and_for_retry_after() {
+        let waits = Arc::new(Mutex::new(Vec::new()));
+        let sleep = {
+            let waits = Arc::clone(&waits);
+            move |wait: Duration| {
+                waits.lock().unwrap().push(wait);
+                std::future::ready(())
+            }
+        };
+        let limit = Limit {
+            burst: 2,
+            every: Duration::from_secs(10),
+        };
+        let source = Catalog::of(&[("core", &["1.0"])]);
+        let limited = RateLimited::new(source, sleep.clone(), limit).limit(RateOp::Discover, None);
+        for _ in 0..3 {
+            assert!(block_on(limited.resolve(&id("core"))).unwrap().is_some());
+            block_on(limited.discover("")).unwrap();
+        }
+        let waited = waits.lock().unwrap().clone();
+        assert_eq!(waited.len(), 1, "only the third resolve outran its bucket");
+        assert!(waited[0] > Duration::from_secs(9));
+
+        waits.lock().unwrap().clear();
+        let overloaded = Overloaded(Catalog::of(&[("core", &["1.0"])]), Mutex::new(true));
+        let limited = RateLimited::new(overloaded, sleep, Limit::per_second(1000));
+        assert!(block_on(limited.resolve(&id("core"))).is_err());
+        assert!(block_on(limited.discover("")).is_ok());
+        let waited = waits.lock().unwrap().clone();
+        assert_eq!(waited.len(), 1);
+        assert!(
+            waited[0] > Duration::from_secs(29),
+            "the hold-off covers every call"
+        );
+    }
+
+    #[test]
+    fn read_through_store_ingests_a_missed_atom_and_answers_from_the_store() {
+        let upstream = Catalog::of(&[("core", &["1.0", "2.0"]), ("util", &["0.1"])]);
+        let cache = ReadThroughStore::new(Catalog::of(&[("local", &["0.0"])]), upstream);
+
+        assert!(block_on(cache.discover("core")).unwrap().is_empty());
+        let entry = block_on(cache.resolve(&id("core"))).unwrap().unwrap();
+        assert_eq!(entry.versions().count(), 2);
+        assert_eq!(
+            cache.store().labels(),
+            ["local", "core"],
+            "only the missed atom"
+        );
+        assert!(block_on(cache.resolve(&id("absent"))).unwrap().is_none());
+        assert!(block_on(cache.content(&id("core"), &[])).unwrap().is_some());
+
+        let published = RawVersion::new("3.0".to_owned());
+        cache.source().take(&id("core"), &published);
+        let cached = block_on(cache.versions(&id("core"))).unwrap();
+        assert!(
+            !cached.contains(&published),
+            "a hit does not ask the source"
+        );
+        block_on(cache.refresh(&id("core"))).unwrap();
+        assert!(
+            block_on(cache.versions(&id("core")))
+                .unwrap()
+                .contains(&published)
+        );
+    }
+
+    /// A source of signed entries, each version with empty content.
+    #[cfg(feature = "serde")]
+    struct Signing(Vec<SignedEntry<Entry>>);
+
+    #[cfg(feature = "serde")]
+    impl AtomSource for Signing {
+        type Entry = SignedEntry<Entry>;
+        type Error = io::Error;
+
+        async fn resolve(&self, id: &AtomId) -> Result<Option<Self::Entry>, Self::Error> {
+            Ok(self.0.iter().find(|e| e.id() == id).cloned())
+        }
+
+        async fn discover(&self, _query: &str) -> Result<Vec<AtomId>, Self::Error> {
+            Ok(self.0.iter().map(|e| e.id().clone()).collect())
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    impl AtomContent for Signing {
+        async fn content(
+            &self,
+            id: &AtomId,
+            _dig: &[u8],
+        ) -> Result<Option<Vec<ContentEntry>>, Self::Error> {
+            Ok(self.0.iter().any(|e| e.id() == id).then(Vec::new))
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn verified_source_checks_each_version_against_its_signatures() {
+        use atom_id::{ClaimPayload, PublishBuilder, SignedPayload, Signer};
+
+        let sk = coz_rs::SigningKey::<coz_rs::Ed25519>::generate();
+        let key = sk.verifying_key().public_key_bytes().to_vec();
+        let signer = Signer::new(Alg::Ed25519, sk.private_key_bytes(), key.clone()).unwrap();
+        let txn = |signed: SignedPayload| SignedTxn {
+            alg: Alg::Ed25519,
+            key: key.clone(),
+            pay: signed.pay_json,
+            sig: signed.sig,
+        };
+        let claim = ClaimPayload::builder()
+            .atom(id("core"))
+            .now(1000)
+            .owner(OwnerRef::single_key(signer.thumbprint()))
+            .pkg("cargo".into())
+            .src(vec![0; 20])
+            .signed_by(&signer)
+            .build()
+            .sign(&signer)
+            .unwrap();
+        let mut publish = PublishBuilder::for_atom(id("core"))
+            .claim(claim.czd.clone())
+            .version(RawVersion::new("1.0".into()))
+            .dig(Vec::new())
+            .src(vec![0; 20])
+            .path(String::new())
+            .now(2000)
+            .signed_by(&signer)
+            .build();
+        publish.content_hash = Some(content_hash(&[]).unwrap().to_vec());
+        let signed = SignedVersion {
+            publish: txn(publish.sign(&signer).unwrap()),
+            claims: vec![txn(claim)],
+        };
+        let entry = |version: &str, signed: Option<&SignedVersion>| {
+            let version = RawVersion::new(version.to_owned());
+            let entry = SignedEntry::new(Entry {
+                id: id("core"),
+                versions: vec![Version(version.clone(), None)],
+            });
+            match signed {
+                Some(signed) => entry.sign(version, signed.clone()),
+                None => entry,
+            }
+        };
+
+        let verified = VerifiedSource::new(Signing(vec![entry("1.0", Some(&signed))]));
+        assert!(block_on(verified.resolve(&id("core"))).unwrap().is_some());
+        assert!(
+            block_on(verified.content(&id("core"), &[]))
+                .unwrap()
+                .is_some()
+        );
+        assert!(
+            block_on(verified.content(&id("core"), &[1]))
+                .unwrap()
+                .is_none(),
+            "no version has that digest"
+        );
+        let untrusted =
+            verified.policy(SignaturePolicy::new().trust(Thumbprint::from_bytes(vec![9; 32])));
+        assert!(matches!(
+            block_on(untrusted.resolve(&id("core"))),
+            Err(VerifiedSourceError::Refused { .. })
+        ));
+
+        let relabeled = VerifiedSource::new(Signing(vec![entry("2.0", Some(&signed))]));
+        assert!(matches!(
+            block_on(relabeled.resolve(&id("core"))),
+            Err(VerifiedSourceError::Mismatch {
+                what: "version",
+                ..
+            })
+        ));
+
+        let unsigned = VerifiedSource::new(Signing(vec![entry("1.0", None)]));
+        assert!(matches!(
+            block_on(unsigned.resolve(&id("core"))),
+            Err(VerifiedSourceError::Unsigned { .. })
+        ));
+        let dev = unsigned.policy(SignaturePolicy::new().admit_unsigned());
+        assert!(block_on(dev.resolve(&id("core"))).unwrap().is_some());
+        assert!(
+            block_on(dev.content(&id("core"), &[])).unwrap().is_none(),
+            "no verified publish vouches for the content"
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn verified_source_resolves_versions_published_before_their_claim_lapsed() {
+        use atom_id::{ClaimPayload, PublishBuilder, SignedPayload, Signer};
+
+        let sk = coz_rs::SigningKey::<coz_rs::Ed25519>::generate();
+        let key = sk.verifying_key().public_key_bytes().to_vec();
+        let signer = Signer::new(Alg::Ed25519, sk.private_key_bytes(), key.clone()).unwrap();
+        let txn = |signed: SignedPayload| SignedTxn {
+            alg: Alg::Ed25519,
+            key: key.clone(),
+            pay: signed.pay_json,
+            sig: signed.sig,
+        };
+        // Lapsed since 1970, by any clock this test runs under.
+        let claim = ClaimPayload::builder()
+            .atom(id("core"))
+            .now(1000)
+            .exp(3000)
+            .owner(OwnerRef::single_key(signer.thumbprint()))
+            .pkg("cargo".into())
+            .src(vec![0; 20])
+            .signed_by(&signer)
+            .build()
+            .sign(&signer)
+            .unwrap();
+        let source_of = |version: &str, now| {
+            let version = RawVersion::new(version.to_owned());
+            let publish = PublishBuilder::for_atom(id("core"))
+                .claim(claim.czd.clone())
+                .version(version.clone())
+                .dig(Vec::new())
+                .src(vec![0; 20])
+                .path(String::new())
+                .now(now)
+                .signed_by(&signer)
+                .build();
+            let signed = SignedVersion {
+                publish: txn(publish.sign(&signer).unwrap()),
+                claims: vec![txn(claim.clone())],
+            };
+            let entry = SignedEntry::new(Entry {
+                id: id("core"),
+                versions: vec![Version(version.clone(), None)],
+            });
+            VerifiedSource::new(Signing(vec![entry.sign(version, signed)]))
+        };
+
+        let before = source_of("1.0", 2000);
+        assert!(block_on(before.resolve(&id("core"))).unwrap().is_some());
+        let after = source_of("2.0", 3000);
+        assert!(matches!(
+            block_on(after.resolve(&id("core"))),
+            Err(VerifiedSourceError::Invalid {
+                error: VerifyError::Expired { exp: 3000 },
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn catalogs_satisfy_the_conformance_laws() {
+        let upstream = Catalog::of(&[("core", &["1.0", "2.0"]), ("util", &["0.1"])]);
+        let store = Catalog::of(&[("core", &["1.0"]), ("local", &["0.0"])]);
+        block_on(async {
+            conformance::assert_source_laws(&upstream).await;
+            conformance::assert_content_laws(&upstream).await;
+            conformance::assert_store_accumulation(&store, &upstream).await;
+            let mirror = Catalog::of(&[]);
+            mirror.ingest(&upstream).await.unwrap();
+            conformance::assert_content_bisimilar(&upstream, &mirror).await;
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "more than once")]
+    fn conformance_catches_a_source_listing_an_atom_twice() {
+        let twice = Catalog::of(&[("core", &["1.0"]), ("core", &["1.0"])]);
+        block_on(conformance::assert_source_laws(&twice));
+    }
+
+    /// Blobs in memory, under their BLAKE3 digests.
+    struct Blobs(Mutex<Vec<(Vec<u8>, Vec<u8>)>>);
+
+    impl AtomBlobs for Blobs {
+        type Blob = io::Cursor<Vec<u8>>;
+        type Error = io::Error;
+
+        async fn get_blob(&self, dig: &[u8]) -> Result<Option<Self::Blob>, Self::Error> {
+            let blobs = self.0.lock().unwrap();
+            Ok(blobs
+                .iter()
+                .find(|(d, _)| d == dig)
+                .map(|(_, bytes)| io::Cursor::new(bytes.clone())))
+        }
+
+        async fn put_blob(
+            &self,
+            data: &mut (impl io::Read + Send),
+        ) -> Result<Vec<u8>, Self::Error> {
+            let mut bytes = Vec::new();
+            data.read_to_end(&mut bytes)?;
+            let dig = blake3::hash(&bytes).as_bytes().to_vec();
+            let mut blobs = self.0.lock().unwrap();
+            if !blobs.iter().any(|(d, _)| *d == dig) {
+                blobs.push((dig.clone(), bytes));
+            }
+            Ok(dig)
+        }
+    }
+
+    #[test]
+    fn blobs_round_trip_and_default_has_blob_checks_presence() {
+        let blobs = Blobs(Mutex::new(Vec::new()));
+        block_on(conformance::assert_blob_round_trip(&blobs, b"fn main() {}"));
+        block_on(conformance::assert_blob_round_trip(&blobs, b""));
+        assert_eq!(blobs.0.lock().unwrap().len(), 2);
+        assert!(!block_on(blobs.has_blob(&[0; 32])).unwrap());
+    }
+
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn instrumented_source_counts_hits_misses_and_ingested_versions() {
+        use metrics_util::debugging::{DebugValue, DebuggingRecorder};
+
+        let recorder = DebuggingRecorder::new();
+        let snapshotter = recorder.snapshotter();
+        let store = InstrumentedSource::new(Catalog::of(&[("core", &["1.0"])]), "local");
+        metrics::with_local_recorder(&recorder, || {
+            assert!(block_on(store.resolve(&id("core"))).unwrap().is_some());
+            assert!(block_on(store.resolve(&id("gone"))).unwrap().is_none());
+            let upstream = Catalog::of(&[("core", &["1.0", "2.0"])]);
+            block_on(store.ingest(&upstream)).unwrap();
+        });
+
+        let counters: Vec<(String, Vec<String>, u64)> = snapshotter
+            .snapshot()
+            .into_vec()
+            .into_iter()
+            .filter_map(|(key, _, _, value)| match value {
+                DebugValue::Counter(n) => Some((
+                    key.key().name().to_owned(),
+                    key.key().labels().map(|l| l.value().to_owned()).collect(),
+                    n,
+                )),
+                _ => None,
+            })
+            .collect();
+        let count = |name: &str, label: &str| {
+            counters
+                .iter()
+                .find(|(n, labels, _)| n == name && labels.iter().any(|l| l == label))
+                .map(|(.., n)| *n)
+        };
+        assert_eq!(count("atom_resolve_total", "hit"), Some(1));
+        assert_eq!(count("atom_resolve_total", "miss"), Some(1));
+        assert_eq!(count("atom_ingest_versions_total", "stored"), Some(1));
+        assert_eq!(count("atom_ingest_versions_total", "skipped"), Some(1));
+    }
+
+    /// A catalog with its own `discover_page` and `since`, answering with
+    /// atoms it does not hold — a wrapper falling back on the defaults
+    /// would list what it holds instead.
+    struct Paged<C>(C);
+
+    impl<C: AtomSource> AtomSource for Paged<C> {
+        type Entry = C::Entry;
+        type Error = C::Error;
+
+        async fn resolve(&self, id: &AtomId) -> Result<Option<Self::Entry>, Self::Error> {
+            self.0.resolve(id).await
+        }
+
+        async fn discover(&self, query: &str) -> Result<Vec<AtomId>, Self::Error> {
+            self.0.discover(query).await
+        }
+
+        async fn discover_page(
+            &self,
+            _query: &str,
+            _cursor: Option<&Cursor>,
+            _limit: usize,
+        ) -> Result<DiscoverPage, Self::Error> {
+            Ok((vec![id("paged")], None))
+        }
+
+        async fn since(&self, _checkpoint: Option<&Checkpoint>) -> Result<Changes, Self::Error> {
+            Ok((vec![id("changed")], Checkpoint::new("log")))
+        }
+    }
+
+    impl<C: AtomContent> AtomContent for Paged<C> {
+        async fn content(
+            &self,
+            id: &AtomId,
+            dig: &[u8],
+        ) -> Result<Option<Vec<ContentEntry>>, Self::Error> {
+            self.0.content(id, dig).await
+        }
+    }
+
+    impl<C: AtomStore> AtomStore for Paged<C> {
+        async fn ingest_with_progress<S: AtomContent>(
+            &self,
+            source: &S,
+            observer: &mut impl IngestObserver,
+        ) -> Result<IngestCounts, Self::Error> {
+            self.0.ingest_with_progress(source, observer).await
+        }
+
+        async fn ingest_filtered<S: AtomContent>(
+            &self,
+            source: &S,
+            filter: &IngestFilter,
+        ) -> Result<(), Self::Error> {
+            self.0.ingest_filtered(source, filter).await
+        }
+
+        async fn contains(&self, id: &(dyn AtomIdKey + Sync)) -> Result<bool, Self::Error> {
+            self.0.contains(id).await
+        }
+    }
+
+    /// Whether `source` answers `discover_page` and `since` with the
+    /// [`Paged`] source's own answers.
+    fn reaches_paged<S: AtomSource>(source: &S) -> bool {
+        let (page, _) = block_on(source.discover_page("", None, 10)).unwrap();
+        let (changed, _) = block_on(source.since(None)).unwrap();
+        page == [id("paged")] && changed == [id("changed")]
+    }
+
+    #[test]
+    fn adapters_forward_overridden_listings() {
+        let paged = || Paged(Catalog::of(&[("core", &["1.0"])]));
+        let hour = Duration::from_secs(3600);
+        let no_wait = |_: Duration| std::future::ready(());
+
+        assert!(reaches_paged(&paged()));
+        assert!(!reaches_paged(&paged().0), "the defaults list the catalog");
+        assert!(reaches_paged(&CachedSource::new(paged(), hour)));
+        assert!(reaches_paged(
+            &TieredSource::new().tier(paged(), TierPolicy::FailFast)
+        ));
+        assert!(reaches_paged(&RetryingSource::new(paged(), no_wait)));
+        assert!(reaches_paged(&RateLimited::new(
+            paged(),
+            no_wait,
+            Limit::per_second(1000)
+        )));
+        assert!(reaches_paged(&LockedStore::new(paged())));
+        assert!(reaches_paged(&AuditedRegistry::new(
+            paged(),
+            Mutex::new(Vec::<AuditRecord>::new()),
+            "ci"
+        )));
+        assert!(reaches_paged(&ReadThroughStore::new(
+            paged(),
+            Catalog::of(&[])
+        )));
+        #[cfg(feature = "serde")]
+        assert!(reaches_paged(&VerifiedSource::new(Paged(Signing(
+            Vec::new()
+        )))));
+        #[cfg(feature = "metrics")]
+        assert!(reaches_paged(&InstrumentedSource::new(paged(), "paged")));
+    }
+
+    /// A registry that takes publishes and refuses every other write.
+    #[cfg(feature = "async-registry")]
+    struct Ledger(Catalog);
+
+    #[cfg(feature = "async-registry")]
+    impl AtomSource for Ledger {
+        type Entry = Entry;
+        type Error = io::Error;
+
+        async fn resolve(&self, id: &AtomId) -> Result<Option<Entry>, Self::Error> {
+            self.0.resolve(id).await
+        }
+
+        async fn discover(&self, query: &str) -> Result<Vec<AtomId>, Self::Error> {
+            self.0.discover(query).await
+        }
+    }
+
+    #[cfg(feature = "async-registry")]
+    impl AtomRegistry for Ledger {
+        fn claim(&self, _id: &AtomId, _owner: &OwnerRef) -> Result<Czd, Self::Error> {
+            Err(io::ErrorKind::Unsupported.into())
+        }
+
+        fn publish(
+            &self,
+            id: &AtomId,
+            _claim: &Czd,
+            version: &RawVersion,
+            _dig: &[u8],
+            _src: &[u8],
+            _path: &str,
+        ) -> Result<(), Self::Error> {
+            self.0.take(id, version);
+            Ok(())
+        }
+
+        fn yank(
+            &self,
+            _id: &AtomId,
+            _version: &RawVersion,
+            _claim: &Czd,
+        ) -> Result<Czd, Self::Error> {
+            Err(io::ErrorKind::Unsupported.into())
+        }
+
+        fn charter(
+            &self,
+            _owner: &[OwnerRef],
+            _src: &[u8],
+            _prior: Option<&Czd>,
+        ) -> Result<Czd, Self::Error> {
+            Err(io::ErrorKind::Unsupported.into())
+        }
+    }
+
+    #[cfg(feature = "async-registry")]
+    #[test]
+    fn every_registry_is_an_async_registry_without_ambiguity() {
+        let ledger = Ledger(Catalog::of(&[]));
+        let claim = Czd::from_bytes(vec![0; 32]);
+        let v = |s: &str| RawVersion::new(s.to_owned());
+        // Both traits are in scope: the sync write still resolves.
+        ledger
+            .publish(&id("core"), &claim, &v("1.0"), &[], &[], "")
+            .unwrap();
+        block_on(ledger.publish_async(&id("core"), &claim, &v("1.1"), &[], &[], "")).unwrap();
+        assert_eq!(
+            block_on(ledger.versions(&id("core"))).unwrap(),
+            [v("1.0"), v("1.1")]
+        );
+        assert!(block_on(ledger.yank_async(&id("core"), &v("1.0"), &claim)).is_err());
+    }
 }
@@ -0,0 +1,448 @@
+//! A readers-writer lock over a store's operations: [`LockedStore`].
+
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::task::{Context, Poll, Waker};
+
+use crate::{
+    Anchor, AtomContent, AtomId, AtomIdKey, AtomSource, AtomStore, AtomStoreMut, AtomStoreResume,
+    Changes, Checkpoint, ContentEntry, Cursor, DiscoverPage, IngestCheckpoint, IngestCounts,
+    IngestFilter, IngestObserver, RawVersion, SnapshotError, VersionMatch, VersionScheme,
+};
+
+/// A store whose writes exclude every other call, for a backend whose
+/// reads may run side by side but not alongside a write — one that keeps
+/// its index in a single file, say, and rewrites it as it ingests.
+///
+/// Reads run side by side. An [`ingest`](AtomStore::ingest), an
+/// [`import`](AtomStore::import), a removal or a resumed ingest waits for
+/// the reads under way, then runs alone: a
+/// [`resolve`](AtomSource::resolve) never sees an ingest half done, but
+/// waits for the whole of it. Reads arriving while a write waits queue
+/// behind it, so a steady stream of them cannot hold writes off forever.
+///
+/// A backend that is single-threaded internally — one wrapping a
+/// connection that serves a single request at a time, say — cannot run
+/// even two reads side by side. Made [`exclusive`](Self::exclusive), the
+/// store runs every call alone, reads included.
+///
+/// The backend is still an [`AtomStore`], so `Send + Sync`: the lock orders
+/// its operations, it does not make a backend that is not `Sync` shareable.
+/// The lock is the wrapper's own: calls made on [`store`](Self::store)
+/// directly bypass it.
+///
+/// ```
+/// use atom_core::{AtomStore, LockedStore};
+///
+/// fn shared<S: AtomStore>(store: S) -> impl AtomStore {
+///     LockedStore::new(store)
+/// }
+/// ```
+#[derive(Debug)]
+pub struct LockedStore<S> {
+    store: S,
+    lock: Mutex<Lock>,
+    /// Whether reads, too, run alone.
+    exclusive: bool,
+}
+
+/// Who holds a [`LockedStore`], and who waits for it.
+#[derive(Debug, Default)]
+struct Lock {
+    readers: usize,
+    writing: bool,
+    writers_waiting: usize,
+    waiters: Vec<Waker>,
+}
+
+impl<S: AtomStore> LockedStore<S> {
+    /// Serialize writes to `store` against every other call.
+    pub fn new(store: S) -> Self {
+        Self {
+            store,
+            lock: Mutex::new(Lock::default()),
+            exclusive: false,
+        }
+    }
+
+    /// Run every call alone, reads included, for a backend that cannot
+    /// serve two calls at once.
+    #[must_use]
+    pub fn exclusive(mut self) -> Self {
+        self.exclusive = true;
+        self
+    }
+
+    /// The store behind the lock.
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    /// The store, unwrapped.
+    pub fn into_store(self) -> S {
+        self.store
+    }
+}
+
+impl<S> LockedStore<S> {
+    /// A read turn, which is a write turn when the store is exclusive.
+    fn read(&self) -> Acquire<'_> {
+        Acquire::new(&self.lock, self.exclusive)
+    }
+
+    fn write(&self) -> Acquire<'_> {
+        Acquire::new(&self.lock, true)
+    }
+}
+
+fn lock(lock: &Mutex<Lock>) -> MutexGuard<'_, Lock> {
+    // Every update leaves the state whole.
+    lock.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Wake everyone waiting, once the state lock is released.
+fn wake_all(mut state: MutexGuard<'_, Lock>) {
+    let waiters = std::mem::take(&mut state.waiters);
+    drop(state);
+    waiters.into_iter().for_each(Waker::wake);
+}
+
+/// Waiting for a read or a write turn on a [`LockedStore`].
+struct Acquire<'a> {
+    lock: &'a Mutex<Lock>,
+    write: bool,
+    /// Whether this is counted among the waiters.
+    queued: bool,
+}
+
+impl<'a> Acquire<'a> {
+    fn new(lock: &'a Mutex<Lock>, write: bool) -> Self {
+        Self {
+            lock,
+            write,
+            queued: false,
+        }
+    }
+}
+
+impl<'a> Future for Acquire<'a> {
+    type Output = Held<'a>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Held<'a>> {
+        let this = &mut *self;
+        let mut state = lock(this.lock);
+        let free = if this.write {
+            !state.writing && state.readers == 0
+        } else {
+            !state.writing && state.writers_waiting == 0
+        };
+        if free {
+            if this.write {
+                state.writing = true;
+                if this.queued {
+                    state.writers_waiting -= 1;
+                }
+            } else {
+                state.readers += 1;
+            }
+            this.queued = false;
+            return Poll::Ready(Held {
+                lock: this.lock,
+                write: this.write,
+            });
+        }
+        if this.write && !this.queued {
+            state.writers_waiting += 1;
+        }
+        this.queued = true;
+        if !state.waiters.iter().any(|w| w.will_wake(cx.waker())) {
+            state.waiters.push(cx.waker().clone());
+        }
+        Poll::Pending
+    }
+}
+
+impl Drop for Acquire<'_> {
+    fn drop(&mut self) {
+        // A writer given up on no longer holds readers back.
+        if self.write && self.queued {
+            let mut state = lock(self.lock);
+            state.writers_waiting -= 1;
+            wake_all(state);
+        }
+    }
+}
+
+/// A read or write turn on a [`LockedStore`], given back on drop.
+struct Held<'a> {
+    lock: &'a Mutex<Lock>,
+    write: bool,
+}
+
+impl Drop for Held<'_> {
+    fn drop(&mut self) {
+        let mut state = lock(self.lock);
+        if self.write {
+            state.writing = false;
+        } else {
+            state.readers -= 1;
+        }
+        if state.readers == 0 {
+            wake_all(state);
+        }
+    }
+}
+
+impl<S: AtomStore> AtomSource for LockedStore<S> {
+    type Entry = S::Entry;
+    type Error = S::Error;
+
+    async fn resolve(&self, id: &AtomId) -> Result<Option<Self::Entry>, Self::Error> {
+        let _held = self.read().await;
+        self.store.resolve(id).await
+    }
+
+    async fn resolve_many(&self, ids: &[AtomId]) -> Result<Vec<Option<Self::Entry>>, Self::Error>
+    where
+        Self::Entry: Send,
+    {
+        let _held = self.read().await;
+        self.store.resolve_many(ids).await
+    }
+
+    async fn versions(&self, id: &AtomId) -> Result<Vec<RawVersion>, Self::Error> {
+        let _held = self.read().await;
+        self.store.versions(id).await
+    }
+
+    async fn resolve_version<V>(
+        &self,
+        id: &AtomId,
+        scheme: &V,
+        req: &V::Requirement,
+    ) -> Result<Option<VersionMatch<Self::Entry>>, Self::Error>
+    where
+        V: VersionScheme + Sync,
+        V::Requirement: Sync,
+    {
+        let _held = self.read().await;
+        self.store.resolve_version(id, scheme, req).await
+    }
+
+    async fn discover(&self, query: &str) -> Result<Vec<AtomId>, Self::Error> {
+        let _held = self.read().await;
+        self.store.discover(query).await
+    }
+
+    async fn discover_page(
+        &self,
+        query: &str,
+        cursor: Option<&Cursor>,
+        limit: usize,
+    ) -> Result<DiscoverPage, Self::Error> {
+        let _held = self.read().await;
+        self.store.discover_page(query, cursor, limit).await
+    }
+
+    async fn since(&self, checkpoint: Option<&Checkpoint>) -> Result<Changes, Self::Error> {
+        let _held = self.read().await;
+        self.store.since(checkpoint).await
+    }
+}
+
+impl<S: AtomStore> AtomContent for LockedStore<S> {
+    async fn content(
+        &self,
+        id: &AtomId,
+        dig: &[u8],
+    ) -> Result<Option<Vec<ContentEntry>>, Self::Error> {
+        let _held = self.read().await;
+        self.store.content(id, dig).await
+    }
+}
+
+/// An export holds a read turn throughout, so it is one consistent
+/// snapshot of the store.
+impl<S: AtomStore> AtomStore for LockedStore<S> {
+    async fn ingest<T: AtomContent>(&self, source: &T) -> Result<(), Self::Error> {
+        let _held = self.write().await;
+        self.store.ingest(source).await
+    }
+
+    async fn ingest_with_progress<T: AtomContent>(
+        &self,
+        source: &T,
+        observer: &mut impl IngestObserver,
+    ) -> Result<IngestCounts, Self::Error> {
+        let _held = self.write().await;
+        self.store.ingest_with_progress(source, observer).await
+    }
+
+    async fn export(
+        &self,
+        writer: &mut (impl io::Write + Send),
+    ) -> Result<(), SnapshotError<Self::Error>> {
+        let _held = self.read().await;
+        self.store.export(writer).await
+    }
+
+    async fn import(
+        &self,
+        reader: &mut (impl io::Read + Send),
+    ) -> Result<IngestCounts, SnapshotError<Self::Error>> {
+        let _held = self.write().await;
+        self.store.import(reader).await
+    }
+
+    async fn ingest_filtered<T: AtomContent>(
+        &self,
+        source: &T,
+        filter: &IngestFilter,
+    ) -> Result<(), Self::Error> {
+        let _held = self.write().await;
+        self.store.ingest_filtered(source, filter).await
+    }
+
+    async fn contains(&self, id: &(dyn AtomIdKey + Sync)) -> Result<bool, Self::Error> {
+        let _held = self.read().await;
+        self.store.contains(id).await
+    }
+
+    async fn resolve_key(
+        &self,
+        id: &(dyn AtomIdKey + Sync),
+    ) -> Result<Option<Self::Entry>, Self::Error> {
+        let _held = self.read().await;
+        self.store.resolve_key(id).await
+    }
+
+    async fn anchors(&self) -> Result<Vec<Anchor>, Self::Error> {
+        let _held = self.read().await;
+        self.store.anchors().await
+    }
+}
+
+/// A [`remove`](AtomStoreMut::remove) or [`gc`](AtomStoreMut::gc) holds
+/// one write turn for all the versions it removes.
+impl<S: AtomStoreMut> AtomStoreMut for LockedStore<S> {
+    async fn remove_version(&self, id: &AtomId, version: &RawVersion) -> Result<bool, Self::Error> {
+        let _held = self.write().await;
+        self.store.remove_version(id, version).await
+    }
+
+    async fn remove(&self, id: &AtomId) -> Result<bool, Self::Error> {
+        let _held = self.write().await;
+        self.store.remove(id).await
+    }
+
+    async fn gc(&self, roots: &[AtomId]) -> Result<Vec<AtomId>, Self::Error> {
+        let _held = self.write().await;
+        self.store.gc(roots).await
+    }
+}
+
+impl<S: AtomStoreResume> AtomStoreResume for LockedStore<S> {
+    async fn ingest_resumable<T: AtomContent>(
+        &self,
+        source: &T,
+        checkpoint: &mut IngestCheckpoint,
+    ) -> Result<IngestCounts, Self::Error> {
+        let _held = self.write().await;
+        self.store.ingest_resumable(source, checkpoint).await
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::task::Wake;
+
+    use super::*;
+
+    /// Counts its wakes.
+    #[derive(Default)]
+    struct Wakes(AtomicUsize);
+
+    impl Wake for Wakes {
+        fn wake(self: Arc<Self>) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    fn poll<'a>(acquire: &mut Acquire<'a>, waker: &Waker) -> Poll<Held<'a>> {
+        Pin::new(acquire).poll(&mut Context::from_waker(waker))
+    }
+
+    #[test]
+    fn a_waiting_writer_holds_off_later_readers() {
+        let lock = Mutex::new(Lock::default());
+        let waker = Waker::noop();
+        let first = poll(&mut Acquire::new(&lock, false), waker);
+        assert!(first.is_ready());
+
+        let mut writer = Acquire::new(&lock, true);
+        assert!(poll(&mut writer, waker).is_pending(), "a read is under way");
+        let mut later = Acquire::new(&lock, false);
+        assert!(
+            poll(&mut later, waker).is_pending(),
+            "queued behind the writer"
+        );
+
+        drop(first);
+        let written = poll(&mut writer, waker);
+        assert!(written.is_ready());
+        assert!(poll(&mut later, waker).is_pending(), "the write runs alone");
+        drop(written);
+        assert!(poll(&mut later, waker).is_ready());
+    }
+
+    #[test]
+    fn an_exclusive_store_runs_reads_alone() {
+        let shared = LockedStore {
+            store: (),
+            lock: Mutex::new(Lock::default()),
+            exclusive: false,
+        };
+        let first = poll(&mut shared.read(), Waker::noop());
+        assert!(poll(&mut shared.read(), Waker::noop()).is_ready());
+        drop(first);
+
+        let exclusive = LockedStore {
+            exclusive: true,
+            ..shared
+        };
+        let first = poll(&mut exclusive.read(), Waker::noop());
+        assert!(first.is_ready());
+        let mut second = exclusive.read();
+        assert!(poll(&mut second, Waker::noop()).is_pending());
+        drop(first);
+        assert!(poll(&mut second, Waker::noop()).is_ready());
+    }
+
+    #[test]
+    fn a_writer_given_up_lets_queued_readers_in() {
+        let lock = Mutex::new(Lock::default());
+        let first = poll(&mut Acquire::new(&lock, false), Waker::noop());
+        assert!(first.is_ready());
+        let mut writer = Acquire::new(&lock, true);
+        assert!(poll(&mut writer, Waker::noop()).is_pending());
+
+        let wakes = Arc::new(Wakes::default());
+        let waker = Waker::from(wakes.clone());
+        let mut later = Acquire::new(&lock, false);
+        assert!(poll(&mut later, &waker).is_pending());
+
+        drop(writer);
+        assert_eq!(wakes.0.load(Ordering::SeqCst), 1, "the reader is woken");
+        assert_eq!(lock.lock().unwrap().writers_waiting, 0);
+        assert!(poll(&mut later, &waker).is_ready());
+        drop(first);
+    }
+}
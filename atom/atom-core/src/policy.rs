@@ -0,0 +1,141 @@
+//! What a store demands of the signatures it takes in: [`VerifyPolicy`].
+
+use std::sync::Arc;
+
+use crate::{Alg, AtomId, Thumbprint};
+
+/// What a store demands of an atom version's signatures, beyond their
+/// verifying, before [`ingest`](crate::AtomStore::ingest) takes the version
+/// in or [`resolve`](crate::AtomSource::resolve) hands it out.
+///
+/// A policy only narrows: it is consulted after every signature verifies
+/// and chains to its owner, so no policy admits a badly-signed version.
+/// The defaults are strict — every algorithm and signer the protocol
+/// verifies, and no unsigned versions — so the accumulation guarantee
+/// cannot launder an unsigned version into a store that did not ask for
+/// them.
+pub trait VerifyPolicy: Send + Sync {
+    /// Whether signatures made with `alg` are accepted.
+    fn admits_alg(&self, alg: Alg) -> bool {
+        let _ = alg;
+        true
+    }
+
+    /// Whether `signer` is trusted to sign for `id`.
+    fn trusts(&self, id: &AtomId, signer: &Thumbprint) -> bool {
+        let _ = (id, signer);
+        true
+    }
+
+    /// Whether versions with no signatures — dev versions — are accepted.
+    fn admits_unsigned(&self) -> bool {
+        false
+    }
+}
+
+impl<P: VerifyPolicy + ?Sized> VerifyPolicy for Arc<P> {
+    fn admits_alg(&self, alg: Alg) -> bool {
+        (**self).admits_alg(alg)
+    }
+
+    fn trusts(&self, id: &AtomId, signer: &Thumbprint) -> bool {
+        (**self).trusts(id, signer)
+    }
+
+    fn admits_unsigned(&self) -> bool {
+        (**self).admits_unsigned()
+    }
+}
+
+/// A [`VerifyPolicy`] built from allow-lists.
+///
+/// The empty policy is the strict default; each builder method relaxes or
+/// narrows it.
+///
+/// ```
+/// use atom_core::{Alg, SignaturePolicy, VerifyPolicy};
+///
+/// let policy = SignaturePolicy::new().algs([Alg::Ed25519]);
+/// assert!(policy.admits_alg(Alg::Ed25519));
+/// assert!(!policy.admits_alg(Alg::ES256));
+/// assert!(!policy.admits_unsigned());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SignaturePolicy {
+    algs: Option<Vec<Alg>>,
+    signers: Option<Vec<Thumbprint>>,
+    unsigned: bool,
+}
+
+impl SignaturePolicy {
+    /// The strict default.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Accept signatures made with `algs` only.
+    #[must_use]
+    pub fn algs(mut self, algs: impl IntoIterator<Item = Alg>) -> Self {
+        self.algs = Some(algs.into_iter().collect());
+        self
+    }
+
+    /// Trust `signer`. Once any signer is trusted, only trusted signers
+    /// are.
+    #[must_use]
+    pub fn trust(mut self, signer: Thumbprint) -> Self {
+        self.signers.get_or_insert_with(Vec::new).push(signer);
+        self
+    }
+
+    /// Accept unsigned versions.
+    #[must_use]
+    pub fn admit_unsigned(mut self) -> Self {
+        self.unsigned = true;
+        self
+    }
+}
+
+impl VerifyPolicy for SignaturePolicy {
+    fn admits_alg(&self, alg: Alg) -> bool {
+        self.algs.as_ref().is_none_or(|algs| algs.contains(&alg))
+    }
+
+    fn trusts(&self, _id: &AtomId, signer: &Thumbprint) -> bool {
+        self.signers
+            .as_ref()
+            .is_none_or(|signers| signers.contains(signer))
+    }
+
+    fn admits_unsigned(&self) -> bool {
+        self.unsigned
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Anchor, Label};
+
+    #[test]
+    fn signature_policy_starts_strict_and_narrows() {
+        let id = AtomId::new(Anchor::new(vec![1; 32]), Label::try_from("pkg").unwrap());
+        let alice = Thumbprint::from_bytes(vec![1; 32]);
+        let bob = Thumbprint::from_bytes(vec![2; 32]);
+
+        let strict = SignaturePolicy::new();
+        assert!(strict.admits_alg(Alg::ES256));
+        assert!(strict.trusts(&id, &bob));
+        assert!(!strict.admits_unsigned());
+
+        let pinned = SignaturePolicy::new().trust(alice.clone()).admit_unsigned();
+        assert!(pinned.trusts(&id, &alice));
+        assert!(!pinned.trusts(&id, &bob));
+        assert!(pinned.admits_unsigned());
+    }
+}
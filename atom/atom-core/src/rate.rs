@@ -0,0 +1,330 @@
+//! Token-bucket limits on calls to a source or registry: [`RateLimited`].
+
+use std::convert::Infallible;
+use std::future::Future;
+use std::io;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+
+use crate::{
+    AtomContent, AtomId, AtomRegistry, AtomSource, Changes, Checkpoint, ContentEntry, Cursor, Czd,
+    DiscoverPage, OwnerRef, RawVersion, Sleep, VersionMatch, VersionScheme,
+};
+
+/// What a backend's error says about calling the backend again.
+///
+/// Implemented by the error types of backends a [`RateLimited`] wraps.
+/// The default says nothing, which suits a backend with no notion of
+/// being called too often.
+pub trait ErrorClass {
+    /// How long the backend asked its callers to hold off — an HTTP
+    /// `Retry-After`, say — or `None` if it asked nothing.
+    fn retry_after(&self) -> Option<Duration> {
+        None
+    }
+}
+
+impl ErrorClass for io::Error {}
+
+impl ErrorClass for Infallible {}
+
+/// A token bucket: `burst` calls at once, then one more every `every`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Limit {
+    /// Calls allowed back to back, with the bucket full.
+    pub burst: u32,
+    /// How long the bucket takes to regain one call.
+    pub every: Duration,
+}
+
+impl Limit {
+    /// `n` calls a second, up to `n` of them back to back.
+    #[must_use]
+    pub fn per_second(n: u32) -> Self {
+        Self {
+            burst: n,
+            every: Duration::from_secs(1) / n.max(1),
+        }
+    }
+}
+
+/// The calls a [`RateLimited`] limits, each against a bucket of its own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RateOp {
+    /// [`resolve`](AtomSource::resolve), and the lookups built on it.
+    Resolve,
+    /// [`discover`](AtomSource::discover), and the listings built on it.
+    Discover,
+    /// [`content`](AtomContent::content).
+    Content,
+    /// Every [`AtomRegistry`] write.
+    Write,
+}
+
+/// A source or registry whose callers are held to per-operation rates, so
+/// a runaway client built on these traits cannot flood the registry
+/// behind it.
+///
+/// Each [`RateOp`] draws on its own token bucket; a call finding its
+/// bucket empty waits until the bucket refills instead of failing. When
+/// the backend answers with an error whose [`ErrorClass::retry_after`] is
+/// set, every call waits that long before reaching it again — the error
+/// itself is returned, for a [`RetryingSource`](crate::RetryingSource)
+/// wrapped around this one to retry.
+///
+/// Reads wait with `sleep`. Registry writes are synchronous, so a write
+/// over its limit blocks the calling thread instead.
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use atom_core::{Limit, RateLimited, RateOp, Snapshot};
+///
+/// let source = RateLimited::new(
+///     Snapshot::new(),
+///     |_: Duration| std::future::ready(()),
+///     Limit::per_second(20),
+/// )
+/// .limit(RateOp::Content, Some(Limit::per_second(5)));
+/// ```
+pub struct RateLimited<S, Z> {
+    source: S,
+    sleep: Z,
+    state: Mutex<State>,
+}
+
+/// The buckets, by [`RateOp`], and when a backend's hold-off ends.
+#[derive(Debug)]
+struct State {
+    buckets: [Option<Bucket>; 4],
+    held_until: Option<Instant>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    limit: Limit,
+    /// Calls left; below zero, calls already waiting for a refill.
+    tokens: f64,
+    at: Instant,
+}
+
+impl Bucket {
+    fn new(limit: Limit) -> Self {
+        Self {
+            limit,
+            tokens: f64::from(limit.burst),
+            at: Instant::now(),
+        }
+    }
+
+    /// Take a call's token, returning how long until it is there.
+    fn take(&mut self, now: Instant) -> Duration {
+        if self.limit.every.is_zero() {
+            return Duration::ZERO;
+        }
+        let refilled = now.duration_since(self.at).as_secs_f64() / self.limit.every.as_secs_f64();
+        self.tokens = (self.tokens + refilled).min(f64::from(self.limit.burst));
+        self.at = now;
+        self.tokens -= 1.0;
+        if self.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            self.limit.every.mul_f64(-self.tokens)
+        }
+    }
+}
+
+impl<S, Z: Sleep> RateLimited<S, Z> {
+    /// Hold every operation on `source` to `limit`, waiting with `sleep`.
+    pub fn new(source: S, sleep: Z, limit: Limit) -> Self {
+        Self {
+            source,
+            sleep,
+            state: Mutex::new(State {
+                buckets: std::array::from_fn(|_| Some(Bucket::new(limit))),
+                held_until: None,
+            }),
+        }
+    }
+
+    /// Hold `op` to `limit` instead — `None` not to limit it at all.
+    #[must_use]
+    pub fn limit(self, op: RateOp, limit: Option<Limit>) -> Self {
+        self.lock().buckets[op as usize] = limit.map(Bucket::new);
+        self
+    }
+
+    /// The source behind the limits.
+    pub fn source(&self) -> &S {
+        &self.source
+    }
+
+    /// The source, unwrapped.
+    pub fn into_source(self) -> S {
+        self.source
+    }
+
+    /// Take a token for `op`, returning how long to wait before calling.
+    fn admit(&self, op: RateOp) -> Duration {
+        let now = Instant::now();
+        let mut state = self.lock();
+        let held = state
+            .held_until
+            .map_or(Duration::ZERO, |until| until.saturating_duration_since(now));
+        let refill = state.buckets[op as usize]
+            .as_mut()
+            .map_or(Duration::ZERO, |bucket| bucket.take(now));
+        held.max(refill)
+    }
+
+    /// Note the hold-off `result`'s error asks for, if any.
+    fn heed<T, E: ErrorClass>(&self, result: Result<T, E>) -> Result<T, E> {
+        if let Some(after) = result.as_ref().err().and_then(ErrorClass::retry_after) {
+            let until = Instant::now() + after;
+            let mut state = self.lock();
+            state.held_until = Some(state.held_until.map_or(until, |held| held.max(until)));
+        }
+        result
+    }
+
+    async fn call<T, E: ErrorClass>(
+        &self,
+        op: RateOp,
+        call: impl Future<Output = Result<T, E>>,
+    ) -> Result<T, E> {
+        let wait = self.admit(op);
+        if !wait.is_zero() {
+            self.sleep.sleep(wait).await;
+        }
+        self.heed(call.await)
+    }
+
+    fn write<T, E: ErrorClass>(&self, write: impl FnOnce() -> Result<T, E>) -> Result<T, E> {
+        let wait = self.admit(RateOp::Write);
+        if !wait.is_zero() {
+            std::thread::sleep(wait);
+        }
+        self.heed(write())
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        // Every update leaves the state whole.
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<S: AtomSource, Z: Sleep> AtomSource for RateLimited<S, Z>
+where
+    S::Error: ErrorClass,
+{
+    type Entry = S::Entry;
+    type Error = S::Error;
+
+    async fn resolve(&self, id: &AtomId) -> Result<Option<Self::Entry>, Self::Error> {
+        self.call(RateOp::Resolve, self.source.resolve(id)).await
+    }
+
+    /// One call, so one token, however many atoms it asks for.
+    async fn resolve_many(&self, ids: &[AtomId]) -> Result<Vec<Option<Self::Entry>>, Self::Error>
+    where
+        Self::Entry: Send,
+    {
+        self.call(RateOp::Resolve, self.source.resolve_many(ids))
+            .await
+    }
+
+    async fn versions(&self, id: &AtomId) -> Result<Vec<RawVersion>, Self::Error> {
+        self.call(RateOp::Resolve, self.source.versions(id)).await
+    }
+
+    async fn resolve_version<V>(
+        &self,
+        id: &AtomId,
+        scheme: &V,
+        req: &V::Requirement,
+    ) -> Result<Option<VersionMatch<Self::Entry>>, Self::Error>
+    where
+        V: VersionScheme + Sync,
+        V::Requirement: Sync,
+    {
+        let call = self.source.resolve_version(id, scheme, req);
+        self.call(RateOp::Resolve, call).await
+    }
+
+    async fn discover(&self, query: &str) -> Result<Vec<AtomId>, Self::Error> {
+        self.call(RateOp::Discover, self.source.discover(query))
+            .await
+    }
+
+    async fn discover_page(
+        &self,
+        query: &str,
+        cursor: Option<&Cursor>,
+        limit: usize,
+    ) -> Result<DiscoverPage, Self::Error> {
+        let call = self.source.discover_page(query, cursor, limit);
+        self.call(RateOp::Discover, call).await
+    }
+
+    async fn since(&self, checkpoint: Option<&Checkpoint>) -> Result<Changes, Self::Error> {
+        self.call(RateOp::Discover, self.source.since(checkpoint))
+            .await
+    }
+}
+
+impl<S: AtomContent, Z: Sleep> AtomContent for RateLimited<S, Z>
+where
+    S::Error: ErrorClass,
+{
+    async fn content(
+        &self,
+        id: &AtomId,
+        dig: &[u8],
+    ) -> Result<Option<Vec<ContentEntry>>, Self::Error> {
+        self.call(RateOp::Content, self.source.content(id, dig))
+            .await
+    }
+}
+
+impl<R: AtomRegistry, Z: Sleep> AtomRegistry for RateLimited<R, Z>
+where
+    R::Error: ErrorClass,
+{
+    fn claim(&self, id: &AtomId, owner: &OwnerRef) -> Result<Czd, Self::Error> {
+        self.write(|| self.source.claim(id, owner))
+    }
+
+    fn publish(
+        &self,
+        id: &AtomId,
+        claim: &Czd,
+        version: &RawVersion,
+        dig: &[u8],
+        src: &[u8],
+        path: &str,
+    ) -> Result<(), Self::Error> {
+        self.write(|| self.source.publish(id, claim, version, dig, src, path))
+    }
+
+    fn yank(&self, id: &AtomId, version: &RawVersion, claim: &Czd) -> Result<Czd, Self::Error> {
+        self.write(|| self.source.yank(id, version, claim))
+    }
+
+    fn charter(
+        &self,
+        owner: &[OwnerRef],
+        src: &[u8],
+        prior: Option<&Czd>,
+    ) -> Result<Czd, Self::Error> {
+        self.write(|| self.source.charter(owner, src, prior))
+    }
+}
+
+impl<S: std::fmt::Debug, Z> std::fmt::Debug for RateLimited<S, Z> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RateLimited")
+            .field("source", &self.source)
+            .field("state", &self.state)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,201 @@
+//! A local store filled on demand from a remote source:
+//! [`ReadThroughStore`].
+
+use crate::{
+    AtomContent, AtomId, AtomIdKey, AtomSource, AtomStore, Changes, Checkpoint, ContentEntry,
+    Cursor, DiscoverPage, IngestCounts, IngestFilter, IngestObserver, RawVersion, VersionMatch,
+    VersionScheme,
+};
+
+/// A store that, asked for an atom it lacks, ingests that one atom from a
+/// source and answers from itself — the lazy local cache of a remote
+/// registry.
+///
+/// Every answer comes from the store. On a
+/// [`resolve`](AtomSource::resolve) miss the atom goes through the store's
+/// own [`ingest_filtered`](AtomStore::ingest_filtered), so it is verified
+/// as any ingest verifies, and accumulates as any ingest accumulates: the
+/// entry returned is the store's, never the source's unchecked. An atom the
+/// source lacks too resolves to `None`.
+///
+/// Once the store has an atom, the source is not asked about it again;
+/// versions published or yanked upstream since arrive with
+/// [`refresh`](Self::refresh). [`discover`](AtomSource::discover) lists
+/// what the store holds, not what the source could supply, and so do its
+/// pages and [`since`](AtomSource::since). The other lookups fill a miss
+/// as `resolve` does; [`resolve_many`](AtomSource::resolve_many) fills
+/// every atom it misses in one ingest.
+///
+/// ```
+/// use atom_core::{AtomContent, AtomSource, AtomStore, ReadThroughStore};
+///
+/// fn cache<S: AtomStore, R: AtomContent>(store: S, remote: R) -> impl AtomSource {
+///     ReadThroughStore::new(store, remote)
+/// }
+/// ```
+#[derive(Debug)]
+pub struct ReadThroughStore<S, R> {
+    store: S,
+    source: R,
+}
+
+impl<S: AtomStore, R: AtomContent> ReadThroughStore<S, R> {
+    /// Answer from `store`, filling it from `source` on a miss.
+    pub fn new(store: S, source: R) -> Self {
+        Self { store, source }
+    }
+
+    /// The local store.
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    /// The source misses are filled from.
+    pub fn source(&self) -> &R {
+        &self.source
+    }
+
+    /// The store and the source, unwrapped.
+    pub fn into_parts(self) -> (S, R) {
+        (self.store, self.source)
+    }
+
+    /// Ingest `id` from the source again, taking in whatever was published
+    /// or yanked since the store last had it.
+    ///
+    /// # Errors
+    ///
+    /// The store's, if the ingest fails.
+    pub async fn refresh(&self, id: &AtomId) -> Result<(), S::Error> {
+        self.fill([id.clone()]).await
+    }
+
+    async fn fill(&self, ids: impl IntoIterator<Item = AtomId>) -> Result<(), S::Error> {
+        let only = IngestFilter::new().atoms(ids);
+        self.store.ingest_filtered(&self.source, &only).await
+    }
+}
+
+impl<S: AtomStore, R: AtomContent> AtomSource for ReadThroughStore<S, R> {
+    type Entry = S::Entry;
+    type Error = S::Error;
+
+    async fn resolve(&self, id: &AtomId) -> Result<Option<Self::Entry>, Self::Error> {
+        if let Some(entry) = self.store.resolve(id).await? {
+            return Ok(Some(entry));
+        }
+        self.refresh(id).await?;
+        self.store.resolve(id).await
+    }
+
+    async fn resolve_many(&self, ids: &[AtomId]) -> Result<Vec<Option<Self::Entry>>, Self::Error>
+    where
+        Self::Entry: Send,
+    {
+        let mut entries = self.store.resolve_many(ids).await?;
+        let missed: Vec<usize> = (0..ids.len()).filter(|&i| entries[i].is_none()).collect();
+        if missed.is_empty() {
+            return Ok(entries);
+        }
+        let asked: Vec<AtomId> = missed.iter().map(|&index| ids[index].clone()).collect();
+        self.fill(asked.iter().cloned()).await?;
+        let filled = self.store.resolve_many(&asked).await?;
+        for (index, entry) in missed.into_iter().zip(filled) {
+            entries[index] = entry;
+        }
+        Ok(entries)
+    }
+
+    async fn versions(&self, id: &AtomId) -> Result<Vec<RawVersion>, Self::Error> {
+        let versions = self.store.versions(id).await?;
+        if !versions.is_empty() {
+            return Ok(versions);
+        }
+        self.refresh(id).await?;
+        self.store.versions(id).await
+    }
+
+    /// Fills only a missing atom: one the store has, but with no version
+    /// matching, is not refreshed.
+    async fn resolve_version<V>(
+        &self,
+        id: &AtomId,
+        scheme: &V,
+        req: &V::Requirement,
+    ) -> Result<Option<VersionMatch<Self::Entry>>, Self::Error>
+    where
+        V: VersionScheme + Sync,
+        V::Requirement: Sync,
+    {
+        if let Some(found) = self.store.resolve_version(id, scheme, req).await? {
+            return Ok(Some(found));
+        }
+        if self.store.contains(id).await? {
+            return Ok(None);
+        }
+        self.refresh(id).await?;
+        self.store.resolve_version(id, scheme, req).await
+    }
+
+    async fn discover(&self, query: &str) -> Result<Vec<AtomId>, Self::Error> {
+        self.store.discover(query).await
+    }
+
+    async fn discover_page(
+        &self,
+        query: &str,
+        cursor: Option<&Cursor>,
+        limit: usize,
+    ) -> Result<DiscoverPage, Self::Error> {
+        self.store.discover_page(query, cursor, limit).await
+    }
+
+    async fn since(&self, checkpoint: Option<&Checkpoint>) -> Result<Changes, Self::Error> {
+        self.store.since(checkpoint).await
+    }
+}
+
+impl<S: AtomStore, R: AtomContent> AtomContent for ReadThroughStore<S, R> {
+    async fn content(
+        &self,
+        id: &AtomId,
+        dig: &[u8],
+    ) -> Result<Option<Vec<ContentEntry>>, Self::Error> {
+        self.store.content(id, dig).await
+    }
+}
+
+/// Ingests go straight to the store.
+impl<S: AtomStore, R: AtomContent> AtomStore for ReadThroughStore<S, R> {
+    async fn ingest_with_progress<T: AtomContent>(
+        &self,
+        source: &T,
+        observer: &mut impl IngestObserver,
+    ) -> Result<IngestCounts, Self::Error> {
+        self.store.ingest_with_progress(source, observer).await
+    }
+
+    async fn ingest_filtered<T: AtomContent>(
+        &self,
+        source: &T,
+        filter: &IngestFilter,
+    ) -> Result<(), Self::Error> {
+        self.store.ingest_filtered(source, filter).await
+    }
+
+    async fn contains(&self, id: &(dyn AtomIdKey + Sync)) -> Result<bool, Self::Error> {
+        self.store.contains(id).await
+    }
+
+    /// Fills a miss as [`resolve`](AtomSource::resolve) does.
+    async fn resolve_key(
+        &self,
+        id: &(dyn AtomIdKey + Sync),
+    ) -> Result<Option<Self::Entry>, Self::Error> {
+        if let Some(entry) = self.store.resolve_key(id).await? {
+            return Ok(Some(entry));
+        }
+        self.refresh(&id.id_ref().to_atom_id()).await?;
+        self.store.resolve_key(id).await
+    }
+}
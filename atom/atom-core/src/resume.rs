@@ -0,0 +1,139 @@
+//! Progress through an interrupted ingest: [`IngestCheckpoint`].
+
+use std::collections::BTreeSet;
+use std::io::{self, BufRead, Write};
+
+use crate::AtomId;
+
+/// The first line of every checkpoint, naming the format and its version.
+const HEADER: &str = "atom-ingest-checkpoint 1";
+
+/// The atoms an [`ingest_resumable`](crate::AtomStoreResume::ingest_resumable)
+/// has finished, so an ingest cut short — by a dropped connection, a
+/// killed process — picks up where it stopped instead of starting over.
+///
+/// An atom is done once every version the source listed for it is in the
+/// store. A checkpoint belongs to one source and one store: resuming
+/// another pair's would skip atoms never ingested.
+///
+/// # Format
+///
+/// [`write_to`](Self::write_to) writes the line `atom-ingest-checkpoint 1`,
+/// then one finished atom id per line. The first line lets a later format
+/// be told apart from this one.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IngestCheckpoint {
+    done: BTreeSet<String>,
+}
+
+impl IngestCheckpoint {
+    /// A checkpoint with nothing done: an ingest from the start.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether `id` is done.
+    #[must_use]
+    pub fn is_done(&self, id: &AtomId) -> bool {
+        self.done.contains(&id.to_string())
+    }
+
+    /// Record `id` as done.
+    pub fn done(&mut self, id: &AtomId) {
+        self.done.insert(id.to_string());
+    }
+
+    /// How many atoms are done.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.done.len()
+    }
+
+    /// Whether no atom is done.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.done.is_empty()
+    }
+
+    /// Forget every atom done, so the next ingest starts over.
+    pub fn clear(&mut self) {
+        self.done.clear();
+    }
+
+    /// Write the checkpoint in the [format](Self#format).
+    ///
+    /// # Errors
+    ///
+    /// If writing fails.
+    pub fn write_to(&self, w: &mut impl Write) -> io::Result<()> {
+        writeln!(w, "{HEADER}")?;
+        for id in &self.done {
+            writeln!(w, "{id}")?;
+        }
+        Ok(())
+    }
+
+    /// Read a checkpoint written by [`write_to`](Self::write_to).
+    ///
+    /// # Errors
+    ///
+    /// If reading fails, or with [`io::ErrorKind::InvalidData`] for input
+    /// that does not start with the format's first line, or a line that is
+    /// not an atom id.
+    pub fn read_from(r: &mut impl BufRead) -> io::Result<Self> {
+        let mut lines = r.lines();
+        match lines.next().transpose()? {
+            Some(header) if header == HEADER => {},
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "not an ingest checkpoint",
+                ));
+            },
+        }
+        let mut checkpoint = Self::new();
+        for line in lines {
+            let line = line?;
+            if line.is_empty() {
+                continue;
+            }
+            let id: AtomId = line.parse().map_err(|_| {
+                io::Error::new(io::ErrorKind::InvalidData, format!("bad atom id: {line}"))
+            })?;
+            checkpoint.done(&id);
+        }
+        Ok(checkpoint)
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Anchor, Label};
+
+    #[test]
+    fn checkpoints_survive_a_round_trip_and_reject_other_input() {
+        let id = |label| AtomId::new(Anchor::new(vec![3; 32]), Label::try_from(label).unwrap());
+        let mut checkpoint = IngestCheckpoint::new();
+        checkpoint.done(&id("core"));
+        checkpoint.done(&id("util"));
+        checkpoint.done(&id("core"));
+        assert_eq!(checkpoint.len(), 2);
+
+        let mut saved = Vec::new();
+        checkpoint.write_to(&mut saved).unwrap();
+        let read = IngestCheckpoint::read_from(&mut saved.as_slice()).unwrap();
+        assert_eq!(read, checkpoint);
+        assert!(read.is_done(&id("util")) && !read.is_done(&id("absent")));
+
+        for bad in ["", "core\n", "atom-ingest-checkpoint 1\nnot an id\n"] {
+            let err = IngestCheckpoint::read_from(&mut bad.as_bytes()).unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        }
+    }
+}
@@ -0,0 +1,259 @@
+//! Retries with backoff for flaky sources: [`RetryingSource`].
+
+use std::collections::hash_map::RandomState;
+use std::future::Future;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+
+use crate::{
+    AtomContent, AtomId, AtomSource, Changes, Checkpoint, ContentEntry, Cursor, DiscoverPage,
+    RawVersion, VersionMatch, VersionScheme,
+};
+
+/// Waiting between retries. This crate carries no executor, so the caller
+/// supplies its runtime's timer — any `Fn(Duration) -> impl Future`, such
+/// as `tokio::time::sleep`.
+pub trait Sleep: Send + Sync + 'static {
+    /// Complete after `duration`.
+    fn sleep(&self, duration: Duration) -> impl Future<Output = ()> + Send;
+}
+
+impl<F, Fut> Sleep for F
+where
+    F: Fn(Duration) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = ()> + Send,
+{
+    fn sleep(&self, duration: Duration) -> impl Future<Output = ()> + Send {
+        self(duration)
+    }
+}
+
+/// How often, and how far apart, a [`RetryingSource`] tries.
+///
+/// The wait before retry `n` (counting from 0) is `base` doubled `n`
+/// times, capped at `max`. With `jitter`, it is drawn uniformly from the
+/// upper half of that, so callers failing together do not retry together.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Backoff {
+    /// Tries in all, the first included. `0` and `1` both mean no retries.
+    pub attempts: u32,
+    /// The wait before the first retry.
+    pub base: Duration,
+    /// The longest wait.
+    pub max: Duration,
+    /// Whether to randomize the waits.
+    pub jitter: bool,
+}
+
+impl Default for Backoff {
+    /// Three tries, waiting 100 ms then 200 ms, with jitter.
+    fn default() -> Self {
+        Self {
+            attempts: 3,
+            base: Duration::from_millis(100),
+            max: Duration::from_secs(10),
+            jitter: true,
+        }
+    }
+}
+
+impl Backoff {
+    /// The wait before retry `retry`, counting from 0.
+    #[must_use]
+    pub fn delay(&self, retry: u32) -> Duration {
+        let full = self
+            .base
+            .saturating_mul(2u32.saturating_pow(retry))
+            .min(self.max);
+        if !self.jitter {
+            return full;
+        }
+        let half = full / 2;
+        let spread = u64::try_from(half.as_nanos()).unwrap_or(u64::MAX);
+        let mut random = RandomState::new().build_hasher();
+        random.write_u32(retry);
+        half + Duration::from_nanos(random.finish() % spread.saturating_add(1))
+    }
+}
+
+type Classifier<E> = Box<dyn Fn(&E) -> bool + Send + Sync>;
+
+/// A source that retries failed lookups with exponential [`Backoff`], so
+/// network backends need not each do it.
+///
+/// Only errors the classifier calls retryable are retried — every error,
+/// unless [`retry_if`](Self::retry_if) narrows it; a permanent error, or
+/// the last try's, is returned as is. `Ok(None)` is an answer, not a
+/// failure, and is never retried.
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use atom_core::{Backoff, RetryingSource, Snapshot};
+///
+/// let source = RetryingSource::new(Snapshot::new(), |_: Duration| std::future::ready(()))
+///     .backoff(Backoff {
+///         attempts: 5,
+///         ..Backoff::default()
+///     });
+/// ```
+pub struct RetryingSource<S: AtomSource, Z> {
+    source: S,
+    sleep: Z,
+    backoff: Backoff,
+    retryable: Classifier<S::Error>,
+}
+
+impl<S: AtomSource, Z: Sleep> RetryingSource<S, Z> {
+    /// Retry `source`'s failures with the default [`Backoff`], waiting
+    /// with `sleep`.
+    pub fn new(source: S, sleep: Z) -> Self {
+        Self {
+            source,
+            sleep,
+            backoff: Backoff::default(),
+            retryable: Box::new(|_| true),
+        }
+    }
+
+    /// Use `backoff` instead.
+    #[must_use]
+    pub fn backoff(mut self, backoff: Backoff) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// Retry only the errors `retryable` accepts.
+    #[must_use]
+    pub fn retry_if(
+        mut self,
+        retryable: impl Fn(&S::Error) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.retryable = Box::new(retryable);
+        self
+    }
+
+    /// The source behind the retries.
+    pub fn source(&self) -> &S {
+        &self.source
+    }
+
+    async fn retry<T, F: Future<Output = Result<T, S::Error>>>(
+        &self,
+        mut attempt: impl FnMut() -> F,
+    ) -> Result<T, S::Error> {
+        let mut retry = 0;
+        loop {
+            match attempt().await {
+                Err(e) if retry + 1 < self.backoff.attempts && (self.retryable)(&e) => {},
+                result => return result,
+            }
+            // Out of the match, so the failed result is not held across the
+            // wait.
+            self.sleep.sleep(self.backoff.delay(retry)).await;
+            retry += 1;
+        }
+    }
+}
+
+impl<S: AtomSource, Z: Sleep> AtomSource for RetryingSource<S, Z> {
+    type Entry = S::Entry;
+    type Error = S::Error;
+
+    async fn resolve(&self, id: &AtomId) -> Result<Option<Self::Entry>, Self::Error> {
+        self.retry(|| self.source.resolve(id)).await
+    }
+
+    async fn resolve_many(&self, ids: &[AtomId]) -> Result<Vec<Option<Self::Entry>>, Self::Error>
+    where
+        Self::Entry: Send,
+    {
+        self.retry(|| self.source.resolve_many(ids)).await
+    }
+
+    async fn versions(&self, id: &AtomId) -> Result<Vec<RawVersion>, Self::Error> {
+        self.retry(|| self.source.versions(id)).await
+    }
+
+    async fn resolve_version<V>(
+        &self,
+        id: &AtomId,
+        scheme: &V,
+        req: &V::Requirement,
+    ) -> Result<Option<VersionMatch<Self::Entry>>, Self::Error>
+    where
+        V: VersionScheme + Sync,
+        V::Requirement: Sync,
+    {
+        self.retry(|| self.source.resolve_version(id, scheme, req))
+            .await
+    }
+
+    async fn discover(&self, query: &str) -> Result<Vec<AtomId>, Self::Error> {
+        self.retry(|| self.source.discover(query)).await
+    }
+
+    async fn discover_page(
+        &self,
+        query: &str,
+        cursor: Option<&Cursor>,
+        limit: usize,
+    ) -> Result<DiscoverPage, Self::Error> {
+        self.retry(|| self.source.discover_page(query, cursor, limit))
+            .await
+    }
+
+    async fn since(&self, checkpoint: Option<&Checkpoint>) -> Result<Changes, Self::Error> {
+        self.retry(|| self.source.since(checkpoint)).await
+    }
+}
+
+impl<S: AtomContent, Z: Sleep> AtomContent for RetryingSource<S, Z> {
+    async fn content(
+        &self,
+        id: &AtomId,
+        dig: &[u8],
+    ) -> Result<Option<Vec<ContentEntry>>, Self::Error> {
+        self.retry(|| self.source.content(id, dig)).await
+    }
+}
+
+impl<S: AtomSource + std::fmt::Debug, Z> std::fmt::Debug for RetryingSource<S, Z> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RetryingSource")
+            .field("source", &self.source)
+            .field("backoff", &self.backoff)
+            .finish_non_exhaustive()
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_doubles_up_to_its_cap_and_jitters_in_the_upper_half() {
+        let backoff = Backoff {
+            attempts: 10,
+            base: Duration::from_millis(100),
+            max: Duration::from_millis(350),
+            jitter: false,
+        };
+        let delays: Vec<u128> = (0..4).map(|n| backoff.delay(n).as_millis()).collect();
+        assert_eq!(delays, [100, 200, 350, 350]);
+        assert_eq!(backoff.delay(u32::MAX), backoff.max);
+
+        let jittered = Backoff {
+            jitter: true,
+            ..backoff
+        };
+        for n in 0..4 {
+            let delay = jittered.delay(n);
+            assert!(delay >= backoff.delay(n) / 2 && delay <= backoff.delay(n));
+        }
+    }
+}
@@ -0,0 +1,98 @@
+//! Entries carrying their versions' signed transactions byte for byte:
+//! [`SignedEntry`].
+
+use crate::{Alg, AtomEntry, AtomId, RawVersion};
+
+/// A signed transaction exactly as it was signed.
+///
+/// `pay` is never re-serialized: a signature covers bytes, and a payload
+/// parsed and written back out need not be the same bytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignedTxn {
+    /// The signing algorithm.
+    pub alg: Alg,
+    /// The public key that signed.
+    pub key: Vec<u8>,
+    /// The payload bytes signed.
+    pub pay: Vec<u8>,
+    /// The signature over `pay`.
+    pub sig: Vec<u8>,
+}
+
+/// Everything needed to check one published version's signatures without
+/// trusting the backend that served it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignedVersion {
+    /// The publish.
+    pub publish: SignedTxn,
+    /// The claim the publish chains to, then each claim it replaced in
+    /// turn, back to the first. Only the first is required.
+    pub claims: Vec<SignedTxn>,
+}
+
+/// A backend's entry, together with the signed transactions behind each
+/// of its published versions — for a backend to return as its
+/// [`Entry`](crate::AtomSource::Entry) when its callers should be able to
+/// check authenticity for themselves, with a `VerifiedSource` or
+/// otherwise.
+///
+/// The entry's own versions are served unchanged; a version with no
+/// [`SignedVersion`] recorded is unsigned.
+#[derive(Debug, Clone)]
+pub struct SignedEntry<E> {
+    entry: E,
+    signed: Vec<(RawVersion, SignedVersion)>,
+}
+
+impl<E: AtomEntry> SignedEntry<E> {
+    /// `entry`, with no version's transactions recorded yet.
+    pub fn new(entry: E) -> Self {
+        Self {
+            entry,
+            signed: Vec::new(),
+        }
+    }
+
+    /// Record the transactions behind `version`, replacing any recorded.
+    #[must_use]
+    pub fn sign(mut self, version: RawVersion, signed: SignedVersion) -> Self {
+        self.signed.retain(|(v, _)| *v != version);
+        self.signed.push((version, signed));
+        self
+    }
+
+    /// The transactions behind `version`, if it was signed.
+    #[must_use]
+    pub fn signed(&self, version: &RawVersion) -> Option<&SignedVersion> {
+        self.signed
+            .iter()
+            .find(|(v, _)| v == version)
+            .map(|(_, signed)| signed)
+    }
+
+    /// The backend's entry.
+    pub fn entry(&self) -> &E {
+        &self.entry
+    }
+
+    /// The backend's entry, unwrapped.
+    pub fn into_entry(self) -> E {
+        self.entry
+    }
+}
+
+impl<E: AtomEntry> AtomEntry for SignedEntry<E> {
+    type Version = E::Version;
+    type VersionIter<'a>
+        = E::VersionIter<'a>
+    where
+        Self: 'a;
+
+    fn id(&self) -> &AtomId {
+        self.entry.id()
+    }
+
+    fn versions(&self) -> Self::VersionIter<'_> {
+        self.entry.versions()
+    }
+}
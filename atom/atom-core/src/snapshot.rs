@@ -0,0 +1,607 @@
+//! Portable store snapshots: [`Snapshot`], written by
+//! [`AtomStore::export`](crate::AtomStore::export) and read back by
+//! [`AtomStore::import`](crate::AtomStore::import).
+//!
+//! A snapshot carries every atom version exactly as signed — claim and
+//! publish messages verbatim — together with its content and the content's
+//! [`content_hash`], so the importing store re-verifies everything it takes
+//! in through the same checks as any ingest. Nothing in a snapshot is
+//! trusted for having been in one.
+
+use std::convert::Infallible;
+use std::io::{self, Read, Write};
+use std::{fmt, slice};
+
+use crate::{
+    AtomContent, AtomEntry, AtomId, AtomSource, AtomVersion, ContentEntry, Czd, Label, RawVersion,
+    content_hash,
+};
+
+/// The bytes every snapshot starts with.
+const MAGIC: &[u8; 8] = b"ATOMSNAP";
+/// The format version this crate writes and reads.
+const FORMAT: u16 = 1;
+
+/// A store's atoms, detached from any backend.
+///
+/// A snapshot is itself an [`AtomContent`], so a store imports one by
+/// ingesting from it.
+///
+/// # Format
+///
+/// A snapshot is binary. It opens with the eight bytes `ATOMSNAP` and a
+/// big-endian `u16` format version, currently 1, followed by records. Each
+/// record is a tag byte and a fixed number of fields; each field is a
+/// big-endian `u32` length and that many bytes:
+///
+/// | tag | record    | fields                                                  |
+/// | --- | --------- | ------------------------------------------------------- |
+/// | `C` | charter   | signed message                                          |
+/// | `A` | atom      | anchor, label                                           |
+/// | `V` | version   | version, dig, claim czd, claim message, publish message, content hash |
+/// | `Y` | yank      | signed message                                          |
+/// | `R` | file      | path, data, flags (one byte: 1 if executable)           |
+/// | `S` | symlink   | path, target                                            |
+/// | `D` | directory | path                                                    |
+/// | `E` | end       | —                                                       |
+///
+/// A version belongs to the atom before it, and yank and content records
+/// to the version before them. An empty czd or message field means the version
+/// has none, as for a dev version. The `E` record is required, so a
+/// truncated snapshot is an error rather than a smaller one.
+#[derive(Debug, Clone, Default)]
+pub struct Snapshot {
+    charters: Vec<String>,
+    atoms: Vec<SnapshotEntry>,
+}
+
+impl Snapshot {
+    /// An empty snapshot.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Every atom version in `source`, with its content.
+    ///
+    /// # Errors
+    ///
+    /// [`SnapshotError::Store`] if `source` fails, and
+    /// [`SnapshotError::MissingContent`] if it has no content for a
+    /// version it lists.
+    pub async fn of<S: AtomContent + ?Sized>(source: &S) -> Result<Self, SnapshotError<S::Error>> {
+        let mut snapshot = Self::new();
+        for id in source.discover("").await.map_err(SnapshotError::Store)? {
+            let mut versions: Vec<SnapshotVersion> = {
+                let Some(entry) = source.resolve(&id).await.map_err(SnapshotError::Store)? else {
+                    continue;
+                };
+                entry
+                    .versions()
+                    .map(|v| SnapshotVersion {
+                        version: v.version().clone(),
+                        dig: v.dig().to_vec(),
+                        czd: v.czd().cloned(),
+                        claim_msg: v.claim_msg().map(String::from),
+                        publish_msg: v.publish_msg().map(String::from),
+                        yank_msg: v.yank_msg().map(String::from),
+                        content: Vec::new(),
+                    })
+                    .collect()
+            };
+            for v in &mut versions {
+                v.content = source
+                    .content(&id, &v.dig)
+                    .await
+                    .map_err(SnapshotError::Store)?
+                    .ok_or_else(|| SnapshotError::MissingContent {
+                        id: id.clone(),
+                        version: v.version.clone(),
+                    })?;
+            }
+            snapshot.atoms.push(SnapshotEntry { id, versions });
+        }
+        Ok(snapshot)
+    }
+
+    /// Add a signed charter message, for backends that keep charters
+    /// beside their atoms and need them to verify claims on import.
+    pub fn add_charter(&mut self, msg: String) {
+        self.charters.push(msg);
+    }
+
+    /// The signed charter messages.
+    #[must_use]
+    pub fn charters(&self) -> &[String] {
+        &self.charters
+    }
+
+    /// The atoms.
+    #[must_use]
+    pub fn atoms(&self) -> &[SnapshotEntry] {
+        &self.atoms
+    }
+
+    /// Write the snapshot in the [format](Self#format).
+    ///
+    /// # Errors
+    ///
+    /// [`SnapshotError::Io`] if writing fails, and
+    /// [`SnapshotError::Format`] if a version's content has a path no
+    /// [`content_hash`] can be computed over.
+    pub fn write_to(&self, w: &mut impl Write) -> Result<(), SnapshotError> {
+        w.write_all(MAGIC)?;
+        w.write_all(&FORMAT.to_be_bytes())?;
+        for msg in &self.charters {
+            record(w, b'C', &[msg.as_bytes()])?;
+        }
+        for atom in &self.atoms {
+            record(
+                w,
+                b'A',
+                &[atom.id.anchor().as_bytes(), atom.id.label().as_bytes()],
+            )?;
+            for v in &atom.versions {
+                let hash =
+                    content_hash(&v.content).map_err(|e| SnapshotError::Format(e.to_string()))?;
+                record(
+                    w,
+                    b'V',
+                    &[
+                        v.version.as_str().as_bytes(),
+                        &v.dig,
+                        v.czd.as_ref().map_or(&[][..], Czd::as_bytes),
+                        v.claim_msg.as_deref().unwrap_or_default().as_bytes(),
+                        v.publish_msg.as_deref().unwrap_or_default().as_bytes(),
+                        &hash,
+                    ],
+                )?;
+                if let Some(msg) = &v.yank_msg {
+                    record(w, b'Y', &[msg.as_bytes()])?;
+                }
+                for entry in &v.content {
+                    match entry {
+                        ContentEntry::Regular {
+                            path,
+                            data,
+                            executable,
+                        } => record(w, b'R', &[path.as_bytes(), data, &[u8::from(*executable)]])?,
+                        ContentEntry::Symlink { path, target } => {
+                            record(w, b'S', &[path.as_bytes(), target])?;
+                        },
+                        ContentEntry::Directory { path } => record(w, b'D', &[path.as_bytes()])?,
+                    }
+                }
+            }
+        }
+        record(w, b'E', &[])?;
+        Ok(())
+    }
+
+    /// Read a snapshot in the [format](Self#format), checking each
+    /// version's content against its content hash.
+    ///
+    /// # Errors
+    ///
+    /// [`SnapshotError::Io`] if reading fails, [`SnapshotError::Format`]
+    /// if the input is not a well-formed snapshot, and
+    /// [`SnapshotError::ContentMismatch`] if a version's content does not
+    /// hash to the recorded digest.
+    pub fn read_from(r: &mut impl Read) -> Result<Self, SnapshotError> {
+        let mut magic = [0; 8];
+        r.read_exact(&mut magic)?;
+        let mut format = [0; 2];
+        r.read_exact(&mut format)?;
+        if &magic != MAGIC {
+            return Err(SnapshotError::Format("not an atom snapshot".into()));
+        }
+        if u16::from_be_bytes(format) != FORMAT {
+            return Err(SnapshotError::Format(format!(
+                "unsupported snapshot format {}",
+                u16::from_be_bytes(format)
+            )));
+        }
+
+        let mut snapshot = Self::new();
+        // The content hash the last version record promised.
+        let mut promised: Option<[u8; 32]> = None;
+        loop {
+            let mut tag = 0;
+            r.read_exact(slice::from_mut(&mut tag))?;
+            if matches!(tag, b'C' | b'A' | b'V' | b'E') {
+                snapshot.check_last(promised.take())?;
+            }
+            match tag {
+                b'C' => snapshot.charters.push(text(field(r)?)?),
+                b'A' => {
+                    let anchor = crate::Anchor::new(field(r)?);
+                    let label = Label::try_from(text(field(r)?)?.as_str())
+                        .map_err(|e| SnapshotError::Format(format!("invalid label: {e}")))?;
+                    snapshot.atoms.push(SnapshotEntry {
+                        id: AtomId::new(anchor, label),
+                        versions: Vec::new(),
+                    });
+                },
+                b'V' => {
+                    let version = RawVersion::new(text(field(r)?)?);
+                    let dig = field(r)?;
+                    let czd = Some(field(r)?)
+                        .filter(|b| !b.is_empty())
+                        .map(Czd::from_bytes);
+                    let claim_msg = Some(text(field(r)?)?).filter(|m| !m.is_empty());
+                    let publish_msg = Some(text(field(r)?)?).filter(|m| !m.is_empty());
+                    let hash = field(r)?.try_into().map_err(|_| {
+                        SnapshotError::Format("content hash is not 32 bytes".into())
+                    })?;
+                    promised = Some(hash);
+                    let atom = snapshot.atoms.last_mut().ok_or_else(|| {
+                        SnapshotError::Format("version record before any atom".into())
+                    })?;
+                    atom.versions.push(SnapshotVersion {
+                        version,
+                        dig,
+                        czd,
+                        claim_msg,
+                        publish_msg,
+                        yank_msg: None,
+                        content: Vec::new(),
+                    });
+                },
+                b'Y' => {
+                    let msg = text(field(r)?)?;
+                    snapshot
+                        .last_version()
+                        .ok_or_else(|| {
+                            SnapshotError::Format("yank record before any version".into())
+                        })?
+                        .yank_msg = Some(msg);
+                },
+                b'R' => {
+                    let path = text(field(r)?)?;
+                    let data = field(r)?;
+                    let executable = field(r)? == [1];
+                    snapshot.push_content(ContentEntry::Regular {
+                        path,
+                        data,
+                        executable,
+                    })?;
+                },
+                b'S' => {
+                    let path = text(field(r)?)?;
+                    let target = field(r)?;
+                    snapshot.push_content(ContentEntry::Symlink { path, target })?;
+                },
+                b'D' => {
+                    let path = text(field(r)?)?;
+                    snapshot.push_content(ContentEntry::Directory { path })?;
+                },
+                b'E' => return Ok(snapshot),
+                other => {
+                    return Err(SnapshotError::Format(format!(
+                        "unknown record tag {other:#04x}"
+                    )));
+                },
+            }
+        }
+    }
+
+    fn last_version(&mut self) -> Option<&mut SnapshotVersion> {
+        self.atoms.last_mut()?.versions.last_mut()
+    }
+
+    fn push_content(&mut self, entry: ContentEntry) -> Result<(), SnapshotError> {
+        self.last_version()
+            .ok_or_else(|| SnapshotError::Format("content record before any version".into()))?
+            .content
+            .push(entry);
+        Ok(())
+    }
+
+    /// Check the last version's content against the hash it promised.
+    fn check_last(&self, promised: Option<[u8; 32]>) -> Result<(), SnapshotError> {
+        let Some(promised) = promised else {
+            return Ok(());
+        };
+        let atom = self.atoms.last().expect("a promise follows a version");
+        let v = atom.versions.last().expect("a promise follows a version");
+        let hash = content_hash(&v.content).map_err(|e| SnapshotError::Format(e.to_string()))?;
+        if hash == promised {
+            Ok(())
+        } else {
+            Err(SnapshotError::ContentMismatch {
+                id: atom.id.clone(),
+                version: v.version.clone(),
+            })
+        }
+    }
+}
+
+/// Write one record.
+fn record(w: &mut impl Write, tag: u8, fields: &[&[u8]]) -> io::Result<()> {
+    w.write_all(&[tag])?;
+    for field in fields {
+        let len = u32::try_from(field.len())
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "field over 4 GiB"))?;
+        w.write_all(&len.to_be_bytes())?;
+        w.write_all(field)?;
+    }
+    Ok(())
+}
+
+/// Read one field.
+fn field(r: &mut impl Read) -> Result<Vec<u8>, SnapshotError> {
+    let mut len = [0; 4];
+    r.read_exact(&mut len)?;
+    let len = u32::from_be_bytes(len);
+    let mut bytes = Vec::new();
+    r.take(u64::from(len)).read_to_end(&mut bytes)?;
+    if bytes.len() as u64 == u64::from(len) {
+        Ok(bytes)
+    } else {
+        Err(io::Error::from(io::ErrorKind::UnexpectedEof).into())
+    }
+}
+
+fn text(bytes: Vec<u8>) -> Result<String, SnapshotError> {
+    String::from_utf8(bytes).map_err(|_| SnapshotError::Format("field is not UTF-8".into()))
+}
+
+/// An atom in a [`Snapshot`].
+#[derive(Debug, Clone)]
+pub struct SnapshotEntry {
+    id: AtomId,
+    versions: Vec<SnapshotVersion>,
+}
+
+impl AtomEntry for SnapshotEntry {
+    type Version = SnapshotVersion;
+    type VersionIter<'a> = slice::Iter<'a, SnapshotVersion>;
+
+    fn id(&self) -> &AtomId {
+        &self.id
+    }
+
+    fn versions(&self) -> Self::VersionIter<'_> {
+        self.versions.iter()
+    }
+}
+
+/// An atom version in a [`Snapshot`], with its content.
+#[derive(Debug, Clone)]
+pub struct SnapshotVersion {
+    version: RawVersion,
+    dig: Vec<u8>,
+    czd: Option<Czd>,
+    claim_msg: Option<String>,
+    publish_msg: Option<String>,
+    yank_msg: Option<String>,
+    content: Vec<ContentEntry>,
+}
+
+impl SnapshotVersion {
+    /// The version's content tree.
+    #[must_use]
+    pub fn content(&self) -> &[ContentEntry] {
+        &self.content
+    }
+}
+
+impl AtomVersion for SnapshotVersion {
+    fn version(&self) -> &RawVersion {
+        &self.version
+    }
+
+    fn dig(&self) -> &[u8] {
+        &self.dig
+    }
+
+    fn czd(&self) -> Option<&Czd> {
+        self.czd.as_ref()
+    }
+
+    fn claim_msg(&self) -> Option<&str> {
+        self.claim_msg.as_deref()
+    }
+
+    fn publish_msg(&self) -> Option<&str> {
+        self.publish_msg.as_deref()
+    }
+
+    fn yank_msg(&self) -> Option<&str> {
+        self.yank_msg.as_deref()
+    }
+}
+
+impl AtomSource for Snapshot {
+    type Entry = SnapshotEntry;
+    type Error = Infallible;
+
+    async fn resolve(&self, id: &AtomId) -> Result<Option<Self::Entry>, Self::Error> {
+        Ok(self.atoms.iter().find(|a| a.id == *id).cloned())
+    }
+
+    /// Atoms whose label contains `query`.
+    async fn discover(&self, query: &str) -> Result<Vec<AtomId>, Self::Error> {
+        Ok(self
+            .atoms
+            .iter()
+            .filter(|a| a.id.label().contains(query))
+            .map(|a| a.id.clone())
+            .collect())
+    }
+}
+
+impl AtomContent for Snapshot {
+    async fn content(
+        &self,
+        id: &AtomId,
+        dig: &[u8],
+    ) -> Result<Option<Vec<ContentEntry>>, Self::Error> {
+        Ok(self
+            .atoms
+            .iter()
+            .filter(|a| a.id == *id)
+            .flat_map(|a| &a.versions)
+            .find(|v| v.dig == dig)
+            .map(|v| v.content.clone()))
+    }
+}
+
+/// Errors writing, reading, exporting or importing a [`Snapshot`].
+#[derive(Debug)]
+pub enum SnapshotError<E = Infallible> {
+    /// Reading or writing the snapshot failed.
+    Io(io::Error),
+    /// The input is not a well-formed snapshot.
+    Format(String),
+    /// A version's content does not hash to the digest recorded for it.
+    ContentMismatch {
+        /// The atom.
+        id: AtomId,
+        /// The version.
+        version: RawVersion,
+    },
+    /// The source lists a version it has no content for.
+    MissingContent {
+        /// The atom.
+        id: AtomId,
+        /// The version.
+        version: RawVersion,
+    },
+    /// The store failed.
+    Store(E),
+}
+
+impl SnapshotError {
+    /// The same error, from a store with error type `E`.
+    #[must_use]
+    pub fn from_store<E>(self) -> SnapshotError<E> {
+        match self {
+            Self::Io(e) => SnapshotError::Io(e),
+            Self::Format(msg) => SnapshotError::Format(msg),
+            Self::ContentMismatch { id, version } => SnapshotError::ContentMismatch { id, version },
+            Self::MissingContent { id, version } => SnapshotError::MissingContent { id, version },
+            Self::Store(never) => match never {},
+        }
+    }
+}
+
+impl<E> From<io::Error> for SnapshotError<E> {
+    fn from(e: io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for SnapshotError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "snapshot I/O failed: {e}"),
+            Self::Format(msg) => write!(f, "malformed snapshot: {msg}"),
+            Self::ContentMismatch { id, version } => write!(
+                f,
+                "content of {id}@{} does not match its content hash",
+                version.as_str()
+            ),
+            Self::MissingContent { id, version } => {
+                write!(f, "no content for {id}@{}", version.as_str())
+            },
+            Self::Store(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl<E: std::error::Error + 'static> std::error::Error for SnapshotError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            Self::Store(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Anchor;
+
+    fn snapshot() -> Snapshot {
+        let id = AtomId::new(Anchor::new(vec![7; 32]), Label::try_from("core").unwrap());
+        let mut snapshot = Snapshot::new();
+        snapshot.add_charter("{\"pay\":{}}".into());
+        snapshot.atoms.push(SnapshotEntry {
+            id,
+            versions: vec![SnapshotVersion {
+                version: RawVersion::new("1.0".into()),
+                dig: vec![1, 2, 3],
+                czd: Some(Czd::from_bytes(vec![4; 32])),
+                claim_msg: Some("claim".into()),
+                publish_msg: None,
+                yank_msg: Some("yank".into()),
+                content: vec![
+                    ContentEntry::Regular {
+                        path: "src/main.rs".into(),
+                        data: b"fn main() {}".to_vec(),
+                        executable: true,
+                    },
+                    ContentEntry::Directory { path: "src".into() },
+                    ContentEntry::Symlink {
+                        path: "main.rs".into(),
+                        target: b"src/main.rs".to_vec(),
+                    },
+                ],
+            }],
+        });
+        snapshot
+    }
+
+    fn bytes(snapshot: &Snapshot) -> Vec<u8> {
+        let mut out = Vec::new();
+        snapshot.write_to(&mut out).unwrap();
+        out
+    }
+
+    #[test]
+    fn round_trips() {
+        let written = bytes(&snapshot());
+        let read = Snapshot::read_from(&mut written.as_slice()).unwrap();
+        assert_eq!(read.charters(), ["{\"pay\":{}}"]);
+        let v = &read.atoms()[0].versions[0];
+        assert_eq!(v.version().as_str(), "1.0");
+        assert_eq!(v.czd().map(Czd::as_bytes), Some(&[4; 32][..]));
+        assert_eq!((v.claim_msg(), v.publish_msg()), (Some("claim"), None));
+        assert!(v.is_yanked());
+        assert_eq!(v.content().len(), 3);
+        assert_eq!(bytes(&read), written);
+    }
+
+    #[test]
+    fn rejects_truncation_and_tampering() {
+        let written = bytes(&snapshot());
+        let truncated = &written[..written.len() - 1];
+        assert!(matches!(
+            Snapshot::read_from(&mut &truncated[..]),
+            Err(SnapshotError::Io(_))
+        ));
+
+        let mut tampered = written.clone();
+        let at = tampered
+            .windows(4)
+            .position(|w| w == b"main")
+            .expect("file data is in the snapshot");
+        tampered[at] = b'M';
+        assert!(matches!(
+            Snapshot::read_from(&mut tampered.as_slice()),
+            Err(SnapshotError::ContentMismatch { .. })
+        ));
+
+        assert!(matches!(
+            Snapshot::read_from(&mut &b"NOTASNAP\0\x01E"[..]),
+            Err(SnapshotError::Format(_))
+        ));
+    }
+}
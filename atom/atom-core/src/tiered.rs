@@ -0,0 +1,345 @@
+//! Sources consulted in priority order: [`TieredSource`].
+
+use std::collections::HashSet;
+
+use crate::{
+    AtomContent, AtomId, AtomSource, Changes, Checkpoint, ContentEntry, Cursor, DiscoverPage,
+    RawVersion, VersionMatch, VersionScheme,
+};
+
+/// What a [`TieredSource`] does when one of its tiers fails.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TierPolicy {
+    /// Fail the whole lookup with the tier's error.
+    #[default]
+    FailFast,
+    /// Treat the tier as not having the atom, and go on to the next.
+    Skip,
+}
+
+/// Several sources of one type, consulted in priority order — "the local
+/// store, then the team mirror, then upstream".
+///
+/// [`resolve`](AtomSource::resolve) and [`content`](AtomContent::content)
+/// answer from the first tier that has the atom; lower tiers are not
+/// asked, and their versions are not merged in.
+/// [`discover`](AtomSource::discover) asks every tier and lists each id
+/// once, where the highest tier listing it put it. Each tier's
+/// [`TierPolicy`] decides whether its failure fails the lookup or is passed
+/// over.
+///
+/// The other lookups keep to the same rules: a tier with the atom answers
+/// [`versions`](AtomSource::versions) and
+/// [`resolve_version`](AtomSource::resolve_version) for it, even with no
+/// version matching, and [`resolve_many`](AtomSource::resolve_many) asks
+/// each tier in one batch for the atoms the tiers above it lacked.
+/// [`discover_page`](AtomSource::discover_page) pages through one tier at
+/// a time, passing over ids a higher tier has;
+/// [`since`](AtomSource::since) lists what changed in any tier, and keeps
+/// each tier's checkpoint — a skipped tier's as it was — in its own.
+///
+/// ```
+/// use atom_core::{Snapshot, TierPolicy, TieredSource};
+///
+/// let sources = TieredSource::new()
+///     .tier(Snapshot::new(), TierPolicy::FailFast)
+///     .tier(Snapshot::new(), TierPolicy::Skip);
+/// assert_eq!(sources.len(), 2);
+/// ```
+#[derive(Debug, Clone)]
+pub struct TieredSource<S> {
+    tiers: Vec<(S, TierPolicy)>,
+}
+
+impl<S> Default for TieredSource<S> {
+    fn default() -> Self {
+        Self { tiers: Vec::new() }
+    }
+}
+
+impl<S> TieredSource<S> {
+    /// No tiers: resolves and discovers nothing.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `source` below every tier added so far.
+    #[must_use]
+    pub fn tier(mut self, source: S, policy: TierPolicy) -> Self {
+        self.tiers.push((source, policy));
+        self
+    }
+
+    /// The sources, highest priority first.
+    pub fn sources(&self) -> impl Iterator<Item = &S> {
+        self.tiers.iter().map(|(source, _)| source)
+    }
+
+    /// How many tiers there are.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.tiers.len()
+    }
+
+    /// Whether there are no tiers.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.tiers.is_empty()
+    }
+}
+
+/// A tier's answer, or its error if its policy fails fast; `None` for a
+/// skipped failure.
+fn settle<T, E>(result: Result<T, E>, policy: TierPolicy) -> Result<Option<T>, E> {
+    match (result, policy) {
+        (Ok(answer), _) => Ok(Some(answer)),
+        (Err(e), TierPolicy::FailFast) => Err(e),
+        (Err(_), TierPolicy::Skip) => Ok(None),
+    }
+}
+
+/// A [`TieredSource`] page cursor: the tier the search is in, then `:` and
+/// that tier's own cursor, unless the search is to start that tier afresh.
+fn tier_cursor(tier: usize, cursor: Option<&Cursor>) -> Cursor {
+    match cursor {
+        Some(cursor) => Cursor::new(format!("{tier}:{}", cursor.as_str())),
+        None => Cursor::new(tier.to_string()),
+    }
+}
+
+fn parse_tier_cursor(cursor: &Cursor) -> Option<(usize, Option<Cursor>)> {
+    match cursor.as_str().split_once(':') {
+        Some((tier, token)) => Some((tier.parse().ok()?, Some(Cursor::new(token)))),
+        None => Some((cursor.as_str().parse().ok()?, None)),
+    }
+}
+
+/// Every tier's checkpoint in one: for each tier, `-` if it has none,
+/// else its token's length, `:` and the token.
+fn pack_checkpoints(checkpoints: &[Option<Checkpoint>]) -> Checkpoint {
+    let mut packed = String::new();
+    for checkpoint in checkpoints {
+        match checkpoint {
+            Some(checkpoint) => {
+                let token = checkpoint.as_str();
+                packed.push_str(&format!("{}:{token}", token.len()));
+            },
+            None => packed.push('-'),
+        }
+    }
+    Checkpoint::new(packed)
+}
+
+/// The `tiers` checkpoints packed in `checkpoint`, or `None` if it does not
+/// hold exactly that many.
+fn unpack_checkpoints(checkpoint: &Checkpoint, tiers: usize) -> Option<Vec<Option<Checkpoint>>> {
+    let mut rest = checkpoint.as_str();
+    let mut checkpoints = Vec::with_capacity(tiers);
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix('-') {
+            checkpoints.push(None);
+            rest = after;
+            continue;
+        }
+        let (len, after) = rest.split_once(':')?;
+        let len: usize = len.parse().ok()?;
+        let token = after.get(..len)?;
+        checkpoints.push(Some(Checkpoint::new(token)));
+        rest = &after[len..];
+    }
+    (checkpoints.len() == tiers).then_some(checkpoints)
+}
+
+impl<S: AtomSource> TieredSource<S> {
+    /// Whether a tier above `tier` has `id`.
+    async fn above_has(&self, tier: usize, id: &AtomId) -> Result<bool, S::Error> {
+        for (source, policy) in &self.tiers[..tier] {
+            if settle(source.versions(id).await, *policy)?.is_some_and(|v| !v.is_empty()) {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+}
+
+impl<S: AtomSource> AtomSource for TieredSource<S> {
+    type Entry = S::Entry;
+    type Error = S::Error;
+
+    async fn resolve(&self, id: &AtomId) -> Result<Option<Self::Entry>, Self::Error> {
+        for (source, policy) in &self.tiers {
+            if let Some(entry) = settle(source.resolve(id).await, *policy)?.flatten() {
+                return Ok(Some(entry));
+            }
+        }
+        Ok(None)
+    }
+
+    async fn resolve_many(&self, ids: &[AtomId]) -> Result<Vec<Option<Self::Entry>>, Self::Error>
+    where
+        Self::Entry: Send,
+    {
+        let mut entries: Vec<Option<Self::Entry>> = ids.iter().map(|_| None).collect();
+        let mut missing: Vec<usize> = (0..ids.len()).collect();
+        for (source, policy) in &self.tiers {
+            if missing.is_empty() {
+                break;
+            }
+            let asked: Vec<AtomId> = missing.iter().map(|&index| ids[index].clone()).collect();
+            let Some(answers) = settle(source.resolve_many(&asked).await, *policy)? else {
+                continue;
+            };
+            let mut still = Vec::new();
+            for (index, entry) in missing.into_iter().zip(answers) {
+                match entry {
+                    Some(entry) => entries[index] = Some(entry),
+                    None => still.push(index),
+                }
+            }
+            missing = still;
+        }
+        Ok(entries)
+    }
+
+    async fn versions(&self, id: &AtomId) -> Result<Vec<RawVersion>, Self::Error> {
+        for (source, policy) in &self.tiers {
+            let versions = settle(source.versions(id).await, *policy)?.unwrap_or_default();
+            if !versions.is_empty() {
+                return Ok(versions);
+            }
+        }
+        Ok(Vec::new())
+    }
+
+    async fn resolve_version<V>(
+        &self,
+        id: &AtomId,
+        scheme: &V,
+        req: &V::Requirement,
+    ) -> Result<Option<VersionMatch<Self::Entry>>, Self::Error>
+    where
+        V: VersionScheme + Sync,
+        V::Requirement: Sync,
+    {
+        for (source, policy) in &self.tiers {
+            let found = source.resolve_version(id, scheme, req).await;
+            if let Some(found) = settle(found, *policy)?.flatten() {
+                return Ok(Some(found));
+            }
+            // A tier with the atom answers for it, match or not.
+            if settle(source.versions(id).await, *policy)?.is_some_and(|v| !v.is_empty()) {
+                return Ok(None);
+            }
+        }
+        Ok(None)
+    }
+
+    async fn discover(&self, query: &str) -> Result<Vec<AtomId>, Self::Error> {
+        let mut seen = HashSet::new();
+        let mut ids: Vec<AtomId> = Vec::new();
+        for (source, policy) in &self.tiers {
+            let found = settle(source.discover(query).await, *policy)?.unwrap_or_default();
+            ids.extend(found.into_iter().filter(|id| seen.insert(id.clone())));
+        }
+        Ok(ids)
+    }
+
+    /// Ends the search with an empty page on a cursor this source did not
+    /// return.
+    async fn discover_page(
+        &self,
+        query: &str,
+        cursor: Option<&Cursor>,
+        limit: usize,
+    ) -> Result<DiscoverPage, Self::Error> {
+        let Some((mut tier, mut inner)) = cursor.map_or(Some((0, None)), parse_tier_cursor) else {
+            return Ok((Vec::new(), None));
+        };
+        let limit = limit.max(1);
+        let mut ids = Vec::new();
+        while ids.len() < limit {
+            let Some((source, policy)) = self.tiers.get(tier) else {
+                break;
+            };
+            let page = source.discover_page(query, inner.as_ref(), limit - ids.len());
+            let (found, next) = settle(page.await, *policy)?.unwrap_or_default();
+            for id in found {
+                if !self.above_has(tier, &id).await? {
+                    ids.push(id);
+                }
+            }
+            inner = next;
+            if inner.is_none() {
+                tier += 1;
+            }
+        }
+        let next = (tier < self.tiers.len()).then(|| tier_cursor(tier, inner.as_ref()));
+        Ok((ids, next))
+    }
+
+    async fn since(&self, checkpoint: Option<&Checkpoint>) -> Result<Changes, Self::Error> {
+        let before = checkpoint
+            .and_then(|checkpoint| unpack_checkpoints(checkpoint, self.tiers.len()))
+            .unwrap_or_else(|| vec![None; self.tiers.len()]);
+        let mut seen = HashSet::new();
+        let mut ids = Vec::new();
+        let mut after = Vec::with_capacity(self.tiers.len());
+        for ((source, policy), before) in self.tiers.iter().zip(before) {
+            match settle(source.since(before.as_ref()).await, *policy)? {
+                Some((changed, next)) => {
+                    ids.extend(changed.into_iter().filter(|id| seen.insert(id.clone())));
+                    after.push(Some(next));
+                },
+                // Asked from the same point next time, so nothing is missed.
+                None => after.push(before),
+            }
+        }
+        Ok((ids, pack_checkpoints(&after)))
+    }
+}
+
+impl<S: AtomContent> AtomContent for TieredSource<S> {
+    async fn content(
+        &self,
+        id: &AtomId,
+        dig: &[u8],
+    ) -> Result<Option<Vec<ContentEntry>>, Self::Error> {
+        for (source, policy) in &self.tiers {
+            if let Some(content) = settle(source.content(id, dig).await, *policy)?.flatten() {
+                return Ok(Some(content));
+            }
+        }
+        Ok(None)
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checkpoints_pack_and_unpack_tier_by_tier() {
+        let checkpoints = vec![
+            Some(Checkpoint::new("12:-")),
+            None,
+            Some(Checkpoint::new("")),
+        ];
+        let packed = pack_checkpoints(&checkpoints);
+        assert_eq!(unpack_checkpoints(&packed, 3), Some(checkpoints));
+        assert_eq!(unpack_checkpoints(&packed, 2), None);
+        assert_eq!(unpack_checkpoints(&Checkpoint::new("9:short"), 1), None);
+
+        let cursor = tier_cursor(2, Some(&Cursor::new("a:b")));
+        assert_eq!(
+            parse_tier_cursor(&cursor),
+            Some((2, Some(Cursor::new("a:b"))))
+        );
+        assert_eq!(parse_tier_cursor(&tier_cursor(1, None)), Some((1, None)));
+        assert_eq!(parse_tier_cursor(&Cursor::new("x")), None);
+    }
+}
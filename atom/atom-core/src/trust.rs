@@ -0,0 +1,197 @@
+//! Pinned owner keys, trusted on first use: [`TrustStore`].
+
+use std::fmt::Write as _;
+use std::io::{self, BufRead, Write};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use crate::{AtomId, Thumbprint, VerifyPolicy};
+
+/// The key pinned for each atom, so a consumer notices when the key
+/// signing for an atom changes between ingests.
+///
+/// The first key seen signing for an atom is pinned (trust on first use);
+/// from then on, [`check`](Self::check) reports any other key as
+/// [`TrustCheck::Changed`] until the pin is replaced with
+/// [`pin`](Self::pin) or dropped with [`unpin`](Self::unpin) — the
+/// explicit decision that a key change is expected.
+///
+/// As a [`VerifyPolicy`], it trusts a signer unless the atom is pinned to
+/// another key, so a store ingesting under it refuses a version signed by
+/// a changed key. Share it with the store through an
+/// [`Arc`](std::sync::Arc) to keep reading and saving its pins.
+///
+/// # Format
+///
+/// [`write_to`](Self::write_to) writes one pin per line: the atom id, a
+/// space, and the key's thumbprint in lowercase hex.
+#[derive(Debug, Default)]
+pub struct TrustStore {
+    pins: Mutex<Vec<(AtomId, Thumbprint)>>,
+}
+
+/// What [`TrustStore::check`] found for a key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TrustCheck {
+    /// The atom had no pin; the key is pinned now.
+    FirstUse,
+    /// The key is the one pinned.
+    Pinned,
+    /// The atom is pinned to a different key, left in place.
+    Changed {
+        /// The key pinned.
+        pinned: Thumbprint,
+    },
+}
+
+impl TrustStore {
+    /// A store with no pins.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The key pinned for `id`.
+    #[must_use]
+    pub fn pinned(&self, id: &AtomId) -> Option<Thumbprint> {
+        self.lock()
+            .iter()
+            .find(|(pinned, _)| pinned == id)
+            .map(|(_, key)| key.clone())
+    }
+
+    /// Check `key` against the pin for `id`, pinning it if there is none.
+    pub fn check(&self, id: &AtomId, key: &Thumbprint) -> TrustCheck {
+        let mut pins = self.lock();
+        match pins.iter().find(|(pinned, _)| pinned == id) {
+            Some((_, pinned)) if pinned == key => TrustCheck::Pinned,
+            Some((_, pinned)) => TrustCheck::Changed {
+                pinned: pinned.clone(),
+            },
+            None => {
+                pins.push((id.clone(), key.clone()));
+                TrustCheck::FirstUse
+            },
+        }
+    }
+
+    /// Pin `key` for `id`, replacing any pin it had.
+    pub fn pin(&self, id: &AtomId, key: Thumbprint) {
+        let mut pins = self.lock();
+        match pins.iter_mut().find(|(pinned, _)| pinned == id) {
+            Some((_, pinned)) => *pinned = key,
+            None => pins.push((id.clone(), key)),
+        }
+    }
+
+    /// Drop the pin for `id`, so the next key seen is trusted on first use.
+    /// `false` if there was none.
+    pub fn unpin(&self, id: &AtomId) -> bool {
+        let mut pins = self.lock();
+        let before = pins.len();
+        pins.retain(|(pinned, _)| pinned != id);
+        pins.len() != before
+    }
+
+    /// Write the pins in the [format](Self#format).
+    ///
+    /// # Errors
+    ///
+    /// If writing fails.
+    pub fn write_to(&self, w: &mut impl Write) -> io::Result<()> {
+        for (id, key) in self.lock().iter() {
+            let hex = key.as_bytes().iter().fold(String::new(), |mut s, b| {
+                let _ = write!(s, "{b:02x}");
+                s
+            });
+            writeln!(w, "{id} {hex}")?;
+        }
+        Ok(())
+    }
+
+    /// Read pins written by [`write_to`](Self::write_to).
+    ///
+    /// # Errors
+    ///
+    /// If reading fails, or with [`io::ErrorKind::InvalidData`] for a line
+    /// that is not a pin.
+    pub fn read_from(r: &mut impl BufRead) -> io::Result<Self> {
+        let store = Self::new();
+        for line in r.lines() {
+            let line = line?;
+            if line.is_empty() {
+                continue;
+            }
+            let invalid = || io::Error::new(io::ErrorKind::InvalidData, format!("bad pin: {line}"));
+            let (id, hex) = line.split_once(' ').ok_or_else(invalid)?;
+            let id: AtomId = id.parse().map_err(|_| invalid())?;
+            let key = decode_hex(hex).ok_or_else(invalid)?;
+            store.pin(&id, Thumbprint::from_bytes(key));
+        }
+        Ok(store)
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Vec<(AtomId, Thumbprint)>> {
+        // Every update is a single push, replace or retain.
+        self.pins.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) || !hex.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+    {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).ok())
+        .collect()
+}
+
+impl VerifyPolicy for TrustStore {
+    /// Pins the first signer seen for `id`.
+    fn trusts(&self, id: &AtomId, signer: &Thumbprint) -> bool {
+        !matches!(self.check(id, signer), TrustCheck::Changed { .. })
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Anchor, Label};
+
+    #[test]
+    fn keys_are_pinned_on_first_use_and_survive_a_round_trip() {
+        let id = AtomId::new(Anchor::new(vec![1; 32]), Label::try_from("pkg").unwrap());
+        let alice = Thumbprint::from_bytes(vec![0xa1; 32]);
+        let bob = Thumbprint::from_bytes(vec![0xb0; 32]);
+
+        let trust = TrustStore::new();
+        assert_eq!(trust.check(&id, &alice), TrustCheck::FirstUse);
+        assert_eq!(trust.check(&id, &alice), TrustCheck::Pinned);
+        assert_eq!(
+            trust.check(&id, &bob),
+            TrustCheck::Changed {
+                pinned: alice.clone()
+            }
+        );
+        assert!(!trust.trusts(&id, &bob));
+
+        let mut saved = Vec::new();
+        trust.write_to(&mut saved).unwrap();
+        let trust = TrustStore::read_from(&mut saved.as_slice()).unwrap();
+        assert_eq!(trust.pinned(&id), Some(alice));
+
+        trust.pin(&id, bob.clone());
+        assert_eq!(trust.check(&id, &bob), TrustCheck::Pinned);
+        assert!(trust.unpin(&id));
+        assert!(!trust.unpin(&id));
+        assert_eq!(trust.pinned(&id), None);
+
+        let bad = TrustStore::read_from(&mut "not a pin\n".as_bytes());
+        assert_eq!(bad.unwrap_err().kind(), io::ErrorKind::InvalidData);
+    }
+}
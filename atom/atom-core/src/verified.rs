@@ -0,0 +1,391 @@
+//! Sources whose answers are checked against their signatures:
+//! [`VerifiedSource`].
+
+use std::fmt;
+
+use atom_id::{ClaimPayload, PublishPayload, Verified, VerifyError};
+
+use crate::{
+    AtomContent, AtomEntry, AtomId, AtomSource, AtomVersion, Changes, Checkpoint, ContentEntry,
+    Cursor, Czd, DiscoverPage, RawVersion, SignaturePolicy, SignedEntry, SignedTxn, SignedVersion,
+    VerifyPolicy, VersionMatch, VersionScheme,
+};
+
+/// A source of [`SignedEntry`]s that checks every version's signatures
+/// before handing an entry out, so a caller trusts the publishers' keys
+/// rather than the backend in between.
+///
+/// For each version, the first claim's signature and the publish's must
+/// verify, the publish must chain to that claim and be authorized by its
+/// owner, and the publish must name the atom, version and digest the entry
+/// reports. Each further claim must verify and be the one its successor
+/// replaced. Whether a replacement was authorized needs the atom-set's
+/// charter, and stays a store's check at ingest. Finally the
+/// [`VerifyPolicy`] must admit the algorithms and trust the publisher.
+///
+/// Content is served only for a digest some verified publish names, and
+/// checked against that publish's `content_hash` when it carries one. A
+/// digest that names no version, or only versions admitted unsigned,
+/// has no content here.
+///
+/// A version that fails any check fails the whole
+/// [`resolve`](AtomSource::resolve), and every lookup that hands out its
+/// entry: the backend is serving something its publishers never signed.
+/// Listings — ids and checkpoints — are passed on as the source gave them.
+///
+/// ```
+/// use atom_core::{AtomEntry, AtomSource, SignedEntry, VerifiedSource};
+///
+/// fn checked<E: AtomEntry, S: AtomSource<Entry = SignedEntry<E>>>(source: S) -> impl AtomSource {
+///     VerifiedSource::new(source)
+/// }
+/// ```
+#[derive(Debug)]
+pub struct VerifiedSource<S, P = SignaturePolicy> {
+    source: S,
+    policy: P,
+}
+
+/// Why a [`VerifiedSource`] refused an answer.
+#[derive(Debug)]
+pub enum VerifiedSourceError<E> {
+    /// The source failed.
+    Source(E),
+    /// A version is unsigned, and the policy does not admit unsigned
+    /// versions.
+    Unsigned {
+        /// The atom.
+        id: AtomId,
+        /// The version.
+        version: RawVersion,
+    },
+    /// A version's transactions do not verify, or do not chain.
+    Invalid {
+        /// The atom.
+        id: AtomId,
+        /// The version.
+        version: RawVersion,
+        /// What failed.
+        error: VerifyError,
+    },
+    /// A version's publish names another atom, version, digest or claim
+    /// than the entry reports, or its content does not match.
+    Mismatch {
+        /// The atom.
+        id: AtomId,
+        /// The version.
+        version: RawVersion,
+        /// What disagrees.
+        what: &'static str,
+    },
+    /// The policy refuses a version's algorithm or publisher.
+    Refused {
+        /// The atom.
+        id: AtomId,
+        /// The version.
+        version: RawVersion,
+    },
+}
+
+impl<S> VerifiedSource<S> {
+    /// Check `source`'s answers under the strict default policy.
+    pub fn new(source: S) -> Self {
+        Self {
+            source,
+            policy: SignaturePolicy::new(),
+        }
+    }
+}
+
+impl<S, P: VerifyPolicy> VerifiedSource<S, P> {
+    /// Check answers under `policy` instead.
+    #[must_use]
+    pub fn policy<Q: VerifyPolicy>(self, policy: Q) -> VerifiedSource<S, Q> {
+        VerifiedSource {
+            source: self.source,
+            policy,
+        }
+    }
+
+    /// The source being checked.
+    pub fn source(&self) -> &S {
+        &self.source
+    }
+
+    /// The source, unwrapped.
+    pub fn into_source(self) -> S {
+        self.source
+    }
+
+    /// Check every version of `entry`, returning each one's publish.
+    fn check<E: AtomEntry, X>(
+        &self,
+        entry: &SignedEntry<E>,
+    ) -> Result<Vec<(RawVersion, PublishPayload)>, VerifiedSourceError<X>> {
+        let mut publishes = Vec::new();
+        for version in entry.versions() {
+            let raw = version.version().clone();
+            let Some(signed) = entry.signed(&raw) else {
+                if self.policy.admits_unsigned() {
+                    continue;
+                }
+                return Err(VerifiedSourceError::Unsigned {
+                    id: entry.id().clone(),
+                    version: raw,
+                });
+            };
+            let publish = self.check_version(entry.id(), version, signed)?;
+            publishes.push((raw, publish));
+        }
+        Ok(publishes)
+    }
+
+    fn check_version<X>(
+        &self,
+        id: &AtomId,
+        version: &impl AtomVersion,
+        signed: &SignedVersion,
+    ) -> Result<PublishPayload, VerifiedSourceError<X>> {
+        let invalid = |error| VerifiedSourceError::Invalid {
+            id: id.clone(),
+            version: version.version().clone(),
+            error,
+        };
+        let mismatch = |what| VerifiedSourceError::Mismatch {
+            id: id.clone(),
+            version: version.version().clone(),
+            what,
+        };
+
+        let claims = signed
+            .claims
+            .iter()
+            .map(verify_claim)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(invalid)?;
+        let (Some(claim), Some(first)) = (claims.first(), signed.claims.first()) else {
+            return Err(mismatch("claim"));
+        };
+        for pair in claims.windows(2) {
+            let prior = pair[0].prior.as_ref().map(Czd::as_bytes);
+            if !prior.is_some_and(|p| atom_id::ct_eq(p, pair[1].czd().as_bytes())) {
+                return Err(mismatch("claim chain"));
+            }
+        }
+        let publish = atom_id::verify_publish_against_claim(
+            &signed.publish.pay,
+            &signed.publish.sig,
+            &first.pay,
+            &first.sig,
+            signed.publish.alg.name(),
+            &signed.publish.key,
+        )
+        .map_err(invalid)?;
+
+        if publish.anchor != *id.anchor() || publish.label != *id.label() {
+            return Err(mismatch("atom"));
+        }
+        if publish.version != *version.version() {
+            return Err(mismatch("version"));
+        }
+        if publish.dig != version.dig() {
+            return Err(mismatch("digest"));
+        }
+        if version
+            .czd()
+            .is_some_and(|czd| !atom_id::ct_eq(czd.as_bytes(), claim.czd().as_bytes()))
+        {
+            return Err(mismatch("claim"));
+        }
+        let algs = claims.iter().map(|c| c.alg).chain([publish.alg]);
+        if !algs.into_iter().all(|alg| self.policy.admits_alg(alg))
+            || !self.policy.trusts(id, publish.signer())
+        {
+            return Err(VerifiedSourceError::Refused {
+                id: id.clone(),
+                version: version.version().clone(),
+            });
+        }
+        Ok(publish.into_payload())
+    }
+}
+
+fn verify_claim(claim: &SignedTxn) -> Result<Verified<ClaimPayload>, VerifyError> {
+    let verified =
+        atom_id::verify_claim(&claim.pay, &claim.sig, claim.alg.name(), &claim.key, None)?;
+    atom_id::verify_claim_key_thumbprint(&verified, claim.alg.name(), &claim.key)?;
+    Ok(verified)
+}
+
+impl<S, E, P> AtomSource for VerifiedSource<S, P>
+where
+    S: AtomSource<Entry = SignedEntry<E>>,
+    E: AtomEntry,
+    P: VerifyPolicy + 'static,
+{
+    type Entry = SignedEntry<E>;
+    type Error = VerifiedSourceError<S::Error>;
+
+    async fn resolve(&self, id: &AtomId) -> Result<Option<Self::Entry>, Self::Error> {
+        let entry = self
+            .source
+            .resolve(id)
+            .await
+            .map_err(VerifiedSourceError::Source)?;
+        if let Some(entry) = &entry {
+            self.check(entry)?;
+        }
+        Ok(entry)
+    }
+
+    async fn resolve_many(&self, ids: &[AtomId]) -> Result<Vec<Option<Self::Entry>>, Self::Error>
+    where
+        Self::Entry: Send,
+    {
+        let entries = self
+            .source
+            .resolve_many(ids)
+            .await
+            .map_err(VerifiedSourceError::Source)?;
+        for entry in entries.iter().flatten() {
+            self.check(entry)?;
+        }
+        Ok(entries)
+    }
+
+    /// Listed from a checked [`resolve`](AtomSource::resolve): a bare
+    /// version string carries no signature to check.
+    async fn versions(&self, id: &AtomId) -> Result<Vec<RawVersion>, Self::Error> {
+        Ok(self
+            .resolve(id)
+            .await?
+            .map(|entry| entry.versions().map(|v| v.version().clone()).collect())
+            .unwrap_or_default())
+    }
+
+    /// Checks the whole entry the pick came from, as
+    /// [`resolve`](AtomSource::resolve) does.
+    async fn resolve_version<V>(
+        &self,
+        id: &AtomId,
+        scheme: &V,
+        req: &V::Requirement,
+    ) -> Result<Option<VersionMatch<Self::Entry>>, Self::Error>
+    where
+        V: VersionScheme + Sync,
+        V::Requirement: Sync,
+    {
+        let found = self
+            .source
+            .resolve_version(id, scheme, req)
+            .await
+            .map_err(VerifiedSourceError::Source)?;
+        if let Some(found) = &found {
+            self.check(found.entry())?;
+        }
+        Ok(found)
+    }
+
+    async fn discover(&self, query: &str) -> Result<Vec<AtomId>, Self::Error> {
+        self.source
+            .discover(query)
+            .await
+            .map_err(VerifiedSourceError::Source)
+    }
+
+    async fn discover_page(
+        &self,
+        query: &str,
+        cursor: Option<&Cursor>,
+        limit: usize,
+    ) -> Result<DiscoverPage, Self::Error> {
+        self.source
+            .discover_page(query, cursor, limit)
+            .await
+            .map_err(VerifiedSourceError::Source)
+    }
+
+    async fn since(&self, checkpoint: Option<&Checkpoint>) -> Result<Changes, Self::Error> {
+        self.source
+            .since(checkpoint)
+            .await
+            .map_err(VerifiedSourceError::Source)
+    }
+}
+
+impl<S, E, P> AtomContent for VerifiedSource<S, P>
+where
+    S: AtomContent<Entry = SignedEntry<E>>,
+    E: AtomEntry,
+    P: VerifyPolicy + 'static,
+{
+    async fn content(
+        &self,
+        id: &AtomId,
+        dig: &[u8],
+    ) -> Result<Option<Vec<ContentEntry>>, Self::Error> {
+        let publishes = match self.source.resolve(id).await {
+            Ok(Some(entry)) => self.check(&entry)?,
+            Ok(None) => Vec::new(),
+            Err(e) => return Err(VerifiedSourceError::Source(e)),
+        };
+        let content = self
+            .source
+            .content(id, dig)
+            .await
+            .map_err(VerifiedSourceError::Source)?;
+        let Some(entries) = &content else {
+            return Ok(content);
+        };
+        let named: Vec<_> = publishes.iter().filter(|(_, p)| p.dig == dig).collect();
+        if named.is_empty() {
+            return Ok(None);
+        }
+        for (version, publish) in named {
+            let Some(expected) = &publish.content_hash else {
+                continue;
+            };
+            let hash = crate::content_hash(entries).ok();
+            if hash.as_ref().map(<[u8; 32]>::as_slice) != Some(expected.as_slice()) {
+                return Err(VerifiedSourceError::Mismatch {
+                    id: id.clone(),
+                    version: version.clone(),
+                    what: "content",
+                });
+            }
+        }
+        Ok(content)
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for VerifiedSourceError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Source(e) => write!(f, "{e}"),
+            Self::Unsigned { id, version } => {
+                write!(f, "{id}@{} is unsigned", version.as_str())
+            },
+            Self::Invalid { id, version, error } => {
+                write!(f, "{id}@{} does not verify: {error}", version.as_str())
+            },
+            Self::Mismatch { id, version, what } => write!(
+                f,
+                "the publish of {id}@{} disagrees with the source on its {what}",
+                version.as_str()
+            ),
+            Self::Refused { id, version } => {
+                write!(f, "the verify policy refuses {id}@{}", version.as_str())
+            },
+        }
+    }
+}
+
+impl<E: std::error::Error + 'static> std::error::Error for VerifiedSourceError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Source(e) => Some(e),
+            Self::Invalid { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
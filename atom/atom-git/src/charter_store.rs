@@ -61,9 +61,15 @@ fn parse_and_verify_charter_commit(
 ) -> Result<(CharterPayload, Czd), GitError> {
     let obj = repo.find_object(oid)?;
     let commit = obj.try_into_commit()?;
-    let msg_str = commit.message_raw_sloppy().to_string();
+    parse_and_verify_charter_msg(&commit.message_raw_sloppy().to_string())
+}
 
-    let envelope: CozMessageEnvelope = serde_json::from_str(&msg_str)?;
+/// [`parse_and_verify_charter_commit`] for a raw charter message not (yet)
+/// in any repository, such as one carried in a snapshot.
+pub(crate) fn parse_and_verify_charter_msg(
+    msg_str: &str,
+) -> Result<(CharterPayload, Czd), GitError> {
+    let envelope: CozMessageEnvelope = serde_json::from_str(msg_str)?;
     let pay_bytes = serde_json::to_vec(&envelope.pay)?;
     let pub_key = envelope.key.as_ref().ok_or_else(|| {
         GitError::Validation("Charter CozMessage is missing the key field".into())
@@ -106,7 +112,7 @@ pub fn resolve_founding_charter(
 
     let (payload, computed_czd) = parse_and_verify_charter_commit(repo, oid)?;
 
-    if computed_czd.as_bytes() != anchor.as_bytes() {
+    if !atom_id::ct_eq(computed_czd.as_bytes(), anchor.as_bytes()) {
         // Binding mismatch: the ref key lied about what it serves.
         return Ok(None);
     }
@@ -167,7 +173,8 @@ pub fn resolve_effective_charter(
         // Bind each enumerated entry to its own ref key too -- an entry
         // stored under the wrong key is not a legitimate chain member and
         // must not silently participate in chain assembly.
-        if crate::store::hex_encode(computed_czd.as_bytes()) != ref_czd_hex {
+        let computed_hex = crate::store::hex_encode(computed_czd.as_bytes());
+        if !atom_id::ct_eq(computed_hex.as_bytes(), ref_czd_hex.as_bytes()) {
             continue;
         }
 
@@ -188,7 +195,7 @@ pub fn resolve_effective_charter(
             .filter(|(_, c)| {
                 c.prior
                     .as_ref()
-                    .is_some_and(|p| p.as_bytes() == current_czd_bytes.as_slice())
+                    .is_some_and(|p| atom_id::ct_eq(p.as_bytes(), &current_czd_bytes))
             })
             .collect();
 
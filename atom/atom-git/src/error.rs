@@ -83,6 +83,14 @@ pub enum GitError {
     #[error("Invalid claim commit tree: claim commit MUST have the well-known empty tree")]
     NonEmptyClaimTree,
 
+    /// A verified version the store's `VerifyPolicy` does not accept.
+    #[error("Rejected by verify policy: {0}")]
+    PolicyRejected(String),
+
+    /// A write from outside the transaction open on the store.
+    #[error("Store busy: a transaction is open; write through it or end it first")]
+    TxnOpen,
+
     /// General validation or specification violation error.
     #[error("Spec validation failure: {0}")]
     Validation(String),
@@ -91,3 +99,6 @@ pub enum GitError {
     #[error("I/O error: {0}")]
     Io(#[from] std::io::Error),
 }
+
+/// A git backend is local, so its errors never ask callers to hold off.
+impl atom_core::ErrorClass for GitError {}
@@ -119,6 +119,17 @@ pub fn write_charter_commit(
     Ok(oid)
 }
 
+/// Create a yank commit: empty tree, parentless, the signed yank
+/// `CozMessage` as its message -- the same object shape as a charter
+/// commit. A version is yanked at most once, so there is no chain to
+/// parent.
+pub fn write_yank_commit(
+    repo: &gix::Repository,
+    yank_message: String,
+) -> Result<ObjectId, GitError> {
+    write_charter_commit(repo, yank_message)
+}
+
 /// Create an annotated tag object carrying a signed publish transaction payload.
 ///
 /// Targets either the atom commit (for initial publish) or the previous publish tag (for updates).
@@ -12,4 +12,4 @@ pub mod store;
 pub use error::GitError;
 pub use registry::GitRegistry;
 pub use source::{GitEntry, GitSource};
-pub use store::GitStore;
+pub use store::{GitStore, GitTxn};
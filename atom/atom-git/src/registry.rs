@@ -6,11 +6,12 @@
 use std::time::SystemTime;
 
 use atom_core::{
-    AtomContent, AtomId, AtomRegistry, AtomSource, ContentEntry, Czd, OwnerRef, RawVersion,
+    AtomContent, AtomEvent, AtomEvents, AtomId, AtomRegistry, AtomSource, ContentEntry, Czd,
+    OwnerRef, RawVersion, Subscribers, Subscription,
 };
 #[cfg(test)]
 use atom_id::Anchor;
-use atom_id::{CharterPayload, ClaimPayload, PublishPayload};
+use atom_id::{CanonicalPayload, CharterPayload, ClaimPayload, PublishPayload, YankPayload};
 use gix::refs::transaction::{Change, LogChange, PreviousValue, RefEdit, RefLog};
 use gix::refs::{FullName, Target};
 
@@ -33,6 +34,8 @@ pub struct GitRegistry {
     pub alg: coz_rs::Alg,
     /// Package ecosystem format identifier (e.g., "cargo", "npm", "ion").
     pub pkg: String,
+    /// Subscribers to the claims and publishes made through this registry.
+    events: Subscribers,
 }
 
 impl GitRegistry {
@@ -50,10 +53,21 @@ impl GitRegistry {
             pub_key,
             alg,
             pkg,
+            events: Subscribers::new(),
         }
     }
 }
 
+impl AtomEvents for GitRegistry {
+    fn subscribe(&self, subscriber: impl Fn(&AtomEvent) + Send + Sync + 'static) -> Subscription {
+        self.events.subscribe(subscriber)
+    }
+
+    fn unsubscribe(&self, subscription: Subscription) -> bool {
+        self.events.unsubscribe(subscription)
+    }
+}
+
 impl AtomSource for GitRegistry {
     type Entry = GitEntry;
     type Error = GitError;
@@ -138,10 +152,9 @@ fn parse_and_verify_claim(
         .and_then(|v| v.as_str())
         .ok_or_else(|| GitError::Validation("Claim alg field is missing or invalid".into()))?;
 
-    let computed_czd = atom_id::czd_for_alg(&pay_bytes, &envelope.sig, alg_str)?;
-    let payload = atom_id::verify_claim(&pay_bytes, &envelope.sig, alg_str, pub_key)?;
+    let verified = atom_id::verify_claim(&pay_bytes, &envelope.sig, alg_str, pub_key, None)?;
 
-    Ok((payload, computed_czd))
+    Ok(verified.into_parts())
 }
 
 /// Resolve a single charter — founding or successor — directly by its own
@@ -167,7 +180,7 @@ fn resolve_charter_by_czd(
     let oid = reference.id().detach();
 
     let (payload, computed_czd) = parse_and_verify_charter(repo, oid)?;
-    if computed_czd != *czd {
+    if !atom_id::ct_eq(computed_czd.as_bytes(), czd.as_bytes()) {
         return Ok(None);
     }
     Ok(Some(payload))
@@ -198,7 +211,11 @@ fn any_successor_names_prior(repo: &gix::Repository, prior: &Czd) -> Result<bool
         if crate::charter_store::charter_ref_name(computed_czd.as_bytes()) != ref_name {
             continue;
         }
-        if payload.prior.as_ref() == Some(prior) {
+        if payload
+            .prior
+            .as_ref()
+            .is_some_and(|p| atom_id::ct_eq(p.as_bytes(), prior.as_bytes()))
+        {
             return Ok(true);
         }
     }
@@ -244,7 +261,9 @@ fn find_earliest_claim(repo: &gix::Repository) -> Result<Option<ClaimPayload>, G
             .and_then(|v| v.as_str())
             .ok_or_else(|| GitError::Validation("Claim alg field is missing or invalid".into()))?;
 
-        let claim_payload = atom_id::verify_claim(&pay_bytes, &envelope.sig, alg_str, pub_key)?;
+        let claim_payload =
+            atom_id::verify_claim(&pay_bytes, &envelope.sig, alg_str, pub_key, None)?
+                .into_payload();
 
         if earliest
             .as_ref()
@@ -361,10 +380,9 @@ impl AtomRegistry for GitRegistry {
         };
 
         // 4. Serialize, sign, and envelope
-        let pay_val = serde_json::to_value(&claim_payload)?;
+        let pay_bytes = claim_payload.to_canonical_json()?;
         let pay_map: indexmap::IndexMap<String, serde_json::Value> =
-            serde_json::from_value(pay_val)?;
-        let pay_bytes = serde_json::to_vec(&pay_map)?;
+            serde_json::from_slice(&pay_bytes)?;
 
         let (sig, _cad) = coz_rs::sign_json(
             &pay_bytes,
@@ -433,6 +451,10 @@ impl AtomRegistry for GitRegistry {
 
         repo.edit_references(edits)?;
 
+        self.events.emit(&AtomEvent::Claimed {
+            id: id.clone(),
+            czd: czd.clone(),
+        });
         Ok(czd)
     }
 
@@ -476,15 +498,15 @@ impl AtomRegistry for GitRegistry {
             &claim_envelope.sig,
             claim_alg_str,
             claim_pub_key,
+            None,
         )?;
 
         // The caller must name the active claim by its spec-defined czd —
         // the digest of (cad, sig) — never by the git object id the claim
-        // commit happens to be stored at. Recompute it from the active
-        // claim's own signed bytes and compare.
-        let active_czd =
-            atom_id::czd_for_alg(&claim_pay_bytes, &claim_envelope.sig, claim_alg_str)?;
-        if active_czd != *claim {
+        // commit happens to be stored at. Verification computed it from the
+        // active claim's own signed bytes; compare.
+        let active_czd = claim_payload.czd();
+        if !atom_id::ct_eq(active_czd.as_bytes(), claim.as_bytes()) {
             return Err(GitError::Validation(format!(
                 "Active claim mismatch: active is {} but expected {}",
                 active_czd.to_b64(),
@@ -544,6 +566,13 @@ impl AtomRegistry for GitRegistry {
         } else {
             current_time
         };
+        // A lapsed claim admits no new publish ([claim-expiry]).
+        if let Some(exp) = claim_payload
+            .exp
+            .filter(|_| claim_payload.is_expired_at(now))
+        {
+            return Err(atom_id::VerifyError::Expired { exp }.into());
+        }
 
         let publish_payload = PublishPayload::new(
             self.alg,
@@ -558,10 +587,9 @@ impl AtomRegistry for GitRegistry {
         );
 
         // 7. Serialize, sign, and envelope
-        let pay_val = serde_json::to_value(&publish_payload)?;
+        let pay_bytes = publish_payload.to_canonical_json()?;
         let pay_map: indexmap::IndexMap<String, serde_json::Value> =
-            serde_json::from_value(pay_val)?;
-        let pay_bytes = serde_json::to_vec(&pay_map)?;
+            serde_json::from_slice(&pay_bytes)?;
 
         let (sig, _cad) = coz_rs::sign_json(
             &pay_bytes,
@@ -570,6 +598,7 @@ impl AtomRegistry for GitRegistry {
             &self.pub_key,
         )
         .ok_or_else(|| GitError::Coz("Failed to sign publish JSON".into()))?;
+        let publish_czd = atom_id::czd_for_alg(&pay_bytes, &sig, self.alg.name())?;
 
         let envelope = CozMessageEnvelope {
             pay: pay_map,
@@ -645,9 +674,125 @@ impl AtomRegistry for GitRegistry {
 
         repo.edit_references(edits)?;
 
+        self.events.emit(&AtomEvent::Published {
+            id: id.clone(),
+            version: version.clone(),
+            czd: publish_czd,
+        });
         Ok(())
     }
 
+    fn yank(&self, id: &AtomId, version: &RawVersion, claim: &Czd) -> Result<Czd, Self::Error> {
+        let repo = self.source.repo();
+
+        // 1. The version must be published and not yet yanked
+        let yank_ref_name = format!("refs/atom/yank/pub/{}/{}", id.label(), version.as_str());
+        if repo.try_find_reference(&yank_ref_name)?.is_some() {
+            return Err(GitError::Validation(format!(
+                "version {} of {} is already yanked",
+                version.as_str(),
+                id
+            )));
+        }
+        let version_ref_name = format!("refs/atom/pub/{}/{}", id.label(), version.as_str());
+        let version_ref = repo.try_find_reference(&version_ref_name)?.ok_or_else(|| {
+            GitError::Validation(format!(
+                "version {} of {} is not published",
+                version.as_str(),
+                id
+            ))
+        })?;
+        let tip_tag = repo
+            .find_object(version_ref.id().detach())?
+            .try_into_tag()?;
+        let tip_envelope: CozMessageEnvelope =
+            serde_json::from_str(&tip_tag.decode()?.message.to_string())?;
+        let publish_payload: PublishPayload =
+            serde_json::from_value(serde_json::to_value(&tip_envelope.pay)?)?;
+
+        // 2. Only the active claim's owner may yank (`[yank-authority]`),
+        // and the claim is named by its czd, exactly as for `publish`.
+        let claim_ref_name = format!("refs/atom/claims/pub/{}", id.label());
+        let claim_ref = repo
+            .try_find_reference(&claim_ref_name)?
+            .ok_or_else(|| GitError::NoActiveClaim(id.label().to_string()))?;
+        let (claim_payload, active_czd) = parse_and_verify_claim(&repo, claim_ref.id().detach())?;
+        if !atom_id::ct_eq(active_czd.as_bytes(), claim.as_bytes()) {
+            return Err(GitError::Validation(format!(
+                "Active claim mismatch: active is {} but expected {}",
+                active_czd.to_b64(),
+                claim.to_b64()
+            )));
+        }
+        let tmb = coz_rs::compute_thumbprint_for_alg(self.alg.name(), &self.pub_key)
+            .ok_or_else(|| GitError::Coz("Failed to compute key thumbprint".into()))?;
+        if !claim_payload.owner.authorizes(&tmb) {
+            return Err(atom_id::VerifyError::Unauthorized.into());
+        }
+
+        // 3. Construct the YankPayload, strictly after the publish
+        let current_time = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let now = current_time.max(publish_payload.now + 1);
+        let yank_payload = YankPayload::new(
+            self.alg,
+            id.clone(),
+            claim.clone(),
+            now,
+            tmb,
+            version.clone(),
+        );
+        atom_id::verify_yank_against_publish(&yank_payload, &publish_payload)?;
+
+        // 4. Serialize, sign, and envelope
+        let pay_bytes = yank_payload.to_canonical_json()?;
+        let pay_map: indexmap::IndexMap<String, serde_json::Value> =
+            serde_json::from_slice(&pay_bytes)?;
+
+        let (sig, _cad) = coz_rs::sign_json(
+            &pay_bytes,
+            self.alg.name(),
+            &self.signing_key,
+            &self.pub_key,
+        )
+        .ok_or_else(|| GitError::Coz("Failed to sign yank JSON".into()))?;
+        let czd = atom_id::czd_for_alg(&pay_bytes, &sig, self.alg.name())?;
+
+        let envelope = CozMessageEnvelope {
+            pay: pay_map,
+            sig,
+            key: Some(self.pub_key.clone()),
+        };
+        let yank_msg = serde_json::to_string(&envelope)?;
+
+        // 5. Write the yank commit beside, never over, the publish
+        let yank_oid = crate::gix_util::write_yank_commit(&repo, yank_msg)?;
+        let yank_ref_fullname = FullName::try_from(yank_ref_name.as_str())
+            .map_err(|e| GitError::Validation(e.to_string()))?;
+        repo.edit_reference(RefEdit {
+            change: Change::Update {
+                log: LogChange {
+                    mode: RefLog::AndReference,
+                    force_create_reflog: false,
+                    message: format!("Yank version {}", version.as_str()).into(),
+                },
+                expected: PreviousValue::MustNotExist,
+                new: Target::Object(yank_oid),
+            },
+            name: yank_ref_fullname,
+            deref: false,
+        })?;
+
+        self.events.emit(&AtomEvent::Yanked {
+            id: id.clone(),
+            version: version.clone(),
+            czd: czd.clone(),
+        });
+        Ok(czd)
+    }
+
     fn charter(
         &self,
         owner: &[OwnerRef],
@@ -740,10 +885,9 @@ impl AtomRegistry for GitRegistry {
 
         // Serialize, sign, and envelope -- mirrors claim()/publish()'s
         // exact idiom.
-        let pay_val = serde_json::to_value(&charter_payload)?;
+        let pay_bytes = charter_payload.to_canonical_json()?;
         let pay_map: indexmap::IndexMap<String, serde_json::Value> =
-            serde_json::from_value(pay_val)?;
-        let pay_bytes = serde_json::to_vec(&pay_map)?;
+            serde_json::from_slice(&pay_bytes)?;
 
         let (sig, _cad) = coz_rs::sign_json(
             &pay_bytes,
@@ -1,7 +1,7 @@
 //! Implementations of [`AtomSource`] and observation types.
 
 use atom_core::{AtomContent, AtomId, AtomSource, ContentEntry, RawVersion};
-use atom_id::{ClaimPayload, PublishPayload};
+use atom_id::{AtomIdRef, ClaimPayload, PublishPayload, YankPayload};
 use coz_rs::Czd;
 use gix::hash::ObjectId;
 use serde::{Deserialize, Serialize};
@@ -79,6 +79,10 @@ pub struct GitVersionEntry {
     pub publish_sig: Option<Vec<u8>>,
     /// Raw publish public key bytes, if signed.
     pub publish_pubkey: Option<Vec<u8>>,
+    /// Deserialized yank payload, if the version was yanked.
+    pub yank_payload: Option<YankPayload>,
+    /// Raw yank CozMessage JSON envelope, if the version was yanked.
+    pub yank_msg: Option<String>,
     /// Whether this version's publish-tag-chain ref tip changed between
     /// when its resolution walk began and when verification completed for
     /// it — [`TipStability::Moved`]/[`TipStability::Vanished`] signal the
@@ -115,13 +119,10 @@ impl GitSource {
     pub fn repo(&self) -> gix::Repository {
         self.repo_ts.to_thread_local()
     }
-}
-
-impl AtomSource for GitSource {
-    type Entry = GitEntry;
-    type Error = GitError;
 
-    async fn resolve(&self, id: &AtomId) -> Result<Option<Self::Entry>, Self::Error> {
+    /// [`resolve`](AtomSource::resolve), probing with a borrowed identity:
+    /// the owned [`AtomId`] is built only for an entry found.
+    pub(crate) fn lookup(&self, id: AtomIdRef<'_>) -> Result<Option<GitEntry>, GitError> {
         let repo = self.repo();
         let mut versions = Vec::new();
 
@@ -155,7 +156,9 @@ impl AtomSource for GitSource {
                 &claim_envelope.sig,
                 alg_str,
                 claim_pub_key,
-            )?;
+                None,
+            )?
+            .into_payload();
 
             // Close the tmb-binding soundness gap (Verification Pipeline
             // step 6, claim side) BEFORE trusting `claim_payload.tmb` for
@@ -277,7 +280,9 @@ impl AtomSource for GitSource {
                             &pub_envelope.sig,
                             pub_alg_str,
                             pub_key_bytes,
-                        )?;
+                            None,
+                        )?
+                        .into_payload();
 
                         // Close the documented tmb-binding soundness gap
                         // (Verification Pipeline step 6, publish side)
@@ -371,6 +376,18 @@ impl AtomSource for GitSource {
                     // the walk, the fields above may already be stale.
                     let moved_tip = tip_stability(&repo, &ref_name, observed_tip)?;
 
+                    let yank_ref_name =
+                        format!("refs/atom/yank/pub/{}/{}", id.label(), version_str);
+                    let (yank_payload, yank_msg) = read_yank(
+                        &repo,
+                        &yank_ref_name,
+                        &claim_pay_bytes,
+                        &claim_envelope.sig,
+                        alg_str,
+                        &pub_payload,
+                    )?
+                    .unzip();
+
                     versions.push(GitVersionEntry {
                         version: RawVersion::new(version_str.to_string()),
                         dig: dig_oid.as_bytes().to_vec(),
@@ -383,6 +400,8 @@ impl AtomSource for GitSource {
                         publish_msg: tip_publish_msg,
                         publish_sig: tip_publish_sig,
                         publish_pubkey: tip_publish_pubkey,
+                        yank_payload,
+                        yank_msg,
                         moved_tip,
                     });
                 }
@@ -476,7 +495,9 @@ impl AtomSource for GitSource {
                 &claim_envelope.sig,
                 alg_str,
                 claim_pub_key,
-            )?;
+                None,
+            )?
+            .into_payload();
 
             // Close the tmb-binding soundness gap -- see the REGISTRY
             // resolution branch above for the identical rationale.
@@ -547,7 +568,9 @@ impl AtomSource for GitSource {
                     &pub_envelope.sig,
                     pub_alg_str,
                     pub_key_bytes,
-                )?;
+                    None,
+                )?
+                .into_payload();
 
                 // Close the documented tmb-binding soundness gap
                 // (Verification Pipeline step 6, publish side) BEFORE
@@ -615,6 +638,17 @@ impl AtomSource for GitSource {
             // already be stale.
             let moved_tip = tip_stability(&repo, &v_ref_name, observed_tip)?;
 
+            let store_key = v_ref_name.strip_prefix(store_version_prefix).unwrap_or("");
+            let (yank_payload, yank_msg) = read_yank(
+                &repo,
+                &format!("refs/atom/yank/d/{}", store_key),
+                &claim_pay_bytes,
+                &claim_envelope.sig,
+                alg_str,
+                &pub_payload,
+            )?
+            .unzip();
+
             versions.push(GitVersionEntry {
                 version: pub_payload.version.clone(),
                 dig: dig_oid.as_bytes().to_vec(),
@@ -627,6 +661,8 @@ impl AtomSource for GitSource {
                 publish_msg: tip_publish_msg,
                 publish_sig: tip_publish_sig,
                 publish_pubkey: tip_publish_pubkey,
+                yank_payload,
+                yank_msg,
                 moved_tip,
             });
         }
@@ -666,6 +702,8 @@ impl AtomSource for GitSource {
                     publish_msg: None,
                     publish_sig: None,
                     publish_pubkey: None,
+                    yank_payload: None,
+                    yank_msg: None,
                     // Dev resolution has no tag chain to walk -- the ref
                     // is read once and used immediately, so there is no
                     // window in which its tip could move mid-resolution.
@@ -678,11 +716,20 @@ impl AtomSource for GitSource {
             Ok(None)
         } else {
             Ok(Some(GitEntry {
-                id: id.clone(),
+                id: id.to_atom_id(),
                 versions,
             }))
         }
     }
+}
+
+impl AtomSource for GitSource {
+    type Entry = GitEntry;
+    type Error = GitError;
+
+    async fn resolve(&self, id: &AtomId) -> Result<Option<Self::Entry>, Self::Error> {
+        self.lookup(id.as_id_ref())
+    }
 
     async fn discover(&self, query: &str) -> Result<Vec<AtomId>, Self::Error> {
         let repo = self.repo();
@@ -722,7 +769,9 @@ impl AtomSource for GitSource {
                 &claim_envelope.sig,
                 alg_str,
                 claim_pub_key,
-            )?;
+                None,
+            )?
+            .into_payload();
             ids.insert(AtomId::new(claim_payload.anchor, claim_payload.label));
         }
 
@@ -760,7 +809,9 @@ impl AtomSource for GitSource {
                 &claim_envelope.sig,
                 alg_str,
                 claim_pub_key,
-            )?;
+                None,
+            )?
+            .into_payload();
             if claim_payload.label.contains(query) {
                 ids.insert(AtomId::new(claim_payload.anchor, claim_payload.label));
             }
@@ -806,6 +857,71 @@ impl atom_core::AtomVersion for GitVersionEntry {
     fn publish_msg(&self) -> Option<&str> {
         self.publish_msg.as_deref()
     }
+
+    fn src(&self) -> Option<&[u8]> {
+        self.publish_payload.as_ref().map(|p| p.src.as_slice())
+    }
+
+    fn path(&self) -> Option<&str> {
+        self.publish_payload.as_ref().map(|p| p.path.as_str())
+    }
+
+    fn published_at(&self) -> Option<u64> {
+        self.publish_payload.as_ref().map(|p| p.now)
+    }
+
+    fn yank_msg(&self) -> Option<&str> {
+        self.yank_msg.as_deref()
+    }
+}
+
+/// Read the yank record at `ref_name`, if there is one, verified against
+/// the version's claim and tip publish.
+fn read_yank(
+    repo: &gix::Repository,
+    ref_name: &str,
+    claim_pay_bytes: &[u8],
+    claim_sig: &[u8],
+    claim_alg: &str,
+    publish: &PublishPayload,
+) -> Result<Option<(YankPayload, String)>, GitError> {
+    let Some(reference) = repo.try_find_reference(ref_name)? else {
+        return Ok(None);
+    };
+    let commit = repo
+        .find_object(reference.id().detach())?
+        .try_into_commit()?;
+    let msg = commit.message_raw_sloppy().to_string();
+    let payload = verify_yank_msg(&msg, claim_pay_bytes, claim_sig, claim_alg, publish)?;
+    Ok(Some((payload, msg)))
+}
+
+/// Verify a raw yank `CozMessage`: its signature and `typ`, that the
+/// version's claim owner signed it, and that it names the version's
+/// publish (`[yank-authority]`).
+pub(crate) fn verify_yank_msg(
+    msg: &str,
+    claim_pay_bytes: &[u8],
+    claim_sig: &[u8],
+    claim_alg: &str,
+    publish: &PublishPayload,
+) -> Result<YankPayload, GitError> {
+    let envelope: CozMessageEnvelope = serde_json::from_str(msg)?;
+    let pay_bytes = serde_json::to_vec(&envelope.pay)?;
+    let pub_key = envelope
+        .key
+        .as_ref()
+        .ok_or_else(|| GitError::Validation("Yank CozMessage is missing the key field".into()))?;
+    let alg_str = envelope
+        .pay
+        .get("alg")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| GitError::Validation("Yank alg field is missing or invalid".into()))?;
+
+    let yank = atom_id::verify_yank(&pay_bytes, &envelope.sig, alg_str, pub_key)?;
+    atom_id::verify_yank_against_claim(&yank, claim_pay_bytes, claim_sig, claim_alg)?;
+    atom_id::verify_yank_against_publish(&yank, publish)?;
+    Ok(yank)
 }
 
 impl AtomContent for GitSource {
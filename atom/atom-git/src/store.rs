@@ -4,13 +4,18 @@
 //! directories into a local Git store repository.
 
 use std::any::Any;
-use std::collections::HashSet;
-use std::fs;
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::time::SystemTime;
+use std::{fs, io};
 
 use atom_core::{
-    AtomContent, AtomEntry, AtomId, AtomSource, AtomStore, AtomVersion, ContentEntry, Label,
-    RawVersion,
+    AtomBlobs, AtomContent, AtomEntry, AtomEvent, AtomEvents, AtomId, AtomIdKey, AtomSource,
+    AtomStore, AtomStoreMut, AtomStoreResume, AtomStoreTxn, AtomVersion, ContentEntry,
+    IngestCheckpoint, IngestCounts, IngestFilter, IngestObserver, Label, RawVersion,
+    RegistryMirror, SignaturePolicy, Snapshot, SnapshotError, StoreTxn, Subscribers, Subscription,
+    SyncStatus, Thumbprint, VerifyPolicy,
 };
 use coz_rs;
 use gix::hash::ObjectId;
@@ -18,7 +23,7 @@ use gix::refs::transaction::{Change, LogChange, PreviousValue, RefEdit, RefLog};
 use gix::refs::{FullName, Target};
 
 use crate::error::GitError;
-use crate::source::{CozMessageEnvelope, GitEntry, GitSource};
+use crate::source::{CozMessageEnvelope, GitEntry, GitSource, GitVersionEntry};
 
 /// Opaque sentinel bytes indicating a filesystem-sourced anchor.
 pub const FS_SENTINEL_ANCHOR: &[u8] = b"fs-sentinel-anchor";
@@ -57,9 +62,22 @@ pub fn hex_encode(bytes: &[u8]) -> String {
 ///
 /// Implements [`AtomStore`] to accumulate package versions, verify coz
 /// signatures locally, and import filesystem paths as local dev packages.
+///
+/// Ingest and resolve consult the store's [`VerifyPolicy`] —
+/// [`SignaturePolicy::new`], the strict default, unless
+/// [`with_policy`](Self::with_policy) sets another.
 pub struct GitStore {
     /// Read-only source interface for resolving and discovering references.
     pub source: GitSource,
+    /// What ingest and resolve demand of a version's signatures.
+    policy: Box<dyn VerifyPolicy>,
+    /// Subscribers to the versions written to this store.
+    events: Subscribers,
+    /// The events of the open [`GitTxn`], held back until it commits;
+    /// `None` while no transaction is open.
+    txn: Mutex<Option<Vec<AtomEvent>>>,
+    /// The outcome of the latest [`RegistryMirror::sync_from`].
+    status: Mutex<SyncStatus>,
 }
 
 impl GitStore {
@@ -67,7 +85,59 @@ impl GitStore {
     pub fn new(repo: gix::Repository) -> Self {
         Self {
             source: GitSource::new(repo),
+            policy: Box::new(SignaturePolicy::new()),
+            events: Subscribers::new(),
+            txn: Mutex::default(),
+            status: Mutex::default(),
+        }
+    }
+
+    /// Verify ingested and resolved versions against `policy` instead.
+    #[must_use]
+    pub fn with_policy(mut self, policy: impl VerifyPolicy + 'static) -> Self {
+        self.policy = Box::new(policy);
+        self
+    }
+
+    /// Tell subscribers of `event` — or, while a transaction is open,
+    /// hold it until the transaction commits.
+    fn emit(&self, event: AtomEvent) {
+        if let Some(held) = self.txn().as_mut() {
+            held.push(event);
+            return;
         }
+        // Not under the lock: a subscriber may write to the store.
+        self.events.emit(&event);
+    }
+
+    /// Refuse a write from outside the open transaction, if there is one:
+    /// its rollback would undo the write.
+    fn check_no_txn(&self) -> Result<(), GitError> {
+        if self.txn().is_some() {
+            return Err(GitError::TxnOpen);
+        }
+        Ok(())
+    }
+
+    /// The open transaction's held events.
+    fn txn(&self) -> MutexGuard<'_, Option<Vec<AtomEvent>>> {
+        self.txn.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Whether the policy accepts a signature by `signer` with `alg` on
+    /// `id`.
+    fn admits_signature(&self, id: &AtomId, alg: coz_rs::Alg, signer: &Thumbprint) -> bool {
+        self.policy.admits_alg(alg) && self.policy.trusts(id, signer)
+    }
+
+    /// Whether the policy accepts a stored version. Unsigned versions are
+    /// kept: they got here through [`import_path`](Self::import_path), or
+    /// an ingest whose policy admitted them.
+    fn admits_stored(&self, id: &AtomId, version: &GitVersionEntry) -> bool {
+        let claim = version.claim_payload.as_ref();
+        let publish = version.publish_payload.as_ref();
+        claim.is_none_or(|c| self.admits_signature(id, c.alg, &c.tmb))
+            && publish.is_none_or(|p| self.admits_signature(id, p.alg, &p.tmb))
     }
 
     /// Import a filesystem directory into the store as an unsigned dev version.
@@ -81,6 +151,7 @@ impl GitStore {
         path: &Path,
         dev_version: &RawVersion,
     ) -> Result<(), GitError> {
+        self.check_no_txn()?;
         let repo = self.source.repo();
 
         // 1. Construct AtomId using the filesystem sentinel anchor
@@ -128,6 +199,11 @@ impl GitStore {
 
         repo.edit_reference(edit)?;
 
+        self.emit(AtomEvent::Ingested {
+            id,
+            version: dev_version.clone(),
+            czd: None,
+        });
         Ok(())
     }
 
@@ -136,9 +212,9 @@ impl GitStore {
     /// `store_key_hex` is the flat store ref's own key,
     /// `hex(blake3(publish_czd))` (`[store-ref-by-publish-czd]`) -- the
     /// same segment `GitStore::ingest` writes under. Implements
-    /// `[store-claim-cleanup]`: after removing the version ref, if no
-    /// other surviving `refs/atom/d/*` ref's publish tag still chains to
-    /// the same claim czd, the corresponding
+    /// `[store-claim-cleanup]`: after removing the version ref (and its
+    /// yank ref, if any), if no other surviving `refs/atom/d/*` ref's
+    /// publish tag still chains to the same claim czd, the corresponding
     /// `refs/atom/claims/d/{claim_czd}` ref is also deleted. The flat
     /// scheme carries no claim segment in its own path, so the owning
     /// claim can only be discovered by inspecting the ref's own publish
@@ -151,6 +227,7 @@ impl GitStore {
     /// could leave an orphan claim ref. Callers must serialize evictions
     /// per claim, or a periodic GC pass should sweep orphaned claims.
     pub fn evict_version(&self, store_key_hex: &str) -> Result<(), GitError> {
+        self.check_no_txn()?;
         let repo = self.source.repo();
         let version_ref_name = format!("refs/atom/d/{}", store_key_hex);
         let version_fullname = FullName::try_from(version_ref_name.as_str())
@@ -164,16 +241,28 @@ impl GitStore {
             None => None,
         };
 
-        // 1. Delete the version reference
-        let edit = RefEdit {
+        // 1. Delete the version reference, and its yank if it has one
+        let mut edits = vec![RefEdit {
             change: Change::Delete {
                 expected: PreviousValue::Any,
                 log: RefLog::AndReference,
             },
             name: version_fullname,
             deref: false,
-        };
-        repo.edit_reference(edit)?;
+        }];
+        let yank_ref_name = format!("refs/atom/yank/d/{}", store_key_hex);
+        if repo.try_find_reference(&yank_ref_name)?.is_some() {
+            edits.push(RefEdit {
+                change: Change::Delete {
+                    expected: PreviousValue::Any,
+                    log: RefLog::AndReference,
+                },
+                name: FullName::try_from(yank_ref_name.as_str())
+                    .map_err(|e| GitError::Validation(e.to_string()))?,
+                deref: false,
+            });
+        }
+        repo.edit_references(edits)?;
 
         let Some(claim_czd) = claim_czd else {
             return Ok(());
@@ -185,7 +274,8 @@ impl GitStore {
         let mut any_left = false;
         for r in repo.references()?.prefixed("refs/atom/d/")? {
             let Ok(r) = r else { continue };
-            if claim_czd_of_store_ref(&repo, &r)?.as_ref() == Some(&claim_czd) {
+            let czd = claim_czd_of_store_ref(&repo, &r)?;
+            if czd.is_some_and(|czd| atom_id::ct_eq(czd.as_bytes(), claim_czd.as_bytes())) {
                 any_left = true;
                 break;
             }
@@ -223,6 +313,15 @@ fn claim_czd_of_store_ref(
     repo: &gix::Repository,
     reference: &gix::Reference,
 ) -> Result<Option<coz_rs::Czd>, GitError> {
+    Ok(publish_payload_of_store_ref(repo, reference)?.map(|payload| payload.claim))
+}
+
+/// Read a store version ref's publish payload, unverified, on the same
+/// terms as [`claim_czd_of_store_ref`].
+fn publish_payload_of_store_ref(
+    repo: &gix::Repository,
+    reference: &gix::Reference,
+) -> Result<Option<atom_id::PublishPayload>, GitError> {
     let oid = reference.id().detach();
     let obj = repo.find_object(oid)?;
     if obj.kind != gix::object::Kind::Tag {
@@ -233,8 +332,7 @@ fn claim_czd_of_store_ref(
     let msg_str = tag_decoded.message.to_string();
     let envelope: CozMessageEnvelope = serde_json::from_str(&msg_str)?;
     let pay_value = serde_json::to_value(&envelope.pay)?;
-    let publish_payload: atom_id::PublishPayload = serde_json::from_value(pay_value)?;
-    Ok(Some(publish_payload.claim))
+    Ok(Some(serde_json::from_value(pay_value)?))
 }
 
 /// Propagate the source's charter chain for `anchor` into the destination
@@ -265,11 +363,12 @@ fn claim_czd_of_store_ref(
 /// takes (b): it is the one place `ingest`'s charter handling is more
 /// tightly coupled to the git backend than its claim/publish handling,
 /// exactly as flagged in this node's own IBC. A source that isn't
-/// downcastable to `GitSource` (impossible today -- no other
-/// implementation exists) fails closed with a clear error rather than
-/// silently skipping charter propagation, so `[c3-fails-closed-still]`
-/// holds even in that hypothetical case. When a second backend is ever
-/// added, this is the seam to revisit -- not before.
+/// downcastable to `GitSource` (today only a [`Snapshot`], whose charters
+/// `GitStore::import` verifies and writes before ingesting it) has nothing
+/// to copy: it passes only if `dest_repo` already resolves a verified
+/// chain for the anchor, and otherwise fails closed with a clear error
+/// rather than silently skipping charter propagation, so
+/// `[c3-fails-closed-still]` holds either way.
 fn propagate_charter_chain<S: AtomContent>(
     dest_repo: &gix::Repository,
     source: &S,
@@ -277,6 +376,9 @@ fn propagate_charter_chain<S: AtomContent>(
 ) -> Result<(), GitError> {
     let source_any: &dyn Any = source;
     let Some(git_source) = source_any.downcast_ref::<GitSource>() else {
+        if crate::charter_store::resolve_effective_charter(dest_repo, anchor, None)?.is_some() {
+            return Ok(());
+        }
         return Err(GitError::Validation(
             "cannot propagate charter chain: ingest's charter propagation is git-native \
              (n3-store-charter-ingest) and this source is not a git-backed AtomContent \
@@ -311,29 +413,74 @@ fn propagate_charter_chain<S: AtomContent>(
                 )
             })?;
 
-        let ref_name = crate::charter_store::charter_ref_name(czd.as_bytes());
-        if dest_repo.try_find_reference(&ref_name)?.is_some() {
-            continue;
-        }
+        write_charter(dest_repo, &czd, raw_msg)?;
+    }
 
-        let oid = crate::gix_util::write_charter_commit(dest_repo, raw_msg)?;
-        let fullname = FullName::try_from(ref_name.as_str())
-            .map_err(|e| GitError::Validation(e.to_string()))?;
-        dest_repo.edit_reference(RefEdit {
-            change: Change::Update {
-                log: LogChange {
-                    mode: RefLog::AndReference,
-                    force_create_reflog: false,
-                    message: "Ingest charter commit".into(),
-                },
-                expected: PreviousValue::Any,
-                new: Target::Object(oid),
+    Ok(())
+}
+
+/// Write a yank commit for an already verified yank message, returning
+/// the edit that points `ref_name` at it and the yank's czd.
+fn yank_edit(
+    dest_repo: &gix::Repository,
+    ref_name: &str,
+    yank_msg: String,
+) -> Result<(RefEdit, atom_id::Czd), GitError> {
+    let envelope: CozMessageEnvelope = serde_json::from_str(&yank_msg)?;
+    let pay_bytes = serde_json::to_vec(&envelope.pay)?;
+    let alg_str = envelope
+        .pay
+        .get("alg")
+        .and_then(|val| val.as_str())
+        .ok_or_else(|| GitError::Validation("Yank alg is missing or invalid".into()))?;
+    let czd = atom_id::czd_for_alg(&pay_bytes, &envelope.sig, alg_str)?;
+
+    let oid = crate::gix_util::write_yank_commit(dest_repo, yank_msg)?;
+    let fullname = FullName::try_from(ref_name).map_err(|e| GitError::Validation(e.to_string()))?;
+    let edit = RefEdit {
+        change: Change::Update {
+            log: LogChange {
+                mode: RefLog::AndReference,
+                force_create_reflog: false,
+                message: "Ingest yank commit".into(),
             },
-            name: fullname,
-            deref: false,
-        })?;
+            expected: PreviousValue::Any,
+            new: Target::Object(oid),
+        },
+        name: fullname,
+        deref: false,
+    };
+    Ok((edit, czd))
+}
+
+/// Write a charter commit and its czd-keyed ref into `dest_repo`, unless
+/// the ref is already there.
+fn write_charter(
+    dest_repo: &gix::Repository,
+    czd: &atom_id::Czd,
+    raw_msg: String,
+) -> Result<(), GitError> {
+    let ref_name = crate::charter_store::charter_ref_name(czd.as_bytes());
+    if dest_repo.try_find_reference(&ref_name)?.is_some() {
+        return Ok(());
     }
 
+    let oid = crate::gix_util::write_charter_commit(dest_repo, raw_msg)?;
+    let fullname =
+        FullName::try_from(ref_name.as_str()).map_err(|e| GitError::Validation(e.to_string()))?;
+    dest_repo.edit_reference(RefEdit {
+        change: Change::Update {
+            log: LogChange {
+                mode: RefLog::AndReference,
+                force_create_reflog: false,
+                message: "Ingest charter commit".into(),
+            },
+            expected: PreviousValue::Any,
+            new: Target::Object(oid),
+        },
+        name: fullname,
+        deref: false,
+    })?;
     Ok(())
 }
 
@@ -387,8 +534,10 @@ impl AtomSource for GitStore {
     type Entry = GitEntry;
     type Error = GitError;
 
+    /// Versions the store's policy no longer accepts — it was narrowed
+    /// after they were ingested — are left out.
     async fn resolve(&self, id: &AtomId) -> Result<Option<Self::Entry>, Self::Error> {
-        self.source.resolve(id).await
+        self.resolve_key(id).await
     }
 
     async fn discover(&self, query: &str) -> Result<Vec<AtomId>, Self::Error> {
@@ -406,8 +555,436 @@ impl AtomContent for GitStore {
     }
 }
 
+/// The object database is a blob store already: a blob's digest is its git
+/// object id. Objects of other kinds are not blobs, and are not found.
+impl AtomBlobs for GitStore {
+    type Blob = io::Cursor<Vec<u8>>;
+    type Error = GitError;
+
+    async fn get_blob(&self, dig: &[u8]) -> Result<Option<Self::Blob>, Self::Error> {
+        let Ok(oid) = crate::gix_util::seam::oid_from_dig_field(dig) else {
+            return Ok(None);
+        };
+        let repo = self.source.repo();
+        let obj = match repo.find_object(oid) {
+            Ok(obj) => obj,
+            Err(gix::object::find::existing::Error::NotFound { .. }) => return Ok(None),
+            Err(e) => return Err(GitError::ObjectFind(e)),
+        };
+        if obj.kind != gix::object::Kind::Blob {
+            return Ok(None);
+        }
+        Ok(Some(io::Cursor::new(obj.detach().data)))
+    }
+
+    async fn put_blob(&self, data: &mut (impl io::Read + Send)) -> Result<Vec<u8>, Self::Error> {
+        let mut bytes = Vec::new();
+        data.read_to_end(&mut bytes)?;
+        let oid = self.source.repo().write_blob(&bytes)?.detach();
+        Ok(oid.as_bytes().to_vec())
+    }
+}
+
+/// The caller's [`IngestObserver`], with a tally of what it was told.
+struct Progress<'o, O> {
+    observer: &'o mut O,
+    counts: IngestCounts,
+}
+
+impl<O: IngestObserver> IngestObserver for Progress<'_, O> {
+    fn started(&mut self, id: &AtomId, version: &RawVersion) {
+        self.counts.started(id, version);
+        self.observer.started(id, version);
+    }
+
+    fn verified(&mut self, id: &AtomId, version: &RawVersion) {
+        self.counts.verified(id, version);
+        self.observer.verified(id, version);
+    }
+
+    fn stored(&mut self, id: &AtomId, version: &RawVersion) {
+        self.counts.stored(id, version);
+        self.observer.stored(id, version);
+    }
+
+    fn skipped(&mut self, id: &AtomId, version: &RawVersion) {
+        self.counts.skipped(id, version);
+        self.observer.skipped(id, version);
+    }
+}
+
 impl AtomStore for GitStore {
-    async fn ingest<S: AtomContent>(&self, source: &S) -> Result<(), Self::Error> {
+    async fn ingest_with_progress<S: AtomContent>(
+        &self,
+        source: &S,
+        observer: &mut impl IngestObserver,
+    ) -> Result<IngestCounts, Self::Error> {
+        self.check_no_txn()?;
+        let mut progress = Progress {
+            observer,
+            counts: IngestCounts::default(),
+        };
+        self.ingest_where(source, &IngestFilter::new(), &mut progress, None)
+            .await?;
+        Ok(progress.counts)
+    }
+
+    async fn ingest_filtered<S: AtomContent>(
+        &self,
+        source: &S,
+        filter: &IngestFilter,
+    ) -> Result<(), Self::Error> {
+        self.check_no_txn()?;
+        self.ingest_where(source, filter, &mut (), None).await
+    }
+
+    /// Carries every charter in the store as well, so the snapshot imports
+    /// into another `GitStore` with its ownership chains intact.
+    async fn export(
+        &self,
+        writer: &mut (impl io::Write + Send),
+    ) -> Result<(), SnapshotError<Self::Error>> {
+        let mut snapshot = Snapshot::of(self).await?;
+        let charters = source_charter_entries(&self.source.repo()).map_err(SnapshotError::Store)?;
+        for (_, raw_msg, _) in charters {
+            snapshot.add_charter(raw_msg);
+        }
+        snapshot.write_to(writer).map_err(SnapshotError::from_store)
+    }
+
+    /// Writes the snapshot's charters first, each verified on its own, so
+    /// its claims are checked against them as in any ingest.
+    async fn import(
+        &self,
+        reader: &mut (impl io::Read + Send),
+    ) -> Result<IngestCounts, SnapshotError<Self::Error>> {
+        self.check_no_txn().map_err(SnapshotError::Store)?;
+        let snapshot = Snapshot::read_from(reader).map_err(SnapshotError::from_store)?;
+        let dest_repo = self.source.repo();
+        for raw_msg in snapshot.charters() {
+            let (_, czd) = crate::charter_store::parse_and_verify_charter_msg(raw_msg)
+                .map_err(SnapshotError::Store)?;
+            write_charter(&dest_repo, &czd, raw_msg.clone()).map_err(SnapshotError::Store)?;
+        }
+        self.ingest_with_progress(&snapshot, &mut ())
+            .await
+            .map_err(SnapshotError::Store)
+    }
+
+    async fn contains(&self, id: &(dyn AtomIdKey + Sync)) -> Result<bool, Self::Error> {
+        // Resolve the identity to see if any versions exist
+        match self.resolve_key(id).await {
+            Ok(Some(entry)) => Ok(!entry.versions.is_empty()),
+            Ok(None) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Probes the refs by the borrowed identity; an owned id is built only
+    /// for an atom found.
+    async fn resolve_key(
+        &self,
+        id: &(dyn AtomIdKey + Sync),
+    ) -> Result<Option<Self::Entry>, Self::Error> {
+        let Some(mut entry) = self.source.lookup(id.id_ref())? else {
+            return Ok(None);
+        };
+        entry.versions.retain(|v| self.admits_stored(&entry.id, v));
+        Ok(Some(entry))
+    }
+}
+
+impl AtomEvents for GitStore {
+    fn subscribe(&self, subscriber: impl Fn(&AtomEvent) + Send + Sync + 'static) -> Subscription {
+        self.events.subscribe(subscriber)
+    }
+
+    fn unsubscribe(&self, subscription: Subscription) -> bool {
+        self.events.unsubscribe(subscription)
+    }
+}
+
+/// A store mirrors a registry as it accumulates anything else: by
+/// ingesting it, which copies every signed message verbatim.
+impl RegistryMirror for GitStore {
+    async fn sync_from<S: AtomContent>(&self, upstream: &S) -> Result<IngestCounts, Self::Error> {
+        let result = self.ingest_with_progress(upstream, &mut ()).await;
+        let mut status = self.status.lock().unwrap_or_else(PoisonError::into_inner);
+        match &result {
+            Ok(counts) => {
+                *status = SyncStatus {
+                    synced_at: Some(
+                        SystemTime::now()
+                            .duration_since(SystemTime::UNIX_EPOCH)
+                            .unwrap_or_default()
+                            .as_secs(),
+                    ),
+                    last: *counts,
+                    error: None,
+                };
+            },
+            Err(e) => status.error = Some(e.to_string()),
+        }
+        result
+    }
+
+    fn sync_status(&self) -> SyncStatus {
+        self.status
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+}
+
+impl AtomStoreMut for GitStore {
+    async fn remove_version(&self, id: &AtomId, version: &RawVersion) -> Result<bool, Self::Error> {
+        self.check_no_txn()?;
+        let repo = self.source.repo();
+
+        // Published versions: the flat store ref names neither the atom
+        // nor the version (`[store-ref-by-publish-czd]`), so find it by
+        // its publish payload, then evict it with its claim cleanup.
+        let mut store_keys = Vec::new();
+        for r in repo.references()?.prefixed("refs/atom/d/")? {
+            let Ok(r) = r else { continue };
+            let Some(payload) = publish_payload_of_store_ref(&repo, &r)? else {
+                continue;
+            };
+            if payload.anchor == *id.anchor()
+                && payload.label == *id.label()
+                && payload.version == *version
+            {
+                let name = r.name().as_bstr().to_string();
+                store_keys.push(name["refs/atom/d/".len()..].to_owned());
+            }
+        }
+        let mut removed = !store_keys.is_empty();
+        for store_key in store_keys {
+            self.evict_version(&store_key)?;
+        }
+
+        // Dev versions, under every digest algorithm `resolve` scans.
+        for alg in [
+            coz_rs::Alg::ES256,
+            coz_rs::Alg::ES384,
+            coz_rs::Alg::ES512,
+            coz_rs::Alg::Ed25519,
+        ] {
+            let digest = atom_core::AtomDigest::compute(id, alg.hash_alg());
+            let dev_ref_name = format!(
+                "refs/atom/dev/{}/{}",
+                dev_ref_digest(&digest),
+                version.as_str()
+            );
+            if repo.try_find_reference(&dev_ref_name)?.is_none() {
+                continue;
+            }
+            let dev_ref_fullname = FullName::try_from(dev_ref_name.as_str())
+                .map_err(|e| GitError::Validation(e.to_string()))?;
+            repo.edit_reference(RefEdit {
+                change: Change::Delete {
+                    expected: PreviousValue::Any,
+                    log: RefLog::AndReference,
+                },
+                name: dev_ref_fullname,
+                deref: false,
+            })?;
+            removed = true;
+        }
+
+        Ok(removed)
+    }
+}
+
+impl AtomStoreTxn for GitStore {
+    type Txn<'a> = GitTxn<'a>;
+
+    /// Fails with [`GitError::TxnOpen`] while another transaction is open.
+    async fn begin(&self) -> Result<GitTxn<'_>, Self::Error> {
+        {
+            let mut txn = self.txn();
+            if txn.is_some() {
+                return Err(GitError::TxnOpen);
+            }
+            *txn = Some(Vec::new());
+        }
+        let refs = atom_refs(&self.source.repo()).inspect_err(|_| {
+            self.txn().take();
+        })?;
+        Ok(GitTxn {
+            store: self,
+            seen: refs.clone(),
+            before: Some(refs),
+        })
+    }
+}
+
+impl AtomStoreResume for GitStore {
+    async fn ingest_resumable<S: AtomContent>(
+        &self,
+        source: &S,
+        checkpoint: &mut IngestCheckpoint,
+    ) -> Result<IngestCounts, Self::Error> {
+        self.check_no_txn()?;
+        let mut progress = Progress {
+            observer: &mut (),
+            counts: IngestCounts::default(),
+        };
+        self.ingest_where(
+            source,
+            &IngestFilter::new(),
+            &mut progress,
+            Some(checkpoint),
+        )
+        .await?;
+        checkpoint.clear();
+        Ok(progress.counts)
+    }
+}
+
+/// A [`GitStore`] transaction.
+///
+/// Writes go to the store as they happen, as in a plain ingest, while
+/// `begin` remembers every `refs/atom/*` ref; rolling back puts those refs
+/// back in one ref transaction. Objects written in the meantime are left
+/// for `git gc`, since nothing references them.
+///
+/// One transaction is open on a store at a time, and while it is, every
+/// other write to the store — a second `begin`, an ingest, an import, a
+/// removal — fails with [`GitError::TxnOpen`]. Each ref is put back only
+/// if it still holds what the transaction left in it, so a change made
+/// behind the store's back (another process on the same repository)
+/// fails the rollback rather than being overwritten. Events are held
+/// until the transaction commits, and dropped if it does not.
+pub struct GitTxn<'a> {
+    store: &'a GitStore,
+    /// The refs as of `begin`; `None` once committed or rolled back.
+    before: Option<HashMap<String, ObjectId>>,
+    /// The refs as the transaction's own writes last left them.
+    seen: HashMap<String, ObjectId>,
+}
+
+impl GitTxn<'_> {
+    /// Put every `refs/atom/*` ref back as it was at `begin`, then end the
+    /// transaction, dropping its events.
+    fn restore(&mut self) -> Result<(), GitError> {
+        let Some(before) = self.before.take() else {
+            return Ok(());
+        };
+        let result = self.put_back(&before);
+        self.store.txn().take();
+        result
+    }
+
+    /// Move each ref the transaction changed back to `before`, provided it
+    /// still holds what the transaction left in it.
+    fn put_back(&self, before: &HashMap<String, ObjectId>) -> Result<(), GitError> {
+        let seen = &self.seen;
+        let mut edits = Vec::new();
+        for (name, oid) in seen.iter().filter(|(name, _)| !before.contains_key(*name)) {
+            edits.push(RefEdit {
+                change: Change::Delete {
+                    expected: PreviousValue::MustExistAndMatch(Target::Object(*oid)),
+                    log: RefLog::AndReference,
+                },
+                name: FullName::try_from(name.as_str())
+                    .map_err(|e| GitError::Validation(e.to_string()))?,
+                deref: false,
+            });
+        }
+        for (name, oid) in before {
+            let expected = match seen.get(name) {
+                Some(left) if left == oid => continue,
+                Some(left) => PreviousValue::MustExistAndMatch(Target::Object(*left)),
+                None => PreviousValue::MustNotExist,
+            };
+            edits.push(RefEdit {
+                change: Change::Update {
+                    log: LogChange {
+                        mode: RefLog::AndReference,
+                        force_create_reflog: false,
+                        message: "Roll back transaction".into(),
+                    },
+                    expected,
+                    new: Target::Object(*oid),
+                },
+                name: FullName::try_from(name.as_str())
+                    .map_err(|e| GitError::Validation(e.to_string()))?,
+                deref: false,
+            });
+        }
+        if !edits.is_empty() {
+            self.store.source.repo().edit_references(edits)?;
+        }
+        Ok(())
+    }
+
+    /// Note what the transaction's last write left in the refs.
+    fn observe(&mut self) -> Result<(), GitError> {
+        self.seen = atom_refs(&self.store.source.repo())?;
+        Ok(())
+    }
+}
+
+impl Drop for GitTxn<'_> {
+    fn drop(&mut self) {
+        let _ = self.restore();
+    }
+}
+
+impl StoreTxn for GitTxn<'_> {
+    type Error = GitError;
+
+    async fn ingest<S: AtomContent>(&mut self, source: &S) -> Result<(), Self::Error> {
+        self.ingest_filtered(source, &IngestFilter::new()).await
+    }
+
+    async fn ingest_filtered<S: AtomContent>(
+        &mut self,
+        source: &S,
+        filter: &IngestFilter,
+    ) -> Result<(), Self::Error> {
+        let result = self.store.ingest_where(source, filter, &mut (), None).await;
+        self.observe()?;
+        result
+    }
+
+    async fn commit(mut self) -> Result<(), Self::Error> {
+        self.before = None;
+        let held = self.store.txn().take();
+        for event in held.unwrap_or_default() {
+            self.store.events.emit(&event);
+        }
+        Ok(())
+    }
+
+    async fn rollback(mut self) -> Result<(), Self::Error> {
+        self.restore()
+    }
+}
+
+/// Every `refs/atom/*` ref and its target.
+fn atom_refs(repo: &gix::Repository) -> Result<HashMap<String, ObjectId>, GitError> {
+    let mut refs = HashMap::new();
+    for r in repo.references()?.prefixed("refs/atom/")? {
+        let r = r.map_err(|e| GitError::Validation(e.to_string()))?;
+        refs.insert(r.name().as_bstr().to_string(), r.id().detach());
+    }
+    Ok(refs)
+}
+
+impl GitStore {
+    /// Ingest the versions of `source` that `filter` admits, reporting
+    /// each to `progress`. With a `checkpoint`, atoms it has done are
+    /// skipped, and each atom is recorded in it once all its versions are
+    /// in.
+    async fn ingest_where<S: AtomContent>(
+        &self,
+        source: &S,
+        filter: &IngestFilter,
+        progress: &mut impl IngestObserver,
+        mut checkpoint: Option<&mut IngestCheckpoint>,
+    ) -> Result<(), GitError> {
         let dest_repo = self.source.repo();
 
         // 2. Discover all atom identities in the source
@@ -425,6 +1002,9 @@ impl AtomStore for GitStore {
         let mut propagated_anchors: HashSet<Vec<u8>> = HashSet::new();
 
         for id in discovered_ids {
+            if !filter.admits_atom(&id) || checkpoint.as_deref().is_some_and(|c| c.is_done(&id)) {
+                continue;
+            }
             let versions_to_ingest = {
                 let entry_opt = source
                     .resolve(&id)
@@ -432,20 +1012,24 @@ impl AtomStore for GitStore {
                     .map_err(|e| GitError::Validation(e.to_string()))?;
                 let mut list = Vec::new();
                 if let Some(entry) = entry_opt {
-                    for v in entry.versions() {
+                    for v in entry.versions().filter(|v| filter.admits_version(*v)) {
                         list.push((
                             v.version().clone(),
                             v.dig().to_vec(),
                             v.czd().cloned(),
                             v.claim_msg().map(String::from),
                             v.publish_msg().map(String::from),
+                            v.yank_msg().map(String::from),
                         ));
                     }
                 }
                 list
             };
 
-            for (version, dig, czd_opt, claim_msg_opt, publish_msg_opt) in versions_to_ingest {
+            for (version, dig, czd_opt, claim_msg_opt, publish_msg_opt, yank_msg_opt) in
+                versions_to_ingest
+            {
+                progress.started(&id, &version);
                 if let Some(czd_val) = &czd_opt {
                     // A published version's claim/publish handling
                     // (below) requires the destination to already
@@ -492,17 +1076,14 @@ impl AtomStore for GitStore {
                         &claim_envelope.sig,
                         claim_alg_str,
                         claim_pub_key,
+                        None,
                     )?;
 
                     // Verify claim-pubkey thumbprint matches payload tmb
-                    let computed_tmb =
-                        coz_rs::compute_thumbprint_for_alg(claim_alg_str, claim_pub_key)
-                            .ok_or_else(|| {
-                                GitError::Validation(
-                                    "Failed to compute claim key thumbprint".into(),
-                                )
-                            })?;
-                    if computed_tmb != claim_payload.tmb {
+                    if !atom_id::ct_eq(
+                        claim_payload.signer().as_bytes(),
+                        claim_payload.tmb.as_bytes(),
+                    ) {
                         return Err(GitError::Validation("Claim thumbprint mismatch".into()));
                     }
 
@@ -523,20 +1104,17 @@ impl AtomStore for GitStore {
                         &publish_envelope.sig,
                         publish_alg_str,
                         pub_key,
+                        None,
                     )?;
 
                     // The store's version ref is keyed by the PUBLISH
                     // transaction's own czd (`[store-ref-by-publish-czd]`),
                     // not the claim's -- distinct from `czd_val`/
                     // `publish_payload.claim` above.
-                    let publish_czd = atom_id::czd_for_alg(
-                        &publish_pay_bytes,
-                        &publish_envelope.sig,
-                        publish_alg_str,
-                    )?;
+                    let publish_czd = publish_payload.czd();
 
                     // Verify publish chains to claim
-                    if publish_payload.claim != *czd_val {
+                    if !atom_id::ct_eq(publish_payload.claim.as_bytes(), czd_val.as_bytes()) {
                         return Err(GitError::Validation(
                             "Publish payload claim czd does not match version czd".into(),
                         ));
@@ -549,6 +1127,60 @@ impl AtomStore for GitStore {
                         ));
                     }
 
+                    // A yank travels with its version, verified the same
+                    // way (`[yank-authority]`).
+                    if let Some(yank_msg) = &yank_msg_opt {
+                        crate::source::verify_yank_msg(
+                            yank_msg,
+                            &claim_pay_bytes,
+                            &claim_envelope.sig,
+                            claim_alg_str,
+                            &publish_payload,
+                        )?;
+                    }
+                    if !self.admits_signature(&id, claim_payload.alg, claim_payload.signer())
+                        || !self.admits_signature(
+                            &id,
+                            publish_payload.alg,
+                            publish_payload.signer(),
+                        )
+                    {
+                        return Err(GitError::PolicyRejected(format!(
+                            "version {} of {id} has a signature the store does not accept",
+                            version.as_str()
+                        )));
+                    }
+                    progress.verified(&id, &version);
+
+                    // The version ref is keyed by the publish czd, so an
+                    // existing one holds this very publish: nothing to do
+                    // but take a yank it has gained since.
+                    let store_key = hex_encode(blake3::hash(publish_czd.as_bytes()).as_bytes());
+                    let store_version_ref = format!("refs/atom/d/{}", store_key);
+                    let store_yank_ref = format!("refs/atom/yank/d/{}", store_key);
+                    let new_yank = match yank_msg_opt {
+                        Some(msg) if dest_repo.try_find_reference(&store_yank_ref)?.is_none() => {
+                            Some(msg)
+                        },
+                        _ => None,
+                    };
+                    if dest_repo.try_find_reference(&store_version_ref)?.is_some() {
+                        match new_yank {
+                            Some(msg) => {
+                                let (edit, yank_czd) = yank_edit(&dest_repo, &store_yank_ref, msg)?;
+                                dest_repo.edit_reference(edit)?;
+                                progress.stored(&id, &version);
+                                self.emit(AtomEvent::Yanked {
+                                    id: id.clone(),
+                                    version,
+                                    czd: yank_czd,
+                                });
+                            },
+                            None => progress.skipped(&id, &version),
+                        }
+                        continue;
+                    }
+
                     // Resolve the claim commit through its own ref family
                     // (`refs/atom/claims/d/{claim_czd}`,
                     // docs/specs/git-storage-format.md `[store-claim-ref]`)
@@ -669,10 +1301,6 @@ impl AtomStore for GitStore {
                     // lives only in the publish payload now, never in the
                     // ref path.
                     let store_claim_ref = format!("refs/atom/claims/d/{}", claim_czd_hex);
-                    let store_version_ref = format!(
-                        "refs/atom/d/{}",
-                        hex_encode(blake3::hash(publish_czd.as_bytes()).as_bytes())
-                    );
 
                     let mut edits = Vec::new();
 
@@ -708,9 +1336,50 @@ impl AtomStore for GitStore {
                         deref: false,
                     });
 
+                    let yank_czd = match new_yank {
+                        Some(msg) => {
+                            let (edit, yank_czd) = yank_edit(&dest_repo, &store_yank_ref, msg)?;
+                            edits.push(edit);
+                            Some(yank_czd)
+                        },
+                        None => None,
+                    };
+
                     dest_repo.edit_references(edits)?;
+                    progress.stored(&id, &version);
+                    self.emit(AtomEvent::Ingested {
+                        id: id.clone(),
+                        version: version.clone(),
+                        czd: Some(publish_czd.clone()),
+                    });
+                    if let Some(czd) = yank_czd {
+                        self.emit(AtomEvent::Yanked {
+                            id: id.clone(),
+                            version,
+                            czd,
+                        });
+                    }
                 } else {
-                    // Ingestion of an unsigned dev version
+                    if !self.policy.admits_unsigned() {
+                        return Err(GitError::PolicyRejected(format!(
+                            "version {} of {id} is unsigned",
+                            version.as_str()
+                        )));
+                    }
+                    // Ingestion of an unsigned dev version. The dev ref
+                    // names the version, not its content, so it is only
+                    // left alone if it already holds this very commit.
+                    let digest = atom_core::AtomDigest::compute(&id, coz_rs::Alg::ES256.hash_alg());
+                    let digest_str = dev_ref_digest(&digest);
+                    let dev_ref_name = format!("refs/atom/dev/{}/{}", digest_str, version.as_str());
+                    if dest_repo
+                        .try_find_reference(&dev_ref_name)?
+                        .is_some_and(|existing| existing.id().as_bytes() == dig)
+                    {
+                        progress.skipped(&id, &version);
+                        continue;
+                    }
+
                     let content_entries = source
                         .content(&id, &dig)
                         .await
@@ -737,11 +1406,6 @@ impl AtomStore for GitStore {
                         ));
                     }
 
-                    // Compute the sha256 store-index digest of the atom id
-                    let digest = atom_core::AtomDigest::compute(&id, coz_rs::Alg::ES256.hash_alg());
-                    let digest_str = dev_ref_digest(&digest);
-
-                    let dev_ref_name = format!("refs/atom/dev/{}/{}", digest_str, version.as_str());
                     let dev_ref_fullname = FullName::try_from(dev_ref_name.as_str())
                         .map_err(|e| GitError::Validation(e.to_string()))?;
 
@@ -760,32 +1424,28 @@ impl AtomStore for GitStore {
                     };
 
                     dest_repo.edit_reference(edit)?;
+                    progress.stored(&id, &version);
+                    self.emit(AtomEvent::Ingested {
+                        id: id.clone(),
+                        version,
+                        czd: None,
+                    });
                 }
             }
+            if let Some(checkpoint) = checkpoint.as_deref_mut() {
+                checkpoint.done(&id);
+            }
         }
 
         Ok(())
     }
 
-    async fn contains(&self, id: &AtomId) -> Result<bool, Self::Error> {
-        // Resolve the identity to see if any versions exist
-        match self.resolve(id).await {
-            Ok(Some(entry)) => Ok(!entry.versions.is_empty()),
-            Ok(None) => Ok(false),
-            Err(e) => Err(e),
-        }
-    }
-}
-
-impl GitStore {
     /// Reconstruct the content tree in the given repository from a sequence of content entries.
     pub fn write_content_tree(
         &self,
         repo: &gix::Repository,
         entries: &[ContentEntry],
     ) -> Result<ObjectId, GitError> {
-        use std::collections::HashMap;
-
         use gix::object::tree::EntryKind;
         use gix::objs::tree::{Entry, EntryMode};
 
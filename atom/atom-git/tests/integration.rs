@@ -1,8 +1,12 @@
 use std::fs;
+use std::sync::{Arc, Mutex};
 
 use atom_core::{
-    AtomContent, AtomEntry, AtomId, AtomRegistry, AtomSource, AtomStore, AtomVersion, ContentEntry,
-    Label, RawVersion,
+    AtomBlobs, AtomContent, AtomEntry, AtomEvent, AtomEvents, AtomId, AtomRegistry, AtomSource,
+    AtomStore, AtomStoreMut, AtomStoreResume, AtomStoreTxn, AtomVersion, AuditOp, AuditRecord,
+    AuditedRegistry, ContentEntry, IngestCheckpoint, IngestCounts, IngestFilter, Label,
+    RawRequirement, RawVersion, RegistryMirror, SignaturePolicy, Snapshot, SnapshotError, StoreTxn,
+    SyncStatus, Thumbprint, TrustStore, conformance,
 };
 use atom_git::{GitError, GitRegistry, GitSource, GitStore};
 use coz_rs::{Alg, Ed25519, SigningKey};
@@ -1085,6 +1089,596 @@ async fn test_local_ingest_propagates_full_succession_chain() {
     assert_eq!(version_entry.version().as_str(), "1.0.0");
 }
 
+/// A registry holding one atom, `pkg`, published at 1.0.0.
+fn registry_with_one_version() -> (TempDir, GitRegistry, AtomId) {
+    let (reg_dir, reg_repo, reg_genesis_oid) = setup_test_repo();
+
+    let sk = SigningKey::<Ed25519>::generate();
+    let prv = sk.private_key_bytes().to_vec();
+    let pub_key = sk.verifying_key().public_key_bytes().to_vec();
+
+    let registry = GitRegistry::new(
+        reg_repo,
+        prv,
+        pub_key.clone(),
+        Alg::Ed25519,
+        "cargo".to_string(),
+    );
+    let reg_repo = registry.source.repo();
+
+    let anchor = found_anchor(&registry, &pub_key, b"src-rev");
+    let id = AtomId::new(anchor, Label::try_from("pkg").unwrap());
+    let claim_czd = registry.claim(&id, &owner_ref(&pub_key)).unwrap();
+
+    let ver_commit_oid = create_commit(
+        &reg_repo,
+        "v1.0.0 src",
+        "src/main.rs",
+        b"main",
+        vec![reg_genesis_oid],
+    );
+    let ver_tree_oid = reg_repo
+        .find_object(ver_commit_oid)
+        .unwrap()
+        .try_into_commit()
+        .unwrap()
+        .tree_id()
+        .unwrap();
+    registry
+        .publish(
+            &id,
+            &claim_czd,
+            &RawVersion::new("1.0.0".to_string()),
+            ver_tree_oid.as_bytes(),
+            ver_commit_oid.as_bytes(),
+            "Cargo.toml",
+        )
+        .unwrap();
+    (reg_dir, registry, id)
+}
+
+/// A second ingest of the same source finds every version already stored:
+/// it verifies and skips, and writes nothing.
+#[tokio::test]
+async fn test_ingest_with_progress_counts_and_skips() {
+    let (_reg_dir, registry, id) = registry_with_one_version();
+    let (_store_dir, store_repo, _store_genesis_oid) = setup_test_repo();
+    let store = GitStore::new(store_repo);
+
+    let mut seen = IngestCounts::default();
+    let first = store
+        .ingest_with_progress(&registry.source, &mut seen)
+        .await
+        .unwrap();
+    assert_eq!(first, seen, "the returned counts tally the events");
+    assert_eq!(
+        first,
+        IngestCounts {
+            started: 1,
+            verified: 1,
+            stored: 1,
+            skipped: 0,
+        }
+    );
+
+    let second = store
+        .ingest_with_progress(&registry.source, &mut ())
+        .await
+        .unwrap();
+    assert_eq!(
+        second,
+        IngestCounts {
+            started: 1,
+            verified: 1,
+            stored: 0,
+            skipped: 1,
+        }
+    );
+    assert!(store.contains(&id).await.unwrap());
+}
+
+/// The git source and store pass atom-core's conformance suite: the
+/// source laws on both, and the ingest homomorphism between them.
+#[tokio::test]
+async fn test_conformance_laws() {
+    let (_reg_dir, registry, _id) = registry_with_one_version();
+    let (_store_dir, store_repo, _store_genesis_oid) = setup_test_repo();
+    let store = GitStore::new(store_repo);
+
+    conformance::assert_source_laws(&registry.source).await;
+    conformance::assert_content_laws(&registry.source).await;
+    conformance::assert_store_accumulation(&store, &registry.source).await;
+    conformance::assert_source_laws(&store).await;
+    conformance::assert_content_laws(&store).await;
+}
+
+/// A store's object database serves as its blob store; a commit is not a
+/// blob.
+#[tokio::test]
+async fn test_store_blobs() {
+    let (_store_dir, store_repo, genesis_oid) = setup_test_repo();
+    let store = GitStore::new(store_repo);
+
+    conformance::assert_blob_round_trip(&store, b"fn main() {}\n").await;
+    assert!(
+        store
+            .get_blob(genesis_oid.as_bytes())
+            .await
+            .unwrap()
+            .is_none()
+    );
+    assert!(!store.has_blob(genesis_oid.as_bytes()).await.unwrap());
+    assert!(store.get_blob(b"not an oid").await.unwrap().is_none());
+}
+
+/// `ingest_filtered` takes only what the filter admits; the rest of the
+/// source stays behind.
+#[tokio::test]
+async fn test_ingest_filtered_takes_only_admitted_versions() {
+    let (_reg_dir, registry, id) = registry_with_one_version();
+    let (_store_dir, store_repo, _store_genesis_oid) = setup_test_repo();
+    let store = GitStore::new(store_repo);
+
+    let other_label = IngestFilter::new().labels("other-*");
+    store
+        .ingest_filtered(&registry.source, &other_label)
+        .await
+        .unwrap();
+    assert!(!store.contains(&id).await.unwrap());
+
+    let newer = IngestFilter::new()
+        .versions(
+            atom_id::NaturalOrderScheme,
+            &RawRequirement::new(">=2".to_string()),
+        )
+        .unwrap();
+    store
+        .ingest_filtered(&registry.source, &newer)
+        .await
+        .unwrap();
+    assert!(!store.contains(&id).await.unwrap());
+
+    let this_atom = IngestFilter::new()
+        .anchor(id.anchor().clone())
+        .labels("p?g");
+    store
+        .ingest_filtered(&registry.source, &this_atom)
+        .await
+        .unwrap();
+    assert!(store.contains(&id).await.unwrap());
+}
+
+/// A resumed ingest skips the atoms its checkpoint has done, and a
+/// finished one clears the checkpoint.
+#[tokio::test]
+async fn test_ingest_resumable_skips_done_atoms() {
+    let (_reg_dir, registry, id) = registry_with_one_version();
+    let (_store_dir, store_repo, _store_genesis_oid) = setup_test_repo();
+    let store = GitStore::new(store_repo);
+
+    let mut checkpoint = IngestCheckpoint::new();
+    checkpoint.done(&id);
+    let counts = store
+        .ingest_resumable(&registry.source, &mut checkpoint)
+        .await
+        .unwrap();
+    assert_eq!(counts.started, 0);
+    assert!(!store.contains(&id).await.unwrap());
+    assert!(checkpoint.is_empty());
+
+    let counts = store
+        .ingest_resumable(&registry.source, &mut checkpoint)
+        .await
+        .unwrap();
+    assert_eq!(counts.stored, 1);
+    assert!(store.contains(&id).await.unwrap());
+}
+
+/// Removal takes a published version out along with its orphaned claim,
+/// and `gc` keeps exactly the retention roots.
+#[tokio::test]
+async fn test_store_removal_and_gc() {
+    let (_reg_dir, registry, id) = registry_with_one_version();
+    let (_store_dir, store_repo, _store_genesis_oid) = setup_test_repo();
+    let store = GitStore::new(store_repo);
+    store.ingest(&registry.source).await.unwrap();
+
+    let version = RawVersion::new("1.0.0".to_string());
+    assert!(store.remove_version(&id, &version).await.unwrap());
+    assert!(!store.remove_version(&id, &version).await.unwrap());
+    assert!(!store.contains(&id).await.unwrap());
+    let repo_store = store.source.repo();
+    let claim_refs = repo_store
+        .references()
+        .unwrap()
+        .prefixed("refs/atom/claims/d/")
+        .unwrap()
+        .count();
+    assert_eq!(claim_refs, 0, "the orphaned claim ref is cleaned up");
+
+    store.ingest(&registry.source).await.unwrap();
+    assert!(
+        store
+            .gc(std::slice::from_ref(&id))
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    assert!(store.contains(&id).await.unwrap());
+    assert_eq!(store.gc(&[]).await.unwrap(), [id.clone()]);
+    assert!(!store.contains(&id).await.unwrap());
+}
+
+/// A rolled-back or dropped transaction leaves the store's refs exactly
+/// as they were; a committed one keeps its writes. While one is open, no
+/// other write reaches the store, and its events wait for the commit.
+#[tokio::test]
+async fn test_store_txn_commit_and_rollback() {
+    let (_reg_dir, registry, id) = registry_with_one_version();
+    let (_store_dir, store_repo, _store_genesis_oid) = setup_test_repo();
+    let store = GitStore::new(store_repo);
+    let atom_ref_count = || {
+        store
+            .source
+            .repo()
+            .references()
+            .unwrap()
+            .prefixed("refs/atom/")
+            .unwrap()
+            .count()
+    };
+    let empty = atom_ref_count();
+    let events = Arc::new(Mutex::new(Vec::new()));
+    store.subscribe({
+        let events = Arc::clone(&events);
+        move |event: &AtomEvent| events.lock().unwrap().push(event.clone())
+    });
+
+    let mut txn = store.begin().await.unwrap();
+    txn.ingest(&registry.source).await.unwrap();
+    assert!(store.contains(&id).await.unwrap());
+    assert!(matches!(store.begin().await, Err(GitError::TxnOpen)));
+    assert!(matches!(
+        store.ingest(&registry.source).await,
+        Err(GitError::TxnOpen)
+    ));
+    assert!(matches!(store.remove(&id).await, Err(GitError::TxnOpen)));
+    txn.rollback().await.unwrap();
+    assert!(!store.contains(&id).await.unwrap());
+    assert_eq!(atom_ref_count(), empty, "charter refs are rolled back too");
+    assert!(events.lock().unwrap().is_empty(), "a rollback is silent");
+
+    let mut txn = store.begin().await.unwrap();
+    txn.ingest(&registry.source).await.unwrap();
+    drop(txn);
+    assert!(!store.contains(&id).await.unwrap());
+
+    assert!(events.lock().unwrap().is_empty());
+
+    let mut txn = store.begin().await.unwrap();
+    txn.ingest(&registry.source).await.unwrap();
+    assert!(events.lock().unwrap().is_empty(), "held until the commit");
+    txn.commit().await.unwrap();
+    assert!(store.contains(&id).await.unwrap());
+    assert!(matches!(
+        events.lock().unwrap().as_slice(),
+        [AtomEvent::Ingested { id: ingested, .. }] if *ingested == id
+    ));
+
+    // A ref moved behind the store's back fails the rollback loudly
+    // instead of being overwritten, and still ends the transaction.
+    let (_other_dir, other_repo, other_genesis_oid) = setup_test_repo();
+    let other = GitStore::new(other_repo);
+    let mut txn = other.begin().await.unwrap();
+    txn.ingest(&registry.source).await.unwrap();
+    let repo = other.source.repo();
+    let written = repo
+        .references()
+        .unwrap()
+        .prefixed("refs/atom/")
+        .unwrap()
+        .next()
+        .unwrap()
+        .unwrap()
+        .name()
+        .as_bstr()
+        .to_string();
+    write_bare_ref(&repo, &written, other_genesis_oid);
+    assert!(matches!(txn.rollback().await, Err(GitError::RefEdit(_))));
+    assert!(other.begin().await.is_ok());
+}
+
+/// A snapshot carries a store's atoms and charters into a fresh store;
+/// one stripped of its charters is refused, not imported unverified.
+#[tokio::test]
+async fn test_store_snapshot_export_and_import() {
+    let (_reg_dir, registry, id) = registry_with_one_version();
+    let (_store_dir, store_repo, _store_genesis_oid) = setup_test_repo();
+    let store = GitStore::new(store_repo);
+    store.ingest(&registry.source).await.unwrap();
+
+    let mut bytes = Vec::new();
+    store.export(&mut bytes).await.unwrap();
+    let snapshot = Snapshot::read_from(&mut bytes.as_slice()).unwrap();
+    assert!(!snapshot.charters().is_empty());
+
+    let (_copy_dir, copy_repo, _copy_genesis_oid) = setup_test_repo();
+    let copy = GitStore::new(copy_repo);
+    let counts = copy.import(&mut bytes.as_slice()).await.unwrap();
+    assert_eq!(counts.stored, 1);
+    assert!(copy.contains(&id).await.unwrap());
+
+    let mut bare = Vec::new();
+    Snapshot::of(&store)
+        .await
+        .unwrap()
+        .write_to(&mut bare)
+        .unwrap();
+    let (_bare_dir, bare_repo, _bare_genesis_oid) = setup_test_repo();
+    let fresh = GitStore::new(bare_repo);
+    assert!(matches!(
+        fresh.import(&mut bare.as_slice()).await,
+        Err(SnapshotError::Store(GitError::Validation(_)))
+    ));
+    assert!(!fresh.contains(&id).await.unwrap());
+}
+
+/// Subscribers hear of each claim a registry makes and each version a
+/// store writes, but not of versions an ingest skips.
+#[tokio::test]
+async fn test_registry_and_store_events() {
+    let (_reg_dir, registry, id) = registry_with_one_version();
+    let (_store_dir, store_repo, _store_genesis_oid) = setup_test_repo();
+    let store = GitStore::new(store_repo);
+
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let record = |events: &Arc<Mutex<Vec<AtomEvent>>>| {
+        let events = Arc::clone(events);
+        move |event: &AtomEvent| events.lock().unwrap().push(event.clone())
+    };
+    registry.subscribe(record(&events));
+    let subscription = store.subscribe(record(&events));
+
+    let other = AtomId::new(id.anchor().clone(), Label::try_from("other").unwrap());
+    let claim_czd = registry
+        .claim(&other, &owner_ref(&registry.pub_key))
+        .unwrap();
+    store.ingest(&registry.source).await.unwrap();
+    store.ingest(&registry.source).await.unwrap();
+
+    {
+        let events = events.lock().unwrap();
+        assert_eq!(events.len(), 2, "the second ingest skips, and is silent");
+        assert_eq!(
+            events[0],
+            AtomEvent::Claimed {
+                id: other,
+                czd: claim_czd,
+            }
+        );
+        assert!(matches!(
+            &events[1],
+            AtomEvent::Ingested { id: ingested, version, czd: Some(_) }
+                if *ingested == id && version.as_str() == "1.0.0"
+        ));
+    }
+
+    assert!(store.unsubscribe(subscription));
+    store.gc(&[]).await.unwrap();
+    store.ingest(&registry.source).await.unwrap();
+    assert_eq!(events.lock().unwrap().len(), 2);
+}
+
+/// A yank withdraws a version without deleting it: it still resolves,
+/// flagged as yanked, in the registry and in every store that ingests it,
+/// including one that ingested the version before it was yanked.
+#[tokio::test]
+async fn test_yank_is_visible_in_registry_and_stores() {
+    let (_reg_dir, registry, id) = registry_with_one_version();
+    let version = RawVersion::new("1.0.0".to_string());
+    let (_early_dir, early_repo, _early_genesis_oid) = setup_test_repo();
+    let early = GitStore::new(early_repo);
+    early.ingest(&registry.source).await.unwrap();
+
+    let yanked = |entry: Option<atom_git::GitEntry>| {
+        let entry = entry.expect("the yanked version still resolves");
+        let v = entry.versions().next().unwrap();
+        (v.version().as_str().to_owned(), v.is_yanked())
+    };
+    let entry = registry.resolve(&id).await.unwrap().unwrap();
+    let claim_czd = entry.versions().next().unwrap().czd().unwrap().clone();
+    assert_eq!(
+        yanked(Some(entry)),
+        ("1.0.0".to_owned(), false),
+        "not yanked yet"
+    );
+
+    let events = Arc::new(Mutex::new(Vec::new()));
+    registry.subscribe({
+        let events = Arc::clone(&events);
+        move |event: &AtomEvent| events.lock().unwrap().push(event.clone())
+    });
+    let yank_czd = registry.yank(&id, &version, &claim_czd).unwrap();
+    assert!(
+        registry.yank(&id, &version, &claim_czd).is_err(),
+        "a version is yanked once"
+    );
+    assert_eq!(
+        *events.lock().unwrap(),
+        [AtomEvent::Yanked {
+            id: id.clone(),
+            version: version.clone(),
+            czd: yank_czd,
+        }]
+    );
+    let entry = registry.resolve(&id).await.unwrap();
+    let yank = entry.as_ref().unwrap().versions[0].yank_payload.clone();
+    assert_eq!(yank.map(|y| y.version), Some(version.clone()));
+    assert_eq!(yanked(entry), ("1.0.0".to_owned(), true));
+
+    let (_store_dir, store_repo, _store_genesis_oid) = setup_test_repo();
+    let store = GitStore::new(store_repo);
+    store.ingest(&registry.source).await.unwrap();
+    assert_eq!(
+        yanked(store.resolve(&id).await.unwrap()),
+        ("1.0.0".to_owned(), true)
+    );
+
+    let counts = early
+        .ingest_with_progress(&registry.source, &mut ())
+        .await
+        .unwrap();
+    assert_eq!(counts.stored, 1, "the yank is taken on its own");
+    assert_eq!(
+        yanked(early.resolve(&id).await.unwrap()),
+        ("1.0.0".to_owned(), true)
+    );
+}
+
+/// A mirror serves its upstream's versions as a source of its own, so a
+/// mirror of a mirror verifies the same signatures; failed syncs are
+/// recorded without losing the last success.
+#[tokio::test]
+async fn test_registry_mirror_chains_and_reports_status() {
+    let (_reg_dir, registry, id) = registry_with_one_version();
+    let (_mirror_dir, mirror_repo, _mirror_genesis_oid) = setup_test_repo();
+    let mirror = GitStore::new(mirror_repo);
+    assert_eq!(mirror.sync_status(), SyncStatus::default());
+
+    let counts = mirror.sync_from(&registry.source).await.unwrap();
+    assert_eq!(counts.stored, 1);
+    let status = mirror.sync_status();
+    assert!(status.synced_at.is_some());
+    assert_eq!((status.last, status.error), (counts, None));
+
+    let (_second_dir, second_repo, _second_genesis_oid) = setup_test_repo();
+    let second = GitStore::new(second_repo);
+    second.sync_from(&mirror.source).await.unwrap();
+    let upstream = registry.resolve(&id).await.unwrap().unwrap();
+    let mirrored = second.resolve(&id).await.unwrap().unwrap();
+    assert_eq!(
+        mirrored.versions[0].publish_msg, upstream.versions[0].publish_msg,
+        "signatures arrive intact"
+    );
+
+    let mut bare = Vec::new();
+    Snapshot::of(&mirror)
+        .await
+        .unwrap()
+        .write_to(&mut bare)
+        .unwrap();
+    let snapshot = Snapshot::read_from(&mut bare.as_slice()).unwrap();
+    let (_third_dir, third_repo, _third_genesis_oid) = setup_test_repo();
+    let third = GitStore::new(third_repo);
+    assert!(
+        third.sync_from(&snapshot).await.is_err(),
+        "no charters to verify against"
+    );
+    assert!(third.sync_status().error.is_some());
+    assert_eq!(third.sync_status().synced_at, None);
+}
+
+/// A store's verify policy refuses at ingest the signatures it does not
+/// accept, and hides at resolve the versions it no longer accepts.
+#[tokio::test]
+async fn test_store_verify_policy() {
+    let (_reg_dir, registry, id) = registry_with_one_version();
+
+    let (_es_dir, es_repo, _es_genesis_oid) = setup_test_repo();
+    let es_only = GitStore::new(es_repo).with_policy(SignaturePolicy::new().algs([Alg::ES256]));
+    assert!(matches!(
+        es_only.ingest(&registry.source).await,
+        Err(GitError::PolicyRejected(_))
+    ));
+    assert!(!es_only.contains(&id).await.unwrap());
+
+    let (_store_dir, store_repo, _store_genesis_oid) = setup_test_repo();
+    let store = GitStore::new(store_repo);
+    store.ingest(&registry.source).await.unwrap();
+    assert!(
+        store.contains(&id).await.unwrap(),
+        "the default accepts Ed25519"
+    );
+
+    let stranger = Thumbprint::from_bytes(vec![0; 32]);
+    let pinned =
+        GitStore::new(store.source.repo()).with_policy(SignaturePolicy::new().trust(stranger));
+    assert!(
+        !pinned.contains(&id).await.unwrap(),
+        "an untrusted signer's version is hidden"
+    );
+    assert!(store.contains(&id).await.unwrap());
+}
+
+/// A store under a trust store pins the first key signing for an atom,
+/// and hides the atom once it is pinned to another.
+#[tokio::test]
+async fn test_trust_store_pins_the_first_signer() {
+    let (_reg_dir, registry, id) = registry_with_one_version();
+    let (_store_dir, store_repo, _store_genesis_oid) = setup_test_repo();
+    let trust = Arc::new(TrustStore::new());
+    let store = GitStore::new(store_repo).with_policy(Arc::clone(&trust));
+    store.ingest(&registry.source).await.unwrap();
+    let entry = store.resolve(&id).await.unwrap().unwrap();
+    let signer = entry.versions[0]
+        .publish_payload
+        .as_ref()
+        .unwrap()
+        .tmb
+        .clone();
+    assert_eq!(trust.pinned(&id), Some(signer));
+
+    trust.pin(&id, Thumbprint::from_bytes(vec![0; 32]));
+    assert!(!store.contains(&id).await.unwrap());
+}
+
+/// An audited registry records every write, failed ones too, with the czd
+/// each signed.
+#[test]
+fn test_audited_registry_records_writes() {
+    let (_dir, repo, _genesis_oid) = setup_test_repo();
+    let sk = SigningKey::<Ed25519>::generate();
+    let prv = sk.private_key_bytes().to_vec();
+    let pub_key = sk.verifying_key().public_key_bytes().to_vec();
+    let registry = GitRegistry::new(
+        repo,
+        prv,
+        pub_key.clone(),
+        Alg::Ed25519,
+        "cargo".to_string(),
+    );
+    let log = Arc::new(Mutex::new(Vec::<AuditRecord>::new()));
+    let audited = AuditedRegistry::new(registry, Arc::clone(&log), "alice");
+
+    let charter_czd = audited
+        .charter(&[owner_ref(&pub_key)], b"src-rev", None)
+        .unwrap();
+    let anchor = atom_core::Anchor::new(charter_czd.as_bytes().to_vec());
+    let id = AtomId::new(anchor, Label::try_from("pkg").unwrap());
+    let claim_czd = audited.claim(&id, &owner_ref(&pub_key)).unwrap();
+    let version = RawVersion::new("1.0.0".to_string());
+    assert!(
+        audited.yank(&id, &version, &claim_czd).is_err(),
+        "nothing to yank"
+    );
+
+    let log = log.lock().unwrap();
+    assert_eq!(log.len(), 3);
+    assert!(log.iter().all(|r| r.caller == "alice" && r.at > 0));
+    assert!(matches!(log[0].op, AuditOp::Charter { prior: None, .. }));
+    assert_eq!(log[0].czd.as_ref(), Some(&charter_czd));
+    assert_eq!(
+        log[1].op,
+        AuditOp::Claim {
+            id: id.clone(),
+            owner: owner_ref(&pub_key),
+        }
+    );
+    assert_eq!(log[1].czd.as_ref(), Some(&claim_czd));
+    assert!(matches!(log[2].op, AuditOp::Yank { .. }));
+    assert!(log[2].czd.is_none() && log[2].error.is_some());
+}
+
 #[test]
 fn test_fs_dev_ingest() {
     let (temp_dir, repo, _genesis_oid) = setup_test_repo();
@@ -0,0 +1,382 @@
+//! `proptest` strategies for protocol values (`arbitrary` feature).
+//!
+//! Every generated value is one this crate would accept: names pass
+//! UAX #31 + NFKC validation (and are not limited to ASCII), anchors are
+//! czd-width, and payloads are built through their constructors so `typ`
+//! and field invariants hold. Downstream backends can property-test their
+//! stores with `any::<AtomId>()`, `any::<PublishPayload>()`, and so on.
+//!
+//! Signatures are out of scope: a generated payload is well-formed, not
+//! signed.
+
+use proptest::prelude::*;
+
+use crate::{
+    Alg, Anchor, AtomId, CharterPayload, ClaimPayload, Czd, DelegatePayload, Identifier, Label,
+    OwnerKind, OwnerRef, PublishPayload, RawVersion, RevokePayload, RevokeReason, RotatePayload,
+    Tag, Thumbprint, TransferPayload, YankPayload,
+};
+
+/// Maximum generated name length, in characters — well under `NAME_MAX`
+/// bytes even for four-byte characters.
+const NAME_CHARS: usize = 24;
+
+fn alg() -> impl Strategy<Value = Alg> {
+    prop_oneof![Just(Alg::Ed25519), Just(Alg::ES256)]
+}
+
+/// Digest-width byte strings: the 32-, 48- and 64-byte czd sizes.
+fn digest_bytes() -> impl Strategy<Value = Vec<u8>> {
+    prop_oneof![
+        proptest::collection::vec(any::<u8>(), 32),
+        proptest::collection::vec(any::<u8>(), 48),
+        proptest::collection::vec(any::<u8>(), 64),
+    ]
+}
+
+fn thumbprint() -> impl Strategy<Value = Thumbprint> {
+    digest_bytes().prop_map(Thumbprint::from_bytes)
+}
+
+fn czd() -> impl Strategy<Value = Czd> {
+    digest_bytes().prop_map(Czd::from_bytes)
+}
+
+/// Strings shaped by `pattern`, kept only where `parse` accepts them.
+fn validated<T: std::fmt::Debug + 'static>(
+    pattern: &str,
+    parse: fn(&str) -> Option<T>,
+) -> BoxedStrategy<T> {
+    proptest::string::string_regex(pattern)
+        .expect("strategy pattern is a valid regex")
+        .prop_filter_map("rejected by name validation", move |s| parse(&s))
+        .boxed()
+}
+
+impl Arbitrary for Identifier {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with((): ()) -> Self::Strategy {
+        validated(
+            &format!(r"\p{{XID_Start}}\p{{XID_Continue}}{{0,{NAME_CHARS}}}"),
+            |s| Self::try_from(s).ok(),
+        )
+    }
+}
+
+impl Arbitrary for Label {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with((): ()) -> Self::Strategy {
+        validated(
+            &format!(r"\p{{XID_Start}}[\p{{XID_Continue}}\-]{{0,{NAME_CHARS}}}"),
+            |s| Self::try_from(s).ok(),
+        )
+    }
+}
+
+impl Arbitrary for Tag {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with((): ()) -> Self::Strategy {
+        validated(
+            &format!(
+                r"\p{{XID_Start}}[\p{{XID_Continue}}\-:]{{0,{NAME_CHARS}}}(\.[\p{{XID_Continue}}\-:]{{1,8}}){{0,2}}"
+            ),
+            |s| Self::try_from(s).ok(),
+        )
+    }
+}
+
+impl Arbitrary for Anchor {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with((): ()) -> Self::Strategy {
+        digest_bytes().prop_map(Self::new).boxed()
+    }
+}
+
+impl Arbitrary for AtomId {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with((): ()) -> Self::Strategy {
+        (any::<Anchor>(), any::<Label>())
+            .prop_map(|(anchor, label)| Self::new(anchor, label))
+            .boxed()
+    }
+}
+
+impl Arbitrary for RawVersion {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    /// Mostly dotted-numeric versions, as real ecosystems use, plus any
+    /// string at all — a raw version is never validated.
+    fn arbitrary_with((): ()) -> Self::Strategy {
+        prop_oneof![
+            3 => r"[0-9]{1,3}(\.[0-9]{1,3}){0,2}(-[a-z0-9.]{1,8})?",
+            1 => any::<String>(),
+        ]
+        .prop_map(Self::new)
+        .boxed()
+    }
+}
+
+impl Arbitrary for OwnerRef {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with((): ()) -> Self::Strategy {
+        let kind = prop_oneof![
+            Just(OwnerKind::SingleKey),
+            Just(OwnerKind::Hierarchical),
+            Just(OwnerKind::RootedIdentity),
+        ];
+        (kind, digest_bytes())
+            .prop_map(|(kind, value)| Self::new(kind, value))
+            .boxed()
+    }
+}
+
+impl Arbitrary for ClaimPayload {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    /// An owner-signed claim: `owner` is the single-key reference to `tmb`.
+    fn arbitrary_with((): ()) -> Self::Strategy {
+        (
+            alg(),
+            any::<AtomId>(),
+            any::<u64>(),
+            "[a-z]{2,10}",
+            digest_bytes(),
+            thumbprint(),
+        )
+            .prop_map(|(alg, id, now, pkg, src, tmb)| {
+                Self::new(alg, id, now, OwnerRef::single_key(&tmb), pkg, src, tmb)
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for PublishPayload {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with((): ()) -> Self::Strategy {
+        (
+            alg(),
+            any::<AtomId>(),
+            czd(),
+            digest_bytes(),
+            any::<u64>(),
+            "[a-z0-9_-]{1,12}(/[a-z0-9_-]{1,12}){0,3}",
+            digest_bytes(),
+            thumbprint(),
+            any::<RawVersion>(),
+        )
+            .prop_map(|(alg, id, claim, dig, now, path, src, tmb, version)| {
+                Self::new(alg, id, claim, dig, now, path, src, tmb, version)
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for CharterPayload {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with((): ()) -> Self::Strategy {
+        (
+            alg(),
+            any::<u64>(),
+            proptest::collection::vec(any::<OwnerRef>(), 1..4),
+            proptest::option::of(czd()),
+            digest_bytes(),
+            thumbprint(),
+        )
+            .prop_map(|(alg, now, owner, prior, src, tmb)| {
+                Self::new(alg, now, owner, prior, src, tmb).expect("owner set is non-empty")
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for DelegatePayload {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    /// A delegation with a non-empty window: `exp > now`.
+    fn arbitrary_with((): ()) -> Self::Strategy {
+        (
+            alg(),
+            any::<AtomId>(),
+            czd(),
+            thumbprint(),
+            0..u64::MAX,
+            1..=u64::MAX,
+            thumbprint(),
+        )
+            .prop_map(|(alg, id, claim, delegate, now, window, tmb)| {
+                Self::new(
+                    alg,
+                    id,
+                    claim,
+                    delegate,
+                    now.saturating_add(window),
+                    now,
+                    tmb,
+                )
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for RevokeReason {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with((): ()) -> Self::Strategy {
+        prop_oneof![
+            Just(Self::Unspecified),
+            Just(Self::KeyCompromise),
+            Just(Self::Abandoned),
+            Just(Self::Superseded),
+        ]
+        .boxed()
+    }
+}
+
+impl Arbitrary for RevokePayload {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with((): ()) -> Self::Strategy {
+        (
+            alg(),
+            any::<AtomId>(),
+            czd(),
+            any::<u64>(),
+            any::<RevokeReason>(),
+            thumbprint(),
+        )
+            .prop_map(|(alg, id, claim, now, reason, tmb)| {
+                Self::new(alg, id, claim, now, reason, tmb)
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for YankPayload {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with((): ()) -> Self::Strategy {
+        (
+            alg(),
+            any::<AtomId>(),
+            czd(),
+            any::<u64>(),
+            thumbprint(),
+            any::<RawVersion>(),
+        )
+            .prop_map(|(alg, id, claim, now, tmb, version)| {
+                Self::new(alg, id, claim, now, tmb, version)
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for TransferPayload {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with((): ()) -> Self::Strategy {
+        (
+            alg(),
+            any::<AtomId>(),
+            any::<u64>(),
+            any::<OwnerRef>(),
+            czd(),
+            thumbprint(),
+        )
+            .prop_map(|(alg, id, now, owner, prior, tmb)| {
+                Self::new(alg, id, now, owner, prior, tmb)
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for RotatePayload {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with((): ()) -> Self::Strategy {
+        (
+            alg(),
+            any::<AtomId>(),
+            czd(),
+            any::<u64>(),
+            any::<u64>(),
+            thumbprint(),
+            thumbprint(),
+        )
+            .prop_map(|(alg, id, claim, grace, now, successor, tmb)| {
+                Self::new(alg, id, claim, grace, now, successor, tmb)
+            })
+            .boxed()
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    proptest! {
+        #[test]
+        fn names_are_valid_and_round_trip(
+            label in any::<Label>(),
+            ident in any::<Identifier>(),
+            tag in any::<Tag>(),
+        ) {
+            prop_assert_eq!(Label::try_from(label.as_ref()), Ok(label.clone()));
+            prop_assert_eq!(Identifier::try_from(ident.as_ref()), Ok(ident.clone()));
+            prop_assert_eq!(Tag::try_from(tag.as_ref()), Ok(tag.clone()));
+        }
+
+        #[test]
+        fn atom_ids_round_trip(id in any::<AtomId>()) {
+            prop_assert_eq!(id.to_string().parse::<AtomId>(), Ok(id.clone()));
+            prop_assert_eq!(AtomId::from_bytes(&id.to_bytes()), Ok(id));
+        }
+
+        #[test]
+        fn claims_are_owner_signed(claim in any::<ClaimPayload>()) {
+            prop_assert!(claim.owner.authorizes(&claim.tmb));
+            prop_assert_eq!(claim.typ.as_str(), crate::TYP_CLAIM);
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    proptest! {
+        #[test]
+        fn payloads_round_trip_through_json(
+            publish in any::<PublishPayload>(),
+            charter in any::<CharterPayload>(),
+        ) {
+            let json = serde_json::to_vec(&publish).unwrap();
+            prop_assert_eq!(serde_json::from_slice::<PublishPayload>(&json).unwrap(), publish);
+            let json = serde_json::to_vec(&charter).unwrap();
+            prop_assert_eq!(serde_json::from_slice::<CharterPayload>(&json).unwrap(), charter);
+        }
+    }
+}
@@ -0,0 +1,138 @@
+//! Batch signature verification.
+//!
+//! Stores ingesting thousands of transactions spend most of their time
+//! checking signatures one at a time. [`verify_batch`] takes the whole
+//! batch at once and returns a result per item, so the strategy behind it
+//! can change without touching callers.
+//!
+//! coz-rs exposes no multi-signature batch primitive today, so each
+//! signature is still checked individually; the batch is instead spread
+//! across the available cores. Per-item results are kept (rather than one
+//! pass/fail for the batch, as true Ed25519 batch verification yields)
+//! because an ingestor needs to know *which* payload to reject.
+
+use crate::VerifyError;
+
+/// One item of a [`verify_batch`] call:
+/// `(pay_json, sig, alg, pub_key)`, exactly the arguments a single
+/// signature check takes.
+pub type BatchItem<'a> = (&'a [u8], &'a [u8], &'a str, &'a [u8]);
+
+/// Batches smaller than this are verified on the calling thread; below it,
+/// spawning threads costs more than it saves.
+const PARALLEL_THRESHOLD: usize = 64;
+
+/// Verify the Coz signatures of many payloads, returning one result per
+/// item in input order.
+///
+/// Each result is exactly what the signature step of
+/// [`crate::verify_claim`] / [`crate::verify_publish`] would return for
+/// that item: `Ok(())`, [`VerifyError::InvalidSignature`], or
+/// [`VerifyError::UnsupportedAlgorithm`]. Payload parsing, `typ`, and
+/// every other pipeline check are still the caller's to run on the items
+/// that pass.
+///
+/// Spec constraint: `[sig-over-pay]`.
+pub fn verify_batch(items: &[BatchItem<'_>]) -> Vec<Result<(), VerifyError>> {
+    let verify_one = |&(pay_json, sig, alg, pub_key): &BatchItem<'_>| {
+        crate::verify_signature(pay_json, sig, alg, pub_key)
+    };
+
+    let workers = std::thread::available_parallelism().map_or(1, std::num::NonZeroUsize::get);
+    if items.len() < PARALLEL_THRESHOLD || workers == 1 {
+        return items.iter().map(verify_one).collect();
+    }
+
+    let chunk = items.len().div_ceil(workers);
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = items
+            .chunks(chunk)
+            .map(|part| scope.spawn(move || part.iter().map(verify_one).collect::<Vec<_>>()))
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|handle| {
+                handle
+                    .join()
+                    .expect("signature verification does not panic")
+            })
+            .collect()
+    })
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn signed(n: usize) -> (Vec<u8>, Vec<u8>, Vec<u8>) {
+        use coz_rs::Ed25519;
+
+        let sk = coz_rs::SigningKey::<Ed25519>::generate();
+        let prv = sk.private_key_bytes();
+        let pub_bytes = sk.verifying_key().public_key_bytes().to_vec();
+        let pay_json = format!(r#"{{"alg":"Ed25519","n":{n}}}"#).into_bytes();
+        let (sig, _cad) = coz_rs::sign_json(&pay_json, "Ed25519", &prv, &pub_bytes).unwrap();
+        (pay_json, sig, pub_bytes)
+    }
+
+    fn check(len: usize) {
+        let mut fixtures: Vec<_> = (0..len).map(signed).collect();
+        // Corrupt every seventh signature.
+        for (_, sig, _) in fixtures.iter_mut().step_by(7) {
+            *sig = vec![0; sig.len()];
+        }
+        let items: Vec<BatchItem<'_>> = fixtures
+            .iter()
+            .map(|(p, s, k)| (p.as_slice(), s.as_slice(), "Ed25519", k.as_slice()))
+            .collect();
+
+        let results = verify_batch(&items);
+        assert_eq!(results.len(), len);
+        for (i, result) in results.iter().enumerate() {
+            if i % 7 == 0 {
+                assert!(
+                    matches!(result, Err(VerifyError::InvalidSignature)),
+                    "item {i}: {result:?}"
+                );
+            } else {
+                assert!(result.is_ok(), "item {i}: {result:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn batch_results_are_per_item_and_ordered() {
+        check(10);
+        check(PARALLEL_THRESHOLD * 3 + 5);
+    }
+
+    #[test]
+    fn batch_reports_unsupported_algorithm_per_item() {
+        let (pay_json, sig, pub_key) = signed(0);
+        let items = [
+            (
+                pay_json.as_slice(),
+                sig.as_slice(),
+                "Ed25519",
+                pub_key.as_slice(),
+            ),
+            (
+                pay_json.as_slice(),
+                sig.as_slice(),
+                "NOPE",
+                pub_key.as_slice(),
+            ),
+        ];
+        let results = verify_batch(&items);
+        assert!(results[0].is_ok());
+        assert!(matches!(
+            results[1],
+            Err(VerifyError::UnsupportedAlgorithm(_))
+        ));
+        assert!(verify_batch(&[]).is_empty());
+    }
+}
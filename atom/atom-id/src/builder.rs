@@ -0,0 +1,545 @@
+//! Typed builders for payloads with many same-typed fields.
+//!
+//! [`PublishPayload::new`] takes nine positional arguments, two of them
+//! (`dig` and `src`) `Vec<u8>` — swapping them compiles and signs a
+//! publish that points at the wrong content. [`PublishBuilder`] and
+//! [`ClaimBuilder`] name every field at its call site and track which
+//! required fields are set in their type, so `build` only exists once all
+//! of them are: a missing field is a compile error, not a runtime one.
+//!
+//! ```
+//! use atom_id::{Alg, Anchor, AtomId, Czd, Label, PublishBuilder, RawVersion, Thumbprint};
+//!
+//! let id = AtomId::new(Anchor::new(vec![1; 32]), Label::try_from("my-pkg").unwrap());
+//! let publish = PublishBuilder::for_atom(id)
+//!     .claim(Czd::from_bytes(vec![2; 32]))
+//!     .version(RawVersion::new("1.0.0".into()))
+//!     .dig(vec![3; 32])
+//!     .src(vec![4; 20])
+//!     .path("crates/my-pkg".into())
+//!     .now(1_700_000_000)
+//!     .signer(Alg::Ed25519, Thumbprint::from_bytes(vec![5; 32]))
+//!     .build();
+//! assert_eq!(publish.dig, vec![3; 32]);
+//! ```
+
+use crate::{
+    Alg, AtomId, ClaimPayload, Czd, Mode, OwnerRef, PublishPayload, RawVersion, Thumbprint,
+};
+
+/// Marks a required builder field that has not been set yet.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Unset;
+
+/// A [`PublishPayload`] under construction.
+///
+/// Each type parameter is [`Unset`] until its field is given, then the
+/// field's type: `C`laim, `V`ersion, `D`ig, `S`rc, `P`ath, `N`ow, and the
+/// `K`ey pair of `alg` and `tmb`. Start from [`PublishBuilder::for_atom`];
+/// the required setters may come in any order.
+#[derive(Debug, Clone)]
+#[must_use = "a builder does nothing until `build` is called"]
+pub struct PublishBuilder<
+    C = Unset,
+    V = Unset,
+    D = Unset,
+    S = Unset,
+    P = Unset,
+    N = Unset,
+    K = Unset,
+> {
+    id: AtomId,
+    claim: C,
+    version: V,
+    dig: D,
+    src: S,
+    path: P,
+    now: N,
+    signer: K,
+    mode: Option<Mode>,
+    content_hash: Option<Vec<u8>>,
+    nonce: Option<Vec<u8>>,
+}
+
+impl PublishBuilder {
+    /// Start a publish of `id`, with every required field unset.
+    pub fn for_atom(id: AtomId) -> Self {
+        Self {
+            id,
+            claim: Unset,
+            version: Unset,
+            dig: Unset,
+            src: Unset,
+            path: Unset,
+            now: Unset,
+            signer: Unset,
+            mode: None,
+            content_hash: None,
+            nonce: None,
+        }
+    }
+}
+
+impl<V, D, S, P, N, K> PublishBuilder<Unset, V, D, S, P, N, K> {
+    /// The czd of the authorizing claim.
+    pub fn claim(self, claim: Czd) -> PublishBuilder<Czd, V, D, S, P, N, K> {
+        PublishBuilder {
+            id: self.id,
+            claim,
+            version: self.version,
+            dig: self.dig,
+            src: self.src,
+            path: self.path,
+            now: self.now,
+            signer: self.signer,
+            mode: self.mode,
+            content_hash: self.content_hash,
+            nonce: self.nonce,
+        }
+    }
+}
+
+impl<C, D, S, P, N, K> PublishBuilder<C, Unset, D, S, P, N, K> {
+    /// The published version.
+    pub fn version(self, version: RawVersion) -> PublishBuilder<C, RawVersion, D, S, P, N, K> {
+        PublishBuilder {
+            id: self.id,
+            claim: self.claim,
+            version,
+            dig: self.dig,
+            src: self.src,
+            path: self.path,
+            now: self.now,
+            signer: self.signer,
+            mode: self.mode,
+            content_hash: self.content_hash,
+            nonce: self.nonce,
+        }
+    }
+}
+
+impl<C, V, S, P, N, K> PublishBuilder<C, V, Unset, S, P, N, K> {
+    /// The atom snapshot hash — the published artifact.
+    pub fn dig(self, dig: Vec<u8>) -> PublishBuilder<C, V, Vec<u8>, S, P, N, K> {
+        PublishBuilder {
+            id: self.id,
+            claim: self.claim,
+            version: self.version,
+            dig,
+            src: self.src,
+            path: self.path,
+            now: self.now,
+            signer: self.signer,
+            mode: self.mode,
+            content_hash: self.content_hash,
+            nonce: self.nonce,
+        }
+    }
+}
+
+impl<C, V, D, P, N, K> PublishBuilder<C, V, D, Unset, P, N, K> {
+    /// The source revision hash the snapshot was taken from.
+    pub fn src(self, src: Vec<u8>) -> PublishBuilder<C, V, D, Vec<u8>, P, N, K> {
+        PublishBuilder {
+            id: self.id,
+            claim: self.claim,
+            version: self.version,
+            dig: self.dig,
+            src,
+            path: self.path,
+            now: self.now,
+            signer: self.signer,
+            mode: self.mode,
+            content_hash: self.content_hash,
+            nonce: self.nonce,
+        }
+    }
+}
+
+impl<C, V, D, S, N, K> PublishBuilder<C, V, D, S, Unset, N, K> {
+    /// The atom's subdirectory in the source content tree.
+    pub fn path(self, path: String) -> PublishBuilder<C, V, D, S, String, N, K> {
+        PublishBuilder {
+            id: self.id,
+            claim: self.claim,
+            version: self.version,
+            dig: self.dig,
+            src: self.src,
+            path,
+            now: self.now,
+            signer: self.signer,
+            mode: self.mode,
+            content_hash: self.content_hash,
+            nonce: self.nonce,
+        }
+    }
+}
+
+impl<C, V, D, S, P, K> PublishBuilder<C, V, D, S, P, Unset, K> {
+    /// The publish timestamp (seconds since Unix epoch).
+    pub fn now(self, now: u64) -> PublishBuilder<C, V, D, S, P, u64, K> {
+        PublishBuilder {
+            id: self.id,
+            claim: self.claim,
+            version: self.version,
+            dig: self.dig,
+            src: self.src,
+            path: self.path,
+            now,
+            signer: self.signer,
+            mode: self.mode,
+            content_hash: self.content_hash,
+            nonce: self.nonce,
+        }
+    }
+}
+
+impl<C, V, D, S, P, N> PublishBuilder<C, V, D, S, P, N, Unset> {
+    /// The signing algorithm and the thumbprint of the key that will sign.
+    pub fn signer(
+        self,
+        alg: Alg,
+        tmb: Thumbprint,
+    ) -> PublishBuilder<C, V, D, S, P, N, (Alg, Thumbprint)> {
+        PublishBuilder {
+            id: self.id,
+            claim: self.claim,
+            version: self.version,
+            dig: self.dig,
+            src: self.src,
+            path: self.path,
+            now: self.now,
+            signer: (alg, tmb),
+            mode: self.mode,
+            content_hash: self.content_hash,
+            nonce: self.nonce,
+        }
+    }
+}
+
+impl<C, V, D, S, P, N, K> PublishBuilder<C, V, D, S, P, N, K> {
+    /// Declare the reproducibility mode (`[publish-mode]`).
+    pub fn mode(mut self, mode: Mode) -> Self {
+        self.mode = Some(mode);
+        self
+    }
+
+    /// Assert a BLAKE3 content-tree digest
+    /// (`[content-hash-is-tree-digest]`).
+    pub fn content_hash(mut self, content_hash: Vec<u8>) -> Self {
+        self.content_hash = Some(content_hash);
+        self
+    }
+
+    /// Set a replay-distinguishing nonce (`[replay-guard]`).
+    pub fn nonce(mut self, nonce: Vec<u8>) -> Self {
+        self.nonce = Some(nonce);
+        self
+    }
+}
+
+impl PublishBuilder<Czd, RawVersion, Vec<u8>, Vec<u8>, String, u64, (Alg, Thumbprint)> {
+    /// Assemble the payload. Only callable once every required field is
+    /// set.
+    #[must_use]
+    pub fn build(self) -> PublishPayload {
+        let (alg, tmb) = self.signer;
+        let mut payload = PublishPayload::new(
+            alg,
+            self.id,
+            self.claim,
+            self.dig,
+            self.now,
+            self.path,
+            self.src,
+            tmb,
+            self.version,
+        );
+        payload.mode = self.mode;
+        payload.content_hash = self.content_hash;
+        payload.nonce = self.nonce;
+        payload
+    }
+}
+
+/// A [`ClaimPayload`] under construction.
+///
+/// As with [`PublishBuilder`], each type parameter is [`Unset`] until its
+/// field is given: the `A`tom id, `N`ow, `O`wner, `P`kg, `S`rc, and the
+/// `K`ey pair of `alg` and `tmb`. Start from [`ClaimPayload::builder`].
+#[derive(Debug, Clone)]
+#[must_use = "a builder does nothing until `build` is called"]
+pub struct ClaimBuilder<A = Unset, N = Unset, O = Unset, P = Unset, S = Unset, K = Unset> {
+    id: A,
+    now: N,
+    owner: O,
+    pkg: P,
+    src: S,
+    signer: K,
+    replaces: Option<(Czd, bool)>,
+    exp: Option<u64>,
+    nonce: Option<Vec<u8>>,
+}
+
+impl ClaimPayload {
+    /// Start building a claim, with every required field unset.
+    pub fn builder() -> ClaimBuilder {
+        ClaimBuilder {
+            id: Unset,
+            now: Unset,
+            owner: Unset,
+            pkg: Unset,
+            src: Unset,
+            signer: Unset,
+            replaces: None,
+            exp: None,
+            nonce: None,
+        }
+    }
+}
+
+impl<N, O, P, S, K> ClaimBuilder<Unset, N, O, P, S, K> {
+    /// The claimed atom.
+    pub fn atom(self, id: AtomId) -> ClaimBuilder<AtomId, N, O, P, S, K> {
+        ClaimBuilder {
+            id,
+            now: self.now,
+            owner: self.owner,
+            pkg: self.pkg,
+            src: self.src,
+            signer: self.signer,
+            replaces: self.replaces,
+            exp: self.exp,
+            nonce: self.nonce,
+        }
+    }
+}
+
+impl<A, O, P, S, K> ClaimBuilder<A, Unset, O, P, S, K> {
+    /// The claim timestamp (seconds since Unix epoch).
+    pub fn now(self, now: u64) -> ClaimBuilder<A, u64, O, P, S, K> {
+        ClaimBuilder {
+            id: self.id,
+            now,
+            owner: self.owner,
+            pkg: self.pkg,
+            src: self.src,
+            signer: self.signer,
+            replaces: self.replaces,
+            exp: self.exp,
+            nonce: self.nonce,
+        }
+    }
+}
+
+impl<A, N, P, S, K> ClaimBuilder<A, N, Unset, P, S, K> {
+    /// The single owner accountable for the label.
+    pub fn owner(self, owner: OwnerRef) -> ClaimBuilder<A, N, OwnerRef, P, S, K> {
+        ClaimBuilder {
+            id: self.id,
+            now: self.now,
+            owner,
+            pkg: self.pkg,
+            src: self.src,
+            signer: self.signer,
+            replaces: self.replaces,
+            exp: self.exp,
+            nonce: self.nonce,
+        }
+    }
+}
+
+impl<A, N, O, S, K> ClaimBuilder<A, N, O, Unset, S, K> {
+    /// The PURL type of the wrapped ecosystem (e.g. `"cargo"`).
+    pub fn pkg(self, pkg: String) -> ClaimBuilder<A, N, O, String, S, K> {
+        ClaimBuilder {
+            id: self.id,
+            now: self.now,
+            owner: self.owner,
+            pkg,
+            src: self.src,
+            signer: self.signer,
+            replaces: self.replaces,
+            exp: self.exp,
+            nonce: self.nonce,
+        }
+    }
+}
+
+impl<A, N, O, P, K> ClaimBuilder<A, N, O, P, Unset, K> {
+    /// The source revision hash at claim time.
+    pub fn src(self, src: Vec<u8>) -> ClaimBuilder<A, N, O, P, Vec<u8>, K> {
+        ClaimBuilder {
+            id: self.id,
+            now: self.now,
+            owner: self.owner,
+            pkg: self.pkg,
+            src,
+            signer: self.signer,
+            replaces: self.replaces,
+            exp: self.exp,
+            nonce: self.nonce,
+        }
+    }
+}
+
+impl<A, N, O, P, S> ClaimBuilder<A, N, O, P, S, Unset> {
+    /// The signing algorithm and the thumbprint of the key that will sign.
+    pub fn signer(
+        self,
+        alg: Alg,
+        tmb: Thumbprint,
+    ) -> ClaimBuilder<A, N, O, P, S, (Alg, Thumbprint)> {
+        ClaimBuilder {
+            id: self.id,
+            now: self.now,
+            owner: self.owner,
+            pkg: self.pkg,
+            src: self.src,
+            signer: (alg, tmb),
+            replaces: self.replaces,
+            exp: self.exp,
+            nonce: self.nonce,
+        }
+    }
+}
+
+impl<A, N, O, P, S, K> ClaimBuilder<A, N, O, P, S, K> {
+    /// Make this a replacement of the claim with czd `prior`, marked as a
+    /// governance replacement or not (`[claim-replacement-authority]`).
+    pub fn replaces(mut self, prior: Czd, governance: bool) -> Self {
+        self.replaces = Some((prior, governance));
+        self
+    }
+
+    /// Let the claim lapse at `exp` (`[claim-expiry]`).
+    pub fn exp(mut self, exp: u64) -> Self {
+        self.exp = Some(exp);
+        self
+    }
+
+    /// Set a replay-distinguishing nonce (`[replay-guard]`).
+    pub fn nonce(mut self, nonce: Vec<u8>) -> Self {
+        self.nonce = Some(nonce);
+        self
+    }
+}
+
+impl ClaimBuilder<AtomId, u64, OwnerRef, String, Vec<u8>, (Alg, Thumbprint)> {
+    /// Assemble the payload. Only callable once every required field is
+    /// set.
+    #[must_use]
+    pub fn build(self) -> ClaimPayload {
+        let (alg, tmb) = self.signer;
+        let mut payload = match self.replaces {
+            None => ClaimPayload::new(alg, self.id, self.now, self.owner, self.pkg, self.src, tmb),
+            Some((prior, governance)) => ClaimPayload::new_replacement(
+                alg, self.id, self.now, self.owner, self.pkg, prior, governance, self.src, tmb,
+            ),
+        };
+        payload.exp = self.exp;
+        payload.nonce = self.nonce;
+        payload
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Anchor, Label};
+
+    fn test_id() -> AtomId {
+        AtomId::new(
+            Anchor::new(vec![1, 2, 3, 4]),
+            Label::try_from("my-pkg").unwrap(),
+        )
+    }
+
+    #[test]
+    fn builder_matches_positional_constructor_in_any_order() {
+        let tmb = Thumbprint::from_bytes(vec![10, 20]);
+        let expected = PublishPayload::new(
+            Alg::Ed25519,
+            test_id(),
+            Czd::from_bytes(vec![5, 6]),
+            vec![7, 8],
+            2000,
+            "src/lib".into(),
+            vec![9, 10],
+            tmb.clone(),
+            RawVersion::new("1.0.0".into()),
+        );
+        let built = PublishBuilder::for_atom(test_id())
+            .signer(Alg::Ed25519, tmb)
+            .src(vec![9, 10])
+            .now(2000)
+            .dig(vec![7, 8])
+            .path("src/lib".into())
+            .version(RawVersion::new("1.0.0".into()))
+            .claim(Czd::from_bytes(vec![5, 6]))
+            .build();
+        assert_eq!(built, expected);
+    }
+
+    #[test]
+    fn builder_sets_optional_fields() {
+        let built = PublishBuilder::for_atom(test_id())
+            .mode(Mode::Reproducible)
+            .claim(Czd::from_bytes(vec![5, 6]))
+            .version(RawVersion::new("1.0.0".into()))
+            .dig(vec![7, 8])
+            .src(vec![9, 10])
+            .path("src/lib".into())
+            .now(2000)
+            .signer(Alg::Ed25519, Thumbprint::from_bytes(vec![1]))
+            .content_hash(vec![0xAB; 32])
+            .nonce(vec![1, 2])
+            .build();
+        assert_eq!(built.mode, Some(Mode::Reproducible));
+        assert_eq!(built.content_hash, Some(vec![0xAB; 32]));
+        assert_eq!(built.nonce, Some(vec![1, 2]));
+        assert_eq!(built.typ, crate::TYP_PUBLISH);
+    }
+
+    #[test]
+    fn claim_builder_matches_constructors() {
+        let tmb = Thumbprint::from_bytes(vec![10, 20]);
+        let owner = OwnerRef::single_key(&tmb);
+        let expected = ClaimPayload::new(
+            Alg::Ed25519,
+            test_id(),
+            1000,
+            owner.clone(),
+            "cargo".into(),
+            vec![0; 4],
+            tmb.clone(),
+        );
+        let built = ClaimPayload::builder()
+            .src(vec![0; 4])
+            .pkg("cargo".into())
+            .owner(owner.clone())
+            .signer(Alg::Ed25519, tmb.clone())
+            .now(1000)
+            .atom(test_id())
+            .build();
+        assert_eq!(built, expected);
+
+        let replacement = ClaimPayload::builder()
+            .atom(test_id())
+            .now(2000)
+            .owner(owner)
+            .pkg("cargo".into())
+            .src(vec![0; 4])
+            .signer(Alg::Ed25519, tmb)
+            .replaces(Czd::from_bytes(vec![5, 6]), true)
+            .exp(3000)
+            .build();
+        assert_eq!(replacement.prior, Some(Czd::from_bytes(vec![5, 6])));
+        assert!(replacement.governance);
+        assert_eq!(replacement.exp, Some(3000));
+    }
+}
@@ -0,0 +1,356 @@
+//! Canonical payload bytes — the exact representation that gets signed.
+//!
+//! A payload's czd is a digest over the bytes that were signed, so two
+//! independent implementations agree on a czd only if they agree on those
+//! bytes byte-for-byte. [`CanonicalPayload`] pins the representation down
+//! rather than leaving it to whatever a serializer happens to emit:
+//!
+//! - no insignificant whitespace;
+//! - top-level protocol fields in the payload type's declared [`CanonicalPayload::FIELDS`] order,
+//!   absent optional fields omitted;
+//! - nested objects (e.g. `meta`) with keys in ascending byte order.
+//!
+//! For every payload type in this crate the result is identical to
+//! `serde_json::to_vec` — the bytes the existing golden vectors sign — as
+//! long as any `meta` object is already key-sorted, so adopting it changes
+//! no committed czd. `meta` is where `to_vec` stops being reliable: with
+//! serde_json's `preserve_order` feature enabled anywhere in the build
+//! graph, its key order becomes insertion order.
+//!
+//! Spec: `docs/specs/atom-transactions.md` `[canonical-payload-bytes]`.
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{
+    CharterPayload, ClaimPayload, DelegatePayload, PublishPayload, RevokePayload, RotatePayload,
+    TransferPayload, YankPayload,
+};
+
+/// A payload with a single canonical JSON byte representation.
+///
+/// Sign [`to_canonical_json`](Self::to_canonical_json)'s output, never an
+/// ad hoc `serde_json::to_vec`, so that the czd every verifier recomputes
+/// from the wire matches the one the signer intended.
+///
+/// Spec constraint: `[canonical-payload-bytes]`.
+pub trait CanonicalPayload: Serialize {
+    /// Top-level field names, in canonical order.
+    const FIELDS: &'static [&'static str];
+
+    /// Serialize to canonical JSON bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization fails, or if the serialized form
+    /// carries a top-level field not listed in [`Self::FIELDS`] — a
+    /// canonical form never silently drops or reorders data.
+    fn to_canonical_json(&self) -> Result<Vec<u8>, serde_json::Error> {
+        let Value::Object(mut map) = serde_json::to_value(self)? else {
+            return Err(serde::ser::Error::custom(
+                "payload must serialize to an object",
+            ));
+        };
+        let mut out = vec![b'{'];
+        let mut first = true;
+        for &field in Self::FIELDS {
+            let Some(value) = map.remove(field) else {
+                continue;
+            };
+            if !first {
+                out.push(b',');
+            }
+            first = false;
+            serde_json::to_writer(&mut out, field)?;
+            out.push(b':');
+            write_value(&value, &mut out)?;
+        }
+        if let Some(extra) = map.keys().next() {
+            return Err(serde::ser::Error::custom(format!(
+                "field '{extra}' has no canonical position"
+            )));
+        }
+        out.push(b'}');
+        Ok(out)
+    }
+}
+
+/// Write `value` compactly, sorting the keys of every object.
+fn write_value(value: &Value, out: &mut Vec<u8>) -> Result<(), serde_json::Error> {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_unstable_by_key(|(key, _)| *key);
+            out.push(b'{');
+            for (i, (key, value)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(b',');
+                }
+                serde_json::to_writer(&mut *out, key)?;
+                out.push(b':');
+                write_value(value, out)?;
+            }
+            out.push(b'}');
+        },
+        Value::Array(items) => {
+            out.push(b'[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(b',');
+                }
+                write_value(item, out)?;
+            }
+            out.push(b']');
+        },
+        scalar => serde_json::to_writer(out, scalar)?,
+    }
+    Ok(())
+}
+
+impl CanonicalPayload for CharterPayload {
+    const FIELDS: &'static [&'static str] = &["alg", "now", "owner", "prior", "src", "tmb", "typ"];
+}
+
+impl CanonicalPayload for ClaimPayload {
+    const FIELDS: &'static [&'static str] = &[
+        "alg",
+        "anchor",
+        "governance",
+        "label",
+        "now",
+        "owner",
+        "pkg",
+        "prior",
+        "src",
+        "tmb",
+        "typ",
+        "exp",
+        "nonce",
+        "pv",
+        "meta",
+    ];
+}
+
+impl CanonicalPayload for DelegatePayload {
+    const FIELDS: &'static [&'static str] = &[
+        "alg", "anchor", "claim", "delegate", "exp", "label", "now", "tmb", "typ",
+    ];
+}
+
+impl CanonicalPayload for PublishPayload {
+    const FIELDS: &'static [&'static str] = &[
+        "alg",
+        "anchor",
+        "claim",
+        "dig",
+        "label",
+        "now",
+        "path",
+        "src",
+        "tmb",
+        "version",
+        "typ",
+        "mode",
+        "content_hash",
+        "nonce",
+        "pv",
+        "meta",
+    ];
+}
+
+impl CanonicalPayload for RevokePayload {
+    const FIELDS: &'static [&'static str] = &[
+        "alg", "anchor", "claim", "label", "now", "reason", "tmb", "typ",
+    ];
+}
+
+impl CanonicalPayload for RotatePayload {
+    const FIELDS: &'static [&'static str] = &[
+        "alg",
+        "anchor",
+        "claim",
+        "grace",
+        "label",
+        "now",
+        "successor",
+        "tmb",
+        "typ",
+    ];
+}
+
+impl CanonicalPayload for TransferPayload {
+    const FIELDS: &'static [&'static str] = &[
+        "alg", "anchor", "label", "now", "owner", "prior", "tmb", "typ",
+    ];
+}
+
+impl CanonicalPayload for YankPayload {
+    const FIELDS: &'static [&'static str] = &[
+        "alg", "anchor", "claim", "label", "now", "tmb", "typ", "version",
+    ];
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Alg, Anchor, AtomId, Czd, Label, Mode, OwnerRef, RawVersion, Thumbprint};
+
+    fn test_id() -> AtomId {
+        AtomId::new(
+            Anchor::new(vec![1, 2, 3, 4]),
+            Label::try_from("my-pkg").unwrap(),
+        )
+    }
+
+    fn test_publish() -> PublishPayload {
+        let mut publish = PublishPayload::new(
+            Alg::Ed25519,
+            test_id(),
+            Czd::from_bytes(vec![5, 6]),
+            vec![7, 8],
+            2000,
+            "src/lib".into(),
+            vec![9, 10],
+            Thumbprint::from_bytes(vec![10, 20, 30]),
+            RawVersion::new("1.0.0".into()),
+        );
+        publish.mode = Some(Mode::Reproducible);
+        let mut meta = serde_json::Map::new();
+        meta.insert(
+            "zeta".into(),
+            serde_json::json!({"b": 1, "a": [2, {"d": 3, "c": 4}]}),
+        );
+        meta.insert("alpha".into(), Value::Bool(true));
+        publish.meta = Some(meta);
+        publish
+    }
+
+    #[test]
+    fn canonical_matches_signed_golden_form() {
+        let claim = ClaimPayload::new(
+            Alg::Ed25519,
+            test_id(),
+            1000,
+            OwnerRef::single_key(&Thumbprint::from_bytes(vec![1])),
+            "cargo".into(),
+            vec![0; 4],
+            Thumbprint::from_bytes(vec![1]),
+        );
+        assert_eq!(
+            claim.to_canonical_json().unwrap(),
+            serde_json::to_vec(&claim).unwrap()
+        );
+        let publish = PublishPayload {
+            meta: None,
+            ..test_publish()
+        };
+        assert_eq!(
+            publish.to_canonical_json().unwrap(),
+            serde_json::to_vec(&publish).unwrap()
+        );
+    }
+
+    #[test]
+    fn canonical_is_independent_of_input_order_and_whitespace() {
+        let publish = test_publish();
+        let canonical = publish.to_canonical_json().unwrap();
+
+        // Reverse every object's key order and pretty-print.
+        fn reversed(value: &Value) -> String {
+            match value {
+                Value::Object(map) => {
+                    let body: Vec<_> = map
+                        .iter()
+                        .rev()
+                        .map(|(k, v)| format!("\n  {}: {}", Value::from(k.as_str()), reversed(v)))
+                        .collect();
+                    format!("{{{}\n}}", body.join(","))
+                },
+                other => other.to_string(),
+            }
+        }
+        let shuffled = reversed(&serde_json::to_value(&publish).unwrap());
+        let reparsed: PublishPayload = serde_json::from_str(&shuffled).unwrap();
+        assert_eq!(reparsed.to_canonical_json().unwrap(), canonical);
+
+        let text = std::str::from_utf8(&canonical).unwrap();
+        assert!(!text.contains(' ') && !text.contains('\n'));
+        assert!(text.contains(r#""meta":{"alpha":true,"zeta":{"a":[2,{"c":4,"d":3}],"b":1}}"#));
+    }
+
+    #[test]
+    fn fields_cover_every_serialized_key() {
+        fn check<T: CanonicalPayload>(payload: &T) {
+            let Value::Object(map) = serde_json::to_value(payload).unwrap() else {
+                panic!("payloads serialize to objects");
+            };
+            for key in map.keys() {
+                assert!(
+                    T::FIELDS.contains(&key.as_str()),
+                    "{key} missing from FIELDS"
+                );
+            }
+            payload.to_canonical_json().unwrap();
+        }
+        let tmb = Thumbprint::from_bytes(vec![1]);
+        let czd = Czd::from_bytes(vec![2]);
+        check(&test_publish());
+        check(
+            &CharterPayload::new(
+                Alg::Ed25519,
+                1,
+                vec![OwnerRef::single_key(&tmb)],
+                Some(czd.clone()),
+                vec![3],
+                tmb.clone(),
+            )
+            .unwrap(),
+        );
+        check(&RevokePayload::new(
+            Alg::Ed25519,
+            test_id(),
+            czd.clone(),
+            1,
+            crate::RevokeReason::Abandoned,
+            tmb.clone(),
+        ));
+        check(&DelegatePayload::new(
+            Alg::Ed25519,
+            test_id(),
+            czd.clone(),
+            tmb.clone(),
+            2,
+            1,
+            tmb.clone(),
+        ));
+        check(&RotatePayload::new(
+            Alg::Ed25519,
+            test_id(),
+            czd.clone(),
+            1,
+            1,
+            tmb.clone(),
+            tmb.clone(),
+        ));
+        check(&TransferPayload::new(
+            Alg::Ed25519,
+            test_id(),
+            1,
+            OwnerRef::single_key(&tmb),
+            czd.clone(),
+            tmb.clone(),
+        ));
+        check(&YankPayload::new(
+            Alg::Ed25519,
+            test_id(),
+            czd,
+            1,
+            tmb,
+            RawVersion::new("1".into()),
+        ));
+    }
+}
@@ -0,0 +1,214 @@
+//! CBOR wire format for payloads and identities (`cbor` feature).
+//!
+//! Bandwidth-sensitive registries and embedded verifiers can carry
+//! [`ClaimPayload`](crate::ClaimPayload), [`PublishPayload`](crate::PublishPayload),
+//! [`AtomId`](crate::AtomId) — or any other serde type in this crate — as
+//! CBOR instead of JSON. The CBOR form is the same serde data model, so a
+//! value round-trips between the two encodings losslessly.
+//!
+//! Encoding is deterministic (RFC 8949 §4.2.1 core deterministic encoding):
+//! shortest-form integers and lengths, definite-length items only, and map
+//! entries sorted by the bytewise order of their encoded keys. Equal values
+//! therefore always produce equal CBOR bytes.
+//!
+//! Signatures are NOT taken over CBOR. A payload's czd is a digest over its
+//! canonical JSON ([`CanonicalPayload`]), and that stays true whatever
+//! encoding carried the payload — otherwise one transaction would have one
+//! czd per wire format, and `claim` links would break across them. A CBOR
+//! consumer re-derives the signed bytes with [`cbor_to_canonical_json`] and
+//! verifies those.
+//!
+//! Spec: `docs/specs/atom-transactions.md` `[cbor-wire-form]`.
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use thiserror::Error;
+
+use crate::CanonicalPayload;
+
+/// Errors from CBOR encoding and decoding.
+#[derive(Error, Debug)]
+pub enum CborError {
+    /// The value could not be encoded as CBOR.
+    #[error("CBOR encoding failed: {0}")]
+    Encode(String),
+    /// The bytes are not valid CBOR for the requested type.
+    #[error("CBOR decoding failed: {0}")]
+    Decode(String),
+    /// The decoded payload could not be re-serialized as canonical JSON.
+    #[error("canonical JSON serialization failed: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+/// Encode `value` as deterministic CBOR.
+///
+/// # Errors
+///
+/// Returns [`CborError::Encode`] if `value` cannot be represented as CBOR.
+///
+/// Spec constraint: `[cbor-wire-form]`.
+pub fn to_canonical_cbor<T: Serialize + ?Sized>(value: &T) -> Result<Vec<u8>, CborError> {
+    let mut value =
+        ciborium::Value::serialized(value).map_err(|e| CborError::Encode(e.to_string()))?;
+    sort_maps(&mut value)?;
+    let mut out = Vec::new();
+    ciborium::into_writer(&value, &mut out).map_err(|e| CborError::Encode(e.to_string()))?;
+    Ok(out)
+}
+
+/// Decode a value from CBOR bytes.
+///
+/// Accepts any well-formed CBOR encoding of the value, deterministic or
+/// not; re-encode with [`to_canonical_cbor`] to obtain the canonical bytes.
+///
+/// # Errors
+///
+/// Returns [`CborError::Decode`] if `bytes` is not a valid CBOR encoding
+/// of `T`.
+pub fn from_cbor<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, CborError> {
+    ciborium::from_reader(bytes).map_err(|e| CborError::Decode(e.to_string()))
+}
+
+/// Decode a CBOR-carried payload and return the canonical JSON bytes its
+/// signature is over.
+///
+/// The result is what [`crate::verify_claim`], [`crate::verify_publish`]
+/// and [`crate::czd_for_alg`] take as `pay_json`.
+///
+/// # Errors
+///
+/// Returns [`CborError::Decode`] if `bytes` is not a valid CBOR encoding
+/// of `P`, or [`CborError::Json`] if canonical serialization fails.
+///
+/// Spec constraints: `[cbor-wire-form]`, `[canonical-payload-bytes]`.
+pub fn cbor_to_canonical_json<P>(bytes: &[u8]) -> Result<Vec<u8>, CborError>
+where
+    P: CanonicalPayload + DeserializeOwned,
+{
+    let payload: P = from_cbor(bytes)?;
+    Ok(payload.to_canonical_json()?)
+}
+
+/// Recursively sort every map's entries by the bytewise order of their
+/// encoded keys.
+fn sort_maps(value: &mut ciborium::Value) -> Result<(), CborError> {
+    match value {
+        ciborium::Value::Map(entries) => {
+            let mut keyed = Vec::with_capacity(entries.len());
+            for (key, mut val) in entries.drain(..) {
+                sort_maps(&mut val)?;
+                let mut encoded = Vec::new();
+                ciborium::into_writer(&key, &mut encoded)
+                    .map_err(|e| CborError::Encode(e.to_string()))?;
+                keyed.push((encoded, key, val));
+            }
+            keyed.sort_by(|a, b| a.0.cmp(&b.0));
+            entries.extend(keyed.into_iter().map(|(_, key, val)| (key, val)));
+        },
+        ciborium::Value::Array(items) => {
+            for item in items {
+                sort_maps(item)?;
+            }
+        },
+        ciborium::Value::Tag(_, inner) => sort_maps(inner)?,
+        _ => {},
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Alg, Anchor, AtomId, ClaimPayload, Czd, Label, OwnerRef, PublishPayload, RawVersion,
+        Thumbprint,
+    };
+
+    fn test_id() -> AtomId {
+        AtomId::new(
+            Anchor::new(vec![1, 2, 3, 4]),
+            Label::try_from("my-pkg").unwrap(),
+        )
+    }
+
+    fn test_claim() -> ClaimPayload {
+        ClaimPayload::new(
+            Alg::Ed25519,
+            test_id(),
+            1000,
+            OwnerRef::single_key(&Thumbprint::from_bytes(vec![1])),
+            "cargo".into(),
+            vec![0; 4],
+            Thumbprint::from_bytes(vec![1]),
+        )
+    }
+
+    fn test_publish() -> PublishPayload {
+        PublishPayload::new(
+            Alg::Ed25519,
+            test_id(),
+            Czd::from_bytes(vec![5, 6]),
+            vec![7, 8],
+            2000,
+            "src/lib".into(),
+            vec![9, 10],
+            Thumbprint::from_bytes(vec![10, 20, 30]),
+            RawVersion::new("1.0.0".into()),
+        )
+    }
+
+    #[test]
+    fn payloads_and_atom_id_roundtrip() {
+        let claim = test_claim();
+        let back: ClaimPayload = from_cbor(&to_canonical_cbor(&claim).unwrap()).unwrap();
+        assert_eq!(back, claim);
+
+        let publish = test_publish();
+        let back: PublishPayload = from_cbor(&to_canonical_cbor(&publish).unwrap()).unwrap();
+        assert_eq!(back, publish);
+
+        let id = test_id();
+        let back: AtomId = from_cbor(&to_canonical_cbor(&id).unwrap()).unwrap();
+        assert_eq!(back, id);
+    }
+
+    #[test]
+    fn encoding_is_deterministic_and_key_sorted() {
+        let mut a = serde_json::Map::new();
+        a.insert("zeta".into(), 1.into());
+        a.insert("alpha".into(), 2.into());
+        let mut b = serde_json::Map::new();
+        b.insert("alpha".into(), 2.into());
+        b.insert("zeta".into(), 1.into());
+        assert_eq!(
+            to_canonical_cbor(&a).unwrap(),
+            to_canonical_cbor(&b).unwrap()
+        );
+
+        // Encoded keys compare length prefix first, so the 3-byte keys lead
+        // and, among them, "alg" sorts first.
+        let cbor = to_canonical_cbor(&test_claim()).unwrap();
+        let first_key = &cbor[1..5];
+        assert_eq!(first_key, [0x63, b'a', b'l', b'g']);
+    }
+
+    #[test]
+    fn cbor_carried_payload_yields_signed_json() {
+        let publish = test_publish();
+        let cbor = to_canonical_cbor(&publish).unwrap();
+        assert_eq!(
+            cbor_to_canonical_json::<PublishPayload>(&cbor).unwrap(),
+            publish.to_canonical_json().unwrap()
+        );
+    }
+
+    #[test]
+    fn garbage_is_a_decode_error() {
+        let result = from_cbor::<ClaimPayload>(&[0xff, 0x00]);
+        assert!(matches!(result, Err(CborError::Decode(_))), "{result:?}");
+    }
+}
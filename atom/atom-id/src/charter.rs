@@ -168,7 +168,7 @@ pub fn verify_charter_key_thumbprint(
 ) -> Result<(), crate::VerifyError> {
     let computed = coz_rs::compute_thumbprint_for_alg(alg, pub_key)
         .ok_or_else(|| crate::VerifyError::UnsupportedAlgorithm(alg.to_string()))?;
-    if computed != charter.tmb {
+    if !crate::ct_eq(computed.as_bytes(), charter.tmb.as_bytes()) {
         return Err(crate::VerifyError::ThumbprintMismatch);
     }
     Ok(())
@@ -261,10 +261,11 @@ pub fn verify_succession_chain(
     // [charter-succession-linear]: at most one valid successor per prior.
     for (i, a) in chain.iter().enumerate() {
         let Some(a_prior) = &a.prior else { continue };
-        if chain[i + 1..]
-            .iter()
-            .any(|b| b.prior.as_ref() == Some(a_prior))
-        {
+        if chain[i + 1..].iter().any(|b| {
+            b.prior
+                .as_ref()
+                .is_some_and(|p| crate::ct_eq(p.as_bytes(), a_prior.as_bytes()))
+        }) {
             return Err(crate::VerifyError::DivergentSuccessors);
         }
     }
@@ -285,7 +286,11 @@ pub fn verify_succession_chain(
     // [chain-monotonicity]: the chain must demonstrably extend past a
     // previously recorded head (see "Known limitation" above).
     if let Some(head) = recorded_head {
-        let extends_past_head = successors.iter().any(|c| c.prior.as_ref() == Some(head));
+        let extends_past_head = successors.iter().any(|c| {
+            c.prior
+                .as_ref()
+                .is_some_and(|p| crate::ct_eq(p.as_bytes(), head.as_bytes()))
+        });
         if !extends_past_head {
             return Err(crate::VerifyError::ChainRegression);
         }
@@ -336,6 +341,38 @@ pub fn verify_charter_chain_signatures(
         .collect()
 }
 
+/// The protocol's [`AnchorScheme`](crate::AnchorScheme): an atom-set's
+/// anchor is the czd of its founding charter.
+///
+/// [`derive`](crate::AnchorScheme::derive) verifies the charter's
+/// signature and `typ`, rejects a successor charter (one carrying
+/// `prior`) with [`VerifyError::NotFoundingCharter`](crate::VerifyError::NotFoundingCharter),
+/// and returns the recomputed czd as the anchor.
+///
+/// Spec constraint: `[charter-anchor]`.
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CharterAnchorScheme;
+
+#[cfg(feature = "serde")]
+impl crate::AnchorScheme for CharterAnchorScheme {
+    type Error = crate::VerifyError;
+    type Input<'a> = CharterLink<'a>;
+
+    fn id(&self) -> &'static str {
+        "charter-czd"
+    }
+
+    fn derive(&self, link: CharterLink<'_>) -> Result<crate::Anchor, crate::VerifyError> {
+        let charter = verify_charter(link.pay_json, link.sig, link.alg, link.pub_key)?;
+        if charter.prior.is_some() {
+            return Err(crate::VerifyError::NotFoundingCharter);
+        }
+        let czd = crate::czd_for_alg(link.pay_json, link.sig, link.alg)?;
+        Ok(crate::Anchor::new(czd.as_bytes().to_vec()))
+    }
+}
+
 /// Verify the founding-charter bootstrap gate.
 ///
 /// Per `[charter-transition]` PRE (founding): if the source already
@@ -406,16 +443,7 @@ pub trait CharterStore {
 mod tests {
     use super::*;
     use crate::OwnerKind;
-
-    fn gen_ed25519_key() -> (Vec<u8>, Vec<u8>, crate::Thumbprint) {
-        use coz_rs::Ed25519;
-
-        let sk = coz_rs::SigningKey::<Ed25519>::generate();
-        let prv = sk.private_key_bytes();
-        let pub_bytes = sk.verifying_key().public_key_bytes().to_vec();
-        let tmb = sk.thumbprint().clone();
-        (prv, pub_bytes, tmb)
-    }
+    use crate::test_support::gen_ed25519_key;
 
     /// A single-entry `single-key` owner set from raw bytes — the common
     /// case throughout these tests, none of which exercise multi-member
@@ -540,6 +568,66 @@ mod tests {
         assert_eq!(verified.typ, TYP_CHARTER);
     }
 
+    #[test]
+    fn charter_anchor_scheme_is_founding_czd() {
+        use crate::AnchorScheme;
+
+        let (prv, pub_bytes, tmb) = gen_ed25519_key();
+        let sign = |prior: Option<Czd>| {
+            let charter = CharterPayload::new(
+                crate::Alg::Ed25519,
+                1000,
+                single_owner(vec![99]),
+                prior,
+                vec![0; 32],
+                tmb.clone(),
+            )
+            .unwrap();
+            let pay_json = serde_json::to_vec(&charter).unwrap();
+            let (sig, _cad) = coz_rs::sign_json(&pay_json, "Ed25519", &prv, &pub_bytes).unwrap();
+            (pay_json, sig)
+        };
+        fn link<'a>(signed: &'a (Vec<u8>, Vec<u8>), pub_key: &'a [u8]) -> CharterLink<'a> {
+            CharterLink {
+                pay_json: &signed.0,
+                sig: &signed.1,
+                alg: "Ed25519",
+                pub_key,
+            }
+        }
+
+        let founding = sign(None);
+        let czd = crate::czd_for_alg(&founding.0, &founding.1, "Ed25519").unwrap();
+        let anchor = crate::Anchor::new(czd.as_bytes().to_vec());
+        assert_eq!(CharterAnchorScheme.id(), "charter-czd");
+        assert_eq!(
+            CharterAnchorScheme
+                .derive(link(&founding, &pub_bytes))
+                .unwrap(),
+            anchor
+        );
+        assert!(
+            CharterAnchorScheme
+                .verify(link(&founding, &pub_bytes), &anchor)
+                .unwrap()
+        );
+        assert!(
+            !CharterAnchorScheme
+                .verify(
+                    link(&founding, &pub_bytes),
+                    &crate::Anchor::new(vec![0; 32])
+                )
+                .unwrap()
+        );
+
+        let successor = sign(Some(czd));
+        let result = CharterAnchorScheme.derive(link(&successor, &pub_bytes));
+        assert!(
+            matches!(result, Err(crate::VerifyError::NotFoundingCharter)),
+            "a successor charter is not an anchor: {result:?}"
+        );
+    }
+
     #[test]
     fn verify_charter_wrong_typ() {
         let (prv, pub_bytes, tmb) = gen_ed25519_key();
@@ -0,0 +1,85 @@
+//! Confusable and mixed-script detection for labels (`confusables`
+//! feature), per Unicode Technical Standard #39.
+//!
+//! [`Label`]'s base rules (UAX #31 + NFKC) admit any script, so
+//! `раckage` — Cyrillic `р` and `а` — is as valid as `package`, and the
+//! two render identically. A registry that wants to refuse such
+//! typosquats at claim time validates new labels with
+//! [`Label::validate_strict`] and compares each against existing labels'
+//! [`Label::skeleton`]s.
+//!
+//! Strictness is opt-in: the protocol itself accepts any valid label, so
+//! none of this runs on the verification path.
+
+use unicode_security::MixedScript;
+
+use crate::{Error, Label};
+
+impl Label {
+    /// Validate like [`Label::try_from`], additionally rejecting labels
+    /// that mix scripts (UTS #39 §5.1 single-script check).
+    ///
+    /// Characters common to all scripts (digits, `-`, `_`) and combining
+    /// marks do not count toward any script.
+    ///
+    /// # Errors
+    ///
+    /// Any error [`Label::try_from`] returns, or [`Error::MixedScript`].
+    pub fn validate_strict(s: &str) -> Result<Self, Error> {
+        let label = Self::try_from(s)?;
+        let normalized: &str = &label;
+        if !normalized.is_single_script() {
+            return Err(Error::MixedScript);
+        }
+        Ok(label)
+    }
+
+    /// The UTS #39 confusable skeleton: labels that render alike map to
+    /// the same skeleton.
+    ///
+    /// Only for comparison — a skeleton is not itself a valid label and
+    /// must never be stored in place of one.
+    #[must_use]
+    pub fn skeleton(&self) -> String {
+        unicode_security::skeleton(self).collect()
+    }
+
+    /// Whether `self` and `other` are distinct labels that a reader could
+    /// mistake for one another.
+    #[must_use]
+    pub fn is_confusable_with(&self, other: &Self) -> bool {
+        self != other && self.skeleton() == other.skeleton()
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strict_rejects_mixed_script() {
+        assert!(Label::validate_strict("package").is_ok());
+        assert!(Label::validate_strict("пакет").is_ok());
+        assert!(Label::validate_strict("my-pkg_2").is_ok());
+        assert_eq!(
+            Label::validate_strict("\u{440}\u{430}ckage"),
+            Err(Error::MixedScript)
+        );
+        assert_eq!(Label::validate_strict(""), Err(Error::Empty));
+    }
+
+    #[test]
+    fn skeletons_expose_lookalikes() {
+        let latin = Label::try_from("package").unwrap();
+        let spoof = Label::try_from("\u{440}\u{430}ckage").unwrap();
+        assert_ne!(latin, spoof);
+        assert_eq!(latin.skeleton(), spoof.skeleton());
+        assert!(latin.is_confusable_with(&spoof));
+        assert!(!latin.is_confusable_with(&latin));
+        assert!(!latin.is_confusable_with(&Label::try_from("packages").unwrap()));
+    }
+}
@@ -0,0 +1,84 @@
+//! Constant-time equality for identity and digest bytes.
+//!
+//! Registries compare attacker-supplied digests — a publish's `claim`
+//! link, a caller-named active claim, a declared `tmb` — against values
+//! they hold. A short-circuiting `==` leaks, through its timing, how long
+//! a prefix of a guess matched. The comparisons here take time dependent
+//! only on the lengths of their inputs, which are public (they follow from
+//! the algorithm).
+//!
+//! [`Anchor`], [`Anchor32`] and [`AtomDigest`] implement
+//! [`ConstantTimeEq`], and their `PartialEq` goes through it. [`Cad`],
+//! [`Czd`] and [`Thumbprint`] are coz-rs types, so they are compared with
+//! [`ct_eq`] over their bytes.
+//!
+//! [`Cad`]: crate::Cad
+//! [`Czd`]: crate::Czd
+//! [`Thumbprint`]: crate::Thumbprint
+
+use subtle::{Choice, ConstantTimeEq};
+
+use crate::{Anchor, Anchor32, AtomDigest};
+
+/// Compare two byte strings in time independent of their contents.
+///
+/// Unequal lengths compare unequal immediately; only the length is
+/// revealed.
+#[must_use]
+pub fn ct_eq(a: &[u8], b: &[u8]) -> bool {
+    a.ct_eq(b).into()
+}
+
+impl ConstantTimeEq for Anchor {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.as_bytes().ct_eq(other.as_bytes())
+    }
+}
+
+impl ConstantTimeEq for Anchor32 {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.as_bytes().ct_eq(other.as_bytes())
+    }
+}
+
+impl ConstantTimeEq for AtomDigest {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        // The algorithm is public; only the digest bytes need protecting.
+        Choice::from(u8::from(self.alg() == other.alg()))
+            & self.cad().as_bytes().ct_eq(other.cad().as_bytes())
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ct_eq_matches_byte_equality() {
+        assert!(ct_eq(b"abcd", b"abcd"));
+        assert!(!ct_eq(b"abcd", b"abce"));
+        assert!(!ct_eq(b"abc", b"abcd"));
+        assert!(ct_eq(b"", b""));
+
+        let a = Anchor::new(vec![1; 32]);
+        let b = Anchor::new(vec![2; 32]);
+        assert_eq!(a, a.clone());
+        assert_ne!(a, b);
+        assert_eq!(Anchor32::new([1; 32]), a);
+        assert_ne!(Anchor32::new([1; 32]), Anchor32::new([2; 32]));
+    }
+
+    #[test]
+    fn atom_digest_equality_includes_the_algorithm() {
+        let bytes = [7; 32];
+        let sha: AtomDigest = format!("sha256:{}", Anchor32::new(bytes)).parse().unwrap();
+        let blake: AtomDigest = format!("blake3:{}", hex::encode(bytes)).parse().unwrap();
+        assert_eq!(sha.cad().as_bytes(), blake.cad().as_bytes());
+        assert_eq!(sha, sha.clone());
+        assert_ne!(sha, blake);
+    }
+}
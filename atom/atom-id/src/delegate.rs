@@ -0,0 +1,393 @@
+//! Delegation transactions — letting a scoped sub-key publish under a
+//! claim.
+//!
+//! A delegation is signed by the claim owner's key and names a `delegate`
+//! thumbprint that may sign publishes for that claim's `(anchor, label)` —
+//! and nothing else — between the delegation's `now` and its `exp`. A CI
+//! system can then release with a short-lived key while the owner key
+//! stays offline; when the window closes, the sub-key is worthless. The
+//! owner's own key is unaffected and keeps authorizing publishes.
+//!
+//! Spec: `docs/specs/atom-transactions.md` §DelegatePayload, `[delegate-*]`.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{Alg, Anchor, AtomId, Czd, Label, Thumbprint};
+
+/// Transaction type for delegations.
+///
+/// Spec constraint: `[delegate-typ]`.
+pub const TYP_DELEGATE: &str = "atom/delegate";
+
+// ============================================================================
+// DelegatePayload
+// ============================================================================
+
+/// Payload for an `atom/delegate` transaction.
+///
+/// Names the claim by its [`Czd`], repeats its `(anchor, label)`, and
+/// authorizes `delegate` to sign publishes for that label from `now` until
+/// `exp`. Only the claim owner may sign it (`[delegate-authority]`).
+///
+/// Spec constraints: `[delegate-typ]`, `[delegate-authority]`,
+/// `[delegate-scope]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DelegatePayload {
+    /// The signing algorithm.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_alg"))]
+    pub alg: Alg,
+    /// The atom-set anchor of the delegated claim.
+    pub anchor: Anchor,
+    /// The [`Czd`] of the delegated claim.
+    pub claim: Czd,
+    /// Coz key thumbprint of the key being authorized.
+    pub delegate: Thumbprint,
+    /// First timestamp (seconds since Unix epoch) at which `delegate` no
+    /// longer authorizes publishes. MUST exceed `now`.
+    pub exp: u64,
+    /// The atom label of the delegated claim — the only label `delegate`
+    /// may publish.
+    pub label: Label,
+    /// Timestamp (seconds since Unix epoch). MUST strictly exceed the
+    /// claim's `now`; `delegate` authorizes publishes from here on.
+    pub now: u64,
+    /// Coz key thumbprint of the signing key — the claim's owner.
+    pub tmb: Thumbprint,
+    /// Transaction type — always [`TYP_DELEGATE`].
+    pub typ: String,
+}
+
+impl DelegatePayload {
+    /// Construct a new delegation payload.
+    ///
+    /// Takes an [`AtomId`] to ensure that the anchor and label come from
+    /// a validated identity pair. Sets `typ` to [`TYP_DELEGATE`]
+    /// automatically.
+    pub fn new(
+        alg: Alg,
+        id: AtomId,
+        claim: Czd,
+        delegate: Thumbprint,
+        exp: u64,
+        now: u64,
+        tmb: Thumbprint,
+    ) -> Self {
+        Self {
+            alg,
+            anchor: id.anchor,
+            claim,
+            delegate,
+            exp,
+            label: id.label,
+            now,
+            tmb,
+            typ: TYP_DELEGATE.to_owned(),
+        }
+    }
+
+    /// Whether `delegate` may sign a publish dated `now`: `self.now <= now
+    /// < self.exp`.
+    #[must_use]
+    pub fn covers(&self, now: u64) -> bool {
+        (self.now..self.exp).contains(&now)
+    }
+}
+
+// ============================================================================
+// Verification
+// ============================================================================
+
+#[cfg(feature = "serde")]
+impl crate::TypedPayload for DelegatePayload {
+    fn typ(&self) -> &str {
+        &self.typ
+    }
+
+    fn tmb(&self) -> &Thumbprint {
+        &self.tmb
+    }
+}
+
+/// Verify a signed `atom/delegate` transaction.
+///
+/// Validates the Coz signature, deserializes the payload, checks that
+/// `typ` is [`TYP_DELEGATE`], and binds the declared `tmb` to `pub_key`
+/// (as [`crate::verify_rotate`] does, for the same reason).
+///
+/// Spec constraints: `[sig-over-pay]`, `[delegate-typ]`.
+#[cfg(feature = "serde")]
+pub fn verify_delegate(
+    pay_json: &[u8],
+    sig: &[u8],
+    alg: &str,
+    pub_key: &[u8],
+) -> Result<DelegatePayload, crate::VerifyError> {
+    crate::verify_typed(pay_json, sig, alg, pub_key, TYP_DELEGATE)
+}
+
+/// Verify a delegation against the claim it names.
+///
+/// Checks, in order:
+///
+/// - `delegate.claim == czd(claim)` ([`crate::VerifyError::ClaimChainMismatch`]).
+/// - `(delegate.anchor, delegate.label)` equals the claim's
+///   ([`crate::VerifyError::AtomIdMismatch`]).
+/// - `claim.owner` authorizes `delegate.tmb` ([`crate::VerifyError::Unauthorized`]).
+/// - `delegate.now > claim.now` ([`crate::VerifyError::DelegateNotAfterClaim`]).
+/// - `delegate.exp > delegate.now` ([`crate::VerifyError::DelegationExpired`]).
+///
+/// As with [`crate::verify_rotate_against_claim`], the claim's czd is
+/// recomputed from its wire components and its signature is assumed
+/// verified upstream. Returns the parsed claim on success.
+///
+/// Spec constraint: `[delegate-authority]`.
+#[cfg(feature = "serde")]
+pub fn verify_delegate_against_claim(
+    delegate: &DelegatePayload,
+    claim_pay_json: &[u8],
+    claim_sig: &[u8],
+    claim_alg: &str,
+) -> Result<crate::ClaimPayload, crate::VerifyError> {
+    let claim_czd = crate::czd_for_alg(claim_pay_json, claim_sig, claim_alg)?;
+    if !crate::ct_eq(delegate.claim.as_bytes(), claim_czd.as_bytes()) {
+        return Err(crate::VerifyError::ClaimChainMismatch);
+    }
+    let claim: crate::ClaimPayload = serde_json::from_slice(claim_pay_json)?;
+    if delegate.anchor != claim.anchor || delegate.label != claim.label {
+        return Err(crate::VerifyError::AtomIdMismatch);
+    }
+    if !claim.owner.authorizes(&delegate.tmb) {
+        return Err(crate::VerifyError::Unauthorized);
+    }
+    if delegate.now <= claim.now {
+        return Err(crate::VerifyError::DelegateNotAfterClaim);
+    }
+    if delegate.exp <= delegate.now {
+        return Err(crate::VerifyError::DelegationExpired { exp: delegate.exp });
+    }
+    Ok(claim)
+}
+
+/// Verify a publish's signer is authorized by a claim or by a delegation
+/// under it — the delegation-aware form of
+/// [`crate::verify_publish_authorized`], completing the
+/// `claim → delegate → publish` chain.
+///
+/// - A key `claim.owner` authorizes is accepted, as without a delegation.
+/// - `delegate.delegate` is accepted for a publish of the delegation's own `(anchor, label)`
+///   ([`crate::VerifyError::AtomIdMismatch`] otherwise) dated inside its window; a publish dated at
+///   or after `exp` fails with [`crate::VerifyError::DelegationExpired`].
+/// - Any other signer, or the delegate before the window opens, is
+///   [`crate::VerifyError::Unauthorized`].
+///
+/// `delegate` MUST already have passed [`verify_delegate`] and
+/// [`verify_delegate_against_claim`]; the same `publish.tmb` binding
+/// precondition as [`crate::verify_publish_authorized`] applies.
+///
+/// Spec constraint: `[delegate-scope]`.
+#[cfg(feature = "serde")]
+pub fn verify_publish_authorized_delegated(
+    publish: &crate::PublishPayload,
+    claim: &crate::ClaimPayload,
+    delegate: &DelegatePayload,
+) -> Result<(), crate::VerifyError> {
+    if claim.owner.authorizes(&publish.tmb) {
+        return Ok(());
+    }
+    if !crate::ct_eq(publish.tmb.as_bytes(), delegate.delegate.as_bytes())
+        || publish.now < delegate.now
+    {
+        return Err(crate::VerifyError::Unauthorized);
+    }
+    if publish.anchor != delegate.anchor || publish.label != delegate.label {
+        return Err(crate::VerifyError::AtomIdMismatch);
+    }
+    if !delegate.covers(publish.now) {
+        return Err(crate::VerifyError::DelegationExpired { exp: delegate.exp });
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{gen_ed25519_key, signed_claim, test_id};
+    use crate::{ClaimPayload, PublishPayload, RawVersion};
+
+    fn publish_by(tmb: &Thumbprint, claim: &Czd, now: u64) -> PublishPayload {
+        PublishPayload::new(
+            Alg::Ed25519,
+            test_id(),
+            claim.clone(),
+            vec![7, 8],
+            now,
+            "src/lib".into(),
+            vec![9, 10],
+            tmb.clone(),
+            RawVersion::new("1.0.0".into()),
+        )
+    }
+
+    #[test]
+    fn delegate_payload_typ_and_serde_roundtrip() {
+        let delegate = DelegatePayload::new(
+            Alg::ES256,
+            test_id(),
+            Czd::from_bytes(vec![5, 6]),
+            Thumbprint::from_bytes(vec![40, 50]),
+            5000,
+            2000,
+            Thumbprint::from_bytes(vec![10, 20, 30]),
+        );
+        assert_eq!(delegate.typ, "atom/delegate");
+        assert!(delegate.covers(2000));
+        assert!(delegate.covers(4999));
+        assert!(!delegate.covers(1999));
+        assert!(!delegate.covers(5000));
+        let json = serde_json::to_string(&delegate).unwrap();
+        let back: DelegatePayload = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, delegate);
+    }
+
+    #[test]
+    fn verify_delegate_roundtrip_and_wrong_typ() {
+        let (prv, pub_bytes, tmb) = gen_ed25519_key();
+        let delegate = DelegatePayload::new(
+            Alg::Ed25519,
+            test_id(),
+            Czd::from_bytes(vec![5, 6]),
+            Thumbprint::from_bytes(vec![40, 50]),
+            5000,
+            2000,
+            tmb,
+        );
+        let pay_json = serde_json::to_vec(&delegate).unwrap();
+        let (sig, _cad) = coz_rs::sign_json(&pay_json, "Ed25519", &prv, &pub_bytes).unwrap();
+        assert_eq!(
+            verify_delegate(&pay_json, &sig, "Ed25519", &pub_bytes).unwrap(),
+            delegate
+        );
+
+        let mut json_val = serde_json::to_value(&delegate).unwrap();
+        json_val["typ"] = serde_json::Value::String("atom/rotate".into());
+        let pay_json = serde_json::to_vec(&json_val).unwrap();
+        let (sig, _cad) = coz_rs::sign_json(&pay_json, "Ed25519", &prv, &pub_bytes).unwrap();
+        let result = verify_delegate(&pay_json, &sig, "Ed25519", &pub_bytes);
+        assert!(
+            matches!(result, Err(crate::VerifyError::WrongTyp { .. })),
+            "{result:?}"
+        );
+    }
+
+    #[test]
+    fn verify_delegate_against_claim_requires_owner() {
+        let (_prv, _pub, owner_tmb) = gen_ed25519_key();
+        let (_ci_prv, _ci_pub, ci_tmb) = gen_ed25519_key();
+        let (claim_json, claim_sig, claim_czd) = signed_claim(&owner_tmb);
+
+        let by_owner = DelegatePayload::new(
+            Alg::Ed25519,
+            test_id(),
+            claim_czd.clone(),
+            ci_tmb.clone(),
+            5000,
+            2000,
+            owner_tmb.clone(),
+        );
+        let result = verify_delegate_against_claim(&by_owner, &claim_json, &claim_sig, "Ed25519");
+        assert!(result.is_ok(), "owner delegation must verify: {result:?}");
+
+        // The delegate cannot extend its own authority.
+        let by_delegate = DelegatePayload {
+            tmb: ci_tmb,
+            ..by_owner.clone()
+        };
+        let result =
+            verify_delegate_against_claim(&by_delegate, &claim_json, &claim_sig, "Ed25519");
+        assert!(
+            matches!(result, Err(crate::VerifyError::Unauthorized)),
+            "{result:?}"
+        );
+
+        let backdated = DelegatePayload {
+            now: 1000,
+            ..by_owner.clone()
+        };
+        let result = verify_delegate_against_claim(&backdated, &claim_json, &claim_sig, "Ed25519");
+        assert!(
+            matches!(result, Err(crate::VerifyError::DelegateNotAfterClaim)),
+            "{result:?}"
+        );
+
+        let empty_window = DelegatePayload {
+            exp: 2000,
+            ..by_owner
+        };
+        let result =
+            verify_delegate_against_claim(&empty_window, &claim_json, &claim_sig, "Ed25519");
+        assert!(
+            matches!(
+                result,
+                Err(crate::VerifyError::DelegationExpired { exp: 2000 })
+            ),
+            "{result:?}"
+        );
+    }
+
+    #[test]
+    fn delegated_publish_authorization_is_scoped() {
+        let (_owner_prv, _owner_pub, owner_tmb) = gen_ed25519_key();
+        let (_ci_prv, _ci_pub, ci_tmb) = gen_ed25519_key();
+        let (_x_prv, _x_pub, stranger_tmb) = gen_ed25519_key();
+        let (claim_json, _claim_sig, claim_czd) = signed_claim(&owner_tmb);
+        let claim: ClaimPayload = serde_json::from_slice(&claim_json).unwrap();
+        let delegate = DelegatePayload::new(
+            Alg::Ed25519,
+            test_id(),
+            claim_czd.clone(),
+            ci_tmb.clone(),
+            5000,
+            2000,
+            owner_tmb.clone(),
+        );
+        let check = |tmb: &Thumbprint, now| {
+            verify_publish_authorized_delegated(
+                &publish_by(tmb, &claim_czd, now),
+                &claim,
+                &delegate,
+            )
+        };
+
+        assert!(check(&owner_tmb, 1500).is_ok());
+        assert!(check(&owner_tmb, 9000).is_ok());
+        assert!(check(&ci_tmb, 2000).is_ok());
+        assert!(check(&ci_tmb, 4999).is_ok());
+        assert!(matches!(
+            check(&ci_tmb, 5000),
+            Err(crate::VerifyError::DelegationExpired { exp: 5000 })
+        ));
+        assert!(matches!(
+            check(&ci_tmb, 1999),
+            Err(crate::VerifyError::Unauthorized)
+        ));
+        assert!(matches!(
+            check(&stranger_tmb, 3000),
+            Err(crate::VerifyError::Unauthorized)
+        ));
+
+        let other_label = PublishPayload {
+            label: Label::try_from("other-pkg").unwrap(),
+            ..publish_by(&ci_tmb, &claim_czd, 3000)
+        };
+        assert!(matches!(
+            verify_publish_authorized_delegated(&other_label, &claim, &delegate),
+            Err(crate::VerifyError::AtomIdMismatch)
+        ));
+    }
+}
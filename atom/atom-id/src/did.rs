@@ -0,0 +1,179 @@
+//! W3C Decentralized Identifiers as atom owners.
+//!
+//! A `did` owner-reference carries a DID (`did:method:method-specific-id`)
+//! as its UTF-8 `value`. The DID itself says nothing about which keys may
+//! sign for it — that lives in the DID document, which only a
+//! method-specific resolver can fetch. This crate therefore validates DID
+//! *syntax* (DID Core §3.1) and delegates key binding to a [`DidResolver`]
+//! the registry supplies.
+//!
+//! Spec: `docs/specs/atom-transactions.md` `[owner-did]`.
+
+use thiserror::Error;
+
+use crate::Thumbprint;
+
+/// Errors from DID validation and resolution.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum DidError {
+    /// The owner value is not valid UTF-8.
+    #[error("DID is not valid UTF-8")]
+    InvalidUnicode,
+    /// The string does not start with `did:`.
+    #[error("DID must start with 'did:'")]
+    MissingScheme,
+    /// The method name is empty or contains characters other than
+    /// lowercase ASCII letters and digits.
+    #[error("invalid DID method name")]
+    InvalidMethod,
+    /// The method-specific identifier is empty, ends in `:`, or contains a
+    /// character outside DID Core's `idchar` set.
+    #[error("invalid DID method-specific identifier")]
+    InvalidMethodSpecificId,
+    /// The resolver could not produce a DID document.
+    #[error("DID resolution failed: {0}")]
+    Resolution(String),
+}
+
+/// Validate `did` against the DID Core §3.1 syntax:
+///
+/// ```text
+/// did                = "did:" method-name ":" method-specific-id
+/// method-name        = 1*( %x61-7A / DIGIT )
+/// method-specific-id = *( *idchar ":" ) 1*idchar
+/// idchar             = ALPHA / DIGIT / "." / "-" / "_" / pct-encoded
+/// ```
+///
+/// # Errors
+///
+/// Returns the [`DidError`] naming the first part that fails.
+///
+/// Spec constraint: `[owner-did]`.
+pub fn validate_did(did: &str) -> Result<(), DidError> {
+    let rest = did.strip_prefix("did:").ok_or(DidError::MissingScheme)?;
+    let (method, id) = rest.split_once(':').ok_or(DidError::InvalidMethod)?;
+    if method.is_empty()
+        || !method
+            .bytes()
+            .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit())
+    {
+        return Err(DidError::InvalidMethod);
+    }
+    if id.is_empty() || id.ends_with(':') {
+        return Err(DidError::InvalidMethodSpecificId);
+    }
+    let bytes = id.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' => {
+                let hex = bytes.get(i + 1..i + 3);
+                if !hex.is_some_and(|h| h.iter().all(u8::is_ascii_hexdigit)) {
+                    return Err(DidError::InvalidMethodSpecificId);
+                }
+                i += 3;
+            },
+            b if b.is_ascii_alphanumeric() || matches!(b, b'.' | b'-' | b'_' | b':') => i += 1,
+            _ => return Err(DidError::InvalidMethodSpecificId),
+        }
+    }
+    Ok(())
+}
+
+/// Resolves a DID to the keys its DID document binds to it.
+///
+/// Implementations own everything method-specific — fetching the
+/// document, checking its proofs, picking which verification
+/// relationships count. They receive only syntactically valid DIDs.
+///
+/// Spec constraint: `[owner-did]`.
+pub trait DidResolver {
+    /// Thumbprints of the keys `did`'s document authorizes to sign on its
+    /// behalf.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DidError::Resolution`] if the document cannot be resolved
+    /// or verified.
+    fn bound_keys(&self, did: &str) -> Result<Vec<Thumbprint>, DidError>;
+}
+
+/// Whether `tmb` is bound to `did` according to `resolver`.
+///
+/// # Errors
+///
+/// Returns a syntax error if `did` is not a valid DID, or the resolver's
+/// error if resolution fails.
+///
+/// Spec constraint: `[owner-did]`.
+pub fn did_binds_key<R: DidResolver + ?Sized>(
+    resolver: &R,
+    did: &str,
+    tmb: &Thumbprint,
+) -> Result<bool, DidError> {
+    validate_did(did)?;
+    Ok(resolver.bound_keys(did)?.contains(tmb))
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn did_syntax() {
+        for ok in [
+            "did:example:123456789abcdefghi",
+            "did:web:example.com",
+            "did:web:example.com:user:alice",
+            "did:key:z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK",
+            "did:plc:a%20b",
+        ] {
+            assert_eq!(validate_did(ok), Ok(()), "{ok}");
+        }
+        for (bad, err) in [
+            ("example:123", DidError::MissingScheme),
+            ("did:example", DidError::InvalidMethod),
+            ("did::123", DidError::InvalidMethod),
+            ("did:Example:123", DidError::InvalidMethod),
+            ("did:example:", DidError::InvalidMethodSpecificId),
+            ("did:example:abc:", DidError::InvalidMethodSpecificId),
+            ("did:example:a b", DidError::InvalidMethodSpecificId),
+            ("did:example:a%2", DidError::InvalidMethodSpecificId),
+            ("did:example:a%zz", DidError::InvalidMethodSpecificId),
+        ] {
+            assert_eq!(validate_did(bad), Err(err), "{bad}");
+        }
+    }
+
+    struct Fixed(Vec<Thumbprint>);
+
+    impl DidResolver for Fixed {
+        fn bound_keys(&self, did: &str) -> Result<Vec<Thumbprint>, DidError> {
+            if did == "did:example:gone" {
+                return Err(DidError::Resolution("not found".into()));
+            }
+            Ok(self.0.clone())
+        }
+    }
+
+    #[test]
+    fn key_binding_goes_through_the_resolver() {
+        let bound = Thumbprint::from_bytes(vec![1; 4]);
+        let other = Thumbprint::from_bytes(vec![2; 4]);
+        let resolver = Fixed(vec![bound.clone()]);
+        assert_eq!(did_binds_key(&resolver, "did:example:a", &bound), Ok(true));
+        assert_eq!(did_binds_key(&resolver, "did:example:a", &other), Ok(false));
+        assert!(matches!(
+            did_binds_key(&resolver, "did:example:gone", &bound),
+            Err(DidError::Resolution(_))
+        ));
+        assert_eq!(
+            did_binds_key(&resolver, "not-a-did", &bound),
+            Err(DidError::MissingScheme)
+        );
+    }
+}
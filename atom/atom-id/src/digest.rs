@@ -9,6 +9,14 @@
 //! own convention), lowercase hex for `sha1` (git object ids) and `blake3`
 //! (content digests). One canonical encoding per token keeps a lock
 //! byte-deterministic (`[lock-canonical-form]`).
+//!
+//! For tools outside the lock — IPFS, OCI and anything else that speaks
+//! [multiformats] — a digest also has a multihash wire form
+//! ([`AtomDigest::to_multihash`]) and a multibase text form
+//! ([`AtomDigest::to_multibase`]).
+//!
+//! [multiformats]: https://multiformats.io
+//! [`AtomId`]: crate::AtomId
 
 use std::fmt;
 use std::str::FromStr;
@@ -19,7 +27,7 @@ use coz_rs::{Cad, Czd};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use thiserror::Error;
 
-use crate::AtomId;
+use crate::AtomIdRef;
 
 /// A hash algorithm that can label an [`AtomDigest`].
 ///
@@ -88,6 +96,32 @@ impl HashAlg {
         }
     }
 
+    /// The algorithm's code in the multicodec table — the prefix of its
+    /// multihash.
+    #[must_use]
+    pub const fn multicodec(self) -> u64 {
+        match self {
+            Self::Sha1 => 0x11,
+            Self::Sha256 => 0x12,
+            Self::Sha512 => 0x13,
+            Self::Blake3 => 0x1e,
+            Self::Sha384 => 0x20,
+        }
+    }
+
+    /// The algorithm with multicodec code `code`, or `None` if unknown.
+    #[must_use]
+    pub const fn from_multicodec(code: u64) -> Option<Self> {
+        match code {
+            0x11 => Some(Self::Sha1),
+            0x12 => Some(Self::Sha256),
+            0x13 => Some(Self::Sha512),
+            0x1e => Some(Self::Blake3),
+            0x20 => Some(Self::Sha384),
+            _ => None,
+        }
+    }
+
     /// The fixed encoding this algorithm's bytes are rendered with.
     const fn encoding(self) -> Encoding {
         match self {
@@ -139,7 +173,11 @@ impl Encoding {
 /// The one digest representation for the store index and every lock digest
 /// field. Its human form is `<token>:<encoding>` — e.g. `sha256:<b64url>`,
 /// `sha1:<hex>`, `blake3:<hex>` — keyed on the hash algorithm.
-#[derive(Debug, Clone, PartialEq, Eq)]
+///
+/// Equality is constant-time in the digest bytes ([`ConstantTimeEq`]).
+///
+/// [`ConstantTimeEq`]: subtle::ConstantTimeEq
+#[derive(Debug, Clone, Eq)]
 #[must_use = "digests should not be discarded"]
 pub struct AtomDigest {
     alg: HashAlg,
@@ -153,7 +191,12 @@ impl AtomDigest {
     /// (field order `["anchor", "label"]`) and hashes it. The parameter is
     /// `coz_rs::HashAlg` because the store index is a coz-family digest;
     /// obtain it from a signing algorithm via [`coz_rs::Alg::hash_alg`].
-    pub fn compute(id: &AtomId, alg: coz_rs::HashAlg) -> Self {
+    ///
+    /// Accepts `&AtomId` or a borrowed [`AtomIdRef`].
+    ///
+    /// [`AtomId`]: crate::AtomId
+    pub fn compute<'a>(id: impl Into<AtomIdRef<'a>>, alg: coz_rs::HashAlg) -> Self {
+        let id = id.into();
         // Anchor.to_b64() (base64url) and Label (UAX #31) are JSON-safe, so
         // this format! produces valid JSON with no escaping concerns.
         let json = format!(
@@ -181,6 +224,100 @@ impl AtomDigest {
     pub fn cad(&self) -> &Cad {
         &self.cad
     }
+
+    /// The multihash: the algorithm's [multicodec](HashAlg::multicodec)
+    /// code and the digest length, each an unsigned varint, then the digest
+    /// bytes.
+    #[must_use]
+    pub fn to_multihash(&self) -> Vec<u8> {
+        let bytes = self.cad.as_bytes();
+        let mut out = Vec::with_capacity(bytes.len() + 2);
+        write_varint(&mut out, self.alg.multicodec());
+        write_varint(&mut out, bytes.len() as u64);
+        out.extend_from_slice(bytes);
+        out
+    }
+
+    /// Parse a multihash written by [`to_multihash`](Self::to_multihash).
+    ///
+    /// # Errors
+    ///
+    /// [`DigestParseError::Multihash`] if the varints are malformed or not
+    /// minimal, or bytes follow the digest;
+    /// [`DigestParseError::UnknownMulticodec`] for an algorithm this crate
+    /// does not know; [`DigestParseError::Length`] if the digest is the
+    /// wrong length for its algorithm.
+    pub fn from_multihash(mut bytes: &[u8]) -> Result<Self, DigestParseError> {
+        let code = read_varint(&mut bytes)?;
+        let alg =
+            HashAlg::from_multicodec(code).ok_or(DigestParseError::UnknownMulticodec(code))?;
+        let len = read_varint(&mut bytes)?;
+        if len != bytes.len() as u64 {
+            return Err(DigestParseError::Multihash);
+        }
+        if bytes.len() != alg.digest_len() {
+            return Err(DigestParseError::Length {
+                alg,
+                expected: alg.digest_len(),
+                got: bytes.len(),
+            });
+        }
+        Ok(Self {
+            alg,
+            cad: Cad::from_bytes(bytes.to_vec()),
+        })
+    }
+
+    /// The [multihash](Self::to_multihash) as multibase text: `u` and
+    /// base64url, unpadded.
+    #[must_use]
+    pub fn to_multibase(&self) -> String {
+        format!("u{}", Encoding::B64Ut.encode(&self.to_multihash()))
+    }
+
+    /// Parse multibase text of a multihash, in base64url (`u`, unpadded) or
+    /// lowercase hex (`f`).
+    ///
+    /// # Errors
+    ///
+    /// [`DigestParseError::UnknownMultibase`] for any other base,
+    /// [`DigestParseError::Encoding`] if the text is not valid in its base,
+    /// and the errors of [`from_multihash`](Self::from_multihash).
+    pub fn from_multibase(s: &str) -> Result<Self, DigestParseError> {
+        let mut chars = s.chars();
+        let encoding = match chars.next() {
+            Some('u') => Encoding::B64Ut,
+            Some('f') => Encoding::Hex,
+            other => return Err(DigestParseError::UnknownMultibase(other)),
+        };
+        Self::from_multihash(&encoding.decode(chars.as_str())?)
+    }
+}
+
+/// Append `value` as an unsigned LEB128 varint.
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+/// Take an unsigned varint off the front of `bytes`. Multiformats allows at
+/// most nine bytes and only the minimal encoding.
+fn read_varint(bytes: &mut &[u8]) -> Result<u64, DigestParseError> {
+    let mut value = 0u64;
+    for (i, &byte) in bytes.iter().enumerate().take(9) {
+        value |= u64::from(byte & 0x7f) << (7 * i);
+        if byte & 0x80 == 0 {
+            if byte == 0 && i > 0 {
+                return Err(DigestParseError::Multihash);
+            }
+            *bytes = &bytes[i + 1..];
+            return Ok(value);
+        }
+    }
+    Err(DigestParseError::Multihash)
 }
 
 /// Within the coz signing family, a [`Czd`]'s byte length unambiguously
@@ -198,6 +335,12 @@ impl AtomDigest {
 /// refused rather than mislabeled. The conversion is sound for whatever
 /// `Czd` reaches it precisely because length is checked against the coz set
 /// on every call, not because the type is guaranteed coz-only.
+impl PartialEq for AtomDigest {
+    fn eq(&self, other: &Self) -> bool {
+        subtle::ConstantTimeEq::ct_eq(self, other).into()
+    }
+}
+
 impl TryFrom<Czd> for AtomDigest {
     type Error = DigestParseError;
 
@@ -276,6 +419,16 @@ pub enum DigestParseError {
     /// (32/48/64 — see `TryFrom<Czd> for AtomDigest`).
     #[error("byte length {0} is not a valid coz-family digest length (expected 32, 48, or 64)")]
     UnknownCzdLength(usize),
+    /// A multihash's varints are malformed or not minimal, or its length
+    /// does not match the bytes that follow.
+    #[error("malformed multihash")]
+    Multihash,
+    /// A multihash's code names no known hash algorithm.
+    #[error("unknown multihash code: {0:#x}")]
+    UnknownMulticodec(u64),
+    /// Multibase text has no prefix, or one naming a base not supported here.
+    #[error("unsupported multibase prefix: {0:?}")]
+    UnknownMultibase(Option<char>),
 }
 
 #[cfg(feature = "serde")]
@@ -302,6 +455,7 @@ impl<'de> Deserialize<'de> for AtomDigest {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::AtomId;
 
     const ALL: [HashAlg; 5] = [
         HashAlg::Sha256,
@@ -347,6 +501,66 @@ mod tests {
         );
     }
 
+    // c-digest-roundtrip, for the multiformats forms.
+    #[test]
+    fn multihash_and_multibase_round_trip_every_alg() {
+        for alg in ALL {
+            let d = digest(alg, 0xAB);
+            let multihash = d.to_multihash();
+            assert_eq!(
+                multihash[..2],
+                [alg.multicodec() as u8, alg.digest_len() as u8]
+            );
+            assert_eq!(AtomDigest::from_multihash(&multihash), Ok(d.clone()));
+            assert_eq!(AtomDigest::from_multibase(&d.to_multibase()), Ok(d.clone()));
+            let hex = format!("f{}", hex::encode(&multihash));
+            assert_eq!(AtomDigest::from_multibase(&hex), Ok(d));
+        }
+    }
+
+    // The well-known sha2-256 multihash of "hello world".
+    #[test]
+    fn multihash_matches_the_multiformats_vector() {
+        let hex = "1220b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9";
+        let d = AtomDigest::from_multihash(&hex::decode(hex).unwrap()).unwrap();
+        assert_eq!(d.alg(), HashAlg::Sha256);
+        assert_eq!(hex::encode(d.to_multihash()), hex);
+    }
+
+    #[test]
+    fn multihash_rejects_malformed_input() {
+        let sha1 = digest(HashAlg::Sha1, 1).to_multihash();
+        let cases: [(&[u8], DigestParseError); 6] = [
+            (&[], DigestParseError::Multihash),
+            (&[0x80, 0x00, 0x00], DigestParseError::Multihash),
+            (&sha1[..sha1.len() - 1], DigestParseError::Multihash),
+            (
+                &[sha1.as_slice(), &[0]].concat(),
+                DigestParseError::Multihash,
+            ),
+            (&[0x14, 0x00], DigestParseError::UnknownMulticodec(0x14)),
+            (
+                &[0x11, 0x01, 0x00],
+                DigestParseError::Length {
+                    alg: HashAlg::Sha1,
+                    expected: 20,
+                    got: 1,
+                },
+            ),
+        ];
+        for (bytes, err) in cases {
+            assert_eq!(AtomDigest::from_multihash(bytes), Err(err), "{bytes:02x?}");
+        }
+        assert_eq!(
+            AtomDigest::from_multibase("zQm"),
+            Err(DigestParseError::UnknownMultibase(Some('z'))),
+        );
+        assert_eq!(
+            AtomDigest::from_multibase(""),
+            Err(DigestParseError::UnknownMultibase(None)),
+        );
+    }
+
     #[test]
     fn parse_rejects_missing_separator() {
         assert_eq!(
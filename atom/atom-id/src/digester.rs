@@ -0,0 +1,175 @@
+//! Streaming byte digests.
+//!
+//! [`ContentDigester`] hashes a byte stream a chunk at a time under any
+//! [`HashAlg`], so a caller never holds the whole stream in memory. It is a
+//! plain hash over exactly the bytes it is fed — no object header, framing
+//! or tree walk — and so does not reproduce a publish's `dig`, which is the
+//! backend's object id of the atom snapshot (`atom-sourcing.md`). Only the
+//! `digester` feature builds it.
+
+use std::io;
+
+use sha1::Sha1;
+use sha2::{Digest as _, Sha256, Sha384, Sha512};
+
+use crate::HashAlg;
+
+/// Incremental hasher for one [`HashAlg`].
+///
+/// Feed bytes with [`update`](Self::update) (or through [`io::Write`],
+/// e.g. with [`io::copy`]) and take the digest with
+/// [`finalize`](Self::finalize). Hashing in chunks yields the same bytes as
+/// hashing the concatenation at once.
+#[derive(Clone)]
+pub struct ContentDigester {
+    state: State,
+}
+
+#[derive(Clone)]
+enum State {
+    Sha256(Sha256),
+    Sha384(Sha384),
+    Sha512(Sha512),
+    Sha1(Sha1),
+    Blake3(Box<blake3::Hasher>),
+}
+
+impl ContentDigester {
+    /// Start an empty digest under `alg`.
+    #[must_use]
+    pub fn new(alg: HashAlg) -> Self {
+        let state = match alg {
+            HashAlg::Sha256 => State::Sha256(Sha256::new()),
+            HashAlg::Sha384 => State::Sha384(Sha384::new()),
+            HashAlg::Sha512 => State::Sha512(Sha512::new()),
+            HashAlg::Sha1 => State::Sha1(Sha1::new()),
+            HashAlg::Blake3 => State::Blake3(Box::default()),
+        };
+        Self { state }
+    }
+
+    /// The algorithm this digester hashes with.
+    #[must_use]
+    pub fn alg(&self) -> HashAlg {
+        match self.state {
+            State::Sha256(_) => HashAlg::Sha256,
+            State::Sha384(_) => HashAlg::Sha384,
+            State::Sha512(_) => HashAlg::Sha512,
+            State::Sha1(_) => HashAlg::Sha1,
+            State::Blake3(_) => HashAlg::Blake3,
+        }
+    }
+
+    /// Hash the next chunk of content.
+    pub fn update(&mut self, chunk: &[u8]) -> &mut Self {
+        match &mut self.state {
+            State::Sha256(h) => h.update(chunk),
+            State::Sha384(h) => h.update(chunk),
+            State::Sha512(h) => h.update(chunk),
+            State::Sha1(h) => h.update(chunk),
+            State::Blake3(h) => {
+                h.update(chunk);
+            },
+        }
+        self
+    }
+
+    /// The digest of everything hashed so far —
+    /// [`HashAlg::digest_len`] bytes.
+    #[must_use]
+    pub fn finalize(self) -> Vec<u8> {
+        match self.state {
+            State::Sha256(h) => h.finalize().to_vec(),
+            State::Sha384(h) => h.finalize().to_vec(),
+            State::Sha512(h) => h.finalize().to_vec(),
+            State::Sha1(h) => h.finalize().to_vec(),
+            State::Blake3(h) => h.finalize().as_bytes().to_vec(),
+        }
+    }
+
+    /// Finish and compare against `expected` in constant time.
+    #[must_use]
+    pub fn matches(self, expected: &[u8]) -> bool {
+        crate::ct_eq(&self.finalize(), expected)
+    }
+}
+
+impl io::Write for ContentDigester {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl std::fmt::Debug for ContentDigester {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ContentDigester")
+            .field("alg", &self.alg())
+            .finish_non_exhaustive()
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL: [HashAlg; 5] = [
+        HashAlg::Sha256,
+        HashAlg::Sha384,
+        HashAlg::Sha512,
+        HashAlg::Sha1,
+        HashAlg::Blake3,
+    ];
+
+    #[test]
+    fn chunked_equals_one_shot_for_every_alg() {
+        let content: Vec<u8> = (0..10_000u32).map(|i| (i % 251) as u8).collect();
+        for alg in ALL {
+            let mut whole = ContentDigester::new(alg);
+            whole.update(&content);
+            let whole = whole.finalize();
+
+            let mut chunked = ContentDigester::new(alg);
+            for chunk in content.chunks(777) {
+                chunked.update(chunk);
+            }
+            assert_eq!(chunked.alg(), alg);
+            let chunked = chunked.finalize();
+
+            assert_eq!(whole, chunked, "{alg:?}");
+            assert_eq!(whole.len(), alg.digest_len(), "{alg:?}");
+        }
+    }
+
+    #[test]
+    fn known_vectors() {
+        let mut sha256 = ContentDigester::new(HashAlg::Sha256);
+        sha256.update(b"abc");
+        assert_eq!(
+            hex::encode(sha256.finalize()),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        let mut sha1 = ContentDigester::new(HashAlg::Sha1);
+        sha1.update(b"abc");
+        assert_eq!(
+            hex::encode(sha1.finalize()),
+            "a9993e364706816aba3e25717850c26c9cd0d89d"
+        );
+    }
+
+    #[test]
+    fn io_copy_feeds_the_digest() {
+        let content = b"streamed bytes".repeat(1000);
+        let mut digester = ContentDigester::new(HashAlg::Blake3);
+        io::copy(&mut content.as_slice(), &mut digester).unwrap();
+        assert!(digester.matches(blake3::hash(&content).as_bytes()));
+    }
+}
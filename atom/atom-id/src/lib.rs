@@ -38,13 +38,43 @@
 #![warn(rust_2018_idioms)]
 #![forbid(unsafe_code)]
 
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+#[cfg(feature = "serde")]
+mod batch;
+mod builder;
+#[cfg(feature = "serde")]
+mod canon;
+#[cfg(feature = "cbor")]
+mod cbor;
 mod charter;
+#[cfg(feature = "confusables")]
+mod confusable;
+mod ct;
+mod delegate;
+mod did;
 mod digest;
+#[cfg(feature = "digester")]
+mod digester;
 mod name;
+mod natural;
+mod negotiate;
+mod replay;
+mod revoke;
+mod rotate;
+#[cfg(feature = "schemars")]
+mod schema;
 #[cfg(feature = "serde")]
 mod serde_alg;
 #[cfg(feature = "serde")]
 mod serde_b64;
+#[cfg(feature = "serde")]
+mod sign;
+mod similarity;
+mod transfer;
+#[cfg(feature = "serde")]
+mod verified;
+mod yank;
 
 /// Serde bridge for `Option<Vec<u8>>` via base64url-unpadded encoding.
 ///
@@ -82,22 +112,68 @@ mod serde_b64_option {
 }
 
 use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::str::FromStr;
 
+#[cfg(feature = "serde")]
+pub use batch::{BatchItem, verify_batch};
+pub use builder::{ClaimBuilder, PublishBuilder, Unset};
+#[cfg(feature = "serde")]
+pub use canon::CanonicalPayload;
+#[cfg(feature = "cbor")]
+pub use cbor::{CborError, cbor_to_canonical_json, from_cbor, to_canonical_cbor};
 #[cfg(feature = "serde")]
 pub use charter::{
-    CharterLink, verify_bootstrap_gate, verify_charter, verify_charter_chain_signatures,
-    verify_charter_key_thumbprint, verify_succession_chain,
+    CharterAnchorScheme, CharterLink, verify_bootstrap_gate, verify_charter,
+    verify_charter_chain_signatures, verify_charter_key_thumbprint, verify_succession_chain,
 };
 pub use charter::{CharterPayload, CharterStore, TYP_CHARTER};
 pub use coz_rs::{Alg, Cad, Czd, Thumbprint, canonical, canonical_hash_for_alg};
+pub use ct::ct_eq;
+pub use delegate::{DelegatePayload, TYP_DELEGATE};
+#[cfg(feature = "serde")]
+pub use delegate::{
+    verify_delegate, verify_delegate_against_claim, verify_publish_authorized_delegated,
+};
+pub use did::{DidError, DidResolver, did_binds_key, validate_did};
 pub use digest::{AtomDigest, DigestParseError, HashAlg};
-pub use name::{Identifier, Label, Name, Tag};
+#[cfg(feature = "digester")]
+pub use digester::ContentDigester;
+pub use name::{
+    CharSpan, FoldedLabel, IDENTIFIER_UNICODE_VERSION, Identifier, Label, LabelPolicy, Name,
+    NormalizationReport, ReservedLabels, Tag, TagFilter, UNICODE_VERSION, normalization_report,
+};
+pub use natural::{NaturalOrderScheme, NaturalParseError, NaturalRequirement, NaturalVersion};
+pub use negotiate::AlgPreferences;
+#[cfg(feature = "serde")]
+pub use replay::verify_not_replayed;
+pub use replay::{MemoryReplayGuard, ReplayGuard};
+pub use revoke::{RevokePayload, RevokeReason, TYP_REVOKE};
+#[cfg(feature = "serde")]
+pub use revoke::{verify_revoke, verify_revoke_against_claim};
+#[cfg(feature = "serde")]
+pub use rotate::{
+    RotateLink, verify_publish_authorized_rotated, verify_rotate, verify_rotate_against_claim,
+    verify_rotate_chain,
+};
+pub use rotate::{RotatePayload, TYP_ROTATE};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 #[cfg(feature = "serde")]
 pub use serde_json;
+#[cfg(feature = "serde")]
+pub use sign::{SignError, SignedPayload, Signer};
+pub use similarity::label_similarity;
+pub use subtle::ConstantTimeEq;
 use thiserror::Error;
+pub use transfer::{TYP_TRANSFER, TransferPayload};
+#[cfg(feature = "serde")]
+pub use transfer::{TransferLink, verify_transfer, verify_transfer_chain};
+#[cfg(feature = "serde")]
+pub use verified::Verified;
+pub use yank::{TYP_YANK, YankPayload};
+#[cfg(feature = "serde")]
+pub use yank::{verify_yank, verify_yank_against_claim, verify_yank_against_publish};
 
 /// Maximum byte length for validated name types.
 pub const NAME_MAX: usize = 128;
@@ -112,6 +188,32 @@ pub const TYP_CLAIM: &str = "atom/claim";
 /// Spec constraint: `[publish-typ]`.
 pub const TYP_PUBLISH: &str = "atom/publish";
 
+/// Payload format version stamped on new claim and publish payloads.
+///
+/// Spec constraint: `[payload-version]`.
+pub const PV_CURRENT: u32 = 1;
+
+/// Payload format versions this crate verifies. A payload declaring a
+/// `pv` outside this range is rejected with
+/// [`VerifyError::UnsupportedPayloadVersion`] rather than parsed with
+/// fields this crate does not know.
+///
+/// Spec constraint: `[payload-version]`.
+pub const PV_SUPPORTED: std::ops::RangeInclusive<u32> = 1..=PV_CURRENT;
+
+/// The version of a payload with no `pv` on the wire — everything signed
+/// before the field existed. Version 1 is also never written, so those
+/// payloads' bytes and czds are unchanged.
+#[cfg(feature = "serde")]
+fn pv_absent() -> u32 {
+    1
+}
+
+#[cfg(feature = "serde")]
+fn pv_is_absent(pv: &u32) -> bool {
+    *pv == pv_absent()
+}
+
 /// Spec shorthand for [`Thumbprint`] (Coz key thumbprint).
 pub type Tmb = Thumbprint;
 
@@ -127,8 +229,9 @@ pub type Tmb = Thumbprint;
 /// (spec `[charter-anchor]`) — backend-agnostic, since the charter is a
 /// coz object regardless of backend.
 ///
-/// Displayed and serialized as a base64url-unpadded string.
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// Displayed and serialized as a base64url-unpadded string. Equality is
+/// constant-time ([`ConstantTimeEq`]).
+#[derive(Debug, Clone, Eq)]
 pub struct Anchor(Vec<u8>);
 
 impl Anchor {
@@ -149,6 +252,56 @@ impl Anchor {
         use coz_rs::base64ct::{Base64UrlUnpadded, Encoding};
         Base64UrlUnpadded::encode_string(&self.0)
     }
+
+    /// Decode from a base64url-unpadded string — the inverse of
+    /// [`to_b64`](Self::to_b64), and what [`FromStr`] accepts.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidAnchor`] if `s` is not base64url-unpadded.
+    pub fn from_b64(s: &str) -> Result<Self, Error> {
+        use coz_rs::base64ct::{Base64UrlUnpadded, Encoding};
+        Base64UrlUnpadded::decode_vec(s)
+            .map(Self)
+            .map_err(|_| Error::InvalidAnchor)
+    }
+
+    /// Parse an anchor, optionally also accepting hex.
+    ///
+    /// With `hex_fallback` unset this is exactly [`FromStr`]. With it set,
+    /// an even-length string of hex digits is read as hex and anything
+    /// else as base64url. The two forms never collide for 32-byte anchors
+    /// (64 hex vs 43 base64url characters), but a shorter base64url string
+    /// can consist solely of hex digits — hence opt-in, for inputs such as
+    /// CLI arguments where a user may paste a git-style hex id.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidAnchor`] if `s` decodes under neither
+    /// accepted encoding.
+    pub fn parse(s: &str, hex_fallback: bool) -> Result<Self, Error> {
+        if hex_fallback && s.len().is_multiple_of(2) && s.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Self::from_hex(s);
+        }
+        Self::from_b64(s)
+    }
+
+    /// Encode as a lowercase hex string, the form git tooling exchanges
+    /// object ids in.
+    #[must_use]
+    pub fn to_hex(&self) -> String {
+        hex::encode(&self.0)
+    }
+
+    /// Decode from a hex string (either case).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidAnchor`] if `s` is not an even-length hex
+    /// string.
+    pub fn from_hex(s: &str) -> Result<Self, Error> {
+        hex::decode(s).map(Self).map_err(|_| Error::InvalidAnchor)
+    }
 }
 
 impl fmt::Display for Anchor {
@@ -157,12 +310,62 @@ impl fmt::Display for Anchor {
     }
 }
 
+impl FromStr for Anchor {
+    type Err = Error;
+
+    /// Parse the base64url-unpadded form [`Display`](fmt::Display) emits.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_b64(s)
+    }
+}
+
 impl AsRef<[u8]> for Anchor {
     fn as_ref(&self) -> &[u8] {
         &self.0
     }
 }
 
+impl PartialEq for Anchor {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl Hash for Anchor {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
+}
+
+/// A rule for computing an [`Anchor`] from the material it commits to.
+///
+/// Stores that record [`id`](Self::id) next to each anchor can later
+/// recompute the anchor from its input and compare, instead of trusting
+/// the bytes as fully opaque. The protocol's own rule is
+/// [`CharterAnchorScheme`] (`Anchor == czd(charter₀)`); any other scheme
+/// names a store-local convention, not a protocol anchor.
+///
+/// Spec constraint: `[charter-anchor]`.
+pub trait AnchorScheme {
+    /// The material an anchor is computed from.
+    type Input<'a>;
+
+    /// Errors produced while computing an anchor.
+    type Error: std::error::Error;
+
+    /// Stable identifier for this scheme, suitable for persisting next to
+    /// the anchors it produced.
+    fn id(&self) -> &'static str;
+
+    /// Compute the anchor `input` commits to.
+    fn derive(&self, input: Self::Input<'_>) -> Result<Anchor, Self::Error>;
+
+    /// Whether `anchor` is the anchor `input` commits to.
+    fn verify(&self, input: Self::Input<'_>, anchor: &Anchor) -> Result<bool, Self::Error> {
+        Ok(self.derive(input)? == *anchor)
+    }
+}
+
 impl From<Vec<u8>> for Anchor {
     fn from(bytes: Vec<u8>) -> Self {
         Self(bytes)
@@ -192,6 +395,109 @@ impl<'de> Deserialize<'de> for Anchor {
     }
 }
 
+/// A 32-byte [`Anchor`], held inline.
+///
+/// Every anchor this protocol produces is a 32-byte czd, so store indexes
+/// can key on `Anchor32` without a heap allocation or a length check per
+/// entry. Converting to [`Anchor`] is infallible; converting back checks
+/// the length once, at the boundary.
+///
+/// Displayed as base64url-unpadded, identically to the equivalent
+/// [`Anchor`]. Equality is constant-time, as for [`Anchor`].
+#[derive(Debug, Clone, Copy, Eq, PartialOrd, Ord)]
+pub struct Anchor32([u8; 32]);
+
+impl Anchor32 {
+    /// Create an anchor from its 32 bytes.
+    #[must_use]
+    pub const fn new(bytes: [u8; 32]) -> Self {
+        Self(bytes)
+    }
+
+    /// The raw anchor bytes.
+    #[must_use]
+    pub const fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+
+    /// Widen to a heap-allocated [`Anchor`].
+    #[must_use]
+    pub fn to_anchor(&self) -> Anchor {
+        Anchor(self.0.to_vec())
+    }
+}
+
+impl fmt::Display for Anchor32 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use coz_rs::base64ct::{Base64UrlUnpadded, Encoding};
+        f.write_str(&Base64UrlUnpadded::encode_string(&self.0))
+    }
+}
+
+impl AsRef<[u8]> for Anchor32 {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl From<[u8; 32]> for Anchor32 {
+    fn from(bytes: [u8; 32]) -> Self {
+        Self(bytes)
+    }
+}
+
+impl From<Anchor32> for Anchor {
+    fn from(anchor: Anchor32) -> Self {
+        anchor.to_anchor()
+    }
+}
+
+impl TryFrom<&Anchor> for Anchor32 {
+    type Error = Error;
+
+    /// Fails with [`Error::InvalidAnchor`] unless `anchor` is exactly 32
+    /// bytes.
+    fn try_from(anchor: &Anchor) -> Result<Self, Self::Error> {
+        anchor
+            .as_bytes()
+            .try_into()
+            .map(Self)
+            .map_err(|_| Error::InvalidAnchor)
+    }
+}
+
+impl TryFrom<Anchor> for Anchor32 {
+    type Error = Error;
+
+    fn try_from(anchor: Anchor) -> Result<Self, Self::Error> {
+        Self::try_from(&anchor)
+    }
+}
+
+impl PartialEq for Anchor32 {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl Hash for Anchor32 {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
+}
+
+impl PartialEq<Anchor> for Anchor32 {
+    fn eq(&self, other: &Anchor) -> bool {
+        ct_eq(&self.0, &other.0)
+    }
+}
+
+impl PartialEq<Anchor32> for Anchor {
+    fn eq(&self, other: &Anchor32) -> bool {
+        other == self
+    }
+}
+
 // ============================================================================
 // AtomId
 // ============================================================================
@@ -211,7 +517,7 @@ impl<'de> Deserialize<'de> for Anchor {
 /// ```text
 /// dGVzdA::my-package
 /// ```
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct AtomId {
     anchor: Anchor,
     label: Label,
@@ -234,6 +540,177 @@ impl AtomId {
     pub fn label(&self) -> &Label {
         &self.label
     }
+
+    /// Borrow as an [`AtomIdRef`].
+    #[must_use]
+    pub fn as_id_ref(&self) -> AtomIdRef<'_> {
+        AtomIdRef::new(&self.anchor, &self.label)
+    }
+
+    /// Encode as compact binary, for use as a database key.
+    ///
+    /// Layout: the anchor's length as a 4-byte big-endian integer, the
+    /// anchor bytes, then the label's UTF-8 bytes to the end. The encoding
+    /// is injective, and ids sharing an anchor share a key prefix, so an
+    /// ordered store can range-scan one atom-set.
+    #[must_use]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let anchor = self.anchor.as_bytes();
+        let len = u32::try_from(anchor.len()).expect("anchor length fits in u32");
+        let mut out = Vec::with_capacity(4 + anchor.len() + self.label.len());
+        out.extend_from_slice(&len.to_be_bytes());
+        out.extend_from_slice(anchor);
+        out.extend_from_slice(self.label.as_bytes());
+        out
+    }
+
+    /// Decode the layout [`to_bytes`](Self::to_bytes) produces.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidFormat`] if `bytes` is shorter than its
+    /// length prefix claims, or [`Error::InvalidLabel`] if the remainder is
+    /// not a valid [`Label`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let (len, rest) = bytes.split_first_chunk::<4>().ok_or(Error::InvalidFormat)?;
+        let len = usize::try_from(u32::from_be_bytes(*len)).map_err(|_| Error::InvalidFormat)?;
+        if rest.len() < len {
+            return Err(Error::InvalidFormat);
+        }
+        let (anchor, label) = rest.split_at(len);
+        let label = std::str::from_utf8(label).map_err(|_| Error::InvalidLabel)?;
+        let label = Label::try_from(label).map_err(|_| Error::InvalidLabel)?;
+        Ok(Self::new(Anchor::new(anchor.to_vec()), label))
+    }
+}
+
+/// An [`AtomId`] borrowed as its parts — the anchor and label a lookup
+/// already holds, without cloning them into an owned `AtomId`.
+///
+/// Equality and hashing agree with `AtomId`'s, so an index keyed by
+/// `AtomId` can be probed with one through [`AtomIdKey`]:
+///
+/// ```
+/// # use std::collections::HashMap;
+/// # use atom_id::{Anchor, AtomId, AtomIdKey, AtomIdRef, Label};
+/// let anchor = Anchor::new(vec![1, 2, 3]);
+/// let label = Label::try_from("pkg").unwrap();
+/// let mut index = HashMap::new();
+/// index.insert(AtomId::new(anchor.clone(), label.clone()), 1);
+///
+/// let probe = AtomIdRef::new(&anchor, &label);
+/// assert_eq!(index.get(&probe as &dyn AtomIdKey), Some(&1));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AtomIdRef<'a> {
+    anchor: &'a Anchor,
+    label: &'a Label,
+}
+
+impl<'a> AtomIdRef<'a> {
+    /// Borrow an anchor and label as one identity.
+    #[must_use]
+    pub fn new(anchor: &'a Anchor, label: &'a Label) -> Self {
+        Self { anchor, label }
+    }
+
+    /// The anchor establishing atom-set identity.
+    #[must_use]
+    pub fn anchor(&self) -> &'a Anchor {
+        self.anchor
+    }
+
+    /// The atom's label within its atom-set.
+    #[must_use]
+    pub fn label(&self) -> &'a Label {
+        self.label
+    }
+
+    /// Clone into an owned [`AtomId`].
+    #[must_use]
+    pub fn to_atom_id(&self) -> AtomId {
+        AtomId::new(self.anchor.clone(), self.label.clone())
+    }
+}
+
+impl<'a> From<&'a AtomId> for AtomIdRef<'a> {
+    fn from(id: &'a AtomId) -> Self {
+        id.as_id_ref()
+    }
+}
+
+impl PartialEq<AtomId> for AtomIdRef<'_> {
+    fn eq(&self, other: &AtomId) -> bool {
+        *self == other.as_id_ref()
+    }
+}
+
+impl PartialEq<AtomIdRef<'_>> for AtomId {
+    fn eq(&self, other: &AtomIdRef<'_>) -> bool {
+        self.as_id_ref() == *other
+    }
+}
+
+impl fmt::Display for AtomIdRef<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}::{}", self.anchor.to_b64(), self.label)
+    }
+}
+
+/// An identity's parts, for looking up `AtomId`-keyed maps and sets by
+/// either an [`AtomId`] or an [`AtomIdRef`].
+///
+/// `AtomId` implements `Borrow<dyn AtomIdKey>`, and `dyn AtomIdKey`'s
+/// `Hash` and `Eq` are `AtomId`'s, as `Borrow` requires.
+pub trait AtomIdKey {
+    /// The identity, borrowed.
+    fn id_ref(&self) -> AtomIdRef<'_>;
+}
+
+impl AtomIdKey for AtomId {
+    fn id_ref(&self) -> AtomIdRef<'_> {
+        self.as_id_ref()
+    }
+}
+
+impl AtomIdKey for AtomIdRef<'_> {
+    fn id_ref(&self) -> AtomIdRef<'_> {
+        *self
+    }
+}
+
+impl<'a> std::borrow::Borrow<dyn AtomIdKey + 'a> for AtomId {
+    fn borrow(&self) -> &(dyn AtomIdKey + 'a) {
+        self
+    }
+}
+
+impl PartialEq for dyn AtomIdKey + '_ {
+    fn eq(&self, other: &Self) -> bool {
+        self.id_ref() == other.id_ref()
+    }
+}
+
+impl Eq for dyn AtomIdKey + '_ {}
+
+impl std::hash::Hash for dyn AtomIdKey + '_ {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.id_ref().hash(state);
+    }
+}
+
+impl std::hash::Hash for AtomIdRef<'_> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.anchor.hash(state);
+        self.label.hash(state);
+    }
+}
+
+/// Hashes exactly as [`AtomIdRef`] does, so the two can share an index.
+impl std::hash::Hash for AtomId {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.as_id_ref().hash(state);
+    }
 }
 
 /// Display as `<anchor_b64ut>::<label>` (double-colon delimited).
@@ -250,16 +727,10 @@ impl FromStr for AtomId {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let (anchor_str, label_str) = s.split_once("::").ok_or(Error::InvalidFormat)?;
 
-        use coz_rs::base64ct::{Base64UrlUnpadded, Encoding};
-        let anchor_bytes =
-            Base64UrlUnpadded::decode_vec(anchor_str).map_err(|_| Error::InvalidAnchor)?;
-
+        let anchor = Anchor::from_b64(anchor_str)?;
         let label = Label::try_from(label_str).map_err(|_| Error::InvalidLabel)?;
 
-        Ok(Self {
-            anchor: Anchor::new(anchor_bytes),
-            label,
-        })
+        Ok(Self { anchor, label })
     }
 }
 
@@ -298,7 +769,8 @@ impl<'de> Deserialize<'de> for AtomId {
 /// `Hierarchical` and `RootedIdentity` are named and reserved — not yet
 /// implemented. A consumer encountering either MUST reject cleanly (treat
 /// the `OwnerRef` as unauthorizable) rather than attempt to interpret
-/// `value`.
+/// `value`. `Did` is evaluated only through a [`DidResolver`]
+/// ([`OwnerRef::authorizes_with`]).
 ///
 /// Spec constraint: `[owner-kind-required]`.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -314,6 +786,9 @@ pub enum OwnerKind {
     /// A Cyphr Principal Root identity. `value` = PR digest. Named and
     /// reserved — not yet implemented.
     RootedIdentity,
+    /// A W3C Decentralized Identifier. `value` = the DID's UTF-8 bytes.
+    /// Key binding is resolved by a [`DidResolver`].
+    Did,
 }
 
 /// One kind-tagged, opaque identity digest — the protocol's unit of
@@ -359,15 +834,148 @@ impl OwnerRef {
     /// always returns `false` for them rather than attempting to interpret
     /// `value` — a clean rejection, not an error, matching
     /// `[owner-kind-required]`'s "treat as unauthorizable" directive.
+    ///
+    /// `Did` needs a resolver to evaluate and so is also `false` here; use
+    /// [`authorizes_with`](Self::authorizes_with).
     #[must_use]
     pub fn authorizes(&self, tmb: &Thumbprint) -> bool {
         match self.kind {
-            OwnerKind::SingleKey => self.value == tmb.as_bytes(),
-            OwnerKind::Hierarchical | OwnerKind::RootedIdentity => false,
+            OwnerKind::SingleKey => ct_eq(&self.value, tmb.as_bytes()),
+            OwnerKind::Hierarchical | OwnerKind::RootedIdentity | OwnerKind::Did => false,
+        }
+    }
+
+    /// [`authorizes`](Self::authorizes), additionally evaluating `did`
+    /// owner-references: authorized iff `resolver` reports `tmb` bound to
+    /// the DID.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`DidError`] if a `did` value is not a valid DID or
+    /// resolution fails. Other kinds never error.
+    ///
+    /// Spec constraint: `[owner-did]`.
+    pub fn authorizes_with<R: DidResolver + ?Sized>(
+        &self,
+        tmb: &Thumbprint,
+        resolver: &R,
+    ) -> Result<bool, DidError> {
+        match self.kind {
+            OwnerKind::Did => {
+                let did = std::str::from_utf8(&self.value).map_err(|_| DidError::InvalidUnicode)?;
+                did_binds_key(resolver, did, tmb)
+            },
+            _ => Ok(self.authorizes(tmb)),
+        }
+    }
+}
+
+impl fmt::Display for OwnerKind {
+    /// The kind's wire name (`single-key`, `hierarchical`,
+    /// `rooted-identity`, `did`).
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::SingleKey => "single-key",
+            Self::Hierarchical => "hierarchical",
+            Self::RootedIdentity => "rooted-identity",
+            Self::Did => "did",
+        })
+    }
+}
+
+impl fmt::Display for OwnerRef {
+    /// `{kind}:{value}`, with `value` base64url-unpadded — e.g.
+    /// `single-key:AQID`. The one textual form of an owner; [`Owner`]
+    /// displays through it.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use coz_rs::base64ct::{Base64UrlUnpadded, Encoding};
+        write!(
+            f,
+            "{}:{}",
+            self.kind,
+            Base64UrlUnpadded::encode_string(&self.value)
+        )
+    }
+}
+
+/// A typed view of an [`OwnerRef`]: what its `value` bytes mean, decided
+/// once by `kind` instead of at every use site.
+///
+/// The wire form is unchanged — an `Owner` serializes as, and converts
+/// losslessly to and from, the kind-tagged `OwnerRef` payloads carry.
+///
+/// Spec constraints: `[owner-abstract]`, `[owner-kind-required]`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(from = "OwnerRef", into = "OwnerRef"))]
+pub enum Owner {
+    /// A Coz key thumbprint (`single-key`).
+    CozThumbprint(Thumbprint),
+    /// A Cyphr Principal Root digest (`rooted-identity`). Reserved: never
+    /// authorizes today.
+    CyphrPr(Vec<u8>),
+    /// A syntactically valid W3C DID (`did`).
+    Did(String),
+    /// A kind this crate carries but does not interpret — `hierarchical`,
+    /// or a `did` whose value is not a valid DID — with its value bytes
+    /// untouched.
+    Opaque(OwnerKind, Vec<u8>),
+}
+
+impl Owner {
+    /// The owner-reference kind this owner is carried as.
+    #[must_use]
+    pub fn kind(&self) -> OwnerKind {
+        match self {
+            Self::CozThumbprint(_) => OwnerKind::SingleKey,
+            Self::CyphrPr(_) => OwnerKind::RootedIdentity,
+            Self::Did(_) => OwnerKind::Did,
+            Self::Opaque(kind, _) => *kind,
+        }
+    }
+
+    /// The Coz thumbprint, if this is a single-key owner.
+    #[must_use]
+    pub fn thumbprint(&self) -> Option<&Thumbprint> {
+        match self {
+            Self::CozThumbprint(tmb) => Some(tmb),
+            _ => None,
+        }
+    }
+}
+
+impl From<OwnerRef> for Owner {
+    fn from(owner: OwnerRef) -> Self {
+        match owner.kind {
+            OwnerKind::SingleKey => Self::CozThumbprint(Thumbprint::from_bytes(owner.value)),
+            OwnerKind::RootedIdentity => Self::CyphrPr(owner.value),
+            OwnerKind::Did => match String::from_utf8(owner.value) {
+                Ok(did) if validate_did(&did).is_ok() => Self::Did(did),
+                Ok(did) => Self::Opaque(owner.kind, did.into_bytes()),
+                Err(e) => Self::Opaque(owner.kind, e.into_bytes()),
+            },
+            OwnerKind::Hierarchical => Self::Opaque(owner.kind, owner.value),
         }
     }
 }
 
+impl From<Owner> for OwnerRef {
+    fn from(owner: Owner) -> Self {
+        match owner {
+            Owner::CozThumbprint(tmb) => Self::single_key(&tmb),
+            Owner::CyphrPr(value) => Self::new(OwnerKind::RootedIdentity, value),
+            Owner::Did(did) => Self::new(OwnerKind::Did, did.into_bytes()),
+            Owner::Opaque(kind, value) => Self::new(kind, value),
+        }
+    }
+}
+
+impl fmt::Display for Owner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        OwnerRef::from(self.clone()).fmt(f)
+    }
+}
+
 /// Whether a signing key with thumbprint `tmb` is authorized by ANY entry
 /// in an owner set, evaluated under each entry's own `kind`.
 ///
@@ -444,6 +1052,73 @@ mod owner_tests {
         assert!(!owner_set_authorizes(&owners, &tmb(9)));
     }
 
+    #[test]
+    fn owner_view_round_trips_every_kind() {
+        for kind in [
+            OwnerKind::SingleKey,
+            OwnerKind::Hierarchical,
+            OwnerKind::RootedIdentity,
+            OwnerKind::Did,
+        ] {
+            let owner_ref = OwnerRef::new(kind, vec![1, 2, 3]);
+            let owner = Owner::from(owner_ref.clone());
+            assert_eq!(owner.kind(), kind);
+            assert_eq!(owner.to_string(), owner_ref.to_string());
+            assert_eq!(OwnerRef::from(owner), owner_ref);
+        }
+        let owner = Owner::from(OwnerRef::single_key(&tmb(7)));
+        assert_eq!(owner, Owner::CozThumbprint(tmb(7)));
+        assert_eq!(owner.thumbprint(), Some(&tmb(7)));
+        assert_eq!(
+            OwnerRef::single_key(&tmb(1)).to_string(),
+            "single-key:AQEBAQ"
+        );
+    }
+
+    #[test]
+    fn did_owner_authorizes_only_through_resolver() {
+        struct Bound(Thumbprint);
+        impl DidResolver for Bound {
+            fn bound_keys(&self, _did: &str) -> Result<Vec<Thumbprint>, DidError> {
+                Ok(vec![self.0.clone()])
+            }
+        }
+
+        let did = OwnerRef::from(Owner::Did("did:example:alice".into()));
+        assert_eq!(did.kind, OwnerKind::Did);
+        assert_eq!(
+            Owner::from(did.clone()),
+            Owner::Did("did:example:alice".into())
+        );
+        assert!(!did.authorizes(&tmb(1)), "no resolver, no authority");
+        assert_eq!(did.authorizes_with(&tmb(1), &Bound(tmb(1))), Ok(true));
+        assert_eq!(did.authorizes_with(&tmb(2), &Bound(tmb(1))), Ok(false));
+
+        let bad = OwnerRef::new(OwnerKind::Did, b"did:Bad".to_vec());
+        assert!(matches!(
+            Owner::from(bad.clone()),
+            Owner::Opaque(OwnerKind::Did, _)
+        ));
+        assert!(bad.authorizes_with(&tmb(1), &Bound(tmb(1))).is_err());
+
+        let single = OwnerRef::single_key(&tmb(1));
+        assert_eq!(single.authorizes_with(&tmb(1), &Bound(tmb(2))), Ok(true));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn owner_serializes_as_owner_ref() {
+        let owner_ref = OwnerRef::new(OwnerKind::RootedIdentity, vec![1, 2, 3]);
+        let owner = Owner::CyphrPr(vec![1, 2, 3]);
+        assert_eq!(
+            serde_json::to_value(&owner).unwrap(),
+            serde_json::to_value(&owner_ref).unwrap()
+        );
+        let back: Owner =
+            serde_json::from_value(serde_json::to_value(&owner_ref).unwrap()).unwrap();
+        assert_eq!(back, owner);
+    }
+
     #[test]
     fn owner_set_authorizes_rejects_empty_set() {
         assert!(!owner_set_authorizes(&[], &tmb(1)));
@@ -543,6 +1218,33 @@ pub struct ClaimPayload {
     pub tmb: Thumbprint,
     /// Transaction type — always [`TYP_CLAIM`].
     pub typ: String,
+    /// Optional expiry (seconds since Unix epoch). From this instant on
+    /// the claim admits no new publish, so a registry can hand out claims
+    /// that lapse if never used; versions published before it stay valid.
+    /// `None` — omitted from the wire — never expires.
+    ///
+    /// Spec constraint: `[claim-expiry]`.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub exp: Option<u64>,
+    /// Optional publisher-chosen nonce. Distinguishes otherwise identical
+    /// payloads — and therefore their czds — for [`ReplayGuard`]. `None`
+    /// is omitted from the wire.
+    ///
+    /// Spec constraint: `[replay-guard]`.
+    #[cfg_attr(feature = "serde", serde(with = "serde_b64_option"))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub nonce: Option<Vec<u8>>,
+    /// Payload format version. Absent on the wire means 1, and version 1
+    /// is never written, so pre-versioning payloads verify unchanged.
+    ///
+    /// Spec constraint: `[payload-version]`.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default = "pv_absent", skip_serializing_if = "pv_is_absent")
+    )]
+    pub pv: u32,
     /// Ecosystem-specific extensions, nested here per
     /// `[claim-payload-extensible]` (root JSON keys are otherwise
     /// reserved for protocol fields). `None` when no extensions are
@@ -556,8 +1258,9 @@ impl ClaimPayload {
     /// Construct a new (non-replacement) claim payload.
     ///
     /// Takes an [`AtomId`] to ensure that the anchor and label come from
-    /// a validated identity pair. Sets `typ` to [`TYP_CLAIM`], `prior` to
-    /// `None`, and `governance` to `false` automatically. Use
+    /// a validated identity pair. Sets `typ` to [`TYP_CLAIM`], `pv` to
+    /// [`PV_CURRENT`], `prior` to `None`, and `governance` to `false`
+    /// automatically. Use
     /// [`ClaimPayload::new_replacement`] to construct a replacement claim.
     pub fn new(
         alg: Alg,
@@ -580,6 +1283,9 @@ impl ClaimPayload {
             src,
             tmb,
             typ: TYP_CLAIM.to_owned(),
+            exp: None,
+            nonce: None,
+            pv: PV_CURRENT,
             #[cfg(feature = "serde")]
             meta: None,
         }
@@ -619,10 +1325,22 @@ impl ClaimPayload {
             src,
             tmb,
             typ: TYP_CLAIM.to_owned(),
+            exp: None,
+            nonce: None,
+            pv: PV_CURRENT,
             #[cfg(feature = "serde")]
             meta: None,
         }
     }
+
+    /// Whether the claim has expired at `clock` (seconds since Unix
+    /// epoch): `exp` is set and `clock` has reached it.
+    ///
+    /// Spec constraint: `[claim-expiry]`.
+    #[must_use]
+    pub fn is_expired_at(&self, clock: u64) -> bool {
+        self.exp.is_some_and(|exp| clock >= exp)
+    }
 }
 
 // ============================================================================
@@ -701,6 +1419,24 @@ pub struct PublishPayload {
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     #[cfg_attr(feature = "serde", serde(default))]
     pub content_hash: Option<Vec<u8>>,
+    /// Optional publisher-chosen nonce. Distinguishes otherwise identical
+    /// payloads — and therefore their czds — for [`ReplayGuard`]. `None`
+    /// is omitted from the wire.
+    ///
+    /// Spec constraint: `[replay-guard]`.
+    #[cfg_attr(feature = "serde", serde(with = "serde_b64_option"))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub nonce: Option<Vec<u8>>,
+    /// Payload format version. Absent on the wire means 1, and version 1
+    /// is never written, so pre-versioning payloads verify unchanged.
+    ///
+    /// Spec constraint: `[payload-version]`.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default = "pv_absent", skip_serializing_if = "pv_is_absent")
+    )]
+    pub pv: u32,
     /// Ecosystem-specific extensions, nested here per
     /// `[publish-payload-extensible]` (root JSON keys are otherwise
     /// reserved for protocol fields). `None` when no extensions are
@@ -714,12 +1450,13 @@ impl PublishPayload {
     /// Construct a new publish payload.
     ///
     /// Takes an [`AtomId`] to ensure that the anchor and label come from
-    /// a validated identity pair. Sets `typ` to [`TYP_PUBLISH`]
-    /// automatically, and leaves `mode`, `content_hash`, and `meta` unset
+    /// a validated identity pair. Sets `typ` to [`TYP_PUBLISH`] and `pv` to
+    /// [`PV_CURRENT`] automatically, and leaves `mode`, `content_hash`, and `meta` unset
     /// — use [`PublishPayload::effective_mode`] to read the resolved
     /// mode, and set `meta`/`mode`/`content_hash` directly on the
     /// returned value if needed (before signing, for `content_hash` —
-    /// `[content-hash-is-tree-digest]`).
+    /// `[content-hash-is-tree-digest]`). [`PublishBuilder`] names each
+    /// field instead of relying on argument position.
     #[allow(clippy::too_many_arguments)]
     pub fn new(
         alg: Alg,
@@ -746,6 +1483,8 @@ impl PublishPayload {
             typ: TYP_PUBLISH.to_owned(),
             mode: None,
             content_hash: None,
+            nonce: None,
+            pv: PV_CURRENT,
             #[cfg(feature = "serde")]
             meta: None,
         }
@@ -803,6 +1542,51 @@ impl FromStr for RawVersion {
     }
 }
 
+// ============================================================================
+// RawRequirement
+// ============================================================================
+
+/// An unparsed version requirement string (e.g., `>=1.0, <2.0`).
+///
+/// The requirement-side counterpart of [`RawVersion`], and opaque for the
+/// same reason: no `Deref`, `AsRef<str>`, or `Into<String>`, only
+/// [`as_str()`](RawRequirement::as_str). A constraint read from a manifest
+/// or a URI stays a `RawRequirement` until a [`VersionScheme`] parses it
+/// with [`parse_requirement`](VersionScheme::parse_requirement).
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RawRequirement(String);
+
+impl RawRequirement {
+    /// Wrap a string as an unparsed requirement.
+    ///
+    /// No validation is performed — any string is a valid raw requirement.
+    /// Interpretation is deferred to a [`VersionScheme`] implementor.
+    pub fn new(s: String) -> Self {
+        Self(s)
+    }
+
+    /// The raw requirement string.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for RawRequirement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl FromStr for RawRequirement {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self(s.to_owned()))
+    }
+}
+
 // ============================================================================
 // VersionScheme
 // ============================================================================
@@ -810,9 +1594,12 @@ impl FromStr for RawVersion {
 /// Abstract version comparison scheme.
 ///
 /// Concrete version formats (semver, calver, etc.) implement this trait
-/// to provide parsing and comparison for [`RawVersion`] strings. The
-/// `atom-id` crate defines no concrete schemes — those live in
-/// ecosystem-specific crates (e.g., `ion-manifest` for semver).
+/// to provide parsing and comparison for [`RawVersion`] and
+/// [`RawRequirement`] strings. The
+/// `atom-id` crate defines no ecosystem schemes — those live in
+/// ecosystem-specific crates (e.g., `ion-manifest` for semver). The one
+/// exception is [`NaturalOrderScheme`], an ecosystem-agnostic fallback for
+/// stores that must pick a newest version when no formal scheme exists.
 pub trait VersionScheme {
     /// A parsed, comparable version value.
     type Version: fmt::Display + Ord;
@@ -826,8 +1613,8 @@ pub trait VersionScheme {
     /// Parse a raw version string into a structured version.
     fn parse_version(&self, raw: &RawVersion) -> Result<Self::Version, Self::Error>;
 
-    /// Parse a version requirement string.
-    fn parse_requirement(&self, raw: &str) -> Result<Self::Requirement, Self::Error>;
+    /// Parse a raw requirement string into a structured requirement.
+    fn parse_requirement(&self, raw: &RawRequirement) -> Result<Self::Requirement, Self::Error>;
 
     /// Check whether a version satisfies a requirement.
     fn matches(&self, version: &Self::Version, req: &Self::Requirement) -> bool;
@@ -844,11 +1631,21 @@ pub enum Error {
     #[error("cannot be empty")]
     Empty,
     /// The name contains invalid characters.
-    #[error("contains invalid characters: '{0}'")]
-    InvalidCharacters(String),
+    #[error("contains invalid characters: '{found}'")]
+    InvalidCharacters {
+        /// The offending characters, in order.
+        found: String,
+        /// Where each offending character (or sequence) sits.
+        spans: Vec<CharSpan>,
+    },
     /// The name starts with an invalid character.
-    #[error("cannot start with: '{0}'")]
-    InvalidStart(char),
+    #[error("cannot start with: '{found}'")]
+    InvalidStart {
+        /// The offending first character.
+        found: char,
+        /// Where it sits.
+        span: CharSpan,
+    },
     /// The name contains invalid Unicode.
     #[error("must be valid unicode")]
     InvalidUnicode,
@@ -864,6 +1661,13 @@ pub enum Error {
     /// Invalid label in atom ID.
     #[error("invalid label in atom ID")]
     InvalidLabel,
+    /// A [`LabelPolicy`] refused the label.
+    #[error("label '{0}' is reserved")]
+    Reserved(String),
+    /// The label mixes scripts (UTS #39), rejected by
+    /// `Label::validate_strict` (`confusables` feature).
+    #[error("mixes characters from more than one script")]
+    MixedScript,
     /// A charter's owner set would contain zero entries.
     ///
     /// Spec constraint: `[charter-owner-set-non-empty]`.
@@ -939,11 +1743,12 @@ pub enum VerifyError {
     /// Spec constraint: `[claim-key-required]`.
     #[error("thumbprint mismatch: declared tmb does not match the signing key")]
     ThumbprintMismatch,
-    /// A publish's `claim` field does not match the referenced claim's
-    /// actual czd.
+    /// A publish's (or revocation's, or yank's) `claim` field does not
+    /// match the referenced claim's actual czd.
     ///
-    /// Spec constraint: `[publish-chains-claim]`.
-    #[error("claim chain mismatch: publish.claim does not match the claim's czd")]
+    /// Spec constraints: `[publish-chains-claim]`, `[revoke-authority]`,
+    /// `[yank-authority]`.
+    #[error("claim chain mismatch: payload's claim does not match the claim's czd")]
     ClaimChainMismatch,
     /// A payload's `(anchor, label)` does not match the expected `AtomId`.
     ///
@@ -980,30 +1785,227 @@ pub enum VerifyError {
          claim's"
     )]
     ReplacementIdentityChanged,
+    /// A revocation's `now` does not strictly exceed the revoked claim's
+    /// `now`.
+    ///
+    /// Spec constraint: `[revoke-authority]`.
+    #[error("revocation not after claim: revoke.now does not exceed the revoked claim's now")]
+    RevokeNotAfterClaim,
+    /// A yank's `version` differs from the publish it is checked against.
+    ///
+    /// Spec constraint: `[yank-authority]`.
+    #[error("yank version mismatch: yank.version does not match the publish's version")]
+    YankVersionMismatch,
+    /// A yank's `now` does not strictly exceed the yanked publish's `now`.
+    ///
+    /// Spec constraint: `[yank-authority]`.
+    #[error("yank not after publish: yank.now does not exceed the publish's now")]
+    YankNotAfterPublish,
+    /// A transfer's `prior` does not match the czd of the preceding link
+    /// (the claim, or the previous transfer).
+    ///
+    /// Spec constraint: `[transfer-chain]`.
+    #[error("transfer chain mismatch: transfer.prior does not match the preceding link's czd")]
+    TransferChainMismatch,
+    /// A transfer's `now` does not strictly exceed the preceding link's
+    /// `now`.
+    ///
+    /// Spec constraint: `[transfer-chain]`.
+    #[error("transfer not after prior: transfer.now does not exceed the preceding link's now")]
+    TransferNotAfterPrior,
+    /// A rotation's `now` does not strictly exceed the rotated claim's
+    /// `now`.
+    ///
+    /// Spec constraint: `[rotate-authority]`.
+    #[error("rotation not after claim: rotate.now does not exceed the rotated claim's now")]
+    RotateNotAfterClaim,
+    /// A rotation's `now` does not strictly exceed the preceding
+    /// rotation's `now`.
+    ///
+    /// Spec constraint: `[rotate-chain]`.
+    #[error("rotation not after prior: rotate.now does not exceed the preceding rotation's now")]
+    RotateNotAfterPrior,
+    /// A publish was signed by a rotated-out key after its grace window
+    /// closed.
+    ///
+    /// Spec constraint: `[rotate-grace]`.
+    #[error("rotation grace expired: publish signed by the retired key after the grace window")]
+    RotationGraceExpired,
+    /// A delegation's `now` does not strictly exceed the delegated
+    /// claim's `now`.
+    ///
+    /// Spec constraint: `[delegate-authority]`.
+    #[error("delegation not after claim: delegate.now does not exceed the claim's now")]
+    DelegateNotAfterClaim,
+    /// A delegation's window is empty, or a publish by the delegate key is
+    /// dated at or after the window's `exp`.
+    ///
+    /// Spec constraints: `[delegate-authority]`, `[delegate-scope]`.
+    #[error("delegation expired at {exp}")]
+    DelegationExpired {
+        /// The delegation's expiry timestamp.
+        exp: u64,
+    },
+    /// A payload's `now` lies outside the verifier's [`TimePolicy`].
+    ///
+    /// Spec constraint: `[time-policy]`.
+    #[error("timestamp out of range: now = {now}")]
+    TimestampOutOfRange {
+        /// The offending payload timestamp.
+        now: u64,
+    },
+    /// The payload's czd has already been accepted by the [`ReplayGuard`].
+    ///
+    /// Spec constraint: `[replay-guard]`.
+    #[error("replayed payload: czd already accepted")]
+    Replayed,
+    /// A publish is dated at or after its claim's `exp`.
+    ///
+    /// Spec constraint: `[claim-expiry]`.
+    #[error("claim expired at {exp}")]
+    Expired {
+        /// The claim's expiry timestamp.
+        exp: u64,
+    },
+    /// The payload declares a format version outside [`PV_SUPPORTED`] —
+    /// typically one written by a newer implementation.
+    ///
+    /// Spec constraint: `[payload-version]`.
+    #[error("unsupported payload version {pv}: supported {min}..={max}")]
+    UnsupportedPayloadVersion {
+        /// The declared `pv`.
+        pv: u32,
+        /// The lowest supported version.
+        min: u32,
+        /// The highest supported version.
+        max: u32,
+    },
+}
+
+// ============================================================================
+// TimePolicy
+// ============================================================================
+
+/// Acceptable range for a payload's `now`, checked during verification.
+///
+/// The protocol itself only orders timestamps relative to each other
+/// (strictly increasing along a chain); it never compares them with a
+/// clock. A consumer that wants to reject absurd values — a claim dated
+/// years in the future, a publish predating the registry — passes a
+/// policy to [`verify_claim`] or [`verify_publish`]. Every bound is
+/// optional; [`TimePolicy::default`] accepts everything.
+///
+/// Spec constraint: `[time-policy]`.
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TimePolicy {
+    /// Maximum seconds `now` may lie ahead of the verifier's clock.
+    /// Timestamps in the past are never skewed — payloads are historical
+    /// records — so this bounds the future direction only.
+    pub max_skew: Option<u64>,
+    /// Earliest acceptable `now` (inclusive).
+    pub not_before: Option<u64>,
+    /// Latest acceptable `now` (inclusive).
+    pub not_after: Option<u64>,
+}
+
+#[cfg(feature = "serde")]
+impl TimePolicy {
+    /// Check `now` against this policy, reading the verifier's clock from
+    /// the system time.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VerifyError::TimestampOutOfRange`] if `now` violates any
+    /// bound.
+    pub fn check(&self, now: u64) -> Result<(), VerifyError> {
+        self.check_at(now, system_clock())
+    }
+
+    /// Check `now` against this policy with an explicit verifier clock
+    /// (seconds since Unix epoch).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VerifyError::TimestampOutOfRange`] if `now` violates any
+    /// bound.
+    pub fn check_at(&self, now: u64, clock: u64) -> Result<(), VerifyError> {
+        let too_early = self.not_before.is_some_and(|min| now < min);
+        let too_late = self.not_after.is_some_and(|max| now > max);
+        let skewed = self
+            .max_skew
+            .is_some_and(|skew| now > clock.saturating_add(skew));
+        if too_early || too_late || skewed {
+            return Err(VerifyError::TimestampOutOfRange { now });
+        }
+        Ok(())
+    }
 }
 
 // ============================================================================
 // Verification functions
 // ============================================================================
 
+/// The verifier's clock: seconds since Unix epoch, from the system time.
+#[cfg(feature = "serde")]
+fn system_clock() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+/// Read a payload's declared `pv` and reject it unless it is in
+/// [`PV_SUPPORTED`].
+///
+/// Runs before the full parse, so a payload from a newer format version
+/// fails with a typed error instead of a parse error — or, worse, a
+/// successful parse that silently drops fields this crate does not know.
+///
+/// Spec constraint: `[payload-version]`.
+#[cfg(feature = "serde")]
+fn check_payload_version(pay_json: &[u8]) -> Result<(), VerifyError> {
+    #[derive(Deserialize)]
+    struct Probe {
+        #[serde(default = "pv_absent")]
+        pv: u32,
+    }
+
+    let Probe { pv } = serde_json::from_slice(pay_json)?;
+    if !PV_SUPPORTED.contains(&pv) {
+        return Err(VerifyError::UnsupportedPayloadVersion {
+            pv,
+            min: *PV_SUPPORTED.start(),
+            max: *PV_SUPPORTED.end(),
+        });
+    }
+    Ok(())
+}
+
 /// Verify a signed `atom/claim` transaction.
 ///
-/// Validates the Coz signature, deserializes the payload, and checks
-/// that `typ` is [`TYP_CLAIM`]. Returns the parsed [`ClaimPayload`]
-/// on success.
+/// Validates the Coz signature, checks the payload version, deserializes
+/// the payload, and checks that `typ` is [`TYP_CLAIM`]. With a
+/// [`TimePolicy`], also rejects a `now` outside its bounds. Returns the parsed [`ClaimPayload`] on
+/// success, as [`Verified`] with the czd and signer thumbprint computed on the way.
 ///
 /// The caller provides raw key bytes — key storage and discovery is
 /// not this crate's concern.
 ///
-/// Spec constraints: `[sig-over-pay]`, `[claim-typ]`, `[claim-key-required]`.
+/// A claim past its `exp` still verifies: expiry bounds the publishes a
+/// claim admits ([`verify_publish_authorized`]), not the claim itself.
+///
+/// Spec constraints: `[sig-over-pay]`, `[claim-typ]`, `[claim-key-required]`,
+/// `[time-policy]`, `[payload-version]`.
 #[cfg(feature = "serde")]
 pub fn verify_claim(
     pay_json: &[u8],
     sig: &[u8],
     alg: &str,
     pub_key: &[u8],
-) -> Result<ClaimPayload, VerifyError> {
+    time: Option<&TimePolicy>,
+) -> Result<Verified<ClaimPayload>, VerifyError> {
     verify_signature(pay_json, sig, alg, pub_key)?;
+    check_payload_version(pay_json)?;
     let payload: ClaimPayload = serde_json::from_slice(pay_json)?;
     if payload.typ != TYP_CLAIM {
         return Err(VerifyError::WrongTyp {
@@ -1011,24 +2013,32 @@ pub fn verify_claim(
             actual: payload.typ,
         });
     }
-    Ok(payload)
+    if let Some(policy) = time {
+        policy.check(payload.now)?;
+    }
+    Verified::new(payload, pay_json, sig, alg, pub_key)
 }
 
 /// Verify a signed `atom/publish` transaction.
 ///
-/// Validates the Coz signature, deserializes the payload, and checks
-/// that `typ` is [`TYP_PUBLISH`]. Returns the parsed [`PublishPayload`]
-/// on success.
+/// Validates the Coz signature, checks the payload version, deserializes
+/// the payload, and checks that `typ` is [`TYP_PUBLISH`]. With a
+/// [`TimePolicy`], also rejects a `now` outside its bounds. Returns the
+/// parsed [`PublishPayload`] on success, as [`Verified`] with the czd and
+/// signer thumbprint computed on the way.
 ///
-/// Spec constraints: `[sig-over-pay]`, `[publish-typ]`.
+/// Spec constraints: `[sig-over-pay]`, `[publish-typ]`, `[time-policy]`,
+/// `[payload-version]`.
 #[cfg(feature = "serde")]
 pub fn verify_publish(
     pay_json: &[u8],
     sig: &[u8],
     alg: &str,
     pub_key: &[u8],
-) -> Result<PublishPayload, VerifyError> {
+    time: Option<&TimePolicy>,
+) -> Result<Verified<PublishPayload>, VerifyError> {
     verify_signature(pay_json, sig, alg, pub_key)?;
+    check_payload_version(pay_json)?;
     let payload: PublishPayload = serde_json::from_slice(pay_json)?;
     if payload.typ != TYP_PUBLISH {
         return Err(VerifyError::WrongTyp {
@@ -1036,7 +2046,10 @@ pub fn verify_publish(
             actual: payload.typ,
         });
     }
-    Ok(payload)
+    if let Some(policy) = time {
+        policy.check(payload.now)?;
+    }
+    Verified::new(payload, pay_json, sig, alg, pub_key)
 }
 
 /// Verify a claim-replacement's two-authority requirement (Verification
@@ -1113,9 +2126,63 @@ pub fn czd_for_alg(pay_json: &[u8], sig: &[u8], alg: &str) -> Result<Czd, Verify
         .ok_or_else(|| VerifyError::UnsupportedAlgorithm(alg.to_string()))
 }
 
+/// A signed transaction payload verified by [`verify_typed`]: one that
+/// names its own type and its signer's thumbprint.
+#[cfg(feature = "serde")]
+trait TypedPayload: serde::de::DeserializeOwned {
+    /// The declared transaction type.
+    fn typ(&self) -> &str;
+    /// The declared signer thumbprint.
+    fn tmb(&self) -> &Thumbprint;
+}
+
+/// Verify a signed transaction of type `typ` in isolation: the signature,
+/// then the parse, then the declared type, then the binding of the
+/// declared thumbprint to `pub_key`.
+///
+/// Shared by the verifiers of transaction types that have no pipeline step
+/// of their own to carry the thumbprint binding (e.g. [`verify_revoke`]).
+#[cfg(feature = "serde")]
+fn verify_typed<P: TypedPayload>(
+    pay_json: &[u8],
+    sig: &[u8],
+    alg: &str,
+    pub_key: &[u8],
+    typ: &'static str,
+) -> Result<P, VerifyError> {
+    verify_signature(pay_json, sig, alg, pub_key)?;
+    let payload: P = serde_json::from_slice(pay_json)?;
+    if payload.typ() != typ {
+        return Err(VerifyError::WrongTyp {
+            expected: typ,
+            actual: payload.typ().to_owned(),
+        });
+    }
+    verify_key_thumbprint(payload.tmb(), alg, pub_key)?;
+    Ok(payload)
+}
+
+/// Bind a payload's declared thumbprint to the key that actually signed it.
+///
+/// The last step of [`verify_typed`].
+#[cfg(feature = "serde")]
+fn verify_key_thumbprint(
+    declared: &Thumbprint,
+    alg: &str,
+    pub_key: &[u8],
+) -> Result<(), VerifyError> {
+    let computed = coz_rs::compute_thumbprint_for_alg(alg, pub_key)
+        .ok_or_else(|| VerifyError::UnsupportedAlgorithm(alg.to_string()))?;
+    if !ct_eq(computed.as_bytes(), declared.as_bytes()) {
+        return Err(VerifyError::ThumbprintMismatch);
+    }
+    Ok(())
+}
+
 /// Verify a Coz signature over raw JSON payload bytes.
 ///
-/// Shared logic for [`verify_claim`] and [`verify_publish`].
+/// Shared logic for [`verify_claim`], [`verify_publish`], and
+/// [`verify_batch`].
 #[cfg(feature = "serde")]
 fn verify_signature(
     pay_json: &[u8],
@@ -1165,7 +2232,7 @@ pub fn verify_claim_key_thumbprint(
 ) -> Result<(), VerifyError> {
     let computed = coz_rs::compute_thumbprint_for_alg(alg, pub_key)
         .ok_or_else(|| VerifyError::UnsupportedAlgorithm(alg.to_string()))?;
-    if computed != claim.tmb {
+    if !ct_eq(computed.as_bytes(), claim.tmb.as_bytes()) {
         return Err(VerifyError::ThumbprintMismatch);
     }
     Ok(())
@@ -1197,7 +2264,7 @@ pub fn verify_publish_key_thumbprint(
 ) -> Result<(), VerifyError> {
     let computed = coz_rs::compute_thumbprint_for_alg(alg, pub_key)
         .ok_or_else(|| VerifyError::UnsupportedAlgorithm(alg.to_string()))?;
-    if computed != publish.tmb {
+    if !ct_eq(computed.as_bytes(), publish.tmb.as_bytes()) {
         return Err(VerifyError::ThumbprintMismatch);
     }
     Ok(())
@@ -1229,7 +2296,7 @@ pub fn verify_claim_chains_charter(
     founding_alg: &str,
 ) -> Result<(), VerifyError> {
     let founding_czd = czd_for_alg(founding_pay_json, founding_sig, founding_alg)?;
-    if claim.anchor.as_bytes() != founding_czd.as_bytes() {
+    if !ct_eq(claim.anchor.as_bytes(), founding_czd.as_bytes()) {
         return Err(VerifyError::ClaimChartersMismatch);
     }
     Ok(())
@@ -1252,7 +2319,7 @@ pub fn verify_publish_chains_claim(
     claim_alg: &str,
 ) -> Result<(), VerifyError> {
     let claim_czd = czd_for_alg(claim_pay_json, claim_sig, claim_alg)?;
-    if publish.claim != claim_czd {
+    if !ct_eq(publish.claim.as_bytes(), claim_czd.as_bytes()) {
         return Err(VerifyError::ClaimChainMismatch);
     }
     Ok(())
@@ -1331,8 +2398,13 @@ pub fn verify_claim_authorized_by_charter(
 /// any key while declaring `publish.tmb = claim.owner`, and this function
 /// will wrongly report "authorized."
 ///
+/// Also rejects a publish dated at or after the claim's `exp`
+/// ([`VerifyError::Expired`]). The check reads the publish's `now`, not a
+/// clock, so a version published while its claim was live keeps verifying
+/// after the claim lapses.
+///
 /// Spec constraints: `[owner-authorization-delegated]`,
-/// `[publish-transition]`.
+/// `[publish-transition]`, `[claim-expiry]`.
 #[cfg(feature = "serde")]
 pub fn verify_publish_authorized(
     publish: &PublishPayload,
@@ -1341,6 +2413,9 @@ pub fn verify_publish_authorized(
     if !claim.owner.authorizes(&publish.tmb) {
         return Err(VerifyError::Unauthorized);
     }
+    if let Some(exp) = claim.exp.filter(|_| claim.is_expired_at(publish.now)) {
+        return Err(VerifyError::Expired { exp });
+    }
     Ok(())
 }
 
@@ -1364,10 +2439,62 @@ pub fn verify_atom_id(
     Ok(())
 }
 
+/// Verify a signed publish against the claim it chains to, in one call.
+///
+/// Runs every claim↔publish cross-check of the Local Verification
+/// pipeline that needs no charter data, in pipeline order:
+///
+/// 1. [`verify_publish`] — signature over `publish_json` and `typ`.
+/// 2. [`verify_publish_key_thumbprint`] — `publish.tmb` is `pub_key`'s (step 6).
+/// 3. [`verify_publish_chains_claim`] — `publish.claim == czd(claim)` (step 8).
+/// 4. [`verify_publish_authorized`] — `claim.owner` authorizes `publish.tmb` (step 11).
+/// 5. The publish's `(anchor, label)` is the claim's, as [`verify_atom_id`] checks against an
+///    expected id (step 13).
+///
+/// `alg` names the algorithm of both messages; a claim and publish signed
+/// under different algorithms must be checked with the individual steps.
+/// The claim's own signature is assumed verified upstream (e.g. by
+/// [`verify_claim`] with the claim's key), so this function only parses
+/// `claim_json` — but it does reject one whose `typ` is not
+/// [`TYP_CLAIM`]. Steps needing charter data (7, 9, 10) are not run.
+///
+/// Returns the verified [`PublishPayload`] on success, as [`verify_publish`]
+/// does.
+///
+/// Spec constraints: `[publish-chains-claim]`,
+/// `[owner-authorization-delegated]`, `[symmetric-payloads]`.
+#[cfg(feature = "serde")]
+pub fn verify_publish_against_claim(
+    publish_json: &[u8],
+    publish_sig: &[u8],
+    claim_json: &[u8],
+    claim_sig: &[u8],
+    alg: &str,
+    pub_key: &[u8],
+) -> Result<Verified<PublishPayload>, VerifyError> {
+    let publish = verify_publish(publish_json, publish_sig, alg, pub_key, None)?;
+    verify_publish_key_thumbprint(&publish, alg, pub_key)?;
+    verify_publish_chains_claim(&publish, claim_json, claim_sig, alg)?;
+    let claim: ClaimPayload = serde_json::from_slice(claim_json)?;
+    if claim.typ != TYP_CLAIM {
+        return Err(VerifyError::WrongTyp {
+            expected: TYP_CLAIM,
+            actual: claim.typ,
+        });
+    }
+    verify_publish_authorized(&publish, &claim)?;
+    if publish.anchor != claim.anchor || publish.label != claim.label {
+        return Err(VerifyError::AtomIdMismatch);
+    }
+    Ok(publish)
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
 
+#[cfg(test)]
+mod test_support;
 #[cfg(test)]
 mod tests;
 
@@ -2,12 +2,19 @@
 //!
 //! These types enforce Unicode identifier rules (UAX #31) with atom-specific
 //! extensions, forming a strict hierarchy: Identifier ⊂ Label ⊂ Tag.
+//!
+//! Each holds its validated string in an `Arc<str>`: names are cloned into
+//! every id, payload and URI that mentions them, and a clone is only a
+//! reference-count bump.
 
+use std::collections::HashSet;
 use std::ffi::OsStr;
 use std::fmt;
-use std::ops::Deref;
+use std::ops::{Deref, Range};
 use std::str::FromStr;
+use std::sync::Arc;
 
+use caseless::Caseless;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 use unicode_normalization::UnicodeNormalization;
@@ -22,13 +29,13 @@ use crate::{Error, NAME_MAX};
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde", serde(try_from = "String"))]
-pub struct Identifier(String);
+pub struct Identifier(Arc<str>);
 
 /// A validated atom label: UAX #31 plus hyphen (`-`).
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde", serde(try_from = "String"))]
-pub struct Label(String);
+pub struct Label(Arc<str>);
 
 /// A convenience alias for [`Label`].
 pub type Name = Label;
@@ -39,7 +46,7 @@ pub type Name = Label;
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde", serde(try_from = "String"))]
-pub struct Tag(String);
+pub struct Tag(Arc<str>);
 
 // ============================================================================
 // VerifiedName trait (sealed)
@@ -60,8 +67,11 @@ pub(crate) trait VerifiedName: Sized + sealed::Construct {
     }
 
     /// Hook for subtype-specific rules (default: no-op).
-    fn extra_validation(_s: &str) -> Result<(), Error> {
-        Ok(())
+    ///
+    /// Returns the offending substrings' byte ranges in the normalized
+    /// name, reported as [`Error::InvalidCharacters`].
+    fn extra_validation(_s: &str) -> Vec<Range<usize>> {
+        Vec::new()
     }
 
     /// NFKC-normalize and validate a string, returning the constructed type.
@@ -72,27 +82,93 @@ pub(crate) trait VerifiedName: Sized + sealed::Construct {
             return Err(Error::TooLong);
         }
 
+        let spans = SpanMap::new(s, &normalized);
         match normalized.chars().next() {
             Some(c) if Self::is_valid_start(c) => {},
-            Some(c) => return Err(Error::InvalidStart(c)),
+            Some(c) => {
+                return Err(Error::InvalidStart {
+                    found: c,
+                    span: spans.span(0..c.len_utf8()),
+                });
+            },
             None => return Err(Error::Empty),
         }
 
-        let invalid: String = normalized
-            .chars()
-            .filter(|&c| !Self::is_valid_char(c))
-            .collect();
-
-        if !invalid.is_empty() {
-            return Err(Error::InvalidCharacters(invalid));
+        let mut found = String::new();
+        let mut ranges = Vec::new();
+        for (i, c) in normalized.char_indices() {
+            if !Self::is_valid_char(c) {
+                found.push(c);
+                ranges.push(i..i + c.len_utf8());
+            }
+        }
+        if found.is_empty() {
+            ranges = Self::extra_validation(&normalized);
+            found = ranges.iter().map(|r| &normalized[r.clone()]).collect();
+        }
+        if !ranges.is_empty() {
+            return Err(Error::InvalidCharacters {
+                found,
+                spans: ranges.into_iter().map(|r| spans.span(r)).collect(),
+            });
         }
-
-        Self::extra_validation(&normalized)?;
 
         Ok(sealed::Construct::new(normalized))
     }
 }
 
+/// Where an offending character sits in a rejected name.
+///
+/// Validation runs on the NFKC-normalized name, so `normalized` is always
+/// known. `original` locates the same characters in the caller's input —
+/// it differs from `normalized` when normalization changed lengths (e.g.
+/// a full-width `！` normalizing to `!`), and is `None` when normalization
+/// composed characters across boundaries and no exact original range
+/// exists.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CharSpan {
+    /// Byte range in the NFKC-normalized name.
+    pub normalized: Range<usize>,
+    /// Byte range in the input as given, when it can be recovered.
+    pub original: Option<Range<usize>>,
+}
+
+/// Maps byte ranges of a normalized name back to the input it came from.
+struct SpanMap {
+    /// `(normalized range, original range)` per input character, when
+    /// normalizing character-by-character reproduces the whole
+    /// normalization.
+    chars: Option<Vec<(Range<usize>, Range<usize>)>>,
+}
+
+impl SpanMap {
+    fn new(original: &str, normalized: &str) -> Self {
+        let mut rebuilt = String::with_capacity(normalized.len());
+        let mut chars = Vec::new();
+        for (i, c) in original.char_indices() {
+            let start = rebuilt.len();
+            rebuilt.extend(c.to_string().nfkc());
+            chars.push((start..rebuilt.len(), i..i + c.len_utf8()));
+        }
+        Self {
+            chars: (rebuilt == normalized).then_some(chars),
+        }
+    }
+
+    fn span(&self, normalized: Range<usize>) -> CharSpan {
+        let original = self.chars.as_ref().and_then(|chars| {
+            let covering = |pos: usize| chars.iter().find(|(n, _)| n.contains(&pos));
+            let (_, first) = covering(normalized.start)?;
+            let (_, last) = covering(normalized.end - 1)?;
+            Some(first.start..last.end)
+        });
+        CharSpan {
+            normalized,
+            original,
+        }
+    }
+}
+
 mod sealed {
     /// Private constructor — prevents external implementations of [`VerifiedName`].
     pub trait Construct {
@@ -109,7 +185,7 @@ macro_rules! verified_name_impls {
     ($Type:ident) => {
         impl sealed::Construct for $Type {
             fn new(s: String) -> Self {
-                Self(s)
+                Self(s.into())
             }
         }
 
@@ -187,11 +263,401 @@ impl VerifiedName for Tag {
         unicode_ident::is_xid_continue(c) || c == '-' || c == '.' || c == ':'
     }
 
-    fn extra_validation(s: &str) -> Result<(), Error> {
-        if s.contains("..") {
-            return Err(Error::InvalidCharacters("..".into()));
+    fn extra_validation(s: &str) -> Vec<Range<usize>> {
+        s.match_indices("..").map(|(i, m)| i..i + m.len()).collect()
+    }
+}
+verified_name_impls!(Tag);
+
+// ============================================================================
+// Case-insensitive comparison
+// ============================================================================
+
+/// A [`Label`] reduced to its case-folded form, for use as a map key when
+/// labels that differ only by case must collide.
+///
+/// Folding is Unicode's NFKC_Casefold: the compatibility caseless match
+/// key of Unicode §3.13 (D146: full case folding interleaved with NFKD),
+/// stripped of `Default_Ignorable_Code_Point` characters and recomposed to
+/// NFKC — so `Straße`, `STRASSE` and `strasse` share one `FoldedLabel`,
+/// as do `ᾼ` and `ΑΙ`. A folded label is a comparison key only: it is not
+/// itself a valid [`Label`] and must never be stored in place of one.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct FoldedLabel(String);
+
+impl FoldedLabel {
+    /// The folded string.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<&Label> for FoldedLabel {
+    fn from(label: &Label) -> Self {
+        let folded = label
+            .chars()
+            .nfd()
+            .default_case_fold()
+            .nfkd()
+            .default_case_fold()
+            .nfkd()
+            .filter(|&c| !is_default_ignorable(c))
+            .nfkc()
+            .collect();
+        Self(folded)
+    }
+}
+
+/// `Default_Ignorable_Code_Point` (Unicode `DerivedCoreProperties.txt`),
+/// which NFKC_Casefold removes. Several — variation selectors, the
+/// combining grapheme joiner, Hangul fillers — are `XID_Continue` and so
+/// may appear in a [`Label`].
+const DEFAULT_IGNORABLE: &[(char, char)] = &[
+    ('\u{00AD}', '\u{00AD}'),
+    ('\u{034F}', '\u{034F}'),
+    ('\u{061C}', '\u{061C}'),
+    ('\u{115F}', '\u{1160}'),
+    ('\u{17B4}', '\u{17B5}'),
+    ('\u{180B}', '\u{180F}'),
+    ('\u{200B}', '\u{200F}'),
+    ('\u{202A}', '\u{202E}'),
+    ('\u{2060}', '\u{206F}'),
+    ('\u{3164}', '\u{3164}'),
+    ('\u{FE00}', '\u{FE0F}'),
+    ('\u{FEFF}', '\u{FEFF}'),
+    ('\u{FFA0}', '\u{FFA0}'),
+    ('\u{FFF0}', '\u{FFF8}'),
+    ('\u{1BCA0}', '\u{1BCA3}'),
+    ('\u{1D173}', '\u{1D17A}'),
+    ('\u{E0000}', '\u{E0FFF}'),
+];
+
+fn is_default_ignorable(c: char) -> bool {
+    DEFAULT_IGNORABLE
+        .iter()
+        .any(|&(lo, hi)| (lo..=hi).contains(&c))
+}
+
+impl fmt::Display for FoldedLabel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl Label {
+    /// This label's [`FoldedLabel`].
+    #[must_use]
+    pub fn folded(&self) -> FoldedLabel {
+        FoldedLabel::from(self)
+    }
+
+    /// Whether `self` and `other` are equal ignoring case.
+    #[must_use]
+    pub fn eq_fold(&self, other: &Self) -> bool {
+        self == other || self.folded() == other.folded()
+    }
+}
+
+// ============================================================================
+// Unicode stability
+// ============================================================================
+
+/// The Unicode version of the NFKC tables names are normalized with.
+///
+/// A name's stored form is its NFKC normalization under these tables. A
+/// later Unicode version can normalize the same input differently, so a
+/// registry records this next to the labels it accepts and re-checks
+/// them with [`normalization_report`] after an upgrade.
+pub const UNICODE_VERSION: (u8, u8, u8) = unicode_normalization::UNICODE_VERSION;
+
+/// The Unicode version of the UAX #31 identifier tables names are
+/// validated against. It moves independently of [`UNICODE_VERSION`]: an
+/// upgrade can make a character valid (or invalid) in a name without
+/// changing how anything normalizes.
+pub const IDENTIFIER_UNICODE_VERSION: (u8, u8, u8) = unicode_ident::UNICODE_VERSION;
+
+/// What NFKC normalization does to one input, and under which Unicode
+/// version — see [`normalization_report`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NormalizationReport {
+    /// The NFKC-normalized form — what a [`Label`] built from the input
+    /// would hold.
+    pub normalized: String,
+    /// Whether normalization changed the input. For an already-stored
+    /// name, `true` means the current tables no longer leave it as it is:
+    /// its identity is not stable across the upgrade.
+    pub changed: bool,
+    /// The Unicode version normalization used ([`UNICODE_VERSION`]).
+    pub unicode_version: (u8, u8, u8),
+}
+
+/// Normalize `input` as names are normalized, reporting whether that
+/// changed it and under which Unicode version.
+///
+/// Does not validate: the report is the same whether or not the
+/// normalized form is a valid name.
+#[must_use]
+pub fn normalization_report(input: &str) -> NormalizationReport {
+    let normalized: String = input.nfkc().collect();
+    NormalizationReport {
+        changed: normalized != input,
+        normalized,
+        unicode_version: UNICODE_VERSION,
+    }
+}
+
+// ============================================================================
+// Label policy
+// ============================================================================
+
+/// A registry's rules for which valid labels it will accept.
+///
+/// The protocol accepts any [`Label`]; a policy is a registry-local
+/// refinement, applied at claim time through [`Label::validate_with`].
+pub trait LabelPolicy {
+    /// Accept or refuse an already-valid label.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Reserved`] (or another [`Error`]) to refuse.
+    fn check(&self, label: &Label) -> Result<(), Error>;
+}
+
+/// The default [`LabelPolicy`]: a case-insensitive deny-list plus a
+/// minimum length in characters.
+///
+/// [`Default`] reserves the standard-library crate names `std`, `core`,
+/// `alloc` and `test`, and refuses single-character labels.
+#[derive(Debug, Clone)]
+pub struct ReservedLabels {
+    denied: HashSet<FoldedLabel>,
+    min_chars: usize,
+}
+
+impl ReservedLabels {
+    /// A policy that accepts every label.
+    #[must_use]
+    pub fn empty() -> Self {
+        Self {
+            denied: HashSet::new(),
+            min_chars: 1,
+        }
+    }
+
+    /// Also refuse `label`, compared case-insensitively.
+    ///
+    /// # Errors
+    ///
+    /// Returns the validation error if `label` is not itself a valid
+    /// [`Label`] — an invalid name needs no reserving.
+    pub fn deny(mut self, label: &str) -> Result<Self, Error> {
+        self.denied.insert(Label::try_from(label)?.folded());
+        Ok(self)
+    }
+
+    /// Refuse labels shorter than `min_chars` characters.
+    #[must_use]
+    pub fn min_chars(mut self, min_chars: usize) -> Self {
+        self.min_chars = min_chars;
+        self
+    }
+}
+
+impl Default for ReservedLabels {
+    fn default() -> Self {
+        let denied = ["std", "core", "alloc", "test"]
+            .into_iter()
+            .map(|s| FoldedLabel(s.to_owned()))
+            .collect();
+        Self {
+            denied,
+            min_chars: 2,
+        }
+    }
+}
+
+impl LabelPolicy for ReservedLabels {
+    fn check(&self, label: &Label) -> Result<(), Error> {
+        if label.chars().count() < self.min_chars || self.denied.contains(&label.folded()) {
+            return Err(Error::Reserved(label.to_string()));
         }
         Ok(())
     }
 }
-verified_name_impls!(Tag);
+
+impl Label {
+    /// Validate like [`Label::try_from`], then apply `policy`.
+    ///
+    /// # Errors
+    ///
+    /// Any error [`Label::try_from`] returns, or the policy's refusal.
+    pub fn validate_with<P: LabelPolicy + ?Sized>(s: &str, policy: &P) -> Result<Self, Error> {
+        let label = Self::try_from(s)?;
+        policy.check(&label)?;
+        Ok(label)
+    }
+}
+
+// ============================================================================
+// Label globs
+// ============================================================================
+
+impl Label {
+    /// Whether this label matches `glob`, where `*` matches any run of
+    /// characters, including none, `?` exactly one, and everything else
+    /// itself, case-sensitively.
+    ///
+    /// The one glob matcher behind discovery queries and ingest filters.
+    #[must_use]
+    pub fn matches_glob(&self, glob: &str) -> bool {
+        let glob: Vec<char> = glob.chars().collect();
+        let text: Vec<char> = self.chars().collect();
+        glob_match(&glob, &text)
+    }
+}
+
+/// Glob matching with `*` and `?`, backtracking only to the most recent
+/// `*` — linear in practice, quadratic at worst.
+fn glob_match(glob: &[char], text: &[char]) -> bool {
+    let (mut g, mut t) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+    while t < text.len() {
+        match glob.get(g) {
+            Some('*') => {
+                star = Some((g, t));
+                g += 1;
+            },
+            Some(&c) if c == '?' || c == text[t] => {
+                g += 1;
+                t += 1;
+            },
+            _ => match star {
+                Some((star_g, star_t)) => {
+                    g = star_g + 1;
+                    t = star_t + 1;
+                    star = Some((star_g, star_t + 1));
+                },
+                None => return false,
+            },
+        }
+    }
+    glob[g..].iter().all(|&c| c == '*')
+}
+
+// ============================================================================
+// Tag hierarchy
+// ============================================================================
+
+impl Tag {
+    /// The `:`-separated segments of this tag, outermost first.
+    ///
+    /// `.` does not separate segments: `release:1.0` is the two segments
+    /// `release` and `1.0`.
+    pub fn segments(&self) -> impl Iterator<Item = &str> {
+        self.0.split(':')
+    }
+
+    /// Whether `prefix` is this tag or one of its ancestors — a
+    /// segment-wise prefix, so `release` is a prefix of `release:stable`
+    /// but not of `releases`.
+    #[must_use]
+    pub fn matches_prefix(&self, prefix: &Tag) -> bool {
+        match self.0.strip_prefix(prefix.as_ref()) {
+            Some(rest) => rest.is_empty() || rest.starts_with(':'),
+            None => false,
+        }
+    }
+}
+
+/// A pattern over tags, segment by segment.
+///
+/// Parsed from `:`-separated segments, each one of:
+///
+/// - a literal segment, matching itself exactly;
+/// - `*`, matching any single segment;
+/// - `**`, as the last segment only, matching zero or more trailing segments.
+///
+/// So `release:*` matches `release:stable` but neither `release` nor
+/// `release:stable:v2`, and `release:**` matches all three.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TagFilter {
+    segments: Vec<TagSegment>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum TagSegment {
+    Literal(String),
+    Any,
+    Rest,
+}
+
+impl TagFilter {
+    /// A filter matching `tag` and every tag beneath it.
+    #[must_use]
+    pub fn prefix(tag: &Tag) -> Self {
+        let mut segments: Vec<_> = tag
+            .segments()
+            .map(|s| TagSegment::Literal(s.to_owned()))
+            .collect();
+        segments.push(TagSegment::Rest);
+        Self { segments }
+    }
+
+    /// Whether `tag` matches this filter.
+    #[must_use]
+    pub fn matches(&self, tag: &Tag) -> bool {
+        let mut tag_segments = tag.segments();
+        for segment in &self.segments {
+            match segment {
+                TagSegment::Rest => return true,
+                TagSegment::Any => {
+                    if tag_segments.next().is_none() {
+                        return false;
+                    }
+                },
+                TagSegment::Literal(lit) => {
+                    if tag_segments.next() != Some(lit) {
+                        return false;
+                    }
+                },
+            }
+        }
+        tag_segments.next().is_none()
+    }
+}
+
+impl FromStr for TagFilter {
+    type Err = Error;
+
+    /// Parse a filter pattern.
+    ///
+    /// Fails with [`Error::InvalidFormat`] if `**` appears anywhere but
+    /// last, or if a literal segment could not appear in a [`Tag`].
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<_> = s.split(':').collect();
+        let last = parts.len() - 1;
+        let segments = parts
+            .into_iter()
+            .enumerate()
+            .map(|(i, part)| match part {
+                "*" => Ok(TagSegment::Any),
+                "**" if i == last => Ok(TagSegment::Rest),
+                "**" => Err(Error::InvalidFormat),
+                literal => {
+                    let literal: String = literal.nfkc().collect();
+                    let valid_start =
+                        i > 0 || literal.chars().next().is_some_and(Tag::is_valid_start);
+                    if !valid_start
+                        || !literal.chars().all(Tag::is_valid_char)
+                        || literal.contains("..")
+                    {
+                        return Err(Error::InvalidFormat);
+                    }
+                    Ok(TagSegment::Literal(literal))
+                },
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { segments })
+    }
+}
@@ -0,0 +1,361 @@
+//! Ecosystem-agnostic fallback [`VersionScheme`] using natural ordering.
+//!
+//! Concrete version dialects (semver, calver, …) live above the atom layer.
+//! Some stores nonetheless need a deterministic "pick newest" over versions
+//! whose ecosystem declares no formal scheme. [`NaturalOrderScheme`] fills
+//! that gap: it splits a version into maximal runs of ASCII digits and of
+//! other alphanumerics, ignores everything else as a separator, and compares
+//! run by run — numeric runs by value (so `1.10 > 1.9`), alphabetic runs
+//! lexicographically.
+//!
+//! It is deliberately naive about pre-release conventions: `1.0rc1` sorts
+//! *after* `1.0` because it has more runs. Ecosystems that care must supply
+//! their own scheme.
+
+use std::cmp::Ordering;
+use std::fmt;
+
+use thiserror::Error;
+
+use crate::{RawRequirement, RawVersion, VersionScheme};
+
+/// The fallback natural-order [`VersionScheme`].
+///
+/// Requirements are a comma-separated conjunction of comparators, each one
+/// of `=`, `>`, `>=`, `<`, `<=` followed by a version, or `*` for any
+/// version. A comparator without an operator means `=`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NaturalOrderScheme;
+
+/// A version parsed by [`NaturalOrderScheme`].
+///
+/// Ordered by its runs first. Versions whose runs compare equal but whose
+/// spelling differs (`1.0`, `1-0`, `01.0`) are tie-broken by the raw string,
+/// keeping `Ord` total and consistent with `Eq`, so "pick newest" is
+/// deterministic over any set of inputs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NaturalVersion {
+    raw: String,
+    runs: Vec<Run>,
+}
+
+/// A version requirement parsed by [`NaturalOrderScheme`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NaturalRequirement(Vec<Comparator>);
+
+/// Errors from parsing natural-order versions and requirements.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum NaturalParseError {
+    /// The version contains no digit or alphanumeric run.
+    #[error("version has no alphanumeric content: '{0}'")]
+    Empty(String),
+    /// A requirement comparator is malformed.
+    #[error("invalid version requirement: '{0}'")]
+    InvalidRequirement(String),
+}
+
+/// A maximal run of digits or of non-digit alphanumerics.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Run {
+    /// Digits with leading zeros stripped (`"0"` for an all-zero run).
+    Numeric(String),
+    /// Non-digit alphanumerics, compared lexicographically.
+    Alpha(String),
+}
+
+/// Comparison operators accepted in a [`NaturalRequirement`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Eq,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Comparator {
+    Any,
+    Cmp(Op, NaturalVersion),
+}
+
+// ============================================================================
+// Impls — NaturalVersion
+// ============================================================================
+
+impl NaturalVersion {
+    /// Parse a version string into its natural-order runs.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NaturalParseError::Empty`] if the string contains no
+    /// alphanumeric characters.
+    pub fn parse(raw: &str) -> Result<Self, NaturalParseError> {
+        let mut runs = Vec::new();
+        let mut chars = raw.char_indices().peekable();
+
+        while let Some((start, c)) = chars.next() {
+            if !c.is_alphanumeric() {
+                continue;
+            }
+            let numeric = c.is_ascii_digit();
+            let mut end = start + c.len_utf8();
+            while let Some(&(i, next)) = chars.peek() {
+                if !next.is_alphanumeric() || next.is_ascii_digit() != numeric {
+                    break;
+                }
+                end = i + next.len_utf8();
+                chars.next();
+            }
+            let run = &raw[start..end];
+            runs.push(if numeric {
+                let trimmed = run.trim_start_matches('0');
+                Run::Numeric(if trimmed.is_empty() { "0" } else { trimmed }.to_owned())
+            } else {
+                Run::Alpha(run.to_owned())
+            });
+        }
+
+        if runs.is_empty() {
+            return Err(NaturalParseError::Empty(raw.to_owned()));
+        }
+        Ok(Self {
+            raw: raw.to_owned(),
+            runs,
+        })
+    }
+
+    /// The original version string.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.raw
+    }
+
+    /// Compare by runs only, ignoring spelling differences.
+    fn cmp_runs(&self, other: &Self) -> Ordering {
+        self.runs.cmp(&other.runs)
+    }
+}
+
+impl Ord for NaturalVersion {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.cmp_runs(other).then_with(|| self.raw.cmp(&other.raw))
+    }
+}
+
+impl PartialOrd for NaturalVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for NaturalVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.raw)
+    }
+}
+
+// ============================================================================
+// Impls — Run
+// ============================================================================
+
+impl Ord for Run {
+    /// Numeric runs compare by value (length of the zero-stripped digits,
+    /// then digit-wise, so arbitrarily long runs never overflow); alphabetic
+    /// runs lexicographically; an alphabetic run sorts before a numeric one.
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Self::Numeric(a), Self::Numeric(b)) => a.len().cmp(&b.len()).then_with(|| a.cmp(b)),
+            (Self::Alpha(a), Self::Alpha(b)) => a.cmp(b),
+            (Self::Alpha(_), Self::Numeric(_)) => Ordering::Less,
+            (Self::Numeric(_), Self::Alpha(_)) => Ordering::Greater,
+        }
+    }
+}
+
+impl PartialOrd for Run {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// ============================================================================
+// Impls — NaturalRequirement
+// ============================================================================
+
+impl NaturalRequirement {
+    /// Parse a comma-separated conjunction of comparators.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NaturalParseError::InvalidRequirement`] if the requirement
+    /// is empty or a comparator has no version operand.
+    pub fn parse(raw: &str) -> Result<Self, NaturalParseError> {
+        let invalid = || NaturalParseError::InvalidRequirement(raw.to_owned());
+        let mut comparators = Vec::new();
+
+        for part in raw.split(',') {
+            let part = part.trim();
+            if part == "*" {
+                comparators.push(Comparator::Any);
+                continue;
+            }
+            let (op, rest) = if let Some(rest) = part.strip_prefix(">=") {
+                (Op::Ge, rest)
+            } else if let Some(rest) = part.strip_prefix("<=") {
+                (Op::Le, rest)
+            } else if let Some(rest) = part.strip_prefix('>') {
+                (Op::Gt, rest)
+            } else if let Some(rest) = part.strip_prefix('<') {
+                (Op::Lt, rest)
+            } else if let Some(rest) = part.strip_prefix('=') {
+                (Op::Eq, rest)
+            } else {
+                (Op::Eq, part)
+            };
+            let version = NaturalVersion::parse(rest.trim()).map_err(|_| invalid())?;
+            comparators.push(Comparator::Cmp(op, version));
+        }
+
+        Ok(Self(comparators))
+    }
+
+    /// Whether `version` satisfies every comparator.
+    ///
+    /// Comparators compare runs only, so `=1.0` matches `1-0` and `01.0`.
+    #[must_use]
+    pub fn matches(&self, version: &NaturalVersion) -> bool {
+        self.0.iter().all(|c| match c {
+            Comparator::Any => true,
+            Comparator::Cmp(op, bound) => {
+                let ord = version.cmp_runs(bound);
+                match op {
+                    Op::Eq => ord == Ordering::Equal,
+                    Op::Gt => ord == Ordering::Greater,
+                    Op::Ge => ord != Ordering::Less,
+                    Op::Lt => ord == Ordering::Less,
+                    Op::Le => ord != Ordering::Greater,
+                }
+            },
+        })
+    }
+}
+
+// ============================================================================
+// Impls — NaturalOrderScheme
+// ============================================================================
+
+impl VersionScheme for NaturalOrderScheme {
+    type Error = NaturalParseError;
+    type Requirement = NaturalRequirement;
+    type Version = NaturalVersion;
+
+    fn parse_version(&self, raw: &RawVersion) -> Result<Self::Version, Self::Error> {
+        NaturalVersion::parse(raw.as_str())
+    }
+
+    fn parse_requirement(&self, raw: &RawRequirement) -> Result<Self::Requirement, Self::Error> {
+        NaturalRequirement::parse(raw.as_str())
+    }
+
+    fn matches(&self, version: &Self::Version, req: &Self::Requirement) -> bool {
+        req.matches(version)
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v(s: &str) -> NaturalVersion {
+        NaturalVersion::parse(s).unwrap()
+    }
+
+    fn req(s: &str) -> NaturalRequirement {
+        NaturalRequirement::parse(s).unwrap()
+    }
+
+    #[test]
+    fn numeric_runs_compare_by_value() {
+        assert!(v("1.10") > v("1.9"));
+        assert!(v("2") > v("1.99.99"));
+        assert!(v("1.0.10") > v("1.0.9"));
+        assert!(v("1.99999999999999999999999") > v("1.99999999999999999999998"));
+    }
+
+    #[test]
+    fn leading_zeros_ignored_but_order_stays_total() {
+        assert_eq!(v("01.0").cmp_runs(&v("1.0")), Ordering::Equal);
+        assert_ne!(v("01.0"), v("1.0"));
+        assert_ne!(v("01.0").cmp(&v("1.0")), Ordering::Equal);
+    }
+
+    #[test]
+    fn alpha_runs_and_mixed_runs() {
+        assert!(v("1.0b") > v("1.0a"));
+        assert!(v("1.0.1") > v("1.0.a"));
+        assert!(v("1.0rc1") > v("1.0"));
+        assert!(v("r10") > v("r9"));
+    }
+
+    #[test]
+    fn pick_newest_is_deterministic() {
+        let mut versions = ["1.9", "1.10", "1-10", "1.2", "0.9"].map(v).to_vec();
+        versions.sort();
+        let order: Vec<_> = versions.iter().map(NaturalVersion::as_str).collect();
+        assert_eq!(order, ["0.9", "1.2", "1.9", "1-10", "1.10"]);
+    }
+
+    #[test]
+    fn empty_versions_rejected() {
+        assert!(matches!(
+            NaturalVersion::parse(""),
+            Err(NaturalParseError::Empty(_))
+        ));
+        assert!(matches!(
+            NaturalVersion::parse("..-"),
+            Err(NaturalParseError::Empty(_))
+        ));
+    }
+
+    #[test]
+    fn requirements_match() {
+        assert!(req(">=1.9, <2").matches(&v("1.10")));
+        assert!(!req(">=1.9, <2").matches(&v("2.0")));
+        assert!(req("=1.0").matches(&v("1-0")));
+        assert!(req("1.0").matches(&v("01.0")));
+        assert!(req("*").matches(&v("anything")));
+        assert!(req(">1.0").matches(&v("1.0.1")));
+        assert!(req("<=1.0").matches(&v("1.0")));
+    }
+
+    #[test]
+    fn invalid_requirements_rejected() {
+        for bad in ["", ">=", "1.0,", "<.."] {
+            assert!(
+                matches!(
+                    NaturalRequirement::parse(bad),
+                    Err(NaturalParseError::InvalidRequirement(_))
+                ),
+                "{bad:?} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn scheme_round_trips_through_raw_version() {
+        let scheme = NaturalOrderScheme;
+        let version = scheme
+            .parse_version(&RawVersion::new("3.1.4".into()))
+            .unwrap();
+        let requirement = scheme
+            .parse_requirement(&RawRequirement::new(">=3, <4".into()))
+            .unwrap();
+        assert!(scheme.matches(&version, &requirement));
+        assert_eq!(version.to_string(), "3.1.4");
+    }
+}
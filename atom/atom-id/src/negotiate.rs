@@ -0,0 +1,154 @@
+//! Algorithm negotiation.
+//!
+//! Stores and registries each support some set of coz algorithms — for
+//! signing and verifying transactions, and, through [`Alg::hash_alg`], for
+//! computing [`crate::AtomDigest`]s. [`AlgPreferences`] records one side's
+//! supported algorithms in preference order and picks the best one the
+//! other side offers, so neither has to assume `ES256`.
+
+use std::fmt;
+
+use coz_rs::Alg;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// An ordered list of acceptable algorithms, most preferred first.
+///
+/// Each algorithm appears at most once. On the wire it is a JSON array of
+/// algorithm names, e.g. `["Ed25519","ES256"]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AlgPreferences(Vec<Alg>);
+
+impl AlgPreferences {
+    /// Preferences in the given order. Repeats of an algorithm already
+    /// listed are dropped.
+    pub fn new(algs: impl IntoIterator<Item = Alg>) -> Self {
+        let mut list = Vec::new();
+        for alg in algs {
+            if !list.contains(&alg) {
+                list.push(alg);
+            }
+        }
+        Self(list)
+    }
+
+    /// The most preferred algorithm, if any are listed.
+    #[must_use]
+    pub fn preferred(&self) -> Option<Alg> {
+        self.0.first().copied()
+    }
+
+    /// The most preferred of our algorithms that `offered` also lists.
+    ///
+    /// Our order decides, not the order of `offered`; `None` means the two
+    /// sides share no algorithm.
+    #[must_use]
+    pub fn select(&self, offered: &[Alg]) -> Option<Alg> {
+        self.0.iter().copied().find(|alg| offered.contains(alg))
+    }
+
+    /// Whether `alg` is acceptable at all.
+    #[must_use]
+    pub fn contains(&self, alg: Alg) -> bool {
+        self.0.contains(&alg)
+    }
+
+    /// The algorithms, most preferred first.
+    #[must_use]
+    pub fn as_slice(&self) -> &[Alg] {
+        &self.0
+    }
+}
+
+/// `ES256`, then `ES384`, `ES512` and `Ed25519` — `ES256` first, as every
+/// caller assumed before negotiation existed.
+impl Default for AlgPreferences {
+    fn default() -> Self {
+        Self(vec![Alg::ES256, Alg::ES384, Alg::ES512, Alg::Ed25519])
+    }
+}
+
+impl FromIterator<Alg> for AlgPreferences {
+    fn from_iter<I: IntoIterator<Item = Alg>>(iter: I) -> Self {
+        Self::new(iter)
+    }
+}
+
+impl fmt::Display for AlgPreferences {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, alg) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(",")?;
+            }
+            f.write_str(alg.name())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for AlgPreferences {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.0.iter().map(|alg| alg.name()))
+    }
+}
+
+/// Unknown names and repeated algorithms are rejected rather than
+/// dropped — a preference list that says something it cannot mean is
+/// malformed.
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for AlgPreferences {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let names = Vec::<String>::deserialize(deserializer)?;
+        let mut list = Vec::with_capacity(names.len());
+        for name in names {
+            let alg = Alg::from_str(&name)
+                .ok_or_else(|| serde::de::Error::custom(format!("unknown algorithm: {name}")))?;
+            if list.contains(&alg) {
+                return Err(serde::de::Error::custom(format!(
+                    "duplicate algorithm: {name}"
+                )));
+            }
+            list.push(alg);
+        }
+        Ok(Self(list))
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn select_follows_our_order() {
+        let prefs = AlgPreferences::new([Alg::Ed25519, Alg::ES256, Alg::Ed25519]);
+        assert_eq!(prefs.as_slice(), &[Alg::Ed25519, Alg::ES256]);
+        assert_eq!(prefs.preferred(), Some(Alg::Ed25519));
+        assert_eq!(
+            prefs.select(&[Alg::ES256, Alg::Ed25519]),
+            Some(Alg::Ed25519)
+        );
+        assert_eq!(prefs.select(&[Alg::ES512, Alg::ES256]), Some(Alg::ES256));
+        assert_eq!(prefs.select(&[Alg::ES384]), None);
+        assert_eq!(AlgPreferences::default().preferred(), Some(Alg::ES256));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_is_a_list_of_names() {
+        let prefs = AlgPreferences::new([Alg::Ed25519, Alg::ES256]);
+        let json = serde_json::to_string(&prefs).unwrap();
+        assert_eq!(json, r#"["Ed25519","ES256"]"#);
+        assert_eq!(
+            serde_json::from_str::<AlgPreferences>(&json).unwrap(),
+            prefs
+        );
+
+        assert!(serde_json::from_str::<AlgPreferences>(r#"["ES256","ES256"]"#).is_err());
+        assert!(serde_json::from_str::<AlgPreferences>(r#"["RS256"]"#).is_err());
+    }
+}
@@ -0,0 +1,153 @@
+//! Replay protection — rejecting resubmission of an already-accepted
+//! signed payload.
+//!
+//! A signed claim or publish stays valid forever: its signature does not
+//! expire. Without replay protection, an attacker holding an old publish
+//! can resubmit it to a registry and roll a store back to a superseded
+//! version. A [`ReplayGuard`] remembers the czd of every payload a
+//! registry has accepted and refuses to accept the same czd twice.
+//!
+//! Keying by czd means "identical signed payload" is exactly "same czd".
+//! A publisher that deliberately re-issues an otherwise byte-identical
+//! payload sets a fresh `nonce` on it, which gives it a fresh czd.
+//!
+//! Spec: `docs/specs/atom-transactions.md` `[replay-guard]`.
+
+use std::collections::HashSet;
+
+use crate::Czd;
+
+/// Records accepted payloads by czd and detects resubmission.
+///
+/// Implementations back this with whatever persistence the registry
+/// already has; [`MemoryReplayGuard`] is the in-process reference
+/// implementation.
+///
+/// Spec constraint: `[replay-guard]`.
+pub trait ReplayGuard {
+    /// Record `czd` as accepted.
+    ///
+    /// Returns `true` if `czd` was new, `false` if it had already been
+    /// recorded — the payload is a replay.
+    fn record(&mut self, czd: &Czd) -> bool;
+}
+
+/// An in-memory [`ReplayGuard`].
+#[derive(Debug, Clone, Default)]
+pub struct MemoryReplayGuard {
+    seen: HashSet<Vec<u8>>,
+}
+
+impl MemoryReplayGuard {
+    /// Create an empty guard.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of recorded czds.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.seen.len()
+    }
+
+    /// Whether no czd has been recorded.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.seen.is_empty()
+    }
+}
+
+impl ReplayGuard for MemoryReplayGuard {
+    fn record(&mut self, czd: &Czd) -> bool {
+        self.seen.insert(czd.as_bytes().to_vec())
+    }
+}
+
+/// Reject a signed payload whose czd `guard` has already recorded, and
+/// record it otherwise.
+///
+/// Call this last, once the payload has passed every other check: the
+/// guard is a record of *accepted* payloads, and a payload rejected for
+/// another reason must not occupy it. Returns the recomputed czd.
+///
+/// Spec constraint: `[replay-guard]`.
+#[cfg(feature = "serde")]
+pub fn verify_not_replayed<G: ReplayGuard + ?Sized>(
+    guard: &mut G,
+    pay_json: &[u8],
+    sig: &[u8],
+    alg: &str,
+) -> Result<Czd, crate::VerifyError> {
+    let czd = crate::czd_for_alg(pay_json, sig, alg)?;
+    if !guard.record(&czd) {
+        return Err(crate::VerifyError::Replayed);
+    }
+    Ok(czd)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+    use crate::{Alg, Anchor, AtomId, Label, PublishPayload, RawVersion};
+
+    fn signed_publish(nonce: Option<Vec<u8>>) -> (Vec<u8>, Vec<u8>) {
+        use coz_rs::Ed25519;
+
+        let sk = coz_rs::SigningKey::<Ed25519>::generate();
+        let prv = sk.private_key_bytes();
+        let pub_bytes = sk.verifying_key().public_key_bytes().to_vec();
+        let mut publish = PublishPayload::new(
+            Alg::Ed25519,
+            AtomId::new(
+                Anchor::new(vec![1, 2, 3, 4]),
+                Label::try_from("my-pkg").unwrap(),
+            ),
+            Czd::from_bytes(vec![5, 6]),
+            vec![7, 8],
+            2000,
+            "src/lib".into(),
+            vec![9, 10],
+            sk.thumbprint().clone(),
+            RawVersion::new("1.0.0".into()),
+        );
+        publish.nonce = nonce;
+        let pay_json = serde_json::to_vec(&publish).unwrap();
+        let (sig, _cad) = coz_rs::sign_json(&pay_json, "Ed25519", &prv, &pub_bytes).unwrap();
+        (pay_json, sig)
+    }
+
+    #[test]
+    fn replayed_payload_is_rejected() {
+        let mut guard = MemoryReplayGuard::new();
+        let (pay_json, sig) = signed_publish(None);
+
+        assert!(verify_not_replayed(&mut guard, &pay_json, &sig, "Ed25519").is_ok());
+        let result = verify_not_replayed(&mut guard, &pay_json, &sig, "Ed25519");
+        assert!(
+            matches!(result, Err(crate::VerifyError::Replayed)),
+            "{result:?}"
+        );
+        assert_eq!(guard.len(), 1);
+    }
+
+    #[test]
+    fn nonce_distinguishes_otherwise_identical_payloads() {
+        let (plain, _) = signed_publish(None);
+        let (nonced, _) = signed_publish(Some(vec![0xAA; 16]));
+        let value: serde_json::Value = serde_json::from_slice(&plain).unwrap();
+        assert!(
+            value.get("nonce").is_none(),
+            "absent nonce stays off the wire"
+        );
+        let value: serde_json::Value = serde_json::from_slice(&nonced).unwrap();
+        assert!(value.get("nonce").is_some());
+
+        let back: PublishPayload = serde_json::from_slice(&nonced).unwrap();
+        assert_eq!(back.nonce, Some(vec![0xAA; 16]));
+    }
+}
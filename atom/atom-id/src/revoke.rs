@@ -0,0 +1,380 @@
+//! Revocation transactions — invalidating a claim.
+//!
+//! A revocation names a claim by its czd and declares it void: a
+//! compromised key or an abandoned label should not keep authorizing new
+//! publishes forever. A revocation is itself a signed coz message, so it
+//! travels through exactly the same payload machinery as a claim or a
+//! publish — it does not delete the claim, it supersedes it.
+//!
+//! Spec: `docs/specs/atom-transactions.md` §RevokePayload, `[revoke-*]`.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{Alg, Anchor, AtomId, Czd, Label, Thumbprint};
+
+/// Transaction type for claim revocations.
+///
+/// Spec constraint: `[revoke-typ]`.
+pub const TYP_REVOKE: &str = "atom/revoke";
+
+// ============================================================================
+// RevokePayload
+// ============================================================================
+
+/// Why a claim was revoked.
+///
+/// Informational only — verification treats every reason identically. The
+/// code exists so that consumers can tell "stop trusting everything this key
+/// signed" (`KeyCompromise`) apart from an orderly retirement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum RevokeReason {
+    /// No reason given.
+    Unspecified,
+    /// The signing key behind the claim is known or suspected to be
+    /// compromised.
+    KeyCompromise,
+    /// The owner has abandoned the label.
+    Abandoned,
+    /// The claim has been superseded by a newer one.
+    Superseded,
+}
+
+/// Payload for an `atom/revoke` transaction.
+///
+/// Names the revoked claim by its [`Czd`] and repeats the claim's
+/// `(anchor, label)` so a revocation is self-describing without fetching
+/// the claim first (`[symmetric-payloads]`'s shape, extended). Only the
+/// revoked claim's owner may sign it (`[revoke-authority]`) — a
+/// governance-driven takeover is a claim replacement, not a revocation.
+///
+/// Spec constraints: `[revoke-typ]`, `[revoke-authority]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RevokePayload {
+    /// The signing algorithm.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_alg"))]
+    pub alg: Alg,
+    /// The atom-set anchor of the revoked claim.
+    pub anchor: Anchor,
+    /// The [`Czd`] of the revoked claim.
+    pub claim: Czd,
+    /// The atom label of the revoked claim.
+    pub label: Label,
+    /// Timestamp (seconds since Unix epoch). MUST strictly exceed the
+    /// revoked claim's `now`.
+    pub now: u64,
+    /// Why the claim is being revoked.
+    pub reason: RevokeReason,
+    /// Coz key thumbprint of the signing key — the revoked claim's owner.
+    pub tmb: Thumbprint,
+    /// Transaction type — always [`TYP_REVOKE`].
+    pub typ: String,
+}
+
+impl RevokePayload {
+    /// Construct a new revocation payload.
+    ///
+    /// Takes an [`AtomId`] to ensure that the anchor and label come from
+    /// a validated identity pair. Sets `typ` to [`TYP_REVOKE`]
+    /// automatically.
+    pub fn new(
+        alg: Alg,
+        id: AtomId,
+        claim: Czd,
+        now: u64,
+        reason: RevokeReason,
+        tmb: Thumbprint,
+    ) -> Self {
+        Self {
+            alg,
+            anchor: id.anchor,
+            claim,
+            label: id.label,
+            now,
+            reason,
+            tmb,
+            typ: TYP_REVOKE.to_owned(),
+        }
+    }
+}
+
+// ============================================================================
+// Verification
+// ============================================================================
+
+#[cfg(feature = "serde")]
+impl crate::TypedPayload for RevokePayload {
+    fn typ(&self) -> &str {
+        &self.typ
+    }
+
+    fn tmb(&self) -> &Thumbprint {
+        &self.tmb
+    }
+}
+
+/// Verify a signed `atom/revoke` transaction.
+///
+/// Validates the Coz signature, deserializes the payload, checks that
+/// `typ` is [`TYP_REVOKE`], and binds the declared `tmb` to `pub_key`.
+/// Unlike claims and publishes — whose thumbprint binding is a separate
+/// Verification Pipeline step — a revocation has no pipeline of its own,
+/// so the binding is folded in here: a revocation whose `tmb` is not its
+/// actual signer would let any key revoke any claim by naming the owner's
+/// thumbprint.
+///
+/// This checks the revocation in isolation. Whether its signer may revoke
+/// the named claim is [`verify_revoke_against_claim`]'s job.
+///
+/// Spec constraints: `[sig-over-pay]`, `[revoke-typ]`.
+#[cfg(feature = "serde")]
+pub fn verify_revoke(
+    pay_json: &[u8],
+    sig: &[u8],
+    alg: &str,
+    pub_key: &[u8],
+) -> Result<RevokePayload, crate::VerifyError> {
+    crate::verify_typed(pay_json, sig, alg, pub_key, TYP_REVOKE)
+}
+
+/// Verify a revocation against the claim it names.
+///
+/// The claim's czd is recomputed from its own raw wire components
+/// (payload JSON, signature, algorithm) via [`crate::czd_for_alg`] rather
+/// than trusted from a caller-supplied value — the same anti-assertion
+/// discipline as [`crate::verify_publish_chains_claim`]. Checks, in order:
+///
+/// - `revoke.claim == czd(claim)` ([`crate::VerifyError::ClaimChainMismatch`]).
+/// - `(revoke.anchor, revoke.label)` equals the claim's ([`crate::VerifyError::AtomIdMismatch`]).
+/// - `claim.owner` authorizes `revoke.tmb` ([`crate::VerifyError::Unauthorized`]).
+/// - `revoke.now > claim.now` ([`crate::VerifyError::RevokeNotAfterClaim`]).
+///
+/// The claim's own signature is assumed to have been verified upstream
+/// (e.g. by [`crate::verify_claim`]); this function parses, but does not
+/// re-authenticate, `claim_pay_json`. Returns the parsed claim on success.
+///
+/// Spec constraint: `[revoke-authority]`.
+#[cfg(feature = "serde")]
+pub fn verify_revoke_against_claim(
+    revoke: &RevokePayload,
+    claim_pay_json: &[u8],
+    claim_sig: &[u8],
+    claim_alg: &str,
+) -> Result<crate::ClaimPayload, crate::VerifyError> {
+    let claim_czd = crate::czd_for_alg(claim_pay_json, claim_sig, claim_alg)?;
+    if !crate::ct_eq(revoke.claim.as_bytes(), claim_czd.as_bytes()) {
+        return Err(crate::VerifyError::ClaimChainMismatch);
+    }
+    let claim: crate::ClaimPayload = serde_json::from_slice(claim_pay_json)?;
+    if revoke.anchor != claim.anchor || revoke.label != claim.label {
+        return Err(crate::VerifyError::AtomIdMismatch);
+    }
+    if !claim.owner.authorizes(&revoke.tmb) {
+        return Err(crate::VerifyError::Unauthorized);
+    }
+    if revoke.now <= claim.now {
+        return Err(crate::VerifyError::RevokeNotAfterClaim);
+    }
+    Ok(claim)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{gen_ed25519_key, signed_claim, test_id};
+
+    #[test]
+    fn revoke_payload_typ_constant() {
+        let revoke = RevokePayload::new(
+            Alg::ES256,
+            test_id(),
+            Czd::from_bytes(vec![5, 6]),
+            2000,
+            RevokeReason::Abandoned,
+            Thumbprint::from_bytes(vec![10, 20, 30]),
+        );
+        assert_eq!(revoke.typ, TYP_REVOKE);
+        assert_eq!(revoke.typ, "atom/revoke");
+    }
+
+    #[test]
+    fn revoke_payload_serde_roundtrip() {
+        let revoke = RevokePayload::new(
+            Alg::ES256,
+            test_id(),
+            Czd::from_bytes(vec![5, 6]),
+            2000,
+            RevokeReason::KeyCompromise,
+            Thumbprint::from_bytes(vec![10, 20, 30]),
+        );
+        let json = serde_json::to_value(&revoke).unwrap();
+        assert_eq!(json["reason"], "key-compromise");
+        let back: RevokePayload = serde_json::from_value(json).unwrap();
+        assert_eq!(back, revoke);
+    }
+
+    #[test]
+    fn verify_revoke_roundtrip() {
+        let (prv, pub_bytes, tmb) = gen_ed25519_key();
+        let revoke = RevokePayload::new(
+            Alg::Ed25519,
+            test_id(),
+            Czd::from_bytes(vec![5, 6]),
+            2000,
+            RevokeReason::Unspecified,
+            tmb,
+        );
+        let pay_json = serde_json::to_vec(&revoke).unwrap();
+        let (sig, _cad) = coz_rs::sign_json(&pay_json, "Ed25519", &prv, &pub_bytes).unwrap();
+
+        let result = verify_revoke(&pay_json, &sig, "Ed25519", &pub_bytes);
+        assert_eq!(result.unwrap(), revoke);
+    }
+
+    #[test]
+    fn verify_revoke_wrong_typ() {
+        let (prv, pub_bytes, tmb) = gen_ed25519_key();
+        let revoke = RevokePayload::new(
+            Alg::Ed25519,
+            test_id(),
+            Czd::from_bytes(vec![5, 6]),
+            2000,
+            RevokeReason::Unspecified,
+            tmb,
+        );
+        let mut json_val = serde_json::to_value(&revoke).unwrap();
+        json_val["typ"] = serde_json::Value::String("atom/claim".into());
+        let pay_json = serde_json::to_vec(&json_val).unwrap();
+        let (sig, _cad) = coz_rs::sign_json(&pay_json, "Ed25519", &prv, &pub_bytes).unwrap();
+
+        let result = verify_revoke(&pay_json, &sig, "Ed25519", &pub_bytes);
+        assert!(
+            matches!(result, Err(crate::VerifyError::WrongTyp { .. })),
+            "tampered typ should fail with WrongTyp: {result:?}"
+        );
+    }
+
+    #[test]
+    fn verify_revoke_rejects_forged_tmb() {
+        // Signed by the attacker's own key while declaring the owner's tmb.
+        let (attacker_prv, attacker_pub, _attacker_tmb) = gen_ed25519_key();
+        let (_owner_prv, _owner_pub, owner_tmb) = gen_ed25519_key();
+        let revoke = RevokePayload::new(
+            Alg::Ed25519,
+            test_id(),
+            Czd::from_bytes(vec![5, 6]),
+            2000,
+            RevokeReason::KeyCompromise,
+            owner_tmb,
+        );
+        let pay_json = serde_json::to_vec(&revoke).unwrap();
+        let (sig, _cad) =
+            coz_rs::sign_json(&pay_json, "Ed25519", &attacker_prv, &attacker_pub).unwrap();
+
+        let result = verify_revoke(&pay_json, &sig, "Ed25519", &attacker_pub);
+        assert!(
+            matches!(result, Err(crate::VerifyError::ThumbprintMismatch)),
+            "a revocation not signed by its declared tmb must be rejected: {result:?}"
+        );
+    }
+
+    #[test]
+    fn verify_revoke_against_claim_accepts_owner() {
+        let (_prv, _pub, owner_tmb) = gen_ed25519_key();
+        let (claim_json, claim_sig, claim_czd) = signed_claim(&owner_tmb);
+        let revoke = RevokePayload::new(
+            Alg::Ed25519,
+            test_id(),
+            claim_czd,
+            2000,
+            RevokeReason::Abandoned,
+            owner_tmb,
+        );
+        let result = verify_revoke_against_claim(&revoke, &claim_json, &claim_sig, "Ed25519");
+        assert!(result.is_ok(), "owner revocation must verify: {result:?}");
+    }
+
+    #[test]
+    fn verify_revoke_against_claim_rejects_non_owner() {
+        let (_prv, _pub, owner_tmb) = gen_ed25519_key();
+        let (_other_prv, _other_pub, other_tmb) = gen_ed25519_key();
+        let (claim_json, claim_sig, claim_czd) = signed_claim(&owner_tmb);
+        let revoke = RevokePayload::new(
+            Alg::Ed25519,
+            test_id(),
+            claim_czd,
+            2000,
+            RevokeReason::Abandoned,
+            other_tmb,
+        );
+        let result = verify_revoke_against_claim(&revoke, &claim_json, &claim_sig, "Ed25519");
+        assert!(
+            matches!(result, Err(crate::VerifyError::Unauthorized)),
+            "[revoke-authority]: only the claim owner may revoke: {result:?}"
+        );
+    }
+
+    #[test]
+    fn verify_revoke_against_claim_rejects_wrong_czd() {
+        let (_prv, _pub, owner_tmb) = gen_ed25519_key();
+        let (claim_json, claim_sig, _claim_czd) = signed_claim(&owner_tmb);
+        let revoke = RevokePayload::new(
+            Alg::Ed25519,
+            test_id(),
+            Czd::from_bytes(vec![0; 32]),
+            2000,
+            RevokeReason::Abandoned,
+            owner_tmb,
+        );
+        let result = verify_revoke_against_claim(&revoke, &claim_json, &claim_sig, "Ed25519");
+        assert!(
+            matches!(result, Err(crate::VerifyError::ClaimChainMismatch)),
+            "revocation naming a different claim must be rejected: {result:?}"
+        );
+    }
+
+    #[test]
+    fn verify_revoke_against_claim_rejects_identity_and_time_mismatch() {
+        let (_prv, _pub, owner_tmb) = gen_ed25519_key();
+        let (claim_json, claim_sig, claim_czd) = signed_claim(&owner_tmb);
+
+        let other_id = AtomId::new(
+            Anchor::new(vec![1, 2, 3, 4]),
+            Label::try_from("other-pkg").unwrap(),
+        );
+        let wrong_label = RevokePayload::new(
+            Alg::Ed25519,
+            other_id,
+            claim_czd.clone(),
+            2000,
+            RevokeReason::Abandoned,
+            owner_tmb.clone(),
+        );
+        let result = verify_revoke_against_claim(&wrong_label, &claim_json, &claim_sig, "Ed25519");
+        assert!(
+            matches!(result, Err(crate::VerifyError::AtomIdMismatch)),
+            "{result:?}"
+        );
+
+        let backdated = RevokePayload::new(
+            Alg::Ed25519,
+            test_id(),
+            claim_czd,
+            1000,
+            RevokeReason::Abandoned,
+            owner_tmb,
+        );
+        let result = verify_revoke_against_claim(&backdated, &claim_json, &claim_sig, "Ed25519");
+        assert!(
+            matches!(result, Err(crate::VerifyError::RevokeNotAfterClaim)),
+            "{result:?}"
+        );
+    }
+}
@@ -0,0 +1,606 @@
+//! Key rotation transactions — moving a claim to a new signing key.
+//!
+//! A rotation is signed by the claim owner's current key and names a
+//! successor thumbprint. From the rotation's `now` on, publishes may be
+//! signed by the successor; the old key keeps authorizing publishes for a
+//! bounded grace window so in-flight releases are not stranded, then stops.
+//! Rotations chain: each later one is signed by the key its predecessor
+//! named.
+//! Losing a laptop no longer means abandoning the atom identity — the claim
+//! (and its czd, and every publish's `claim` link) stays put.
+//!
+//! Spec: `docs/specs/atom-transactions.md` §RotatePayload, `[rotate-*]`.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{Alg, Anchor, AtomId, Czd, Label, Thumbprint};
+
+/// Transaction type for key rotations.
+///
+/// Spec constraint: `[rotate-typ]`.
+pub const TYP_ROTATE: &str = "atom/rotate";
+
+// ============================================================================
+// RotatePayload
+// ============================================================================
+
+/// Payload for an `atom/rotate` transaction.
+///
+/// Names the claim by its [`Czd`], repeats its `(anchor, label)`, and binds
+/// `successor` as the key authorized from `now` on. Only the claim owner
+/// may sign it (`[rotate-authority]`).
+///
+/// Spec constraints: `[rotate-typ]`, `[rotate-authority]`,
+/// `[rotate-grace]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RotatePayload {
+    /// The signing algorithm.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_alg"))]
+    pub alg: Alg,
+    /// The atom-set anchor of the rotated claim.
+    pub anchor: Anchor,
+    /// The [`Czd`] of the rotated claim.
+    pub claim: Czd,
+    /// Seconds after `now` during which the old key still authorizes
+    /// publishes. `0` retires it immediately.
+    pub grace: u64,
+    /// The atom label of the rotated claim.
+    pub label: Label,
+    /// Timestamp (seconds since Unix epoch). MUST strictly exceed the
+    /// claim's `now`.
+    pub now: u64,
+    /// Coz key thumbprint of the new signing key.
+    pub successor: Thumbprint,
+    /// Coz key thumbprint of the signing key — the claim's owner.
+    pub tmb: Thumbprint,
+    /// Transaction type — always [`TYP_ROTATE`].
+    pub typ: String,
+}
+
+impl RotatePayload {
+    /// Construct a new rotation payload.
+    ///
+    /// Takes an [`AtomId`] to ensure that the anchor and label come from
+    /// a validated identity pair. Sets `typ` to [`TYP_ROTATE`]
+    /// automatically.
+    pub fn new(
+        alg: Alg,
+        id: AtomId,
+        claim: Czd,
+        grace: u64,
+        now: u64,
+        successor: Thumbprint,
+        tmb: Thumbprint,
+    ) -> Self {
+        Self {
+            alg,
+            anchor: id.anchor,
+            claim,
+            grace,
+            label: id.label,
+            now,
+            successor,
+            tmb,
+            typ: TYP_ROTATE.to_owned(),
+        }
+    }
+
+    /// The first timestamp at which the old key no longer authorizes
+    /// publishes (saturating at `u64::MAX`).
+    #[must_use]
+    pub fn grace_end(&self) -> u64 {
+        self.now.saturating_add(self.grace)
+    }
+}
+
+/// A single rotation's raw wire components — one link in a rotation
+/// chain, as needed to re-verify its signature.
+///
+/// The rotation-chain analogue of [`TransferLink`](crate::TransferLink).
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, Copy)]
+pub struct RotateLink<'a> {
+    /// The rotation's canonical payload JSON bytes.
+    pub pay_json: &'a [u8],
+    /// The Coz signature over `pay_json`.
+    pub sig: &'a [u8],
+    /// The signing algorithm name.
+    pub alg: &'a str,
+    /// The raw public key bytes the signature is checked against.
+    pub pub_key: &'a [u8],
+}
+
+// ============================================================================
+// Verification
+// ============================================================================
+
+#[cfg(feature = "serde")]
+impl crate::TypedPayload for RotatePayload {
+    fn typ(&self) -> &str {
+        &self.typ
+    }
+
+    fn tmb(&self) -> &Thumbprint {
+        &self.tmb
+    }
+}
+
+/// Verify a signed `atom/rotate` transaction.
+///
+/// Validates the Coz signature, deserializes the payload, checks that
+/// `typ` is [`TYP_ROTATE`], and binds the declared `tmb` to `pub_key` (as
+/// [`crate::verify_revoke`] does, for the same reason).
+///
+/// Spec constraints: `[sig-over-pay]`, `[rotate-typ]`.
+#[cfg(feature = "serde")]
+pub fn verify_rotate(
+    pay_json: &[u8],
+    sig: &[u8],
+    alg: &str,
+    pub_key: &[u8],
+) -> Result<RotatePayload, crate::VerifyError> {
+    crate::verify_typed(pay_json, sig, alg, pub_key, TYP_ROTATE)
+}
+
+/// Verify a rotation against the claim it names.
+///
+/// Checks, in order:
+///
+/// - `rotate.claim == czd(claim)` ([`crate::VerifyError::ClaimChainMismatch`]).
+/// - `(rotate.anchor, rotate.label)` equals the claim's ([`crate::VerifyError::AtomIdMismatch`]).
+/// - `claim.owner` authorizes `rotate.tmb` ([`crate::VerifyError::Unauthorized`]).
+/// - `rotate.now > claim.now` ([`crate::VerifyError::RotateNotAfterClaim`]).
+///
+/// As with [`crate::verify_revoke_against_claim`], the claim's czd is
+/// recomputed from its wire components and its signature is assumed
+/// verified upstream. Returns the parsed claim on success.
+///
+/// Spec constraint: `[rotate-authority]`.
+#[cfg(feature = "serde")]
+pub fn verify_rotate_against_claim(
+    rotate: &RotatePayload,
+    claim_pay_json: &[u8],
+    claim_sig: &[u8],
+    claim_alg: &str,
+) -> Result<crate::ClaimPayload, crate::VerifyError> {
+    let claim_czd = crate::czd_for_alg(claim_pay_json, claim_sig, claim_alg)?;
+    if !crate::ct_eq(rotate.claim.as_bytes(), claim_czd.as_bytes()) {
+        return Err(crate::VerifyError::ClaimChainMismatch);
+    }
+    let claim: crate::ClaimPayload = serde_json::from_slice(claim_pay_json)?;
+    if rotate.anchor != claim.anchor || rotate.label != claim.label {
+        return Err(crate::VerifyError::AtomIdMismatch);
+    }
+    if !claim.owner.authorizes(&rotate.tmb) {
+        return Err(crate::VerifyError::Unauthorized);
+    }
+    if rotate.now <= claim.now {
+        return Err(crate::VerifyError::RotateNotAfterClaim);
+    }
+    Ok(claim)
+}
+
+/// Walk a claim → rotate → rotate … chain and return the verified
+/// rotations, oldest first.
+///
+/// `rotations` is ordered by the caller, oldest first. Each link is
+/// verified with [`verify_rotate`], then checked against the claim and its
+/// predecessor:
+///
+/// - `rotate.claim` equals the claim's recomputed czd ([`crate::VerifyError::ClaimChainMismatch`]).
+/// - `(anchor, label)` equals the claim's ([`crate::VerifyError::AtomIdMismatch`]).
+/// - The first link is signed by a key `claim.owner` authorizes, each later one by its
+///   predecessor's `successor` ([`crate::VerifyError::Unauthorized`]).
+/// - The first link's `now` strictly exceeds the claim's
+///   ([`crate::VerifyError::RotateNotAfterClaim`]), each later one's its predecessor's
+///   ([`crate::VerifyError::RotateNotAfterPrior`]).
+///
+/// An empty `rotations` slice returns no rotations. The claim's signature
+/// is assumed verified upstream (e.g. by [`crate::verify_claim`]).
+///
+/// Spec constraints: `[rotate-authority]`, `[rotate-chain]`.
+#[cfg(feature = "serde")]
+pub fn verify_rotate_chain(
+    claim_pay_json: &[u8],
+    claim_sig: &[u8],
+    claim_alg: &str,
+    rotations: &[RotateLink<'_>],
+) -> Result<Vec<RotatePayload>, crate::VerifyError> {
+    let claim: crate::ClaimPayload = serde_json::from_slice(claim_pay_json)?;
+    let claim_czd = crate::czd_for_alg(claim_pay_json, claim_sig, claim_alg)?;
+    let mut verified: Vec<RotatePayload> = Vec::with_capacity(rotations.len());
+
+    for link in rotations {
+        let rotate = verify_rotate(link.pay_json, link.sig, link.alg, link.pub_key)?;
+        if !crate::ct_eq(rotate.claim.as_bytes(), claim_czd.as_bytes()) {
+            return Err(crate::VerifyError::ClaimChainMismatch);
+        }
+        if rotate.anchor != claim.anchor || rotate.label != claim.label {
+            return Err(crate::VerifyError::AtomIdMismatch);
+        }
+        match verified.last() {
+            None => {
+                if !claim.owner.authorizes(&rotate.tmb) {
+                    return Err(crate::VerifyError::Unauthorized);
+                }
+                if rotate.now <= claim.now {
+                    return Err(crate::VerifyError::RotateNotAfterClaim);
+                }
+            },
+            Some(prior) => {
+                if !crate::ct_eq(rotate.tmb.as_bytes(), prior.successor.as_bytes()) {
+                    return Err(crate::VerifyError::Unauthorized);
+                }
+                if rotate.now <= prior.now {
+                    return Err(crate::VerifyError::RotateNotAfterPrior);
+                }
+            },
+        }
+        verified.push(rotate);
+    }
+
+    Ok(verified)
+}
+
+/// Verify a publish's signer is authorized by a claim that has been
+/// rotated — the rotation-aware form of [`crate::verify_publish_authorized`].
+///
+/// `publish.now` is asserted by the signer, so a retired key could date
+/// a publish back into its own grace window. Every window here is judged
+/// against `accepted_at` instead: the time an ordering witness outside the
+/// signer's control — the registry that accepted the publish — recorded
+/// for it.
+///
+/// Walking `rotations` oldest first, each key is in force over a window:
+///
+/// - A key the claim owner authorizes, until the first rotation's `grace_end()` (for ever if there
+///   are none).
+/// - Each rotation's `successor`, from that rotation's `now` until the next rotation's
+///   `grace_end()` (for ever for the last).
+///
+/// A signer in force at `accepted_at` is authorized. One whose window has
+/// closed fails with [`crate::VerifyError::RotationGraceExpired`]; any
+/// other signer, or a successor before its window opens, is
+/// [`crate::VerifyError::Unauthorized`].
+///
+/// `rotations` MUST be the output of [`verify_rotate_chain`] for `claim`;
+/// the same `publish.tmb` binding precondition as
+/// [`crate::verify_publish_authorized`] applies.
+///
+/// Spec constraints: `[rotate-grace]`, `[rotate-chain]`.
+#[cfg(feature = "serde")]
+pub fn verify_publish_authorized_rotated(
+    publish: &crate::PublishPayload,
+    claim: &crate::ClaimPayload,
+    rotations: &[RotatePayload],
+    accepted_at: u64,
+) -> Result<(), crate::VerifyError> {
+    let mut retired = false;
+    if claim.owner.authorizes(&publish.tmb) {
+        match rotations.first() {
+            Some(first) if accepted_at >= first.grace_end() => retired = true,
+            _ => return Ok(()),
+        }
+    }
+    for (i, rotate) in rotations.iter().enumerate() {
+        if !crate::ct_eq(publish.tmb.as_bytes(), rotate.successor.as_bytes())
+            || accepted_at < rotate.now
+        {
+            continue;
+        }
+        match rotations.get(i + 1) {
+            Some(next) if accepted_at >= next.grace_end() => retired = true,
+            _ => return Ok(()),
+        }
+    }
+    if retired {
+        return Err(crate::VerifyError::RotationGraceExpired);
+    }
+    Err(crate::VerifyError::Unauthorized)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{gen_ed25519_key, signed_claim, test_id};
+    use crate::{ClaimPayload, PublishPayload, RawVersion};
+
+    fn publish_by(tmb: &Thumbprint, claim: &Czd, now: u64) -> PublishPayload {
+        PublishPayload::new(
+            Alg::Ed25519,
+            test_id(),
+            claim.clone(),
+            vec![7, 8],
+            now,
+            "src/lib".into(),
+            vec![9, 10],
+            tmb.clone(),
+            RawVersion::new("1.0.0".into()),
+        )
+    }
+
+    #[test]
+    fn rotate_payload_typ_and_serde_roundtrip() {
+        let rotate = RotatePayload::new(
+            Alg::ES256,
+            test_id(),
+            Czd::from_bytes(vec![5, 6]),
+            600,
+            2000,
+            Thumbprint::from_bytes(vec![40, 50]),
+            Thumbprint::from_bytes(vec![10, 20, 30]),
+        );
+        assert_eq!(rotate.typ, "atom/rotate");
+        assert_eq!(rotate.grace_end(), 2600);
+        let json = serde_json::to_string(&rotate).unwrap();
+        let back: RotatePayload = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, rotate);
+    }
+
+    #[test]
+    fn verify_rotate_roundtrip_and_wrong_typ() {
+        let (prv, pub_bytes, tmb) = gen_ed25519_key();
+        let rotate = RotatePayload::new(
+            Alg::Ed25519,
+            test_id(),
+            Czd::from_bytes(vec![5, 6]),
+            600,
+            2000,
+            Thumbprint::from_bytes(vec![40, 50]),
+            tmb,
+        );
+        let pay_json = serde_json::to_vec(&rotate).unwrap();
+        let (sig, _cad) = coz_rs::sign_json(&pay_json, "Ed25519", &prv, &pub_bytes).unwrap();
+        assert_eq!(
+            verify_rotate(&pay_json, &sig, "Ed25519", &pub_bytes).unwrap(),
+            rotate
+        );
+
+        let mut json_val = serde_json::to_value(&rotate).unwrap();
+        json_val["typ"] = serde_json::Value::String("atom/claim".into());
+        let pay_json = serde_json::to_vec(&json_val).unwrap();
+        let (sig, _cad) = coz_rs::sign_json(&pay_json, "Ed25519", &prv, &pub_bytes).unwrap();
+        let result = verify_rotate(&pay_json, &sig, "Ed25519", &pub_bytes);
+        assert!(
+            matches!(result, Err(crate::VerifyError::WrongTyp { .. })),
+            "{result:?}"
+        );
+    }
+
+    #[test]
+    fn verify_rotate_against_claim_requires_owner() {
+        let (_prv, _pub, owner_tmb) = gen_ed25519_key();
+        let (_other_prv, _other_pub, other_tmb) = gen_ed25519_key();
+        let (claim_json, claim_sig, claim_czd) = signed_claim(&owner_tmb);
+
+        let by_owner = RotatePayload::new(
+            Alg::Ed25519,
+            test_id(),
+            claim_czd.clone(),
+            600,
+            2000,
+            other_tmb.clone(),
+            owner_tmb.clone(),
+        );
+        let result = verify_rotate_against_claim(&by_owner, &claim_json, &claim_sig, "Ed25519");
+        assert!(result.is_ok(), "owner rotation must verify: {result:?}");
+
+        let by_other = RotatePayload {
+            tmb: other_tmb,
+            ..by_owner.clone()
+        };
+        let result = verify_rotate_against_claim(&by_other, &claim_json, &claim_sig, "Ed25519");
+        assert!(
+            matches!(result, Err(crate::VerifyError::Unauthorized)),
+            "{result:?}"
+        );
+
+        let backdated = RotatePayload {
+            now: 1000,
+            ..by_owner
+        };
+        let result = verify_rotate_against_claim(&backdated, &claim_json, &claim_sig, "Ed25519");
+        assert!(
+            matches!(result, Err(crate::VerifyError::RotateNotAfterClaim)),
+            "{result:?}"
+        );
+    }
+
+    /// A rotation of the claim `claim` to `successor`, signed by `key`,
+    /// as `(payload, pay_json, sig)`.
+    fn signed_rotate(
+        claim: &Czd,
+        now: u64,
+        successor: &Thumbprint,
+        key: &(Vec<u8>, Vec<u8>, Thumbprint),
+    ) -> (RotatePayload, Vec<u8>, Vec<u8>) {
+        let rotate = RotatePayload::new(
+            Alg::Ed25519,
+            test_id(),
+            claim.clone(),
+            600,
+            now,
+            successor.clone(),
+            key.2.clone(),
+        );
+        let pay_json = serde_json::to_vec(&rotate).unwrap();
+        let (sig, _cad) = coz_rs::sign_json(&pay_json, "Ed25519", &key.0, &key.1).unwrap();
+        (rotate, pay_json, sig)
+    }
+
+    fn link<'a>(pay_json: &'a [u8], sig: &'a [u8], pub_key: &'a [u8]) -> RotateLink<'a> {
+        RotateLink {
+            pay_json,
+            sig,
+            alg: "Ed25519",
+            pub_key,
+        }
+    }
+
+    #[test]
+    fn rotated_publish_authorization_honours_grace_window() {
+        let (_old_prv, _old_pub, old_tmb) = gen_ed25519_key();
+        let (_new_prv, _new_pub, new_tmb) = gen_ed25519_key();
+        let (_x_prv, _x_pub, stranger_tmb) = gen_ed25519_key();
+        let (claim_json, _claim_sig, claim_czd) = signed_claim(&old_tmb);
+        let claim: ClaimPayload = serde_json::from_slice(&claim_json).unwrap();
+        let rotations = [RotatePayload::new(
+            Alg::Ed25519,
+            test_id(),
+            claim_czd.clone(),
+            600,
+            2000,
+            new_tmb.clone(),
+            old_tmb.clone(),
+        )];
+        let check = |tmb: &Thumbprint, accepted_at| {
+            let publish = publish_by(tmb, &claim_czd, accepted_at);
+            verify_publish_authorized_rotated(&publish, &claim, &rotations, accepted_at)
+        };
+
+        assert!(check(&old_tmb, 1500).is_ok());
+        assert!(check(&old_tmb, 2599).is_ok());
+        assert!(matches!(
+            check(&old_tmb, 2600),
+            Err(crate::VerifyError::RotationGraceExpired)
+        ));
+        assert!(check(&new_tmb, 2000).is_ok());
+        assert!(check(&new_tmb, 9000).is_ok());
+        assert!(matches!(
+            check(&new_tmb, 1999),
+            Err(crate::VerifyError::Unauthorized)
+        ));
+        assert!(matches!(
+            check(&stranger_tmb, 2100),
+            Err(crate::VerifyError::Unauthorized)
+        ));
+    }
+
+    #[test]
+    fn rotated_publish_authorization_ignores_a_backdated_now() {
+        let (_old_prv, _old_pub, old_tmb) = gen_ed25519_key();
+        let (_new_prv, _new_pub, new_tmb) = gen_ed25519_key();
+        let (claim_json, _claim_sig, claim_czd) = signed_claim(&old_tmb);
+        let claim: ClaimPayload = serde_json::from_slice(&claim_json).unwrap();
+        let rotate = RotatePayload::new(
+            Alg::Ed25519,
+            test_id(),
+            claim_czd.clone(),
+            600,
+            2000,
+            new_tmb,
+            old_tmb.clone(),
+        );
+
+        // The retired key dates its publish inside the grace window, but
+        // the registry accepted it long after.
+        let backdated = publish_by(&old_tmb, &claim_czd, 2100);
+        let result = verify_publish_authorized_rotated(&backdated, &claim, &[rotate], 9000);
+        assert!(
+            matches!(result, Err(crate::VerifyError::RotationGraceExpired)),
+            "{result:?}"
+        );
+    }
+
+    #[test]
+    fn rotate_chain_hands_authority_down_each_link() {
+        let k0 = gen_ed25519_key();
+        let k1 = gen_ed25519_key();
+        let k2 = gen_ed25519_key();
+        let (claim_json, claim_sig, claim_czd) = signed_claim(&k0.2);
+        let claim: ClaimPayload = serde_json::from_slice(&claim_json).unwrap();
+        let (_, r1_json, r1_sig) = signed_rotate(&claim_czd, 2000, &k1.2, &k0);
+        let (_, r2_json, r2_sig) = signed_rotate(&claim_czd, 3000, &k2.2, &k1);
+
+        let rotations = verify_rotate_chain(
+            &claim_json,
+            &claim_sig,
+            "Ed25519",
+            &[
+                link(&r1_json, &r1_sig, &k0.1),
+                link(&r2_json, &r2_sig, &k1.1),
+            ],
+        )
+        .unwrap();
+        assert_eq!(rotations.len(), 2);
+
+        let check = |tmb: &Thumbprint, accepted_at| {
+            let publish = publish_by(tmb, &claim_czd, accepted_at);
+            verify_publish_authorized_rotated(&publish, &claim, &rotations, accepted_at)
+        };
+        assert!(check(&k0.2, 2599).is_ok());
+        assert!(matches!(
+            check(&k0.2, 2600),
+            Err(crate::VerifyError::RotationGraceExpired)
+        ));
+        assert!(check(&k1.2, 3599).is_ok());
+        assert!(matches!(
+            check(&k1.2, 3600),
+            Err(crate::VerifyError::RotationGraceExpired)
+        ));
+        assert!(matches!(
+            check(&k2.2, 2999),
+            Err(crate::VerifyError::Unauthorized)
+        ));
+        assert!(check(&k2.2, 3000).is_ok());
+    }
+
+    #[test]
+    fn rotate_chain_rejects_a_stale_signer_and_backdating() {
+        let k0 = gen_ed25519_key();
+        let k1 = gen_ed25519_key();
+        let k2 = gen_ed25519_key();
+        let (claim_json, claim_sig, claim_czd) = signed_claim(&k0.2);
+        let (_, r1_json, r1_sig) = signed_rotate(&claim_czd, 2000, &k1.2, &k0);
+
+        // The second rotation must come from k1, not the retired k0.
+        let (_, stale_json, stale_sig) = signed_rotate(&claim_czd, 3000, &k2.2, &k0);
+        let result = verify_rotate_chain(
+            &claim_json,
+            &claim_sig,
+            "Ed25519",
+            &[
+                link(&r1_json, &r1_sig, &k0.1),
+                link(&stale_json, &stale_sig, &k0.1),
+            ],
+        );
+        assert!(
+            matches!(result, Err(crate::VerifyError::Unauthorized)),
+            "{result:?}"
+        );
+
+        let (_, early_json, early_sig) = signed_rotate(&claim_czd, 2000, &k2.2, &k1);
+        let result = verify_rotate_chain(
+            &claim_json,
+            &claim_sig,
+            "Ed25519",
+            &[
+                link(&r1_json, &r1_sig, &k0.1),
+                link(&early_json, &early_sig, &k1.1),
+            ],
+        );
+        assert!(
+            matches!(result, Err(crate::VerifyError::RotateNotAfterPrior)),
+            "{result:?}"
+        );
+
+        let other = Czd::from_bytes(vec![0; 32]);
+        let (_, wrong_json, wrong_sig) = signed_rotate(&other, 2000, &k1.2, &k0);
+        let result = verify_rotate_chain(
+            &claim_json,
+            &claim_sig,
+            "Ed25519",
+            &[link(&wrong_json, &wrong_sig, &k0.1)],
+        );
+        assert!(
+            matches!(result, Err(crate::VerifyError::ClaimChainMismatch)),
+            "{result:?}"
+        );
+    }
+}
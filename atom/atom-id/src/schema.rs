@@ -0,0 +1,199 @@
+//! JSON Schema descriptions of the wire forms (`schemars` feature).
+//!
+//! Registry HTTP APIs can publish these schemas and reject malformed
+//! submissions before spending a signature check on them. The schemas are
+//! written by hand rather than derived: the payloads' byte fields travel as
+//! base64url strings and several field types come from coz-rs, so a derive
+//! would describe the Rust types instead of the JSON a client actually
+//! sends.
+//!
+//! A schema is a first filter only. Passing it says nothing about the
+//! signature, the label's UAX #31 validity, or any chain check — the
+//! verification pipeline still runs in full.
+
+use std::borrow::Cow;
+
+use schemars::{JsonSchema, Schema, SchemaGenerator, json_schema};
+use serde_json::{Value, json};
+
+use crate::{AtomId, ClaimPayload, PublishPayload, TYP_CLAIM, TYP_PUBLISH};
+
+/// A base64url-unpadded byte string.
+fn b64() -> Value {
+    json!({
+        "type": "string",
+        "pattern": "^[A-Za-z0-9_-]*$",
+        "contentEncoding": "base64url",
+    })
+}
+
+fn label() -> Value {
+    json!({ "type": "string", "minLength": 1 })
+}
+
+fn owner_ref() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "kind": {
+                "enum": ["single-key", "hierarchical", "rooted-identity", "did"],
+            },
+            "value": b64(),
+        },
+        "required": ["kind", "value"],
+        "additionalProperties": false,
+    })
+}
+
+impl JsonSchema for AtomId {
+    fn schema_name() -> Cow<'static, str> {
+        "AtomId".into()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "description": "`<anchor_b64ut>::<label>`",
+            "type": "string",
+            "pattern": "^[A-Za-z0-9_-]*::.+$",
+        })
+    }
+}
+
+impl JsonSchema for ClaimPayload {
+    fn schema_name() -> Cow<'static, str> {
+        "ClaimPayload".into()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "type": "object",
+            "properties": {
+                "alg": { "type": "string" },
+                "anchor": b64(),
+                "governance": { "type": "boolean" },
+                "label": label(),
+                "now": { "type": "integer", "minimum": 0 },
+                "owner": owner_ref(),
+                "pkg": { "type": "string" },
+                "prior": { "anyOf": [b64(), { "type": "null" }] },
+                "src": b64(),
+                "tmb": b64(),
+                "typ": { "const": TYP_CLAIM },
+                "exp": { "type": "integer", "minimum": 0 },
+                "nonce": b64(),
+                "pv": { "type": "integer", "minimum": 1 },
+                "meta": { "type": "object" },
+            },
+            "required": [
+                "alg", "anchor", "governance", "label", "now", "owner", "pkg", "src", "tmb",
+                "typ",
+            ],
+            "additionalProperties": false,
+        })
+    }
+}
+
+impl JsonSchema for PublishPayload {
+    fn schema_name() -> Cow<'static, str> {
+        "PublishPayload".into()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "type": "object",
+            "properties": {
+                "alg": { "type": "string" },
+                "anchor": b64(),
+                "claim": b64(),
+                "dig": b64(),
+                "label": label(),
+                "now": { "type": "integer", "minimum": 0 },
+                "path": { "type": "string" },
+                "src": b64(),
+                "tmb": b64(),
+                "version": { "type": "string" },
+                "typ": { "const": TYP_PUBLISH },
+                "mode": { "enum": ["reproducible", "witnessed"] },
+                "content_hash": b64(),
+                "nonce": b64(),
+                "pv": { "type": "integer", "minimum": 1 },
+                "meta": { "type": "object" },
+            },
+            "required": [
+                "alg", "anchor", "claim", "dig", "label", "now", "path", "src", "tmb",
+                "version", "typ",
+            ],
+            "additionalProperties": false,
+        })
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Alg, Anchor, Czd, Label, Mode, OwnerRef, RawVersion, Thumbprint};
+
+    fn test_id() -> AtomId {
+        AtomId::new(
+            Anchor::new(vec![1, 2, 3, 4]),
+            Label::try_from("my-pkg").unwrap(),
+        )
+    }
+
+    /// Every key the payload serializes is a declared property, and every
+    /// required property is serialized.
+    fn check<T: JsonSchema + serde::Serialize>(payload: &T) {
+        let schema = schemars::schema_for!(T);
+        let properties = schema.get("properties").and_then(Value::as_object).unwrap();
+        let Value::Object(wire) = serde_json::to_value(payload).unwrap() else {
+            panic!("payloads serialize to objects");
+        };
+        for key in wire.keys() {
+            assert!(properties.contains_key(key), "{key} missing from schema");
+        }
+        for key in schema.get("required").and_then(Value::as_array).unwrap() {
+            assert!(
+                wire.contains_key(key.as_str().unwrap()),
+                "{key} not on the wire"
+            );
+        }
+    }
+
+    #[test]
+    fn schemas_cover_the_wire_form() {
+        let tmb = Thumbprint::from_bytes(vec![1]);
+        let mut claim = ClaimPayload::new(
+            Alg::Ed25519,
+            test_id(),
+            1000,
+            OwnerRef::single_key(&tmb),
+            "cargo".into(),
+            vec![0; 4],
+            tmb.clone(),
+        );
+        claim.nonce = Some(vec![9]);
+        check(&claim);
+
+        let mut publish = PublishPayload::new(
+            Alg::Ed25519,
+            test_id(),
+            Czd::from_bytes(vec![5, 6]),
+            vec![7, 8],
+            2000,
+            "src/lib".into(),
+            vec![9, 10],
+            tmb,
+            RawVersion::new("1.0.0".into()),
+        );
+        publish.mode = Some(Mode::Reproducible);
+        publish.content_hash = Some(vec![1; 32]);
+        check(&publish);
+
+        let id = schemars::schema_for!(AtomId);
+        assert_eq!(id.get("type"), Some(&json!("string")));
+    }
+}
@@ -0,0 +1,239 @@
+//! Signing payloads in one call.
+//!
+//! Producing a transaction by hand means serializing the payload, signing
+//! the bytes with `coz_rs::sign_json`, recomputing the czd, and keeping
+//! the payload's `alg`/`tmb` consistent with the key throughout. A
+//! [`Signer`] owns the key material and derives the thumbprint once;
+//! [`ClaimPayload::sign`] and [`PublishPayload::sign`] then produce the
+//! canonical bytes, signature and czd together as a [`SignedPayload`].
+
+use std::fmt;
+
+use thiserror::Error;
+
+use crate::{
+    Alg, Cad, CanonicalPayload, ClaimBuilder, ClaimPayload, Czd, PublishBuilder, PublishPayload,
+    Thumbprint, Unset,
+};
+
+/// Errors from [`Signer`] construction and payload signing.
+#[derive(Error, Debug)]
+pub enum SignError {
+    /// The payload could not be serialized to canonical JSON.
+    #[error("payload serialization error: {0}")]
+    Serialize(#[from] serde_json::Error),
+    /// The signing algorithm is not supported by coz-rs.
+    #[error("unsupported algorithm: {0}")]
+    UnsupportedAlgorithm(String),
+    /// The payload's `alg`/`tmb` do not name the signer's key.
+    #[error("payload alg/tmb do not match the signing key")]
+    KeyMismatch,
+    /// coz-rs rejected the key material.
+    #[error("signing failed")]
+    Signing,
+}
+
+/// A signing key: algorithm, key pair, and the derived thumbprint.
+///
+/// `Debug` never prints the private key.
+#[derive(Clone)]
+pub struct Signer {
+    alg: Alg,
+    prv: Vec<u8>,
+    pub_key: Vec<u8>,
+    tmb: Thumbprint,
+}
+
+impl Signer {
+    /// Wrap a key pair for `alg`, deriving its thumbprint.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SignError::UnsupportedAlgorithm`] if coz-rs cannot
+    /// thumbprint keys of `alg`.
+    pub fn new(alg: Alg, prv: Vec<u8>, pub_key: Vec<u8>) -> Result<Self, SignError> {
+        let tmb = coz_rs::compute_thumbprint_for_alg(alg.name(), &pub_key)
+            .ok_or_else(|| SignError::UnsupportedAlgorithm(alg.name().to_owned()))?;
+        Ok(Self {
+            alg,
+            prv,
+            pub_key,
+            tmb,
+        })
+    }
+
+    /// The signing algorithm.
+    #[must_use]
+    pub fn alg(&self) -> Alg {
+        self.alg
+    }
+
+    /// The public key bytes — the `key` a Coz message carries.
+    #[must_use]
+    pub fn pub_key(&self) -> &[u8] {
+        &self.pub_key
+    }
+
+    /// The key's thumbprint — the `tmb` every payload it signs declares.
+    #[must_use]
+    pub fn thumbprint(&self) -> &Thumbprint {
+        &self.tmb
+    }
+
+    /// Sign `payload`'s canonical JSON. `alg` and `tmb` are the payload's
+    /// declared values, checked against this key before anything is
+    /// signed.
+    fn sign_canonical<P: CanonicalPayload>(
+        &self,
+        payload: &P,
+        alg: Alg,
+        tmb: &Thumbprint,
+    ) -> Result<SignedPayload, SignError> {
+        if alg != self.alg || !crate::ct_eq(tmb.as_bytes(), self.tmb.as_bytes()) {
+            return Err(SignError::KeyMismatch);
+        }
+        let pay_json = payload.to_canonical_json()?;
+        let (sig, cad) = coz_rs::sign_json(&pay_json, self.alg.name(), &self.prv, &self.pub_key)
+            .ok_or(SignError::Signing)?;
+        let czd = coz_rs::czd_for_alg(&cad, &sig, self.alg.name())
+            .ok_or_else(|| SignError::UnsupportedAlgorithm(self.alg.name().to_owned()))?;
+        Ok(SignedPayload {
+            pay_json,
+            sig,
+            cad,
+            czd,
+        })
+    }
+}
+
+impl fmt::Debug for Signer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Signer")
+            .field("alg", &self.alg)
+            .field("tmb", &self.tmb)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The output of signing a payload: everything a Coz message and a store
+/// index need.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignedPayload {
+    /// The canonical JSON bytes that were signed (`[canonical-payload-bytes]`).
+    pub pay_json: Vec<u8>,
+    /// The signature over `pay_json`.
+    pub sig: Vec<u8>,
+    /// The canonical digest of `pay_json`.
+    pub cad: Cad,
+    /// The payload's czd — what a publish's `claim` field or a store's
+    /// index refers to it by.
+    pub czd: Czd,
+}
+
+impl ClaimPayload {
+    /// Sign this claim with `signer`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SignError::KeyMismatch`] if the claim's `alg` or `tmb`
+    /// does not name `signer`'s key, or a serialization or signing error.
+    pub fn sign(&self, signer: &Signer) -> Result<SignedPayload, SignError> {
+        signer.sign_canonical(self, self.alg, &self.tmb)
+    }
+}
+
+impl PublishPayload {
+    /// Sign this publish with `signer`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SignError::KeyMismatch`] if the publish's `alg` or `tmb`
+    /// does not name `signer`'s key, or a serialization or signing error.
+    pub fn sign(&self, signer: &Signer) -> Result<SignedPayload, SignError> {
+        signer.sign_canonical(self, self.alg, &self.tmb)
+    }
+}
+
+impl<A, N, O, P, S> ClaimBuilder<A, N, O, P, S, Unset> {
+    /// Take `alg` and `tmb` from `signer` — the key [`ClaimPayload::sign`]
+    /// will then accept.
+    pub fn signed_by(self, signer: &Signer) -> ClaimBuilder<A, N, O, P, S, (Alg, Thumbprint)> {
+        self.signer(signer.alg, signer.tmb.clone())
+    }
+}
+
+impl<C, V, D, S, P, N> PublishBuilder<C, V, D, S, P, N, Unset> {
+    /// Take `alg` and `tmb` from `signer` — the key
+    /// [`PublishPayload::sign`] will then accept.
+    pub fn signed_by(self, signer: &Signer) -> PublishBuilder<C, V, D, S, P, N, (Alg, Thumbprint)> {
+        self.signer(signer.alg, signer.tmb.clone())
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Anchor, AtomId, Label, OwnerRef};
+
+    fn test_signer() -> Signer {
+        use coz_rs::Ed25519;
+
+        let sk = coz_rs::SigningKey::<Ed25519>::generate();
+        let pub_key = sk.verifying_key().public_key_bytes().to_vec();
+        Signer::new(Alg::Ed25519, sk.private_key_bytes(), pub_key).unwrap()
+    }
+
+    fn test_id() -> AtomId {
+        AtomId::new(
+            Anchor::new(vec![1, 2, 3, 4]),
+            Label::try_from("my-pkg").unwrap(),
+        )
+    }
+
+    #[test]
+    fn signed_claim_verifies_and_carries_its_czd() {
+        let signer = test_signer();
+        let claim = ClaimPayload::builder()
+            .atom(test_id())
+            .now(1000)
+            .owner(OwnerRef::single_key(signer.thumbprint()))
+            .pkg("cargo".into())
+            .src(vec![0; 20])
+            .signed_by(&signer)
+            .build();
+        let signed = claim.sign(&signer).unwrap();
+
+        let verified = crate::verify_claim(
+            &signed.pay_json,
+            &signed.sig,
+            "Ed25519",
+            signer.pub_key(),
+            None,
+        )
+        .unwrap();
+        assert_eq!(verified.payload(), &claim);
+        assert_eq!(verified.czd(), &signed.czd);
+        assert_eq!(verified.cad(), &signed.cad);
+        assert_eq!(signed.pay_json, claim.to_canonical_json().unwrap());
+    }
+
+    #[test]
+    fn signing_rejects_a_payload_declaring_another_key() {
+        let signer = test_signer();
+        let claim = ClaimPayload::new(
+            Alg::Ed25519,
+            test_id(),
+            1000,
+            OwnerRef::single_key(signer.thumbprint()),
+            "cargo".into(),
+            vec![0; 20],
+            Thumbprint::from_bytes(vec![9; 32]),
+        );
+        assert!(matches!(claim.sign(&signer), Err(SignError::KeyMismatch)));
+        assert!(!format!("{signer:?}").contains("prv"));
+    }
+}
@@ -0,0 +1,106 @@
+//! Similarity scoring between labels.
+//!
+//! Two consumers need to know how alike two labels look: `discover`
+//! ranking, which orders near matches for a query, and registries, which
+//! flag a claim whose label sits suspiciously close to an existing one
+//! (`my_pkg` next to `my-pkg`, `Serde` next to `serde`, `раckage` next to
+//! `package`). [`label_similarity`] serves both with one score.
+
+use crate::Label;
+
+/// How alike `a` and `b` look, from `0.0` (nothing in common) to `1.0`
+/// (indistinguishable to a reader).
+///
+/// Both labels are first reduced to a comparison key: case-folded
+/// ([`Label::folded`]), with `_` read as `-`, and — with the
+/// `confusables` feature — mapped to their UTS #39 skeleton. Labels with
+/// equal keys score `1.0` even when they differ as labels; otherwise the
+/// score is one minus the Levenshtein distance between the keys, over the
+/// longer key's length in characters.
+///
+/// The score is symmetric. Where to draw the line is the caller's policy:
+/// a registry might refuse claims above `0.9` and warn above `0.75`.
+#[must_use]
+pub fn label_similarity(a: &Label, b: &Label) -> f64 {
+    if a == b {
+        return 1.0;
+    }
+    let (a, b) = (comparison_key(a), comparison_key(b));
+    let longest = a.len().max(b.len());
+    if longest == 0 {
+        return 1.0;
+    }
+    // Key lengths are bounded by NAME_MAX, so the casts are exact.
+    1.0 - levenshtein(&a, &b) as f64 / longest as f64
+}
+
+/// The characters a reader compares `label` by.
+fn comparison_key(label: &Label) -> Vec<char> {
+    let folded = label.folded();
+    #[cfg(feature = "confusables")]
+    let key: String = unicode_security::skeleton(folded.as_str()).collect();
+    #[cfg(not(feature = "confusables"))]
+    let key = folded.as_str().to_owned();
+    key.chars()
+        .map(|c| if c == '_' { '-' } else { c })
+        .collect()
+}
+
+/// Levenshtein distance over characters, in one row of `O(len(b))` memory.
+fn levenshtein(a: &[char], b: &[char]) -> usize {
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.iter().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn label(s: &str) -> Label {
+        Label::try_from(s).unwrap()
+    }
+
+    #[test]
+    fn lookalikes_score_one() {
+        assert_eq!(label_similarity(&label("serde"), &label("serde")), 1.0);
+        assert_eq!(label_similarity(&label("Serde"), &label("serde")), 1.0);
+        assert_eq!(label_similarity(&label("my_pkg"), &label("my-pkg")), 1.0);
+    }
+
+    #[test]
+    fn score_falls_with_edit_distance() {
+        let base = label("package");
+        let one_off = label_similarity(&base, &label("packages"));
+        let two_off = label_similarity(&base, &label("pakcage"));
+        let unrelated = label_similarity(&base, &label("tokio"));
+        assert!((one_off - 7.0 / 8.0).abs() < f64::EPSILON);
+        assert!(two_off < one_off);
+        assert!(unrelated < two_off);
+        assert!((0.0..=1.0).contains(&unrelated));
+        assert_eq!(
+            label_similarity(&base, &label("pakcage")),
+            label_similarity(&label("pakcage"), &base)
+        );
+    }
+
+    #[test]
+    fn levenshtein_counts_edits() {
+        let chars = |s: &str| s.chars().collect::<Vec<_>>();
+        assert_eq!(levenshtein(&chars("kitten"), &chars("sitting")), 3);
+        assert_eq!(levenshtein(&chars(""), &chars("abc")), 3);
+        assert_eq!(levenshtein(&chars("abc"), &chars("abc")), 0);
+    }
+}
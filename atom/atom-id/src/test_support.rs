@@ -0,0 +1,41 @@
+//! Fixtures shared by the unit tests of the signed transaction modules.
+
+use crate::{Alg, Anchor, AtomId, ClaimPayload, Czd, Label, OwnerRef, Thumbprint};
+
+/// Generate an Ed25519 key pair and return `(prv_bytes, pub_bytes, thumbprint)`.
+pub(crate) fn gen_ed25519_key() -> (Vec<u8>, Vec<u8>, Thumbprint) {
+    use coz_rs::Ed25519;
+
+    let sk = coz_rs::SigningKey::<Ed25519>::generate();
+    let prv = sk.private_key_bytes();
+    let pub_bytes = sk.verifying_key().public_key_bytes().to_vec();
+    let tmb = sk.thumbprint().clone();
+    (prv, pub_bytes, tmb)
+}
+
+/// The identity every transaction fixture is about.
+pub(crate) fn test_id() -> AtomId {
+    AtomId::new(
+        Anchor::new(vec![1, 2, 3, 4]),
+        Label::try_from("my-pkg").unwrap(),
+    )
+}
+
+/// A claim of [`test_id`] owned by `owner`, signed by a fresh key and
+/// returned as `(pay_json, sig, czd)`.
+pub(crate) fn signed_claim(owner: &Thumbprint) -> (Vec<u8>, Vec<u8>, Czd) {
+    let (prv, pub_bytes, tmb) = gen_ed25519_key();
+    let claim = ClaimPayload::new(
+        Alg::Ed25519,
+        test_id(),
+        1000,
+        OwnerRef::single_key(owner),
+        "cargo".into(),
+        vec![0; 32],
+        tmb,
+    );
+    let pay_json = serde_json::to_vec(&claim).unwrap();
+    let (sig, _cad) = coz_rs::sign_json(&pay_json, "Ed25519", &prv, &pub_bytes).unwrap();
+    let czd = crate::czd_for_alg(&pay_json, &sig, "Ed25519").unwrap();
+    (pay_json, sig, czd)
+}
@@ -1,16 +1,27 @@
 //! Tests for atom identity types.
 
 use std::ffi::OsStr;
+use std::ops::Range;
 use std::str::FromStr;
 
+use crate::test_support::gen_ed25519_key;
 use crate::{
-    Anchor, AtomId, Error, Identifier, Label, NAME_MAX, OwnerKind, OwnerRef, RawVersion, Tag,
+    Anchor, Anchor32, AtomId, CharSpan, Error, Identifier, Label, NAME_MAX, OwnerKind, OwnerRef,
+    RawRequirement, RawVersion, Tag,
 };
 
 // ============================================================================
 // Label
 // ============================================================================
 
+/// The span of a rejected character in an input NFKC leaves unchanged.
+fn at(range: Range<usize>) -> CharSpan {
+    CharSpan {
+        normalized: range.clone(),
+        original: Some(range),
+    }
+}
+
 #[test]
 fn label_valid_representative() {
     // Latin + extensions, CJK, Mixed — 3 representative cases
@@ -35,7 +46,10 @@ fn label_rejects_invalid_start() {
     ] {
         assert_eq!(
             Label::try_from(input),
-            Err(Error::InvalidStart(expected_char)),
+            Err(Error::InvalidStart {
+                found: expected_char,
+                span: at(0..1),
+            }),
         );
     }
 }
@@ -45,31 +59,78 @@ fn label_rejects_invalid_chars() {
     // Multiple invalid chars collected
     assert_eq!(
         Label::try_from("a-!@#$%^&*()_-asdf"),
-        Err(Error::InvalidCharacters("!@#$%^&*()".into())),
+        Err(Error::InvalidCharacters {
+            found: "!@#$%^&*()".into(),
+            spans: (2..12).map(|i| at(i..i + 1)).collect(),
+        }),
     );
     // Space
     assert_eq!(
         Label::try_from("hello world"),
-        Err(Error::InvalidCharacters(" ".into())),
+        Err(Error::InvalidCharacters {
+            found: " ".into(),
+            spans: vec![at(5..6)],
+        }),
     );
     // Emoji
     assert_eq!(
         Label::try_from("Café♥"),
-        Err(Error::InvalidCharacters("♥".into())),
+        Err(Error::InvalidCharacters {
+            found: "♥".into(),
+            spans: vec![at(5..8)],
+        }),
     );
 }
 
+#[test]
+fn label_error_spans_locate_original_input() {
+    // Full-width characters normalize to shorter ASCII.
+    assert_eq!(
+        Label::try_from("ａｂ！"),
+        Err(Error::InvalidCharacters {
+            found: "!".into(),
+            spans: vec![CharSpan {
+                normalized: 2..3,
+                original: Some(6..9),
+            }],
+        }),
+    );
+    assert_eq!(
+        Label::try_from("！ab"),
+        Err(Error::InvalidStart {
+            found: '!',
+            span: CharSpan {
+                normalized: 0..1,
+                original: Some(0..3),
+            },
+        }),
+    );
+    // A combining mark composes with the preceding character, so no
+    // original range maps exactly; the normalized range still does.
+    let Err(Error::InvalidCharacters { spans, .. }) = Label::try_from("e\u{301}!") else {
+        panic!("expected InvalidCharacters");
+    };
+    assert_eq!(spans[0].normalized, 2..3);
+    assert_eq!(spans[0].original, None);
+}
+
 #[test]
 fn label_rejects_zero_width_space() {
     // ZWS as start
     assert_eq!(
         Label::try_from("\u{200B}"),
-        Err(Error::InvalidStart('\u{200B}')),
+        Err(Error::InvalidStart {
+            found: '\u{200B}',
+            span: at(0..3),
+        }),
     );
     // ZWS in middle
     assert_eq!(
         Label::try_from("α\u{200B}"),
-        Err(Error::InvalidCharacters("\u{200B}".into())),
+        Err(Error::InvalidCharacters {
+            found: "\u{200B}".into(),
+            spans: vec![at(2..5)],
+        }),
     );
 }
 
@@ -78,7 +139,10 @@ fn label_rejects_control_chars() {
     for (input, bad) in [("Öö\t", "\t"), ("Ææ\n", "\n"), ("Łł\r", "\r")] {
         assert_eq!(
             Label::try_from(input),
-            Err(Error::InvalidCharacters(bad.into())),
+            Err(Error::InvalidCharacters {
+                found: bad.into(),
+                spans: vec![at(4..5)],
+            }),
         );
     }
 }
@@ -114,6 +178,73 @@ fn label_display_deref() {
     assert_eq!(s, "myLabel");
 }
 
+#[test]
+fn label_case_fold() {
+    let label = |s| Label::try_from(s).unwrap();
+    assert!(label("MyPkg").eq_fold(&label("mypkg")));
+    assert!(label("Straße").eq_fold(&label("STRASSE")));
+    assert!(label("ΣΟΦΟΣ").eq_fold(&label("σοφος")));
+    assert!(!label("my-pkg").eq_fold(&label("my_pkg")));
+    assert_eq!(label("ẞig").folded().as_str(), "ssig");
+}
+
+#[test]
+fn label_case_fold_is_nfkc_casefold() {
+    let label = |s| Label::try_from(s).unwrap();
+    // Full foldings that lowercasing alone misses.
+    assert!(label("ᾼ").eq_fold(&label("ΑΙ")));
+    assert!(label("ᾳ").eq_fold(&label("αι")));
+    assert!(label("ᾈ").eq_fold(&label("ἀι")));
+    assert_eq!(label("ᾼ").folded().as_str(), "αι");
+    // Cherokee folds to its uppercase.
+    assert!(label("ꮳꮃꭹ").eq_fold(&label("ᏣᎳᎩ")));
+    assert_eq!(label("ꮳꮃꭹ").folded().as_str(), "ᏣᎳᎩ");
+    // Dotted capital I keeps its dot as a combining mark.
+    assert_eq!(label("İ").folded().as_str(), "i\u{307}");
+    assert!(!label("İ").eq_fold(&label("i")));
+    // Default ignorables drop out.
+    assert!(label("ab\u{FE0F}").eq_fold(&label("ab")));
+    assert!(label("a\u{034F}b").eq_fold(&label("AB")));
+    assert_ne!(label("MyPkg"), label("mypkg"), "labels stay case-sensitive");
+}
+
+#[test]
+fn label_policy_refuses_reserved() {
+    use crate::ReservedLabels;
+
+    let policy = ReservedLabels::default();
+    assert!(Label::validate_with("my-pkg", &policy).is_ok());
+    assert_eq!(
+        Label::validate_with("STD", &policy),
+        Err(Error::Reserved("STD".into()))
+    );
+    assert_eq!(
+        Label::validate_with("x", &policy),
+        Err(Error::Reserved("x".into()))
+    );
+    assert_eq!(
+        Label::validate_with("1x", &policy),
+        Err(Error::InvalidStart {
+            found: '1',
+            span: at(0..1),
+        })
+    );
+
+    let custom = ReservedLabels::empty().deny("Admin").unwrap().min_chars(3);
+    assert!(Label::validate_with("std", &custom).is_ok());
+    assert!(Label::validate_with("admin", &custom).is_err());
+    assert!(Label::validate_with("ab", &custom).is_err());
+    assert!(ReservedLabels::empty().deny("no way").is_err());
+}
+
+#[test]
+fn name_clones_share_storage() {
+    let label = Label::try_from("my-pkg").unwrap();
+    assert_eq!(label.clone().as_ptr(), label.as_ptr());
+    let tag = Tag::try_from("release:1.0").unwrap();
+    assert_eq!(tag.clone().as_ptr(), tag.as_ptr());
+}
+
 #[test]
 fn label_from_str() {
     let parsed: Label = "hello".parse().unwrap();
@@ -173,7 +304,13 @@ fn identifier_rejects_empty() {
 
 #[test]
 fn identifier_rejects_number_start() {
-    assert_eq!(Identifier::try_from("1foo"), Err(Error::InvalidStart('1')));
+    assert_eq!(
+        Identifier::try_from("1foo"),
+        Err(Error::InvalidStart {
+            found: '1',
+            span: at(0..1),
+        })
+    );
 }
 
 #[test]
@@ -203,14 +340,58 @@ fn tag_allows_hyphen() {
 fn tag_rejects_double_dot() {
     assert_eq!(
         Tag::try_from("bad..tag"),
-        Err(Error::InvalidCharacters("..".into())),
+        Err(Error::InvalidCharacters {
+            found: "..".into(),
+            spans: vec![at(3..5)],
+        }),
     );
 }
 
 #[test]
 fn tag_rejects_invalid_start() {
-    assert_eq!(Tag::try_from(".tag"), Err(Error::InvalidStart('.')));
-    assert_eq!(Tag::try_from(":tag"), Err(Error::InvalidStart(':')));
+    assert_eq!(
+        Tag::try_from(".tag"),
+        Err(Error::InvalidStart {
+            found: '.',
+            span: at(0..1),
+        })
+    );
+    assert_eq!(
+        Tag::try_from(":tag"),
+        Err(Error::InvalidStart {
+            found: ':',
+            span: at(0..1),
+        })
+    );
+}
+
+#[test]
+fn tag_hierarchy_matching() {
+    use crate::TagFilter;
+
+    let tag = |s| Tag::try_from(s).unwrap();
+    assert!(tag("release:stable").matches_prefix(&tag("release")));
+    assert!(tag("release").matches_prefix(&tag("release")));
+    assert!(!tag("releases:stable").matches_prefix(&tag("release")));
+    assert!(!tag("release").matches_prefix(&tag("release:stable")));
+
+    let one: TagFilter = "release:*".parse().unwrap();
+    assert!(one.matches(&tag("release:stable")));
+    assert!(!one.matches(&tag("release")));
+    assert!(!one.matches(&tag("release:stable:v2")));
+    assert!(!one.matches(&tag("nightly:stable")));
+
+    let rest: TagFilter = "release:**".parse().unwrap();
+    assert!(rest.matches(&tag("release")));
+    assert!(rest.matches(&tag("release:stable:v2")));
+    assert_eq!(rest, TagFilter::prefix(&tag("release")));
+
+    let mid: TagFilter = "*:1.0".parse().unwrap();
+    assert!(mid.matches(&tag("release:1.0")));
+    assert!(!mid.matches(&tag("release:1.1")));
+
+    assert_eq!("**:x".parse::<TagFilter>(), Err(Error::InvalidFormat));
+    assert!("release:a b".parse::<TagFilter>().is_err());
 }
 
 #[test]
@@ -222,6 +403,25 @@ fn tag_serde_roundtrip() {
     assert_eq!(back, tag);
 }
 
+// ============================================================================
+// Unicode stability
+// ============================================================================
+
+#[test]
+fn normalization_report_flags_changed_input() {
+    let stable = crate::normalization_report("my-pkg");
+    assert_eq!(stable.normalized, "my-pkg");
+    assert!(!stable.changed);
+    assert_eq!(stable.unicode_version, crate::UNICODE_VERSION);
+
+    // Fullwidth letters fold to ASCII under NFKC.
+    let folded = crate::normalization_report("\u{FF41}bc");
+    assert_eq!(folded.normalized, "abc");
+    assert!(folded.changed);
+    assert_eq!(folded.normalized, &*Label::try_from("\u{FF41}bc").unwrap());
+    assert!(crate::UNICODE_VERSION.0 > 0 && crate::IDENTIFIER_UNICODE_VERSION.0 > 0);
+}
+
 // ============================================================================
 // Anchor
 // ============================================================================
@@ -256,6 +456,45 @@ fn anchor_from_vec() {
     assert_eq!(anchor.as_bytes(), &bytes);
 }
 
+#[test]
+fn anchor_hex_roundtrip() {
+    let anchor = Anchor::new(vec![0x00, 0xAB, 0xCD, 0xEF]);
+    assert_eq!(anchor.to_hex(), "00abcdef");
+    assert_eq!(Anchor::from_hex("00abcdef"), Ok(anchor.clone()));
+    assert_eq!(Anchor::from_hex("00ABCDEF"), Ok(anchor));
+    assert_eq!(Anchor::from_hex("abc"), Err(Error::InvalidAnchor));
+    assert_eq!(Anchor::from_hex("zz"), Err(Error::InvalidAnchor));
+}
+
+#[test]
+fn anchor_from_str() {
+    let anchor = Anchor::new(vec![0xDE, 0xAD, 0xBE, 0xEF]);
+    assert_eq!(Anchor::from_str(&anchor.to_string()), Ok(anchor.clone()));
+    assert_eq!(Anchor::from_str("not b64!"), Err(Error::InvalidAnchor));
+    // "deadbeef" is also valid base64url; hex is only read when asked for.
+    assert_ne!(Anchor::from_str("deadbeef"), Ok(anchor.clone()));
+    assert_eq!(Anchor::parse("deadbeef", true), Ok(anchor.clone()));
+    assert_eq!(Anchor::parse(&anchor.to_b64(), true), Ok(anchor));
+
+    let fixed = Anchor::new(vec![0x5A; 32]);
+    assert_eq!(Anchor::parse(&fixed.to_hex(), true), Ok(fixed.clone()));
+    assert_eq!(Anchor::parse(&fixed.to_b64(), true), Ok(fixed));
+}
+
+#[test]
+fn anchor32_converts_at_the_boundary() {
+    let fixed = Anchor32::new([7; 32]);
+    let anchor = Anchor::from(fixed);
+    assert_eq!(anchor.as_bytes(), &[7; 32]);
+    assert_eq!(fixed, anchor);
+    assert_eq!(fixed.to_string(), anchor.to_string());
+    assert_eq!(Anchor32::try_from(&anchor), Ok(fixed));
+    assert_eq!(
+        Anchor32::try_from(Anchor::new(vec![7; 31])),
+        Err(Error::InvalidAnchor)
+    );
+}
+
 // ============================================================================
 // AtomId
 // ============================================================================
@@ -293,6 +532,60 @@ fn atom_id_accessors() {
     assert_eq!(id.label(), &label);
 }
 
+#[test]
+fn atom_id_ref_probes_owned_indexes() {
+    use std::collections::HashSet;
+
+    use crate::{AtomDigest, AtomIdKey, AtomIdRef};
+
+    let anchor = Anchor::new(vec![10, 20]);
+    let label = Label::try_from("myPkg").unwrap();
+    let id = AtomId::new(anchor.clone(), label.clone());
+    let probe = AtomIdRef::new(&anchor, &label);
+
+    assert_eq!(probe, id);
+    assert_eq!(id.as_id_ref(), probe);
+    assert_eq!(probe.to_string(), id.to_string());
+    assert_eq!(probe.to_atom_id(), id);
+
+    let index = HashSet::from([id.clone()]);
+    assert!(index.contains(&probe as &dyn AtomIdKey));
+    let other = Label::try_from("other").unwrap();
+    assert!(!index.contains(&AtomIdRef::new(&anchor, &other) as &dyn AtomIdKey));
+
+    let alg = coz_rs::HashAlg::Sha256;
+    assert_eq!(
+        AtomDigest::compute(probe, alg),
+        AtomDigest::compute(&id, alg)
+    );
+}
+
+#[test]
+fn atom_id_bytes_roundtrip() {
+    let id = AtomId::new(
+        Anchor::new(vec![1, 2, 3]),
+        Label::try_from("my-pkg").unwrap(),
+    );
+    let bytes = id.to_bytes();
+    assert_eq!(&bytes[..7], &[0, 0, 0, 3, 1, 2, 3]);
+    assert_eq!(&bytes[7..], b"my-pkg");
+    assert_eq!(AtomId::from_bytes(&bytes), Ok(id));
+
+    assert_eq!(AtomId::from_bytes(&[0, 0]), Err(Error::InvalidFormat));
+    assert_eq!(
+        AtomId::from_bytes(&[0, 0, 0, 9, 1, 2]),
+        Err(Error::InvalidFormat)
+    );
+    assert_eq!(
+        AtomId::from_bytes(&[0, 0, 0, 1, 1]),
+        Err(Error::InvalidLabel)
+    );
+    assert_eq!(
+        AtomId::from_bytes(&[0, 0, 0, 1, 1, 0xFF]),
+        Err(Error::InvalidLabel)
+    );
+}
+
 #[test]
 fn atom_id_display_format() {
     let id = AtomId::new(
@@ -399,6 +692,27 @@ fn rawversion_serde_roundtrip() {
     assert_eq!(back, v);
 }
 
+// ============================================================================
+// RawRequirement
+// ============================================================================
+
+#[test]
+fn rawrequirement_display_and_from_str() {
+    let r: RawRequirement = ">=1.0, <2.0".parse().unwrap();
+    assert_eq!(r.as_str(), ">=1.0, <2.0");
+    assert_eq!(r.to_string(), ">=1.0, <2.0");
+    assert_eq!(r, RawRequirement::new(">=1.0, <2.0".into()));
+}
+
+#[test]
+fn rawrequirement_serde_roundtrip() {
+    let r = RawRequirement::new("^1.2".into());
+    let json = serde_json::to_string(&r).unwrap();
+    assert_eq!(json, "\"^1.2\"");
+    let back: RawRequirement = serde_json::from_str(&json).unwrap();
+    assert_eq!(back, r);
+}
+
 // ============================================================================
 // ClaimPayload
 // ============================================================================
@@ -860,17 +1174,6 @@ fn both_payloads_same_identity() {
 // Verification
 // ============================================================================
 
-/// Helper: generate an Ed25519 key pair and return (prv_bytes, pub_bytes, thumbprint).
-fn gen_ed25519_key() -> (Vec<u8>, Vec<u8>, crate::Thumbprint) {
-    use coz_rs::Ed25519;
-
-    let sk = coz_rs::SigningKey::<Ed25519>::generate();
-    let prv = sk.private_key_bytes();
-    let pub_bytes = sk.verifying_key().public_key_bytes().to_vec();
-    let tmb = sk.thumbprint().clone();
-    (prv, pub_bytes, tmb)
-}
-
 #[test]
 fn verify_claim_roundtrip() {
     let (prv, pub_bytes, tmb) = gen_ed25519_key();
@@ -881,17 +1184,26 @@ fn verify_claim_roundtrip() {
         OwnerRef::new(OwnerKind::SingleKey, vec![99]),
         "cargo".to_string(),
         vec![0; 32],
-        tmb,
+        tmb.clone(),
     );
     let pay_json = serde_json::to_vec(&claim).unwrap();
-    let (sig, _cad) = coz_rs::sign_json(&pay_json, "Ed25519", &prv, &pub_bytes).unwrap();
+    let (sig, cad) = coz_rs::sign_json(&pay_json, "Ed25519", &prv, &pub_bytes).unwrap();
 
-    let result = crate::verify_claim(&pay_json, &sig, "Ed25519", &pub_bytes);
+    let result = crate::verify_claim(&pay_json, &sig, "Ed25519", &pub_bytes, None);
     assert!(result.is_ok(), "valid claim should verify: {result:?}");
     let verified = result.unwrap();
     assert_eq!(verified.anchor, test_anchor());
     assert_eq!(verified.label, test_label());
     assert_eq!(verified.typ, crate::TYP_CLAIM);
+
+    // The digests come back with the payload.
+    assert_eq!(verified.cad(), &cad);
+    assert_eq!(
+        verified.czd(),
+        &crate::czd_for_alg(&pay_json, &sig, "Ed25519").unwrap()
+    );
+    assert_eq!(verified.signer(), &tmb);
+    assert_eq!(verified.into_payload(), claim);
 }
 
 #[test]
@@ -911,7 +1223,7 @@ fn verify_publish_roundtrip() {
     let pay_json = serde_json::to_vec(&publish).unwrap();
     let (sig, _cad) = coz_rs::sign_json(&pay_json, "Ed25519", &prv, &pub_bytes).unwrap();
 
-    let result = crate::verify_publish(&pay_json, &sig, "Ed25519", &pub_bytes);
+    let result = crate::verify_publish(&pay_json, &sig, "Ed25519", &pub_bytes, None);
     assert!(result.is_ok(), "valid publish should verify: {result:?}");
     let verified = result.unwrap();
     assert_eq!(verified.anchor, test_anchor());
@@ -919,6 +1231,228 @@ fn verify_publish_roundtrip() {
     assert_eq!(verified.typ, crate::TYP_PUBLISH);
 }
 
+#[test]
+fn time_policy_bounds() {
+    let policy = crate::TimePolicy {
+        max_skew: Some(300),
+        not_before: Some(1000),
+        not_after: Some(5000),
+    };
+    let clock = 2000;
+    assert!(policy.check_at(1000, clock).is_ok());
+    assert!(policy.check_at(2300, clock).is_ok());
+    for now in [999, 2301, 5001] {
+        assert!(
+            matches!(
+                policy.check_at(now, clock),
+                Err(crate::VerifyError::TimestampOutOfRange { now: n }) if n == now
+            ),
+            "now = {now} must be out of range"
+        );
+    }
+    // Past timestamps are never skew violations.
+    assert!(crate::TimePolicy::default().check_at(0, u64::MAX).is_ok());
+    assert!(
+        crate::TimePolicy {
+            max_skew: Some(0),
+            ..Default::default()
+        }
+        .check_at(1, 2)
+        .is_ok()
+    );
+}
+
+#[test]
+fn verify_claim_applies_time_policy() {
+    let (prv, pub_bytes, tmb) = gen_ed25519_key();
+    let claim = crate::ClaimPayload::new(
+        crate::Alg::Ed25519,
+        test_id(),
+        1000,
+        OwnerRef::new(OwnerKind::SingleKey, vec![99]),
+        "cargo".to_string(),
+        vec![0; 32],
+        tmb,
+    );
+    let pay_json = serde_json::to_vec(&claim).unwrap();
+    let (sig, _cad) = coz_rs::sign_json(&pay_json, "Ed25519", &prv, &pub_bytes).unwrap();
+
+    let accepting = crate::TimePolicy {
+        not_before: Some(1000),
+        ..Default::default()
+    };
+    let result = crate::verify_claim(&pay_json, &sig, "Ed25519", &pub_bytes, Some(&accepting));
+    assert!(result.is_ok(), "{result:?}");
+
+    // A claim dated 1000 is far in the past, never future-skewed; only a
+    // not_before bound can reject it.
+    let rejecting = crate::TimePolicy {
+        max_skew: Some(0),
+        not_before: Some(1001),
+        not_after: None,
+    };
+    let result = crate::verify_claim(&pay_json, &sig, "Ed25519", &pub_bytes, Some(&rejecting));
+    assert!(
+        matches!(
+            result,
+            Err(crate::VerifyError::TimestampOutOfRange { now: 1000 })
+        ),
+        "{result:?}"
+    );
+}
+
+#[test]
+fn claim_expiry_refuses_later_publishes_only() {
+    let (prv, pub_bytes, tmb) = gen_ed25519_key();
+    let mut claim = crate::ClaimPayload::new(
+        crate::Alg::Ed25519,
+        test_id(),
+        1000,
+        OwnerRef::new(OwnerKind::SingleKey, tmb.as_bytes().to_vec()),
+        "cargo".to_string(),
+        vec![0; 32],
+        tmb.clone(),
+    );
+    assert!(!claim.is_expired_at(u64::MAX), "no exp never expires");
+    claim.exp = Some(2000);
+    assert!(!claim.is_expired_at(1999));
+    assert!(claim.is_expired_at(2000));
+
+    let sign = |pay_json: Vec<u8>| {
+        let (sig, _cad) = coz_rs::sign_json(&pay_json, "Ed25519", &prv, &pub_bytes).unwrap();
+        (pay_json, sig)
+    };
+    // Long past its `exp` by any clock, the claim itself still verifies.
+    let (claim_json, claim_sig) = sign(serde_json::to_vec(&claim).unwrap());
+    let verified =
+        crate::verify_claim(&claim_json, &claim_sig, "Ed25519", &pub_bytes, None).unwrap();
+    assert_eq!(verified.exp, Some(2000));
+
+    let publish_at = |now| {
+        let publish = crate::PublishPayload::new(
+            crate::Alg::Ed25519,
+            test_id(),
+            verified.czd().clone(),
+            vec![7, 8],
+            now,
+            "src/lib".into(),
+            vec![9, 10],
+            tmb.clone(),
+            crate::RawVersion::new("1.0.0".into()),
+        );
+        let (pay_json, sig) = sign(serde_json::to_vec(&publish).unwrap());
+        crate::verify_publish_against_claim(
+            &pay_json,
+            &sig,
+            &claim_json,
+            &claim_sig,
+            "Ed25519",
+            &pub_bytes,
+        )
+    };
+    // Published while the claim was live: valid now and after.
+    assert!(publish_at(1999).is_ok());
+    let result = publish_at(2000);
+    assert!(
+        matches!(result, Err(crate::VerifyError::Expired { exp: 2000 })),
+        "{result:?}"
+    );
+}
+
+#[test]
+fn verify_publish_rejects_far_future_now() {
+    let (prv, pub_bytes, tmb) = gen_ed25519_key();
+    let publish = crate::PublishPayload::new(
+        crate::Alg::Ed25519,
+        test_id(),
+        crate::Czd::from_bytes(vec![5, 6]),
+        vec![7, 8],
+        u64::MAX,
+        "src/lib".into(),
+        vec![9, 10],
+        tmb,
+        crate::RawVersion::new("1.0.0".into()),
+    );
+    let pay_json = serde_json::to_vec(&publish).unwrap();
+    let (sig, _cad) = coz_rs::sign_json(&pay_json, "Ed25519", &prv, &pub_bytes).unwrap();
+
+    let policy = crate::TimePolicy {
+        max_skew: Some(3600),
+        ..Default::default()
+    };
+    let result = crate::verify_publish(&pay_json, &sig, "Ed25519", &pub_bytes, Some(&policy));
+    assert!(
+        matches!(result, Err(crate::VerifyError::TimestampOutOfRange { .. })),
+        "{result:?}"
+    );
+}
+
+#[test]
+fn payload_version_defaults_and_stays_off_the_wire() {
+    let claim = crate::ClaimPayload::new(
+        crate::Alg::Ed25519,
+        test_id(),
+        1000,
+        OwnerRef::new(OwnerKind::SingleKey, vec![99]),
+        "cargo".to_string(),
+        vec![0; 32],
+        crate::Thumbprint::from_bytes(vec![1]),
+    );
+    assert_eq!(claim.pv, crate::PV_CURRENT);
+    let json: serde_json::Value = serde_json::to_value(&claim).unwrap();
+    assert!(json.get("pv").is_none(), "version 1 is never written");
+    let back: crate::ClaimPayload = serde_json::from_value(json).unwrap();
+    assert_eq!(back.pv, 1);
+
+    let mut versioned = claim;
+    versioned.pv = 2;
+    let json = serde_json::to_value(&versioned).unwrap();
+    assert_eq!(json["pv"], 2);
+}
+
+#[test]
+fn verify_rejects_unsupported_payload_version() {
+    let (prv, pub_bytes, tmb) = gen_ed25519_key();
+    let mut publish = crate::PublishPayload::new(
+        crate::Alg::Ed25519,
+        test_id(),
+        crate::Czd::from_bytes(vec![5, 6]),
+        vec![7, 8],
+        2000,
+        "src/lib".into(),
+        vec![9, 10],
+        tmb,
+        crate::RawVersion::new("1.0.0".into()),
+    );
+    publish.pv = crate::PV_SUPPORTED.end() + 1;
+    let pay_json = serde_json::to_vec(&publish).unwrap();
+    let (sig, _cad) = coz_rs::sign_json(&pay_json, "Ed25519", &prv, &pub_bytes).unwrap();
+    let result = crate::verify_publish(&pay_json, &sig, "Ed25519", &pub_bytes, None);
+    assert!(
+        matches!(
+            result,
+            Err(crate::VerifyError::UnsupportedPayloadVersion { pv, min: 1, .. })
+                if pv == crate::PV_CURRENT + 1
+        ),
+        "{result:?}"
+    );
+
+    // A future version is rejected by its `pv` even when the rest of the
+    // payload no longer parses as a version-1 publish.
+    let mut value: serde_json::Value = serde_json::from_slice(&pay_json).unwrap();
+    value["version"] = serde_json::json!({ "scheme": "semver", "raw": "1.0.0" });
+    let pay_json = serde_json::to_vec(&value).unwrap();
+    let (sig, _cad) = coz_rs::sign_json(&pay_json, "Ed25519", &prv, &pub_bytes).unwrap();
+    let result = crate::verify_publish(&pay_json, &sig, "Ed25519", &pub_bytes, None);
+    assert!(
+        matches!(
+            result,
+            Err(crate::VerifyError::UnsupportedPayloadVersion { .. })
+        ),
+        "{result:?}"
+    );
+}
+
 #[test]
 fn verify_claim_wrong_sig() {
     let (_prv, pub_bytes, tmb) = gen_ed25519_key();
@@ -934,7 +1468,7 @@ fn verify_claim_wrong_sig() {
     let pay_json = serde_json::to_vec(&claim).unwrap();
     let bad_sig = vec![0u8; 64]; // garbage signature
 
-    let result = crate::verify_claim(&pay_json, &bad_sig, "Ed25519", &pub_bytes);
+    let result = crate::verify_claim(&pay_json, &bad_sig, "Ed25519", &pub_bytes, None);
     assert!(
         matches!(result, Err(crate::VerifyError::InvalidSignature)),
         "wrong sig should be InvalidSignature: {result:?}"
@@ -959,7 +1493,7 @@ fn verify_claim_wrong_typ() {
     let pay_json = serde_json::to_vec(&json_val).unwrap();
     let (sig, _cad) = coz_rs::sign_json(&pay_json, "Ed25519", &prv, &pub_bytes).unwrap();
 
-    let result = crate::verify_claim(&pay_json, &sig, "Ed25519", &pub_bytes);
+    let result = crate::verify_claim(&pay_json, &sig, "Ed25519", &pub_bytes, None);
     assert!(
         matches!(result, Err(crate::VerifyError::WrongTyp { .. })),
         "tampered typ should fail with WrongTyp: {result:?}"
@@ -980,7 +1514,7 @@ fn verify_claim_unknown_alg() {
     );
     let pay_json = serde_json::to_vec(&claim).unwrap();
 
-    let result = crate::verify_claim(&pay_json, &[], "UNSUPPORTED", &pub_bytes);
+    let result = crate::verify_claim(&pay_json, &[], "UNSUPPORTED", &pub_bytes, None);
     assert!(
         matches!(result, Err(crate::VerifyError::UnsupportedAlgorithm(_))),
         "unknown alg should be UnsupportedAlgorithm: {result:?}"
@@ -1367,14 +1901,14 @@ mod proptests {
             let (sig, _cad) = coz_rs::sign_json(&pay_json, "Ed25519", &prv, &pub_bytes).unwrap();
 
             // Assert normal verification succeeds
-            let result = crate::verify_claim(&pay_json, &sig, "Ed25519", &pub_bytes);
+            let result = crate::verify_claim(&pay_json, &sig, "Ed25519", &pub_bytes, None);
             prop_assert!(result.is_ok(), "Verification failed for valid claim: {:?}", result);
 
             // Mutate a byte in the signature
             let mut corrupted_sig = sig.clone();
             if mutate_index < corrupted_sig.len() {
                 corrupted_sig[mutate_index] ^= mutation;
-                let result = crate::verify_claim(&pay_json, &corrupted_sig, "Ed25519", &pub_bytes);
+                let result = crate::verify_claim(&pay_json, &corrupted_sig, "Ed25519", &pub_bytes, None);
                 prop_assert!(result.is_err(), "Verification should fail for corrupted signature");
             }
 
@@ -1382,7 +1916,7 @@ mod proptests {
             let mut corrupted_pub = pub_bytes.clone();
             if mutate_index < corrupted_pub.len() {
                 corrupted_pub[mutate_index] ^= mutation;
-                let result = crate::verify_claim(&pay_json, &sig, "Ed25519", &corrupted_pub);
+                let result = crate::verify_claim(&pay_json, &sig, "Ed25519", &corrupted_pub, None);
                 prop_assert!(result.is_err(), "Verification should fail for corrupted public key");
             }
         }
@@ -1480,11 +2014,21 @@ fn test_verify_robustness_bolero() {
                 _ => "UNSUPPORTED",
             };
             if input.is_claim {
-                let _ =
-                    crate::verify_claim(&input.payload, &input.signature, alg, &input.public_key);
+                let _ = crate::verify_claim(
+                    &input.payload,
+                    &input.signature,
+                    alg,
+                    &input.public_key,
+                    None,
+                );
             } else {
-                let _ =
-                    crate::verify_publish(&input.payload, &input.signature, alg, &input.public_key);
+                let _ = crate::verify_publish(
+                    &input.payload,
+                    &input.signature,
+                    alg,
+                    &input.public_key,
+                    None,
+                );
             }
         });
 }
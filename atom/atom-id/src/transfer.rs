@@ -0,0 +1,364 @@
+//! Ownership transfer transactions — handing a claim to a new owner.
+//!
+//! A transfer is signed by the current owner of a claim and names the new
+//! owner, linking back via `prior` to either the claim itself (first
+//! transfer) or the preceding transfer. Walking claim → transfer →
+//! transfer … yields the claim's current owner without replacing the
+//! claim: its czd — and therefore every publish's `claim` link — stays
+//! put.
+//!
+//! Spec: `docs/specs/atom-transactions.md` §TransferPayload, `[transfer-*]`.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{Alg, Anchor, AtomId, Czd, Label, OwnerRef, Thumbprint};
+
+/// Transaction type for ownership transfers.
+///
+/// Spec constraint: `[transfer-typ]`.
+pub const TYP_TRANSFER: &str = "atom/transfer";
+
+// ============================================================================
+// TransferPayload
+// ============================================================================
+
+/// Payload for an `atom/transfer` transaction.
+///
+/// Spec constraints: `[transfer-typ]`, `[transfer-chain]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TransferPayload {
+    /// The signing algorithm.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_alg"))]
+    pub alg: Alg,
+    /// The atom-set anchor of the transferred claim.
+    pub anchor: Anchor,
+    /// The atom label of the transferred claim.
+    pub label: Label,
+    /// Timestamp (seconds since Unix epoch). MUST strictly exceed the
+    /// `now` of the link named by `prior`.
+    pub now: u64,
+    /// The new owner-reference, accountable for the label from this
+    /// transfer on.
+    ///
+    /// Spec constraint: `[claim-owner-single]`.
+    pub owner: OwnerRef,
+    /// The czd of the preceding link: the claim for the first transfer,
+    /// the previous transfer otherwise.
+    pub prior: Czd,
+    /// Coz key thumbprint of the signing key — authorized by the owner
+    /// in force at `prior`.
+    pub tmb: Thumbprint,
+    /// Transaction type — always [`TYP_TRANSFER`].
+    pub typ: String,
+}
+
+impl TransferPayload {
+    /// Construct a new transfer payload.
+    ///
+    /// Takes an [`AtomId`] to ensure that the anchor and label come from
+    /// a validated identity pair. Sets `typ` to [`TYP_TRANSFER`]
+    /// automatically.
+    pub fn new(
+        alg: Alg,
+        id: AtomId,
+        now: u64,
+        owner: OwnerRef,
+        prior: Czd,
+        tmb: Thumbprint,
+    ) -> Self {
+        Self {
+            alg,
+            anchor: id.anchor,
+            label: id.label,
+            now,
+            owner,
+            prior,
+            tmb,
+            typ: TYP_TRANSFER.to_owned(),
+        }
+    }
+}
+
+/// A single transfer's raw wire components — one link in a transfer
+/// chain, as needed to re-verify its signature and recompute its czd.
+///
+/// The transfer-chain analogue of [`CharterLink`](crate::CharterLink).
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, Copy)]
+pub struct TransferLink<'a> {
+    /// The transfer's canonical payload JSON bytes.
+    pub pay_json: &'a [u8],
+    /// The Coz signature over `pay_json`.
+    pub sig: &'a [u8],
+    /// The signing algorithm name.
+    pub alg: &'a str,
+    /// The raw public key bytes the signature is checked against.
+    pub pub_key: &'a [u8],
+}
+
+// ============================================================================
+// Verification
+// ============================================================================
+
+#[cfg(feature = "serde")]
+impl crate::TypedPayload for TransferPayload {
+    fn typ(&self) -> &str {
+        &self.typ
+    }
+
+    fn tmb(&self) -> &Thumbprint {
+        &self.tmb
+    }
+}
+
+/// Verify a signed `atom/transfer` transaction.
+///
+/// Validates the Coz signature, deserializes the payload, checks that
+/// `typ` is [`TYP_TRANSFER`], and binds the declared `tmb` to `pub_key`
+/// (as [`crate::verify_revoke`] does, for the same reason).
+///
+/// Spec constraints: `[sig-over-pay]`, `[transfer-typ]`.
+#[cfg(feature = "serde")]
+pub fn verify_transfer(
+    pay_json: &[u8],
+    sig: &[u8],
+    alg: &str,
+    pub_key: &[u8],
+) -> Result<TransferPayload, crate::VerifyError> {
+    crate::verify_typed(pay_json, sig, alg, pub_key, TYP_TRANSFER)
+}
+
+/// Walk a claim → transfer → transfer … chain and return the current
+/// owner.
+///
+/// `transfers` is ordered by the caller, oldest first. Each link is
+/// verified with [`verify_transfer`], then checked against its
+/// predecessor:
+///
+/// - `transfer.prior` equals the predecessor's recomputed czd
+///   ([`crate::VerifyError::TransferChainMismatch`]).
+/// - `(anchor, label)` equals the claim's ([`crate::VerifyError::AtomIdMismatch`]).
+/// - The owner in force at the predecessor authorizes `transfer.tmb`
+///   ([`crate::VerifyError::Unauthorized`]).
+/// - `transfer.now` strictly exceeds the predecessor's `now`
+///   ([`crate::VerifyError::TransferNotAfterPrior`]).
+///
+/// An empty `transfers` slice returns the claim's own owner. The claim's
+/// signature is assumed verified upstream (e.g. by [`crate::verify_claim`]).
+///
+/// Publish authorization after a transfer is against the owner returned
+/// here, not `claim.owner`: pass a claim whose `owner` has been replaced
+/// by this result to [`crate::verify_publish_authorized`].
+///
+/// Spec constraint: `[transfer-chain]`.
+#[cfg(feature = "serde")]
+pub fn verify_transfer_chain(
+    claim_pay_json: &[u8],
+    claim_sig: &[u8],
+    claim_alg: &str,
+    transfers: &[TransferLink<'_>],
+) -> Result<OwnerRef, crate::VerifyError> {
+    let claim: crate::ClaimPayload = serde_json::from_slice(claim_pay_json)?;
+    let mut head_czd = crate::czd_for_alg(claim_pay_json, claim_sig, claim_alg)?;
+    let mut head_now = claim.now;
+    let mut owner = claim.owner;
+
+    for link in transfers {
+        let transfer = verify_transfer(link.pay_json, link.sig, link.alg, link.pub_key)?;
+        if !crate::ct_eq(transfer.prior.as_bytes(), head_czd.as_bytes()) {
+            return Err(crate::VerifyError::TransferChainMismatch);
+        }
+        if transfer.anchor != claim.anchor || transfer.label != claim.label {
+            return Err(crate::VerifyError::AtomIdMismatch);
+        }
+        if !owner.authorizes(&transfer.tmb) {
+            return Err(crate::VerifyError::Unauthorized);
+        }
+        if transfer.now <= head_now {
+            return Err(crate::VerifyError::TransferNotAfterPrior);
+        }
+        head_czd = crate::czd_for_alg(link.pay_json, link.sig, link.alg)?;
+        head_now = transfer.now;
+        owner = transfer.owner;
+    }
+
+    Ok(owner)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ClaimPayload;
+    use crate::test_support::test_id;
+
+    struct Key {
+        prv: Vec<u8>,
+        pub_bytes: Vec<u8>,
+        tmb: Thumbprint,
+    }
+
+    fn gen_key() -> Key {
+        use coz_rs::Ed25519;
+
+        let sk = coz_rs::SigningKey::<Ed25519>::generate();
+        Key {
+            prv: sk.private_key_bytes(),
+            pub_bytes: sk.verifying_key().public_key_bytes().to_vec(),
+            tmb: sk.thumbprint().clone(),
+        }
+    }
+
+    /// Serialize and sign `payload` with `key`, returning `(pay_json, sig)`.
+    fn sign<T: Serialize>(payload: &T, key: &Key) -> (Vec<u8>, Vec<u8>) {
+        let pay_json = serde_json::to_vec(payload).unwrap();
+        let (sig, _cad) =
+            coz_rs::sign_json(&pay_json, "Ed25519", &key.prv, &key.pub_bytes).unwrap();
+        (pay_json, sig)
+    }
+
+    fn czd(pay_json: &[u8], sig: &[u8]) -> Czd {
+        crate::czd_for_alg(pay_json, sig, "Ed25519").unwrap()
+    }
+
+    /// A claim owned by `owner`, signed by `owner` itself.
+    fn signed_claim(owner: &Key) -> (Vec<u8>, Vec<u8>) {
+        let claim = ClaimPayload::new(
+            Alg::Ed25519,
+            test_id(),
+            1000,
+            OwnerRef::single_key(&owner.tmb),
+            "cargo".into(),
+            vec![0; 32],
+            owner.tmb.clone(),
+        );
+        sign(&claim, owner)
+    }
+
+    fn transfer(prior: Czd, now: u64, from: &Key, to: &Key) -> TransferPayload {
+        TransferPayload::new(
+            Alg::Ed25519,
+            test_id(),
+            now,
+            OwnerRef::single_key(&to.tmb),
+            prior,
+            from.tmb.clone(),
+        )
+    }
+
+    fn link<'a>(pay_json: &'a [u8], sig: &'a [u8], key: &'a Key) -> TransferLink<'a> {
+        TransferLink {
+            pay_json,
+            sig,
+            alg: "Ed25519",
+            pub_key: &key.pub_bytes,
+        }
+    }
+
+    #[test]
+    fn transfer_payload_typ_and_serde_roundtrip() {
+        let payload = TransferPayload::new(
+            Alg::ES256,
+            test_id(),
+            2000,
+            OwnerRef::single_key(&Thumbprint::from_bytes(vec![7])),
+            Czd::from_bytes(vec![5, 6]),
+            Thumbprint::from_bytes(vec![10, 20, 30]),
+        );
+        assert_eq!(payload.typ, "atom/transfer");
+        let json = serde_json::to_string(&payload).unwrap();
+        let back: TransferPayload = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, payload);
+    }
+
+    #[test]
+    fn verify_transfer_wrong_typ() {
+        let key = gen_key();
+        let payload = transfer(Czd::from_bytes(vec![1]), 2000, &key, &key);
+        let mut json_val = serde_json::to_value(&payload).unwrap();
+        json_val["typ"] = serde_json::Value::String("atom/claim".into());
+        let (pay_json, sig) = sign(&json_val, &key);
+        let result = verify_transfer(&pay_json, &sig, "Ed25519", &key.pub_bytes);
+        assert!(
+            matches!(result, Err(crate::VerifyError::WrongTyp { .. })),
+            "{result:?}"
+        );
+    }
+
+    #[test]
+    fn transfer_chain_returns_current_owner() {
+        let (a, b, c) = (gen_key(), gen_key(), gen_key());
+        let (claim_json, claim_sig) = signed_claim(&a);
+
+        let (t1_json, t1_sig) = sign(&transfer(czd(&claim_json, &claim_sig), 2000, &a, &b), &a);
+        let (t2_json, t2_sig) = sign(&transfer(czd(&t1_json, &t1_sig), 3000, &b, &c), &b);
+
+        let owner = verify_transfer_chain(
+            &claim_json,
+            &claim_sig,
+            "Ed25519",
+            &[link(&t1_json, &t1_sig, &a), link(&t2_json, &t2_sig, &b)],
+        )
+        .unwrap();
+        assert_eq!(owner, OwnerRef::single_key(&c.tmb));
+
+        let unchanged = verify_transfer_chain(&claim_json, &claim_sig, "Ed25519", &[]).unwrap();
+        assert_eq!(unchanged, OwnerRef::single_key(&a.tmb));
+    }
+
+    #[test]
+    fn transfer_chain_rejects_stale_owner() {
+        // After A hands the claim to B, A can no longer transfer it.
+        let (a, b, c) = (gen_key(), gen_key(), gen_key());
+        let (claim_json, claim_sig) = signed_claim(&a);
+
+        let (t1_json, t1_sig) = sign(&transfer(czd(&claim_json, &claim_sig), 2000, &a, &b), &a);
+        let (t2_json, t2_sig) = sign(&transfer(czd(&t1_json, &t1_sig), 3000, &a, &c), &a);
+
+        let result = verify_transfer_chain(
+            &claim_json,
+            &claim_sig,
+            "Ed25519",
+            &[link(&t1_json, &t1_sig, &a), link(&t2_json, &t2_sig, &a)],
+        );
+        assert!(
+            matches!(result, Err(crate::VerifyError::Unauthorized)),
+            "{result:?}"
+        );
+    }
+
+    #[test]
+    fn transfer_chain_rejects_broken_link_and_backdating() {
+        let (a, b) = (gen_key(), gen_key());
+        let (claim_json, claim_sig) = signed_claim(&a);
+
+        let (bad_json, bad_sig) = sign(&transfer(Czd::from_bytes(vec![0; 32]), 2000, &a, &b), &a);
+        let result = verify_transfer_chain(
+            &claim_json,
+            &claim_sig,
+            "Ed25519",
+            &[link(&bad_json, &bad_sig, &a)],
+        );
+        assert!(
+            matches!(result, Err(crate::VerifyError::TransferChainMismatch)),
+            "{result:?}"
+        );
+
+        let (old_json, old_sig) = sign(&transfer(czd(&claim_json, &claim_sig), 1000, &a, &b), &a);
+        let result = verify_transfer_chain(
+            &claim_json,
+            &claim_sig,
+            "Ed25519",
+            &[link(&old_json, &old_sig, &a)],
+        );
+        assert!(
+            matches!(result, Err(crate::VerifyError::TransferNotAfterPrior)),
+            "{result:?}"
+        );
+    }
+}
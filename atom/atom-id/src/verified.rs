@@ -0,0 +1,102 @@
+//! Verified payloads together with the digests verification computed.
+//!
+//! Every caller of [`crate::verify_claim`] or [`crate::verify_publish`]
+//! needs the payload's czd next — to check a publish's `claim` link, to
+//! key a store ref, to record it in a [`crate::ReplayGuard`] — and used to
+//! recompute it from the same bytes. [`Verified`] hands it back alongside
+//! the payload, with the canonical digest and the signer's thumbprint.
+
+use std::ops::Deref;
+
+use crate::{Cad, Czd, Thumbprint, VerifyError};
+
+/// A payload whose signature has been verified, with the canonical digest
+/// (`cad`), czd, and signing-key thumbprint computed along the way.
+///
+/// Only this crate's verifiers construct it. It dereferences to the
+/// payload, so field access reads as before; use
+/// [`into_payload`](Self::into_payload) to take the payload by value.
+///
+/// `signer` is the thumbprint of the key that actually signed. It is not
+/// compared with the payload's declared `tmb` here — that binding is the
+/// `verify_*_key_thumbprint` step's job.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Verified<T> {
+    payload: T,
+    cad: Cad,
+    czd: Czd,
+    signer: Thumbprint,
+}
+
+impl<T> Verified<T> {
+    /// Wrap `payload`, computing the digests of its signed `pay_json` and
+    /// the thumbprint of `pub_key`.
+    pub(crate) fn new(
+        payload: T,
+        pay_json: &[u8],
+        sig: &[u8],
+        alg: &str,
+        pub_key: &[u8],
+    ) -> Result<Self, VerifyError> {
+        let unsupported = || VerifyError::UnsupportedAlgorithm(alg.to_owned());
+        let cad = coz_rs::canonical_hash_for_alg(pay_json, alg, None).ok_or_else(unsupported)?;
+        let czd = coz_rs::czd_for_alg(&cad, sig, alg).ok_or_else(unsupported)?;
+        let signer = coz_rs::compute_thumbprint_for_alg(alg, pub_key).ok_or_else(unsupported)?;
+        Ok(Self {
+            payload,
+            cad,
+            czd,
+            signer,
+        })
+    }
+
+    /// The verified payload.
+    #[must_use]
+    pub fn payload(&self) -> &T {
+        &self.payload
+    }
+
+    /// The canonical digest of the signed payload bytes.
+    #[must_use]
+    pub fn cad(&self) -> &Cad {
+        &self.cad
+    }
+
+    /// The payload's czd — the digest of `(cad, sig)`.
+    #[must_use]
+    pub fn czd(&self) -> &Czd {
+        &self.czd
+    }
+
+    /// Thumbprint of the key whose signature verified.
+    #[must_use]
+    pub fn signer(&self) -> &Thumbprint {
+        &self.signer
+    }
+
+    /// Take the payload, dropping the digests.
+    #[must_use]
+    pub fn into_payload(self) -> T {
+        self.payload
+    }
+
+    /// Split into `(payload, czd)`.
+    #[must_use]
+    pub fn into_parts(self) -> (T, Czd) {
+        (self.payload, self.czd)
+    }
+}
+
+impl<T> Deref for Verified<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.payload
+    }
+}
+
+impl<T> AsRef<T> for Verified<T> {
+    fn as_ref(&self) -> &T {
+        &self.payload
+    }
+}
@@ -0,0 +1,338 @@
+//! Yank transactions — withdrawing a published version.
+//!
+//! A yank marks one published version of an atom as withdrawn without
+//! deleting it: the publish, its content, and every lock already pinning
+//! it stay resolvable, but resolvers SHOULD stop selecting it for new
+//! resolutions. It is the standalone, self-describing form of the
+//! claim-owner-gated `yanked` fact (`[fact-kind-table]`) — usable by
+//! consumers that never walk a publish's tag chain.
+//!
+//! Spec: `docs/specs/atom-transactions.md` §YankPayload, `[yank-*]`.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{Alg, Anchor, AtomId, Czd, Label, RawVersion, Thumbprint};
+
+/// Transaction type for version yanks.
+///
+/// Spec constraint: `[yank-typ]`.
+pub const TYP_YANK: &str = "atom/yank";
+
+// ============================================================================
+// YankPayload
+// ============================================================================
+
+/// Payload for an `atom/yank` transaction.
+///
+/// Binds an [`AtomId`] (as `anchor`/`label`), a [`RawVersion`], and the
+/// [`Czd`] of the claim authorizing the yank. Only the claim's owner may
+/// sign it (`[yank-authority]`) — the same claim-owner gate the `yanked`
+/// fact kind carries.
+///
+/// Spec constraints: `[yank-typ]`, `[yank-authority]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct YankPayload {
+    /// The signing algorithm.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_alg"))]
+    pub alg: Alg,
+    /// The atom-set anchor.
+    pub anchor: Anchor,
+    /// The [`Czd`] of the claim authorizing the yank — the same claim the
+    /// yanked version's publish chains to.
+    pub claim: Czd,
+    /// The atom label.
+    pub label: Label,
+    /// Timestamp (seconds since Unix epoch). MUST strictly exceed the
+    /// yanked publish's `now`.
+    pub now: u64,
+    /// Coz key thumbprint of the signing key.
+    pub tmb: Thumbprint,
+    /// Transaction type — always [`TYP_YANK`].
+    pub typ: String,
+    /// The withdrawn version (unparsed).
+    pub version: RawVersion,
+}
+
+impl YankPayload {
+    /// Construct a new yank payload.
+    ///
+    /// Takes an [`AtomId`] to ensure that the anchor and label come from
+    /// a validated identity pair. Sets `typ` to [`TYP_YANK`] automatically.
+    pub fn new(
+        alg: Alg,
+        id: AtomId,
+        claim: Czd,
+        now: u64,
+        tmb: Thumbprint,
+        version: RawVersion,
+    ) -> Self {
+        Self {
+            alg,
+            anchor: id.anchor,
+            claim,
+            label: id.label,
+            now,
+            tmb,
+            typ: TYP_YANK.to_owned(),
+            version,
+        }
+    }
+
+    /// The yanked atom's identity.
+    #[must_use]
+    pub fn atom_id(&self) -> AtomId {
+        AtomId::new(self.anchor.clone(), self.label.clone())
+    }
+}
+
+// ============================================================================
+// Verification
+// ============================================================================
+
+#[cfg(feature = "serde")]
+impl crate::TypedPayload for YankPayload {
+    fn typ(&self) -> &str {
+        &self.typ
+    }
+
+    fn tmb(&self) -> &Thumbprint {
+        &self.tmb
+    }
+}
+
+/// Verify a signed `atom/yank` transaction.
+///
+/// Validates the Coz signature, deserializes the payload, checks that
+/// `typ` is [`TYP_YANK`], and binds the declared `tmb` to `pub_key` —
+/// folded in for the same reason as [`crate::verify_revoke`]: a yank has
+/// no pipeline step of its own to carry the binding.
+///
+/// Spec constraints: `[sig-over-pay]`, `[yank-typ]`.
+#[cfg(feature = "serde")]
+pub fn verify_yank(
+    pay_json: &[u8],
+    sig: &[u8],
+    alg: &str,
+    pub_key: &[u8],
+) -> Result<YankPayload, crate::VerifyError> {
+    crate::verify_typed(pay_json, sig, alg, pub_key, TYP_YANK)
+}
+
+/// Verify a yank against its authorizing claim.
+///
+/// Recomputes the claim's czd from its raw wire components, then checks
+/// `yank.claim == czd(claim)` ([`crate::VerifyError::ClaimChainMismatch`]),
+/// that the `(anchor, label)` pairs match ([`crate::VerifyError::AtomIdMismatch`]),
+/// and that `claim.owner` authorizes `yank.tmb`
+/// ([`crate::VerifyError::Unauthorized`]). The claim's own signature is
+/// assumed verified upstream. Returns the parsed claim on success.
+///
+/// Spec constraint: `[yank-authority]`.
+#[cfg(feature = "serde")]
+pub fn verify_yank_against_claim(
+    yank: &YankPayload,
+    claim_pay_json: &[u8],
+    claim_sig: &[u8],
+    claim_alg: &str,
+) -> Result<crate::ClaimPayload, crate::VerifyError> {
+    let claim_czd = crate::czd_for_alg(claim_pay_json, claim_sig, claim_alg)?;
+    if !crate::ct_eq(yank.claim.as_bytes(), claim_czd.as_bytes()) {
+        return Err(crate::VerifyError::ClaimChainMismatch);
+    }
+    let claim: crate::ClaimPayload = serde_json::from_slice(claim_pay_json)?;
+    if yank.anchor != claim.anchor || yank.label != claim.label {
+        return Err(crate::VerifyError::AtomIdMismatch);
+    }
+    if !claim.owner.authorizes(&yank.tmb) {
+        return Err(crate::VerifyError::Unauthorized);
+    }
+    Ok(claim)
+}
+
+/// Verify a yank targets a specific publish.
+///
+/// Checks that the publish chains to the same claim as the yank
+/// ([`crate::VerifyError::ClaimChainMismatch`]), that identity and version
+/// match ([`crate::VerifyError::AtomIdMismatch`],
+/// [`crate::VerifyError::YankVersionMismatch`]), and that `yank.now >
+/// publish.now` ([`crate::VerifyError::YankNotAfterPublish`]) — a version
+/// cannot be withdrawn before it was published.
+///
+/// Spec constraint: `[yank-authority]`.
+#[cfg(feature = "serde")]
+pub fn verify_yank_against_publish(
+    yank: &YankPayload,
+    publish: &crate::PublishPayload,
+) -> Result<(), crate::VerifyError> {
+    if !crate::ct_eq(yank.claim.as_bytes(), publish.claim.as_bytes()) {
+        return Err(crate::VerifyError::ClaimChainMismatch);
+    }
+    if yank.anchor != publish.anchor || yank.label != publish.label {
+        return Err(crate::VerifyError::AtomIdMismatch);
+    }
+    if yank.version != publish.version {
+        return Err(crate::VerifyError::YankVersionMismatch);
+    }
+    if yank.now <= publish.now {
+        return Err(crate::VerifyError::YankNotAfterPublish);
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PublishPayload;
+    use crate::test_support::{gen_ed25519_key, signed_claim, test_id};
+
+    fn version() -> RawVersion {
+        RawVersion::new("1.0.0".into())
+    }
+
+    fn publish(claim: Czd) -> PublishPayload {
+        PublishPayload::new(
+            Alg::Ed25519,
+            test_id(),
+            claim,
+            vec![7, 8],
+            2000,
+            "src/lib".into(),
+            vec![9, 10],
+            Thumbprint::from_bytes(vec![1]),
+            version(),
+        )
+    }
+
+    #[test]
+    fn yank_payload_typ_constant() {
+        let yank = YankPayload::new(
+            Alg::ES256,
+            test_id(),
+            Czd::from_bytes(vec![5, 6]),
+            3000,
+            Thumbprint::from_bytes(vec![10, 20, 30]),
+            version(),
+        );
+        assert_eq!(yank.typ, TYP_YANK);
+        assert_eq!(yank.typ, "atom/yank");
+        assert_eq!(yank.atom_id(), test_id());
+    }
+
+    #[test]
+    fn yank_payload_serde_roundtrip() {
+        let yank = YankPayload::new(
+            Alg::ES256,
+            test_id(),
+            Czd::from_bytes(vec![5, 6]),
+            3000,
+            Thumbprint::from_bytes(vec![10, 20, 30]),
+            version(),
+        );
+        let json = serde_json::to_string(&yank).unwrap();
+        let back: YankPayload = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, yank);
+    }
+
+    #[test]
+    fn verify_yank_roundtrip_and_wrong_typ() {
+        let (prv, pub_bytes, tmb) = gen_ed25519_key();
+        let yank = YankPayload::new(
+            Alg::Ed25519,
+            test_id(),
+            Czd::from_bytes(vec![5, 6]),
+            3000,
+            tmb,
+            version(),
+        );
+        let pay_json = serde_json::to_vec(&yank).unwrap();
+        let (sig, _cad) = coz_rs::sign_json(&pay_json, "Ed25519", &prv, &pub_bytes).unwrap();
+        assert_eq!(
+            verify_yank(&pay_json, &sig, "Ed25519", &pub_bytes).unwrap(),
+            yank
+        );
+
+        let mut json_val = serde_json::to_value(&yank).unwrap();
+        json_val["typ"] = serde_json::Value::String("atom/publish".into());
+        let pay_json = serde_json::to_vec(&json_val).unwrap();
+        let (sig, _cad) = coz_rs::sign_json(&pay_json, "Ed25519", &prv, &pub_bytes).unwrap();
+        let result = verify_yank(&pay_json, &sig, "Ed25519", &pub_bytes);
+        assert!(
+            matches!(result, Err(crate::VerifyError::WrongTyp { .. })),
+            "tampered typ should fail with WrongTyp: {result:?}"
+        );
+    }
+
+    #[test]
+    fn verify_yank_against_claim_requires_owner() {
+        let (_prv, _pub, owner_tmb) = gen_ed25519_key();
+        let (_other_prv, _other_pub, other_tmb) = gen_ed25519_key();
+        let (claim_json, claim_sig, claim_czd) = signed_claim(&owner_tmb);
+
+        let by_owner = YankPayload::new(
+            Alg::Ed25519,
+            test_id(),
+            claim_czd.clone(),
+            3000,
+            owner_tmb,
+            version(),
+        );
+        let result = verify_yank_against_claim(&by_owner, &claim_json, &claim_sig, "Ed25519");
+        assert!(result.is_ok(), "owner yank must verify: {result:?}");
+
+        let by_other = YankPayload::new(
+            Alg::Ed25519,
+            test_id(),
+            claim_czd,
+            3000,
+            other_tmb,
+            version(),
+        );
+        let result = verify_yank_against_claim(&by_other, &claim_json, &claim_sig, "Ed25519");
+        assert!(
+            matches!(result, Err(crate::VerifyError::Unauthorized)),
+            "[yank-authority]: only the claim owner may yank: {result:?}"
+        );
+    }
+
+    #[test]
+    fn verify_yank_against_publish_checks_version_and_order() {
+        let claim = Czd::from_bytes(vec![5, 6]);
+        let target = publish(claim.clone());
+        let tmb = Thumbprint::from_bytes(vec![1]);
+
+        let ok = YankPayload::new(
+            Alg::Ed25519,
+            test_id(),
+            claim.clone(),
+            3000,
+            tmb.clone(),
+            version(),
+        );
+        assert!(verify_yank_against_publish(&ok, &target).is_ok());
+
+        let other_version = YankPayload::new(
+            Alg::Ed25519,
+            test_id(),
+            claim.clone(),
+            3000,
+            tmb.clone(),
+            RawVersion::new("2.0.0".into()),
+        );
+        assert!(matches!(
+            verify_yank_against_publish(&other_version, &target),
+            Err(crate::VerifyError::YankVersionMismatch)
+        ));
+
+        let too_early = YankPayload::new(Alg::Ed25519, test_id(), claim, 2000, tmb, version());
+        assert!(matches!(
+            verify_yank_against_publish(&too_early, &target),
+            Err(crate::VerifyError::YankNotAfterPublish)
+        ));
+    }
+}
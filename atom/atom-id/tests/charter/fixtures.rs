@@ -100,7 +100,8 @@ pub struct SignedClaim {
 impl SignedClaim {
     pub fn verify(&self) -> Result<ClaimPayload, atom_id::VerifyError> {
         let pay_json = serde_json::to_vec(&self.payload).expect("payload always serializes");
-        atom_id::verify_claim(&pay_json, &self.sig, &self.alg, &self.pub_key)
+        atom_id::verify_claim(&pay_json, &self.sig, &self.alg, &self.pub_key, None)
+            .map(atom_id::Verified::into_payload)
     }
 
     /// The czd a replacement's `prior` would name if it replaced this
@@ -378,6 +378,7 @@ fn golden_vectors_signatures_verify() {
             &vectors.claim.sig,
             &vectors.claim.alg,
             &vectors.claim.pub_key,
+            None,
         )
         .unwrap_or_else(|e| panic!("{}: claim signature must verify: {e}", case.file));
 
@@ -387,6 +388,7 @@ fn golden_vectors_signatures_verify() {
             &vectors.publish.sig,
             &vectors.publish.alg,
             &vectors.publish.pub_key,
+            None,
         )
         .unwrap_or_else(|e| panic!("{}: publish signature must verify: {e}", case.file));
     }
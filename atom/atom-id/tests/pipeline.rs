@@ -14,7 +14,8 @@
 use atom_id::{
     Alg, Anchor, AtomId, ClaimPayload, Czd, Label, OwnerKind, OwnerRef, PublishPayload, RawVersion,
     Thumbprint, VerifyError, czd_for_alg, verify_atom_id, verify_claim_key_thumbprint,
-    verify_publish_authorized, verify_publish_chains_claim, verify_publish_key_thumbprint,
+    verify_publish_against_claim, verify_publish_authorized, verify_publish_chains_claim,
+    verify_publish_key_thumbprint,
 };
 use coz_rs::Ed25519;
 use serde::Serialize;
@@ -317,3 +318,85 @@ fn step13_rejects_payload_with_mismatched_label() {
         "a mismatched label must be rejected: {result:?}"
     );
 }
+
+// ============================================================================
+// Steps 6, 8, 11, 13 composed — verify_publish_against_claim
+// ============================================================================
+
+/// An owner-signed claim and its wire bytes, for the composed checks.
+fn owned_claim(seed: u8) -> (Thumbprint, Vec<u8>, Vec<u8>, Czd) {
+    let (_prv, _pub_bytes, tmb) = key(seed);
+    let (_claim, pay_json, sig, _pub) = build_claim(seed, tmb.as_bytes().to_vec(), tmb.clone());
+    let czd = czd_for_alg(&pay_json, &sig, "Ed25519").expect("Ed25519 is a supported algorithm");
+    (tmb, pay_json, sig, czd)
+}
+
+#[test]
+fn composed_accepts_owner_signed_publish_chaining_to_its_claim() {
+    let (tmb, claim_json, claim_sig, claim_czd) = owned_claim(70);
+    let (_prv, pub_bytes, _tmb) = key(70);
+    let (publish, pub_json, pub_sig) = build_publish(70, claim_czd, tmb);
+
+    let result = verify_publish_against_claim(
+        &pub_json,
+        &pub_sig,
+        &claim_json,
+        &claim_sig,
+        "Ed25519",
+        &pub_bytes,
+    );
+    assert_eq!(result.expect("every cross-check holds").payload(), &publish);
+}
+
+#[test]
+fn composed_rejects_each_forgotten_cross_check() {
+    let (tmb, claim_json, claim_sig, claim_czd) = owned_claim(71);
+    let (_prv, pub_bytes, _tmb) = key(71);
+    let check = |pub_json: &[u8], pub_sig: &[u8], pub_key: &[u8]| {
+        verify_publish_against_claim(
+            pub_json,
+            pub_sig,
+            &claim_json,
+            &claim_sig,
+            "Ed25519",
+            pub_key,
+        )
+    };
+
+    // Step 6: signed by key 72 while declaring the owner's tmb.
+    let (_prv_x, pub_x, _tmb_x) = key(72);
+    let (_p, pub_json, pub_sig) = build_publish(72, claim_czd.clone(), tmb.clone());
+    let result = check(&pub_json, &pub_sig, &pub_x);
+    assert!(
+        matches!(result, Err(VerifyError::ThumbprintMismatch)),
+        "{result:?}"
+    );
+
+    // Step 8: names an unrelated claim czd.
+    let (_p, pub_json, pub_sig) = build_publish(71, Czd::from_bytes(vec![0xFF; 32]), tmb.clone());
+    let result = check(&pub_json, &pub_sig, &pub_bytes);
+    assert!(
+        matches!(result, Err(VerifyError::ClaimChainMismatch)),
+        "{result:?}"
+    );
+
+    // Step 11: honestly signed by a key that is not the claim's owner.
+    let (_prv_s, pub_s, tmb_s) = key(73);
+    let (_p, pub_json, pub_sig) = build_publish(73, claim_czd.clone(), tmb_s);
+    let result = check(&pub_json, &pub_sig, &pub_s);
+    assert!(
+        matches!(result, Err(VerifyError::Unauthorized)),
+        "{result:?}"
+    );
+
+    // Step 13: same anchor, different label.
+    let (prv, _pub, _tmb) = key(71);
+    let mut publish = build_publish(71, claim_czd, tmb).0;
+    publish.label = Label::try_from("some-other-label").expect("valid label");
+    let (pub_json, pub_sig) = sign(&publish, &prv, &pub_bytes);
+    let result = check(&pub_json, &pub_sig, &pub_bytes);
+    assert!(
+        matches!(result, Err(VerifyError::AtomIdMismatch)),
+        "{result:?}"
+    );
+}
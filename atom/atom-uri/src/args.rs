@@ -0,0 +1,170 @@
+//! Command-line arguments: [`parse_args`].
+
+use std::fmt;
+use std::ops::Range;
+
+use crate::{RawAtomUri, UriError};
+
+/// An argument that failed to parse as an atom URI.
+#[derive(Debug)]
+pub struct ArgError {
+    /// The argument's position in the input, counting from 0.
+    pub index: usize,
+    /// The bytes of the argument the error is about — the label for an
+    /// invalid label, the version for an empty version, and so on; the
+    /// whole argument when no one component is to blame. Empty where
+    /// something is missing, positioned where it should be.
+    pub span: Range<usize>,
+    /// Why the argument is invalid.
+    pub error: UriError,
+}
+
+impl fmt::Display for ArgError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "argument {}: {}", self.index, self.error)
+    }
+}
+
+impl std::error::Error for ArgError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// The result of [`parse_args`]: every URI that parsed and every argument
+/// that did not, each in input order.
+#[derive(Debug, Default)]
+pub struct ParsedArgs {
+    /// The arguments that parsed.
+    pub uris: Vec<RawAtomUri>,
+    /// The arguments that did not.
+    pub errors: Vec<ArgError>,
+}
+
+impl ParsedArgs {
+    /// The URIs if every argument parsed, otherwise every error.
+    ///
+    /// # Errors
+    ///
+    /// The [`ArgError`]s, if there are any.
+    pub fn into_result(self) -> Result<Vec<RawAtomUri>, Vec<ArgError>> {
+        if self.errors.is_empty() {
+            Ok(self.uris)
+        } else {
+            Err(self.errors)
+        }
+    }
+}
+
+/// Parse each argument as a [`RawAtomUri`], collecting failures rather than
+/// stopping at the first, so a CLI can report every bad argument in one
+/// pass and point at the offending part of each.
+pub fn parse_args<I: IntoIterator<Item = String>>(args: I) -> ParsedArgs {
+    let mut parsed = ParsedArgs::default();
+    for (index, arg) in args.into_iter().enumerate() {
+        match arg.parse() {
+            Ok(uri) => parsed.uris.push(uri),
+            Err(error) => parsed.errors.push(ArgError {
+                index,
+                span: error_span(&arg, &error),
+                error,
+            }),
+        }
+    }
+    parsed
+}
+
+/// Where each component of a URI string lies, split by the same rules as
+/// [`RawAtomUriRef::parse`](crate::RawAtomUriRef::parse) but without
+/// rejecting anything.
+struct Spans {
+    label: Range<usize>,
+    tag: Option<Range<usize>>,
+    version: Option<Range<usize>>,
+    fragment: Option<Range<usize>>,
+}
+
+impl Spans {
+    fn of(s: &str) -> Self {
+        let start = s.rfind("::").map_or(0, |i| i + 2);
+        let end = s.len();
+        let (ref_end, fragment) = match s[start..].find('#') {
+            Some(i) => (start + i, Some(start + i + 1..end)),
+            None => (end, None),
+        };
+        let (label_end, version) = match s[start..ref_end].rfind('@') {
+            Some(i) => (start + i, Some(start + i + 1..ref_end)),
+            None => (ref_end, None),
+        };
+        let (label, tag) = match s[start..label_end].find(':') {
+            Some(i) => (start..start + i, Some(start + i + 1..label_end)),
+            None => (start..label_end, None),
+        };
+        Self {
+            label,
+            tag,
+            version,
+            fragment,
+        }
+    }
+}
+
+/// The span of `arg` that `error` is about.
+fn error_span(arg: &str, error: &UriError) -> Range<usize> {
+    let spans = Spans::of(arg);
+    let span = match error {
+        UriError::MissingLabel | UriError::InvalidLabel(_) => Some(spans.label),
+        UriError::EmptyTag | UriError::InvalidTag(_) => spans.tag,
+        UriError::EmptyVersion => spans.version,
+        UriError::EmptyFragment | UriError::InvalidFragment(_) => spans.fragment,
+        UriError::InvalidEscape(text) => [spans.tag, spans.version, spans.fragment]
+            .into_iter()
+            .flatten()
+            .find(|span| arg[span.clone()] == **text),
+        _ => None,
+    };
+    span.unwrap_or(0..arg.len())
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| (*s).to_owned()).collect()
+    }
+
+    #[test]
+    fn collects_every_success_and_failure() {
+        let parsed = parse_args(args(&["good", "src::bad label@1", "x::ok@2", "a@"]));
+        let rendered: Vec<String> = parsed.uris.iter().map(ToString::to_string).collect();
+        assert_eq!(rendered, ["good", "x::ok@2"]);
+
+        let errors: Vec<(usize, Range<usize>)> = parsed
+            .errors
+            .iter()
+            .map(|e| (e.index, e.span.clone()))
+            .collect();
+        assert_eq!(errors, [(1, 5..14), (3, 2..2)]);
+        assert!(parsed.errors[0].to_string().starts_with("argument 1: "));
+        assert!(parse_args(args(&["a", "b"])).into_result().is_ok());
+    }
+
+    #[test]
+    fn spans_point_at_the_bad_component() {
+        fn span(arg: &str) -> &str {
+            let mut parsed = parse_args([arg.to_owned()]);
+            let error = parsed.errors.pop().expect("argument should fail");
+            &arg[error.span]
+        }
+        assert_eq!(span("src::atom:@1"), "");
+        assert_eq!(span("src::atom:Bad Tag@1"), "Bad Tag");
+        assert_eq!(span("atom#../up"), "../up");
+        assert_eq!(span("atom@1%zz"), "1%zz");
+        assert_eq!(span("src::"), "");
+    }
+}
@@ -0,0 +1,241 @@
+//! Alias configuration files: [`AliasFile`], found by
+//! [`RawAtomUri::resolve_from_env`](crate::RawAtomUri::resolve_from_env).
+
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::{fmt, io};
+
+use alurl::{AliasMap, AliasSource, ConflictError};
+
+/// Environment variable naming the alias file, overriding the XDG path.
+pub const ALIASES_ENV: &str = "ATOM_ALIASES";
+
+/// An alias file on disk, loaded as an [`AliasSource`].
+///
+/// The format is one `name = value` per line; blank lines and lines
+/// starting with `#` are ignored, and whitespace around names and values is
+/// trimmed:
+///
+/// ```text
+/// # forges
+/// gh   = github.com
+/// work = +gh/my-org
+/// ```
+///
+/// An alias defined twice with different values is an error, not
+/// last-one-wins, so a mistaken edit cannot silently redirect a source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AliasFile {
+    path: PathBuf,
+}
+
+impl AliasFile {
+    /// The alias file at `path`.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// The file's path.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The conventional alias file location, in order of precedence:
+    ///
+    /// 1. the path in `$ATOM_ALIASES` ([`ALIASES_ENV`]), which must exist if set;
+    /// 2. `$XDG_CONFIG_HOME/atom/aliases`;
+    /// 3. `$HOME/.config/atom/aliases`.
+    ///
+    /// The XDG locations are only returned if the file exists; `None`
+    /// means there is no alias configuration to load.
+    #[must_use]
+    pub fn discover() -> Option<Self> {
+        discover_with(|key| std::env::var_os(key), Path::exists)
+    }
+}
+
+/// [`AliasFile::discover`] over an injected environment and filesystem.
+fn discover_with(
+    var: impl Fn(&str) -> Option<OsString>,
+    exists: impl Fn(&Path) -> bool,
+) -> Option<AliasFile> {
+    if let Some(path) = var(ALIASES_ENV).filter(|p| !p.is_empty()) {
+        return Some(AliasFile::new(path));
+    }
+    let config_home = var("XDG_CONFIG_HOME")
+        .filter(|p| !p.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| var("HOME").map(|home| Path::new(&home).join(".config")))?;
+    let path = config_home.join("atom").join("aliases");
+    exists(&path).then(|| AliasFile::new(path))
+}
+
+impl AliasSource for AliasFile {
+    type Error = AliasConfigError;
+
+    fn load(&self) -> Result<AliasMap, Self::Error> {
+        let text = std::fs::read_to_string(&self.path).map_err(|e| AliasConfigError::Io {
+            path: self.path.clone(),
+            source: e,
+        })?;
+        parse_aliases(&text).map_err(|kind| kind.at(&self.path))
+    }
+}
+
+/// Parse the `name = value` format into a map.
+fn parse_aliases(text: &str) -> Result<AliasMap, ParseFailure> {
+    let mut builder = AliasMap::builder();
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        match line.split_once('=') {
+            Some((name, value)) if !name.trim().is_empty() => {
+                builder.insert(name.trim(), value.trim());
+            },
+            _ => return Err(ParseFailure::Syntax { line: index + 1 }),
+        }
+    }
+    builder.build().map_err(ParseFailure::Conflict)
+}
+
+/// A parse failure not yet tied to a file.
+enum ParseFailure {
+    Syntax { line: usize },
+    Conflict(ConflictError),
+}
+
+impl ParseFailure {
+    fn at(self, path: &Path) -> AliasConfigError {
+        let path = path.to_owned();
+        match self {
+            Self::Syntax { line } => AliasConfigError::Syntax { path, line },
+            Self::Conflict(source) => AliasConfigError::Conflict { path, source },
+        }
+    }
+}
+
+/// Errors loading an [`AliasFile`].
+#[derive(Debug)]
+pub enum AliasConfigError {
+    /// The file could not be read.
+    Io {
+        /// The alias file.
+        path: PathBuf,
+        /// The underlying error.
+        source: io::Error,
+    },
+    /// A line is not `name = value`.
+    Syntax {
+        /// The alias file.
+        path: PathBuf,
+        /// The line number, counting from 1.
+        line: usize,
+    },
+    /// Aliases defined more than once with different values.
+    Conflict {
+        /// The alias file.
+        path: PathBuf,
+        /// Every conflicting alias.
+        source: ConflictError,
+    },
+}
+
+impl fmt::Display for AliasConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io { path, source } => {
+                write!(f, "cannot read alias file {}: {source}", path.display())
+            },
+            Self::Syntax { path, line } => {
+                write!(f, "{}:{line}: expected 'name = value'", path.display())
+            },
+            Self::Conflict { path, source } => write!(f, "{}: {source}", path.display()),
+        }
+    }
+}
+
+impl std::error::Error for AliasConfigError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io { source, .. } => Some(source),
+            Self::Syntax { .. } => None,
+            Self::Conflict { source, .. } => Some(source),
+        }
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env<'a>(pairs: &'a [(&'a str, &'a str)]) -> impl Fn(&str) -> Option<OsString> + 'a {
+        move |key| {
+            pairs
+                .iter()
+                .find(|(k, _)| *k == key)
+                .map(|(_, v)| OsString::from(v))
+        }
+    }
+
+    #[test]
+    fn discovery_precedence() {
+        let found = |pairs: &[(&str, &str)], exists: bool| {
+            discover_with(env(pairs), |_| exists).map(|f| f.path().to_owned())
+        };
+        let all = [
+            (ALIASES_ENV, "/etc/atom-aliases"),
+            ("XDG_CONFIG_HOME", "/xdg"),
+            ("HOME", "/home/me"),
+        ];
+        assert_eq!(found(&all, false), Some(PathBuf::from("/etc/atom-aliases")));
+        assert_eq!(
+            found(&all[1..], true),
+            Some(PathBuf::from("/xdg/atom/aliases"))
+        );
+        assert_eq!(
+            found(&all[2..], true),
+            Some(PathBuf::from("/home/me/.config/atom/aliases"))
+        );
+        assert_eq!(found(&all[1..], false), None);
+        assert_eq!(found(&[], true), None);
+    }
+
+    #[test]
+    fn loads_name_value_lines() {
+        let path = std::env::temp_dir().join(format!("atom-uri-aliases-{}", std::process::id()));
+        std::fs::write(&path, "# forges\n gh = github.com \n\nwork=+gh/my-org\n").unwrap();
+        let map = AliasFile::new(&path).load();
+        std::fs::remove_file(&path).unwrap();
+
+        let map = map.unwrap();
+        assert_eq!(
+            map.resolve("+work/repo").unwrap().url(),
+            "github.com/my-org/repo"
+        );
+    }
+
+    #[test]
+    fn reports_bad_lines_and_conflicts() {
+        assert!(matches!(
+            parse_aliases("gh = github.com\njust text\n"),
+            Err(ParseFailure::Syntax { line: 2 })
+        ));
+        assert!(matches!(
+            parse_aliases("= value\n"),
+            Err(ParseFailure::Syntax { line: 1 })
+        ));
+        assert!(matches!(
+            parse_aliases("gh = github.com\ngh = gitlab.com\n"),
+            Err(ParseFailure::Conflict(_))
+        ));
+        let missing = AliasFile::new("/nonexistent/atom/aliases").load();
+        assert!(matches!(missing, Err(AliasConfigError::Io { .. })));
+    }
+}
@@ -0,0 +1,135 @@
+//! Escaping of the components after the label, so that
+//! `parse(display(uri)) == uri` for every URI.
+//!
+//! The delimiters `::`, `@` and `#` are found by position — rightmost `::`,
+//! first `#`, rightmost `@` — so a tag, version or fragment that contains
+//! one would be split in the wrong place. Display escapes the offending
+//! characters as `%XX` and parsing reverses it:
+//!
+//! | character               | escaped in              | as    |
+//! | ----------------------- | ----------------------- | ----- |
+//! | `%`                     | version, fragment       | `%25` |
+//! | `@`                     | version                 | `%40` |
+//! | `#`                     | version                 | `%23` |
+//! | `:` directly after `:`  | tag, version, fragment  | `%3A` |
+//!
+//! Only these four escapes exist; any other `%` is an error. Tags cannot
+//! contain `%`, `@` or `#`, so only their `::` needs care. Sources are never
+//! escaped: the rightmost `::` is always the source delimiter once nothing
+//! after it can contain `::`.
+
+use std::borrow::Cow;
+use std::fmt::{self, Write as _};
+
+use atom_id::{RawVersion, Tag};
+
+use crate::UriError;
+
+/// Characters always escaped in a version.
+const VERSION_RESERVED: &[char] = &['%', '@', '#'];
+/// Characters always escaped in a fragment.
+const FRAGMENT_RESERVED: &[char] = &['%'];
+
+/// Write `[:tag] [@version] [#fragment]`, escaped.
+pub(crate) fn write_tail(
+    f: &mut fmt::Formatter<'_>,
+    tag: Option<&Tag>,
+    version: Option<&RawVersion>,
+    fragment: Option<&str>,
+) -> fmt::Result {
+    if let Some(tag) = tag {
+        f.write_char(':')?;
+        write_escaped(f, tag, &[])?;
+    }
+    if let Some(ver) = version {
+        f.write_char('@')?;
+        write_escaped(f, ver.as_str(), VERSION_RESERVED)?;
+    }
+    if let Some(frag) = fragment {
+        f.write_char('#')?;
+        write_escaped(f, frag, FRAGMENT_RESERVED)?;
+    }
+    Ok(())
+}
+
+fn write_escaped(f: &mut fmt::Formatter<'_>, s: &str, reserved: &[char]) -> fmt::Result {
+    let mut prev = None;
+    for c in s.chars() {
+        if reserved.contains(&c) || (c == ':' && prev == Some(':')) {
+            write!(f, "%{:02X}", u32::from(c))?;
+        } else {
+            f.write_char(c)?;
+        }
+        prev = Some(c);
+    }
+    Ok(())
+}
+
+/// Reverse the escaping of a tag, version or fragment.
+pub(crate) fn unescape(s: &str) -> Result<Cow<'_, str>, UriError> {
+    if !s.contains('%') {
+        return Ok(Cow::Borrowed(s));
+    }
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(i) = rest.find('%') {
+        out.push_str(&rest[..i]);
+        let c = match rest
+            .get(i + 1..i + 3)
+            .map(str::to_ascii_uppercase)
+            .as_deref()
+        {
+            Some("25") => '%',
+            Some("3A") => ':',
+            Some("40") => '@',
+            Some("23") => '#',
+            _ => return Err(UriError::InvalidEscape(s.to_owned())),
+        };
+        out.push(c);
+        rest = &rest[i + 3..];
+    }
+    out.push_str(rest);
+    Ok(Cow::Owned(out))
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Tail<'a>(Option<&'a str>, Option<&'a str>);
+
+    impl fmt::Display for Tail<'_> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            let version = self.0.map(|v| RawVersion::new(v.to_owned()));
+            write_tail(f, None, version.as_ref(), self.1)
+        }
+    }
+
+    #[test]
+    fn escapes_only_what_would_misparse() {
+        assert_eq!(Tail(Some("1.0:rc"), None).to_string(), "@1.0:rc");
+        assert_eq!(
+            Tail(Some("a::b@c#d%e"), None).to_string(),
+            "@a:%3Ab%40c%23d%25e"
+        );
+        assert_eq!(Tail(None, Some("x@2x.png")).to_string(), "#x@2x.png");
+        assert_eq!(Tail(None, Some("a:::b/%")).to_string(), "#a:%3A%3Ab/%25");
+    }
+
+    #[test]
+    fn unescape_reverses_and_rejects_unknown_escapes() {
+        assert_eq!(unescape("a:%3Ab%40c%23d%25e").unwrap(), "a::b@c#d%e");
+        assert_eq!(unescape("%3a").unwrap(), ":");
+        assert!(matches!(unescape("plain").unwrap(), Cow::Borrowed(_)));
+        for bad in ["%", "%2", "%2F", "100%"] {
+            assert!(
+                matches!(unescape(bad), Err(UriError::InvalidEscape(_))),
+                "{bad}"
+            );
+        }
+    }
+}
@@ -4,23 +4,52 @@
 //! and at a specific version. The grammar is:
 //!
 //! ```text
-//! [source::] label [@version]
+//! [source::] label [:tag] [@version] [#fragment]
 //! ```
 //!
 //! - **source** — a URL, SCP-style address, path, or `+`-prefixed alias (resolved via [`alurl`]).
-//!   Split from the atom-ref by the **rightmost** `::` delimiter.
+//!   Split from the atom-ref by the **rightmost** `::` delimiter. It may instead be an [`Anchor`],
+//!   base64url-encoded, so an [`AtomId`]'s `anchor::label` form is itself a URI.
 //! - **label** — a validated [`Label`] identifying the atom within its set.
 //! - **version** — an unparsed [`RawVersion`] string. Interpretation is deferred to a
 //!   [`VersionScheme`](atom_id::VersionScheme) implementor.
+//! - **tag** — a [`Tag`] selecting a channel or track of the atom (e.g.
+//!   `src::atom:channel.stable@1.0`), so the selection need not be smuggled into the version. Split
+//!   from the label at its **first** `:`, which no label contains.
+//! - **label** may also be a brace group, `{a,b,c}`, naming several atoms of one source at one
+//!   version; [`RawAtomUri::expand`] splits it into one URI per label.
+//! - **fragment** — a relative path to a file or subtree inside the atom (e.g.
+//!   `gh::tools@1.2#scripts/install.sh`). Split from the atom-ref at its **first** `#`; a `::`
+//!   inside it is escaped (see [Escaping](#escaping)).
 //!
 //! The `@` for version extraction uses **rightmost** split to avoid ambiguity
 //! with `@` in source URLs (e.g., `git@github.com:repo::atom@1.0`).
 //!
+//! # Escaping
+//!
+//! A tag, version or fragment that contains a delimiter is written with
+//! `%XX` escapes — `%25` for `%`, `%40` for `@` and `%23` for `#` in a
+//! version, `%25` in a fragment, and `%3A` for any `:` that directly
+//! follows another — and unescaped on parsing. No other escape exists. So
+//! every URI round-trips: parsing its [`Display`](fmt::Display) form gives
+//! it back.
+//!
 //! # Types
 //!
 //! - [`RawAtomUri`] — parsed but unresolved (alias not yet expanded).
+//! - [`RawAtomUriRef`] — borrowed, allocation-free split of a URI string, for scanning many.
+//! - [`AtomPattern`] — a discovery query, `[source::] glob`, with `*` and `?` over labels.
+//!
+//! - [`LockedAtomRef`] — a lockfile entry pinning a URI to a version, revision and digest.
+//! - [`SourcePolicy`] — the source transports [`RawAtomUri::resolve_checked`] accepts.
+//!
+//! [`parse_uri_list`] reads a requirements-style file of URIs, one per line, and
+//! [`parse_args`] a command line's worth, reporting every bad argument.
 //! - [`AtomUri`] — fully resolved (source aliases expanded via [`AliasMap`]).
 //!
+//! With the `serde` feature, both serialize as their [`Display`](fmt::Display)
+//! string and deserialize through [`FromStr`].
+//!
 //! # Examples
 //!
 //! ```
@@ -43,11 +72,34 @@
 #![warn(rust_2018_idioms)]
 #![forbid(unsafe_code)]
 
+use std::borrow::Cow;
+use std::ffi::{OsStr, OsString};
 use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::path::Path;
 use std::str::FromStr;
 
 pub use alurl::{AliasMap, AliasSource, AliasedUrl};
-pub use atom_id::{Label, RawVersion};
+use atom_id::VersionScheme;
+pub use atom_id::{Anchor, AtomId, Label, RawRequirement, RawVersion, Tag};
+
+mod args;
+mod config;
+mod escape;
+mod list;
+mod lock;
+mod pattern;
+mod policy;
+mod punycode;
+mod source;
+
+pub use args::{ArgError, ParsedArgs, parse_args};
+pub use config::{ALIASES_ENV, AliasConfigError, AliasFile};
+pub use list::{LineError, ListError, parse_uri_list};
+pub use lock::LockedAtomRef;
+pub use pattern::AtomPattern;
+pub use policy::{PolicyError, SourcePolicy};
+pub use source::SourceKind;
 
 // ============================================================================
 // Errors
@@ -60,10 +112,45 @@ pub enum UriError {
     MissingLabel,
     /// The label portion failed [`Label`] validation.
     InvalidLabel(atom_id::Error),
+    /// An empty tag after the label's `:` (e.g., `atom:@1.0`).
+    EmptyTag,
+    /// The tag portion failed [`Tag`] validation.
+    InvalidTag(atom_id::Error),
+    /// An empty source before `::` (e.g., `::atom`), rejected by
+    /// [`RawAtomUri::parse_with`] unless
+    /// [`ParseOptions::allow_empty_source`] is set.
+    EmptySource,
     /// An empty version string after `@` (e.g., `atom@`).
     EmptyVersion,
+    /// An empty fragment after `#` (e.g., `atom#`).
+    EmptyFragment,
+    /// A fragment that is not a relative path inside the atom — absolute,
+    /// or with a `.`/`..`/empty segment.
+    InvalidFragment(String),
     /// Alias resolution failed during [`RawAtomUri::resolve`].
     AliasError(alurl::ResolveError),
+    /// The alias file for [`RawAtomUri::resolve_from_env`] could not be
+    /// loaded.
+    AliasConfig(AliasConfigError),
+    /// [`RawAtomUri::resolve_checked`] resolved to a source its
+    /// [`SourcePolicy`] does not allow.
+    Policy(PolicyError),
+    /// A Unicode host too long to write in Punycode, under
+    /// [`ResolveOptions::ascii_hosts`].
+    InvalidHost(String),
+    /// A path or command-line argument is not valid UTF-8.
+    InvalidUnicode,
+    /// A [`LockedAtomRef`] that is malformed or cannot be written in its
+    /// text form.
+    InvalidLockEntry(String),
+    /// A `%` in a tag, version or fragment that does not start one of the
+    /// escapes `%25`, `%3A`, `%40` or `%23`.
+    InvalidEscape(String),
+    /// An [`AtomPattern`] glob that no label could match.
+    InvalidPattern(String),
+    /// [`RawAtomUri::resolve`] was given a brace group (`{a,b}`), which
+    /// names several atoms; [`expand`](RawAtomUri::expand) it first.
+    UnexpandedGroup,
 }
 
 impl fmt::Display for UriError {
@@ -71,8 +158,26 @@ impl fmt::Display for UriError {
         match self {
             Self::MissingLabel => write!(f, "missing atom label"),
             Self::InvalidLabel(e) => write!(f, "invalid atom label: {e}"),
+            Self::EmptyTag => write!(f, "empty tag after ':'"),
+            Self::InvalidTag(e) => write!(f, "invalid atom tag: {e}"),
+            Self::EmptySource => write!(f, "empty source before '::'"),
             Self::EmptyVersion => write!(f, "empty version after '@'"),
+            Self::EmptyFragment => write!(f, "empty fragment after '#'"),
+            Self::InvalidFragment(frag) => {
+                write!(
+                    f,
+                    "fragment is not a relative path inside the atom: '{frag}'"
+                )
+            },
             Self::AliasError(e) => write!(f, "alias resolution failed: {e}"),
+            Self::AliasConfig(e) => write!(f, "alias configuration failed: {e}"),
+            Self::Policy(e) => write!(f, "{e}"),
+            Self::InvalidHost(src) => write!(f, "host cannot be written in punycode: '{src}'"),
+            Self::InvalidUnicode => write!(f, "must be valid unicode"),
+            Self::InvalidLockEntry(msg) => write!(f, "invalid lock entry: {msg}"),
+            Self::InvalidEscape(s) => write!(f, "invalid '%' escape in '{s}'"),
+            Self::InvalidPattern(glob) => write!(f, "invalid label pattern: '{glob}'"),
+            Self::UnexpandedGroup => write!(f, "label group must be expanded before resolution"),
         }
     }
 }
@@ -80,8 +185,10 @@ impl fmt::Display for UriError {
 impl std::error::Error for UriError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
-            Self::InvalidLabel(e) => Some(e),
+            Self::InvalidLabel(e) | Self::InvalidTag(e) => Some(e),
             Self::AliasError(e) => Some(e),
+            Self::AliasConfig(e) => Some(e),
+            Self::Policy(e) => Some(e),
             _ => None,
         }
     }
@@ -99,6 +206,42 @@ impl From<alurl::ResolveError> for UriError {
     }
 }
 
+impl From<AliasConfigError> for UriError {
+    fn from(e: AliasConfigError) -> Self {
+        Self::AliasConfig(e)
+    }
+}
+
+// ============================================================================
+// ParseOptions
+// ============================================================================
+
+/// Options for [`RawAtomUri::parse_with`].
+///
+/// The default is strict: every option is off.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ParseOptions {
+    /// Accept an empty source, as in `::atom`. Off by default: an empty
+    /// source is almost always a templating slip, and downstream code
+    /// cannot tell it from the working directory.
+    pub allow_empty_source: bool,
+}
+
+/// Options for [`RawAtomUri::resolve_with`].
+///
+/// The default resolves exactly as [`RawAtomUri::resolve`] does.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ResolveOptions {
+    /// Write a Unicode host in the resolved source as Punycode ASCII —
+    /// `https://bücher.example/repo` becomes
+    /// `https://xn--bcher-kva.example/repo` — for transports that only
+    /// accept ASCII hosts. Applied after alias expansion, so an alias
+    /// value may be Unicode too. Labels with non-ASCII characters are
+    /// lowercased and encoded; the full UTS #46 mapping is not applied,
+    /// so hosts are expected in NFC.
+    pub ascii_hosts: bool,
+}
+
 // ============================================================================
 // RawAtomUri
 // ============================================================================
@@ -106,56 +249,236 @@ impl From<alurl::ResolveError> for UriError {
 /// A parsed but unresolved atom URI.
 ///
 /// Contains the raw source string (which may include `+`-prefixed aliases),
-/// a validated [`Label`], and an optional [`RawVersion`]. No alias resolution
+/// a validated [`Label`], an optional [`Tag`], an optional [`RawVersion`],
+/// and an optional intra-atom path fragment. No alias resolution
 /// has been performed — call [`resolve`](RawAtomUri::resolve) to expand
 /// aliases via an [`AliasMap`].
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct RawAtomUri {
     source: Option<String>,
     label: Label,
+    /// Labels after the first in a `{a,b,c}` group; empty otherwise.
+    group: Vec<Label>,
+    tag: Option<Tag>,
     version: Option<RawVersion>,
+    fragment: Option<String>,
 }
 
 impl RawAtomUri {
+    /// Parse an atom URI string under `options`.
+    ///
+    /// [`FromStr`] is the lenient parse; this is the entry point for
+    /// input that should be held to [`ParseOptions`] — by default,
+    /// rejecting `::atom`.
+    ///
+    /// # Errors
+    ///
+    /// [`UriError::EmptySource`] if the source is empty and
+    /// [`ParseOptions::allow_empty_source`] is off, and otherwise whatever
+    /// [`FromStr`] returns.
+    pub fn parse_with(s: &str, options: ParseOptions) -> Result<Self, UriError> {
+        let parsed = RawAtomUriRef::parse(s)?;
+        if parsed.source() == Some("") && !options.allow_empty_source {
+            return Err(UriError::EmptySource);
+        }
+        parsed.into_owned()
+    }
+
     /// The source component, if present (before `::` delimiter).
     #[must_use]
     pub fn source(&self) -> Option<&str> {
         self.source.as_deref()
     }
 
-    /// The atom label.
+    /// The atom label — for a brace group, its first label.
     #[must_use]
     pub fn label(&self) -> &Label {
         &self.label
     }
 
+    /// Every label the URI names, in order: one, or each label of a
+    /// `{a,b,c}` group.
+    pub fn labels(&self) -> impl Iterator<Item = &Label> {
+        std::iter::once(&self.label).chain(&self.group)
+    }
+
+    /// Whether the URI is a brace group naming more than one atom.
+    #[must_use]
+    pub fn is_group(&self) -> bool {
+        !self.group.is_empty()
+    }
+
+    /// One URI per label, each with this URI's source, version and
+    /// fragment. A single-label URI expands to itself.
+    #[must_use]
+    pub fn expand(&self) -> Vec<RawAtomUri> {
+        self.labels()
+            .map(|label| RawAtomUri {
+                label: label.clone(),
+                group: Vec::new(),
+                ..self.clone()
+            })
+            .collect()
+    }
+
+    /// The tag, if present (after the label's `:`).
+    #[must_use]
+    pub fn tag(&self) -> Option<&Tag> {
+        self.tag.as_ref()
+    }
+
     /// The raw version string, if present (after `@`).
     #[must_use]
     pub fn version(&self) -> Option<&RawVersion> {
         self.version.as_ref()
     }
 
+    /// The path inside the atom, if present (after `#`).
+    #[must_use]
+    pub fn fragment(&self) -> Option<&str> {
+        self.fragment.as_deref()
+    }
+
     /// Resolve aliases in the source component.
     ///
     /// If the source contains a `+`-prefixed alias at a valid host position,
     /// it is expanded via the [`AliasMap`]. If no source is present, or the
-    /// source contains no alias, resolution still succeeds.
+    /// source contains no alias, resolution still succeeds. A source that
+    /// is a base64url anchor of czd size (32, 48 or 64 bytes) resolves to
+    /// an anchor-qualified URI ([`AtomUri::anchor`]) with no source URL.
     ///
     /// # Errors
     ///
     /// - [`UriError::AliasError`] — alias resolution failed (not found, invalid name, or cycle).
+    /// - [`UriError::UnexpandedGroup`] — the URI is a brace group; [`expand`](Self::expand) it.
     pub fn resolve(&self, map: &AliasMap) -> Result<AtomUri, UriError> {
+        if self.is_group() {
+            return Err(UriError::UnexpandedGroup);
+        }
+        if let Some(src) = &self.source
+            && let Some(anchor) = source::anchor(src)
+        {
+            return Ok(AtomUri {
+                source: None,
+                written_source: Some(src.clone()),
+                anchor: Some(anchor),
+                label: self.label.clone(),
+                tag: self.tag.clone(),
+                version: self.version.clone(),
+                fragment: self.fragment.clone(),
+            });
+        }
         let resolved_source = match &self.source {
             Some(src) => Some(map.resolve(src)?),
             None => None,
         };
+        let written_source = match &resolved_source {
+            Some(AliasedUrl::Expanded { .. }) => self.source.clone(),
+            _ => None,
+        };
 
         Ok(AtomUri {
             source: resolved_source,
+            written_source,
+            anchor: None,
             label: self.label.clone(),
+            tag: self.tag.clone(),
             version: self.version.clone(),
+            fragment: self.fragment.clone(),
         })
     }
+
+    /// Resolve like [`resolve`](Self::resolve), under `options`.
+    ///
+    /// # Errors
+    ///
+    /// - [`UriError::InvalidHost`] — [`ascii_hosts`](ResolveOptions::ascii_hosts) is set and the
+    ///   host cannot be encoded.
+    /// - Otherwise as for [`resolve`](Self::resolve).
+    pub fn resolve_with(
+        &self,
+        map: &AliasMap,
+        options: ResolveOptions,
+    ) -> Result<AtomUri, UriError> {
+        let mut uri = self.resolve(map)?;
+        if options.ascii_hosts
+            && let Some(src) = &uri.source
+        {
+            let url = src.url();
+            let ascii =
+                source::ascii_host(url).ok_or_else(|| UriError::InvalidHost(url.to_owned()))?;
+            if let Cow::Owned(ascii) = ascii {
+                uri.source = Some(match src {
+                    AliasedUrl::Expanded { alias, .. } => AliasedUrl::Expanded {
+                        alias: alias.clone(),
+                        url: ascii,
+                    },
+                    AliasedUrl::Raw(_) => AliasedUrl::Raw(ascii),
+                });
+            }
+        }
+        Ok(uri)
+    }
+
+    /// Resolve like [`resolve`](Self::resolve), then check the resolved
+    /// source against `policy` — for deployments that must never fetch
+    /// over, say, plaintext `http://`. The check sees the source after
+    /// alias expansion, so an alias cannot route around it.
+    ///
+    /// # Errors
+    ///
+    /// - [`UriError::Policy`] — the policy does not allow the resolved source.
+    /// - Otherwise as for [`resolve`](Self::resolve).
+    pub fn resolve_checked(
+        &self,
+        map: &AliasMap,
+        policy: &SourcePolicy,
+    ) -> Result<AtomUri, UriError> {
+        let uri = self.resolve(map)?;
+        policy.check(&uri)?;
+        Ok(uri)
+    }
+
+    /// Resolve with the user's configured aliases: the [`AliasFile`] found
+    /// by [`AliasFile::discover`] (`$ATOM_ALIASES`, then the XDG config
+    /// directory), or no aliases if there is none. For small tools that
+    /// should honour aliases without their own configuration plumbing.
+    ///
+    /// # Errors
+    ///
+    /// - [`UriError::AliasConfig`] — the alias file exists but cannot be loaded.
+    /// - Otherwise as for [`resolve`](Self::resolve).
+    pub fn resolve_from_env(&self) -> Result<AtomUri, UriError> {
+        let map = match AliasFile::discover() {
+            Some(file) => file.load()?,
+            None => AliasMap::new(),
+        };
+        self.resolve(&map)
+    }
+
+    /// Resolve like [`resolve`](Self::resolve), but a bare-label URI takes
+    /// `default_source` — e.g. a configured default registry — as its
+    /// source. The default may itself use an alias; it is expanded through
+    /// `map` like any other source. A URI with its own source, even an
+    /// empty one (`::label`), keeps it.
+    ///
+    /// # Errors
+    ///
+    /// As for [`resolve`](Self::resolve).
+    pub fn resolve_with_default(
+        &self,
+        map: &AliasMap,
+        default_source: &str,
+    ) -> Result<AtomUri, UriError> {
+        if self.source.is_some() {
+            return self.resolve(map);
+        }
+        RawAtomUri {
+            source: Some(default_source.to_owned()),
+            ..self.clone()
+        }
+        .resolve(map)
+    }
 }
 
 impl FromStr for RawAtomUri {
@@ -163,51 +486,245 @@ impl FromStr for RawAtomUri {
 
     /// Parse an atom URI string.
     ///
-    /// Grammar: `[source::] label [@version]`
+    /// Grammar: `[source::] label [@version] [#fragment]`
     ///
     /// Uses rightmost `::` for source split and rightmost `@` for version
-    /// split to avoid ambiguity with `@` in URLs and `::` in paths.
+    /// split to avoid ambiguity with `@` in URLs and `::` in paths. The
+    /// fragment is split off the atom-ref at its first `#`.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        RawAtomUriRef::parse(s)?.into_owned()
+    }
+}
+
+/// For command-line arguments: non-UTF-8 input is rejected with
+/// [`UriError::InvalidUnicode`] rather than lossily converted.
+impl TryFrom<&OsStr> for RawAtomUri {
+    type Error = UriError;
+
+    fn try_from(s: &OsStr) -> Result<Self, Self::Error> {
+        s.to_str().ok_or(UriError::InvalidUnicode)?.parse()
+    }
+}
+
+impl TryFrom<OsString> for RawAtomUri {
+    type Error = UriError;
+
+    fn try_from(s: OsString) -> Result<Self, Self::Error> {
+        Self::try_from(s.as_os_str())
+    }
+}
+
+/// Parse the inside of a `{a,b,c}` group. Repeated labels are dropped; a
+/// one-label group is just that label.
+fn parse_group(group: &str) -> Result<Vec<Label>, UriError> {
+    let mut labels: Vec<Label> = Vec::new();
+    for part in group.split(',') {
+        if part.is_empty() {
+            return Err(UriError::MissingLabel);
+        }
+        let label = Label::try_from(part)?;
+        if !labels.contains(&label) {
+            labels.push(label);
+        }
+    }
+    Ok(labels)
+}
+
+/// Validate a fragment as a relative path inside the atom: non-empty,
+/// not absolute, and made of non-empty segments other than `.` and `..`.
+fn check_fragment(frag: &str) -> Result<(), UriError> {
+    if frag.is_empty() {
+        return Err(UriError::EmptyFragment);
+    }
+    if frag
+        .split('/')
+        .any(|seg| seg.is_empty() || seg == "." || seg == "..")
+    {
+        return Err(UriError::InvalidFragment(frag.to_owned()));
+    }
+    Ok(())
+}
+
+impl fmt::Display for RawAtomUri {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(src) = &self.source {
+            write!(f, "{src}::")?;
+        }
+        if self.is_group() {
+            f.write_str("{")?;
+            for (i, label) in self.labels().enumerate() {
+                if i > 0 {
+                    f.write_str(",")?;
+                }
+                write!(f, "{label}")?;
+            }
+            f.write_str("}")?;
+        } else {
+            write!(f, "{}", self.label)?;
+        }
+        escape::write_tail(
+            f,
+            self.tag.as_ref(),
+            self.version.as_ref(),
+            self.fragment.as_deref(),
+        )
+    }
+}
+
+// ============================================================================
+// RawAtomUriRef
+// ============================================================================
+
+/// A borrowed atom URI: the components of a URI string as slices of it.
+///
+/// [`parse`](Self::parse) splits the string exactly as [`RawAtomUri`]'s
+/// [`FromStr`] does and checks the version and fragment, but allocates
+/// nothing and leaves the label unvalidated — it is the text as written,
+/// brace group included. That suits hot paths, such as scanning a lockfile
+/// for one label, that would otherwise build thousands of owned URIs only to
+/// drop them. [`into_owned`](Self::into_owned) validates the label and
+/// yields the [`RawAtomUri`] when one is needed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RawAtomUriRef<'a> {
+    source: Option<&'a str>,
+    label: &'a str,
+    tag: Option<&'a str>,
+    version: Option<&'a str>,
+    fragment: Option<&'a str>,
+}
+
+impl<'a> RawAtomUriRef<'a> {
+    /// Split an atom URI string without allocating.
+    ///
+    /// # Errors
+    ///
+    /// - [`UriError::MissingLabel`] — the label is empty.
+    /// - [`UriError::EmptyTag`] — nothing follows the label's `:`.
+    /// - [`UriError::EmptyVersion`] — nothing follows `@`.
+    /// - [`UriError::EmptyFragment`] / [`UriError::InvalidFragment`] — the fragment is not a
+    ///   relative path inside the atom.
+    pub fn parse(s: &'a str) -> Result<Self, UriError> {
         let (source, atom_ref) = match s.rsplit_once("::") {
-            Some((src, rest)) => (Some(src.to_string()), rest),
+            Some((src, rest)) => (Some(src), rest),
             None => (None, s),
         };
 
+        let (atom_ref, fragment) = match atom_ref.split_once('#') {
+            Some((rest, frag)) => {
+                check_fragment(frag)?;
+                (rest, Some(frag))
+            },
+            None => (atom_ref, None),
+        };
+
         // Split label from version at rightmost '@'.
-        let (label_str, version) = match atom_ref.rsplit_once('@') {
+        let (label, version) = match atom_ref.rsplit_once('@') {
             Some((lbl, ver)) => {
                 if ver.is_empty() {
                     return Err(UriError::EmptyVersion);
                 }
-                (lbl, Some(RawVersion::new(ver.to_owned())))
+                (lbl, Some(ver))
             },
             None => (atom_ref, None),
         };
 
-        if label_str.is_empty() {
+        // Split the tag off the label at its first ':'.
+        let (label, tag) = match label.split_once(':') {
+            Some((lbl, tag)) => {
+                if tag.is_empty() {
+                    return Err(UriError::EmptyTag);
+                }
+                (lbl, Some(tag))
+            },
+            None => (label, None),
+        };
+
+        if label.is_empty() {
             return Err(UriError::MissingLabel);
         }
 
-        let label = Label::try_from(label_str)?;
-
-        Ok(RawAtomUri {
+        Ok(Self {
             source,
             label,
+            tag,
             version,
+            fragment,
         })
     }
-}
 
-impl fmt::Display for RawAtomUri {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        if let Some(src) = &self.source {
-            write!(f, "{src}::")?;
-        }
-        write!(f, "{}", self.label)?;
-        if let Some(ver) = &self.version {
-            write!(f, "@{ver}")?;
-        }
-        Ok(())
+    /// The source component, if present (before `::` delimiter).
+    #[must_use]
+    pub fn source(&self) -> Option<&'a str> {
+        self.source
+    }
+
+    /// The label as written, unvalidated and unnormalized — for a brace
+    /// group, the whole `{a,b,c}`.
+    #[must_use]
+    pub fn label(&self) -> &'a str {
+        self.label
+    }
+
+    /// The tag as written — unvalidated, escapes included — if present
+    /// (after the label's `:`).
+    #[must_use]
+    pub fn tag(&self) -> Option<&'a str> {
+        self.tag
+    }
+
+    /// The version as written, escapes included, if present (after `@`).
+    #[must_use]
+    pub fn version(&self) -> Option<&'a str> {
+        self.version
+    }
+
+    /// The fragment as written, escapes included, if present (after `#`).
+    #[must_use]
+    pub fn fragment(&self) -> Option<&'a str> {
+        self.fragment
+    }
+
+    /// Validate the label and tag and build the owned [`RawAtomUri`].
+    ///
+    /// # Errors
+    ///
+    /// - [`UriError::InvalidLabel`] — a label fails [`Label`] validation.
+    /// - [`UriError::MissingLabel`] — a brace group has an empty member.
+    /// - [`UriError::InvalidTag`] — the tag fails [`Tag`] validation.
+    /// - [`UriError::InvalidEscape`] — a `%` that is not one of the escapes.
+    pub fn into_owned(self) -> Result<RawAtomUri, UriError> {
+        let mut labels = match self
+            .label
+            .strip_prefix('{')
+            .and_then(|l| l.strip_suffix('}'))
+        {
+            Some(group) => parse_group(group)?,
+            None => vec![Label::try_from(self.label)?],
+        };
+        let label = labels.remove(0);
+        let tag = match self.tag {
+            Some(tag) => {
+                Some(Tag::try_from(escape::unescape(tag)?.as_ref()).map_err(UriError::InvalidTag)?)
+            },
+            None => None,
+        };
+        let version = match self.version {
+            Some(ver) => Some(RawVersion::new(escape::unescape(ver)?.into_owned())),
+            None => None,
+        };
+        let fragment = match self.fragment {
+            Some(frag) => Some(escape::unescape(frag)?.into_owned()),
+            None => None,
+        };
+
+        Ok(RawAtomUri {
+            source: self.source.map(str::to_owned),
+            label,
+            group: labels,
+            tag,
+            version,
+            fragment,
+        })
     }
 }
 
@@ -220,11 +737,25 @@ impl fmt::Display for RawAtomUri {
 /// The source component (if present) has been processed through
 /// [`AliasMap::resolve`], yielding an [`AliasedUrl`] that is either
 /// `Expanded` (alias was found and expanded) or `Raw` (no alias detected).
+///
+/// Equality and hashing look at what the URI points to, not how it was
+/// written: the resolved source URL or anchor, label, tag, version and fragment. An
+/// `Expanded` source and a `Raw` one with the same URL are equal, so
+/// `+gh/o/r::atom` and `github.com/o/r::atom` share a cache entry. No
+/// other normalization is applied; use [`canonicalize`](Self::canonicalize)
+/// for that.
 #[derive(Debug, Clone)]
 pub struct AtomUri {
     source: Option<AliasedUrl>,
+    /// The source as written, kept when it was expanded from an alias or
+    /// is an anchor.
+    written_source: Option<String>,
+    /// The anchor, for an anchor-qualified URI; `source` is then `None`.
+    anchor: Option<Anchor>,
     label: Label,
+    tag: Option<Tag>,
     version: Option<RawVersion>,
+    fragment: Option<String>,
 }
 
 impl AtomUri {
@@ -243,17 +774,250 @@ impl AtomUri {
         self.source.as_ref().map(|s| s.url())
     }
 
+    /// The source as the user wrote it, if present: the aliased spelling
+    /// (`+gh/owner/repo`) if the source was expanded from an alias, the
+    /// anchor of an anchor-qualified URI, otherwise the same as
+    /// [`source_url`](Self::source_url).
+    ///
+    /// [`canonicalize`](Self::canonicalize) and
+    /// [`canonicalize_path`](Self::canonicalize_path) rewrite the source,
+    /// so their results no longer remember an alias.
+    #[must_use]
+    pub fn as_raw_source(&self) -> Option<&str> {
+        self.written_source.as_deref().or_else(|| self.source_url())
+    }
+
+    /// Convert back to a [`RawAtomUri`] with the source as the user wrote
+    /// it ([`as_raw_source`](Self::as_raw_source)), so a tool that resolved
+    /// a URI to validate it can still persist `+gh/owner/repo::atom` rather
+    /// than the expansion.
+    #[must_use]
+    pub fn into_raw(self) -> RawAtomUri {
+        RawAtomUri {
+            source: self
+                .written_source
+                .or_else(|| self.source.map(|src| src.url().to_owned())),
+            label: self.label,
+            group: Vec::new(),
+            tag: self.tag,
+            version: self.version,
+            fragment: self.fragment,
+        }
+    }
+
+    /// The anchor, if the URI is anchor-qualified (`<anchor>::label`).
+    #[must_use]
+    pub fn anchor(&self) -> Option<&Anchor> {
+        self.anchor.as_ref()
+    }
+
+    /// The [`AtomId`] an anchor-qualified URI names — an exact identity
+    /// rather than a place to fetch from, as found in signed payloads.
+    #[must_use]
+    pub fn atom_id(&self) -> Option<AtomId> {
+        self.anchor
+            .as_ref()
+            .map(|anchor| AtomId::new(anchor.clone(), self.label.clone()))
+    }
+
     /// The atom label.
     #[must_use]
     pub fn label(&self) -> &Label {
         &self.label
     }
 
+    /// The tag, if present.
+    #[must_use]
+    pub fn tag(&self) -> Option<&Tag> {
+        self.tag.as_ref()
+    }
+
     /// The raw version string, if present.
     #[must_use]
     pub fn version(&self) -> Option<&RawVersion> {
         self.version.as_ref()
     }
+
+    /// Parse the version component (`@^1.0`) as a requirement of `scheme`.
+    ///
+    /// Returns `Ok(None)` if the URI names no version.
+    ///
+    /// # Errors
+    ///
+    /// Whatever `scheme` returns for a requirement it cannot parse.
+    pub fn requirement<S: VersionScheme>(
+        &self,
+        scheme: &S,
+    ) -> Result<Option<S::Requirement>, S::Error> {
+        self.version
+            .as_ref()
+            .map(|v| scheme.parse_requirement(&RawRequirement::new(v.as_str().to_owned())))
+            .transpose()
+    }
+
+    /// The path inside the atom, if present.
+    #[must_use]
+    pub fn fragment(&self) -> Option<&str> {
+        self.fragment.as_deref()
+    }
+
+    /// The shape of the resolved source, if present — URL with a scheme,
+    /// schemeless URL, SCP-style address, or absolute or relative path —
+    /// so a backend picks its transport without re-parsing the string.
+    #[must_use]
+    pub fn source_kind(&self) -> Option<SourceKind> {
+        self.source_url().map(SourceKind::of)
+    }
+
+    /// Resolve a relative path source against `base_dir`, so
+    /// `../sets/core::atom` names the same set whichever directory a build
+    /// tool runs from. Path sources come back absolute and lexically
+    /// normalized (`.` and `..` folded away, without consulting the
+    /// filesystem); other sources, and bare labels, are returned unchanged.
+    ///
+    /// # Errors
+    ///
+    /// [`UriError::InvalidUnicode`] if the resolved path is not UTF-8.
+    pub fn canonicalize_path(&self, base_dir: &Path) -> Result<Self, UriError> {
+        let Some(src) = self.source_url() else {
+            return Ok(self.clone());
+        };
+        if !matches!(
+            SourceKind::of(src),
+            SourceKind::AbsolutePath | SourceKind::RelativePath
+        ) {
+            return Ok(self.clone());
+        }
+        let path = source::absolute_path(src, base_dir);
+        let path = path.to_str().ok_or(UriError::InvalidUnicode)?;
+        Ok(Self {
+            source: Some(AliasedUrl::Raw(path.to_owned())),
+            written_source: None,
+            ..self.clone()
+        })
+    }
+
+    /// Render with the source spelled through the shortest alias in `map`
+    /// that covers it ([`AliasMap::compress`]), so status and lock output
+    /// shows `+gh/owner/repo::atom@1.0` rather than an expanded URL the
+    /// user never typed. Sources no alias covers are written as resolved.
+    #[must_use]
+    pub fn display_with(&self, map: &AliasMap) -> String {
+        struct WithAliases<'a>(&'a AtomUri, &'a AliasMap);
+
+        impl fmt::Display for WithAliases<'_> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                let Self(uri, map) = self;
+                if let Some(url) = uri.source_url() {
+                    match map.compress(url) {
+                        Some(short) => write!(f, "{short}::")?,
+                        None => write!(f, "{url}::")?,
+                    }
+                }
+                if let Some(anchor) = &uri.anchor {
+                    write!(f, "{anchor}::")?;
+                }
+                uri.fmt_atom_ref(f)
+            }
+        }
+
+        WithAliases(self, map).to_string()
+    }
+
+    /// Write everything after the `::`: label, tag, version and fragment.
+    fn fmt_atom_ref(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.label)?;
+        escape::write_tail(
+            f,
+            self.tag.as_ref(),
+            self.version.as_ref(),
+            self.fragment.as_deref(),
+        )
+    }
+
+    /// A normalized copy for use as a cache key, so spellings of the same
+    /// source map to one key: `GitHub.com/owner/repo/` and
+    /// `github.com/owner/repo.git` both become `github.com/owner/repo`.
+    ///
+    /// The source URL's scheme and host are lowercased, trailing slashes
+    /// are dropped, and so is a trailing `.git` on remote sources. Paths
+    /// keep their case — whether they are case-sensitive is up to the
+    /// host — and local paths keep `.git`, which names a bare repository
+    /// on disk. The source becomes [`AliasedUrl::Raw`], since which alias
+    /// it came from is not part of its identity. Label, tag, version and
+    /// fragment are unchanged.
+    #[must_use]
+    pub fn canonicalize(&self) -> Self {
+        let Some(src) = &self.source else {
+            return self.clone();
+        };
+        Self {
+            source: Some(AliasedUrl::Raw(source::canonical_source(src.url()))),
+            written_source: None,
+            ..self.clone()
+        }
+    }
+
+    /// Whether both URIs name the same source, compared in
+    /// [`canonicalize`](Self::canonicalize)d form — so
+    /// `GitHub.com/owner/repo/` and `github.com/owner/repo.git` are the
+    /// same, however each was aliased. Two bare labels have the same
+    /// (absent) source, and anchor-qualified URIs the same source when
+    /// their anchors are equal. Relative paths are compared as written; resolve
+    /// them with [`canonicalize_path`](Self::canonicalize_path) first if
+    /// they may come from different directories.
+    #[must_use]
+    pub fn same_source(&self, other: &Self) -> bool {
+        let canonical = |uri: &Self| uri.source_url().map(source::canonical_source);
+        self.anchor == other.anchor && canonical(self) == canonical(other)
+    }
+
+    /// Whether both URIs name the same atom: the [`same_source`](Self::same_source)
+    /// and the same label. Tag, version and fragment are ignored, so
+    /// `src::atom@1.0` and `src::atom:beta@^2` are the same atom — what
+    /// dependency tooling needs to spot one atom required at two versions.
+    #[must_use]
+    pub fn same_atom(&self, other: &Self) -> bool {
+        self.label == other.label && self.same_source(other)
+    }
+}
+
+impl FromStr for AtomUri {
+    type Err = UriError;
+
+    /// Parse an already-resolved URI, such as one [`Display`](fmt::Display)
+    /// emitted. The source must contain no `+` alias: there is no
+    /// [`AliasMap`] here to expand it with.
+    ///
+    /// A source parsed this way is always [`AliasedUrl::Raw`]; whether it
+    /// was once expanded from an alias is not part of the string.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse::<RawAtomUri>()?.resolve(&AliasMap::new())
+    }
+}
+
+impl PartialEq for AtomUri {
+    fn eq(&self, other: &Self) -> bool {
+        self.source_url() == other.source_url()
+            && self.anchor == other.anchor
+            && self.label == other.label
+            && self.tag == other.tag
+            && self.version == other.version
+            && self.fragment == other.fragment
+    }
+}
+
+impl Eq for AtomUri {}
+
+impl Hash for AtomUri {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.source_url().hash(state);
+        self.anchor.hash(state);
+        self.label.hash(state);
+        self.tag.hash(state);
+        self.version.hash(state);
+        self.fragment.hash(state);
+    }
 }
 
 impl fmt::Display for AtomUri {
@@ -261,11 +1025,48 @@ impl fmt::Display for AtomUri {
         if let Some(src) = &self.source {
             write!(f, "{}::", src.url())?;
         }
-        write!(f, "{}", self.label)?;
-        if let Some(ver) = &self.version {
-            write!(f, "@{ver}")?;
+        if let Some(anchor) = &self.anchor {
+            write!(f, "{anchor}::")?;
+        }
+        self.fmt_atom_ref(f)
+    }
+}
+
+// ============================================================================
+// Serde
+// ============================================================================
+
+#[cfg(feature = "serde")]
+mod serde_impls {
+    use serde::de::Error as _;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::*;
+
+    impl Serialize for RawAtomUri {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.collect_str(self)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for RawAtomUri {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let s = String::deserialize(deserializer)?;
+            s.parse().map_err(D::Error::custom)
+        }
+    }
+
+    impl Serialize for AtomUri {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.collect_str(self)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for AtomUri {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let s = String::deserialize(deserializer)?;
+            s.parse().map_err(D::Error::custom)
         }
-        Ok(())
     }
 }
 
@@ -438,6 +1239,170 @@ mod tests {
         assert!(matches!(result, Err(UriError::InvalidLabel(_))));
     }
 
+    // ========================================================================
+    // Parsing: tags
+    // ========================================================================
+
+    #[test]
+    fn tag_before_version() {
+        let uri: RawAtomUri = "src::atom:channel.stable@1.0".parse().unwrap();
+        assert_eq!(uri.label().to_string(), "atom");
+        assert_eq!(uri.tag().unwrap().to_string(), "channel.stable");
+        assert_eq!(uri.version().unwrap().as_str(), "1.0");
+        assert_eq!(uri.to_string(), "src::atom:channel.stable@1.0");
+    }
+
+    #[test]
+    fn tag_keeps_inner_colons_and_survives_resolution() {
+        let uri: RawAtomUri = "git@host:o/r::atom:release:stable#bin".parse().unwrap();
+        assert_eq!(uri.source(), Some("git@host:o/r"));
+        assert_eq!(uri.tag().unwrap().to_string(), "release:stable");
+        assert_eq!(uri.fragment(), Some("bin"));
+        let resolved = uri.resolve(&AliasMap::new()).unwrap();
+        assert_eq!(resolved.tag(), uri.tag());
+        assert_eq!(resolved.to_string(), uri.to_string());
+        assert_ne!(resolved, "git@host:o/r::atom#bin".parse().unwrap());
+    }
+
+    #[test]
+    fn tag_errors() {
+        assert!(matches!(
+            "src::atom:@1".parse::<RawAtomUri>(),
+            Err(UriError::EmptyTag)
+        ));
+        assert!(matches!(
+            "src::atom:a..b".parse::<RawAtomUri>(),
+            Err(UriError::InvalidTag(_))
+        ));
+        assert!(matches!(
+            ":tag".parse::<RawAtomUri>(),
+            Err(UriError::MissingLabel)
+        ));
+    }
+
+    // ========================================================================
+    // Escaping
+    // ========================================================================
+
+    #[test]
+    fn pathological_components_round_trip() {
+        let uri: RawAtomUri = "a:::atom:x:%3Ay@1%3A%3A2%40b%23c%25#p:%3Aq/r%25"
+            .parse()
+            .unwrap();
+        assert_eq!(uri.source(), Some("a:"));
+        assert_eq!(uri.tag().unwrap().to_string(), "x::y");
+        assert_eq!(uri.version().unwrap().as_str(), "1::2@b#c%");
+        assert_eq!(uri.fragment(), Some("p::q/r%"));
+        assert_eq!(uri.to_string().parse::<RawAtomUri>().unwrap(), uri);
+
+        assert!(matches!(
+            "src::atom@100%".parse::<RawAtomUri>(),
+            Err(UriError::InvalidEscape(_))
+        ));
+    }
+
+    // ========================================================================
+    // Parsing: fragments
+    // ========================================================================
+
+    #[test]
+    fn fragment_after_version() {
+        let uri: RawAtomUri = "gh::tools@1.2#scripts/install.sh".parse().unwrap();
+        assert_eq!(uri.source().unwrap(), "gh");
+        assert_eq!(uri.label().to_string(), "tools");
+        assert_eq!(uri.version().unwrap().as_str(), "1.2");
+        assert_eq!(uri.fragment(), Some("scripts/install.sh"));
+        assert_eq!(uri.to_string(), "gh::tools@1.2#scripts/install.sh");
+    }
+
+    #[test]
+    fn fragment_without_version() {
+        let uri: RawAtomUri = "tools#bin".parse().unwrap();
+        assert_eq!(uri.label().to_string(), "tools");
+        assert_eq!(uri.version(), None);
+        assert_eq!(uri.fragment(), Some("bin"));
+    }
+
+    #[test]
+    fn fragment_errors() {
+        assert!(matches!(
+            "tools#".parse::<RawAtomUri>(),
+            Err(UriError::EmptyFragment)
+        ));
+        for bad in [
+            "tools#/etc/passwd",
+            "tools#a/../b",
+            "tools#a//b",
+            "tools#./a",
+        ] {
+            assert!(
+                matches!(bad.parse::<RawAtomUri>(), Err(UriError::InvalidFragment(_))),
+                "{bad}"
+            );
+        }
+    }
+
+    #[test]
+    fn fragment_survives_resolution() {
+        let map = aliases(&[("gh", "github.com")]);
+        let uri: RawAtomUri = "+gh/o/r::tools@1#scripts/install.sh".parse().unwrap();
+        let resolved = uri.resolve(&map).unwrap();
+        assert_eq!(resolved.fragment(), Some("scripts/install.sh"));
+        assert_eq!(
+            resolved.to_string(),
+            "github.com/o/r::tools@1#scripts/install.sh"
+        );
+    }
+
+    // ========================================================================
+    // Parsing: brace groups
+    // ========================================================================
+
+    #[test]
+    fn brace_group_expands_per_label() {
+        let uri: RawAtomUri = "+gh/o/r::{a,b,c}@1.0#bin".parse().unwrap();
+        assert!(uri.is_group());
+        assert_eq!(uri.label().to_string(), "a");
+        assert_eq!(uri.to_string(), "+gh/o/r::{a,b,c}@1.0#bin");
+
+        let expanded: Vec<String> = uri.expand().iter().map(ToString::to_string).collect();
+        assert_eq!(
+            expanded,
+            [
+                "+gh/o/r::a@1.0#bin",
+                "+gh/o/r::b@1.0#bin",
+                "+gh/o/r::c@1.0#bin"
+            ]
+        );
+        assert!(matches!(
+            uri.resolve(&AliasMap::new()),
+            Err(UriError::UnexpandedGroup)
+        ));
+    }
+
+    #[test]
+    fn brace_group_edge_cases() {
+        let single: RawAtomUri = "{a}".parse().unwrap();
+        assert!(!single.is_group());
+        assert_eq!(single.expand(), vec![single.clone()]);
+
+        let dup: RawAtomUri = "{a,b,a}".parse().unwrap();
+        assert_eq!(dup.labels().count(), 2);
+
+        assert!(matches!(
+            "{}".parse::<RawAtomUri>(),
+            Err(UriError::MissingLabel)
+        ));
+        assert!(matches!(
+            "{a,,b}".parse::<RawAtomUri>(),
+            Err(UriError::MissingLabel)
+        ));
+        assert!(matches!(
+            "{a,9b}".parse::<RawAtomUri>(),
+            Err(UriError::InvalidLabel(_))
+        ));
+    }
+
     // ========================================================================
     // Display roundtrip
     // ========================================================================
@@ -503,6 +1468,67 @@ mod tests {
         assert_eq!(resolved.version().unwrap().as_str(), "^1");
     }
 
+    #[test]
+    fn resolve_with_ascii_hosts() {
+        let map = aliases(&[("de", "bücher.example")]);
+        let ascii = ResolveOptions { ascii_hosts: true };
+        let uri: RawAtomUri = "https://bücher.example/repo::atom".parse().unwrap();
+        assert_eq!(
+            uri.resolve(&map).unwrap().source_url(),
+            Some("https://bücher.example/repo")
+        );
+        assert_eq!(
+            uri.resolve_with(&map, ascii).unwrap().source_url(),
+            Some("https://xn--bcher-kva.example/repo")
+        );
+
+        let aliased: RawAtomUri = "+de/repo::atom".parse().unwrap();
+        let resolved = aliased.resolve_with(&map, ascii).unwrap();
+        assert_eq!(resolved.source_url(), Some("xn--bcher-kva.example/repo"));
+        assert_eq!(resolved.as_raw_source(), Some("+de/repo"));
+        assert_eq!(
+            aliased
+                .resolve_with(&map, ResolveOptions::default())
+                .unwrap(),
+            aliased.resolve(&map).unwrap()
+        );
+    }
+
+    #[test]
+    fn resolve_checked_sees_expanded_aliases() {
+        let map = aliases(&[
+            ("plain", "http://example.com"),
+            ("sec", "https://example.com"),
+        ]);
+        let policy = SourcePolicy::encrypted();
+        let ok: RawAtomUri = "+sec/repo::atom".parse().unwrap();
+        assert_eq!(
+            ok.resolve_checked(&map, &policy).unwrap().source_url(),
+            Some("https://example.com/repo")
+        );
+        let denied: RawAtomUri = "+plain/repo::atom".parse().unwrap();
+        let Err(UriError::Policy(err)) = denied.resolve_checked(&map, &policy) else {
+            panic!("expected a policy error");
+        };
+        assert_eq!(err.url, "http://example.com/repo");
+    }
+
+    #[test]
+    fn resolve_with_default_fills_bare_labels() {
+        let map = aliases(&[("reg", "registry.example.com/atoms")]);
+        let bare: RawAtomUri = "my-atom@1.0".parse().unwrap();
+        let resolved = bare.resolve_with_default(&map, "+reg").unwrap();
+        assert_eq!(resolved.source_url().unwrap(), "registry.example.com/atoms");
+        assert_eq!(resolved.version().unwrap().as_str(), "1.0");
+
+        let sourced: RawAtomUri = "github.com/o/r::my-atom".parse().unwrap();
+        let resolved = sourced.resolve_with_default(&map, "+reg").unwrap();
+        assert_eq!(resolved.source_url().unwrap(), "github.com/o/r");
+
+        let unknown = bare.resolve_with_default(&map, "+nope");
+        assert!(matches!(unknown, Err(UriError::AliasError(_))));
+    }
+
     #[test]
     fn resolved_display() {
         let map = aliases(&[("gh", "github.com")]);
@@ -511,6 +1537,284 @@ mod tests {
         assert_eq!(resolved.to_string(), "github.com/owner/repo::my-atom@1.0");
     }
 
+    #[test]
+    fn resolved_uri_parses_from_its_display() {
+        let map = aliases(&[("gh", "github.com")]);
+        let resolved = "+gh/o/r::my-atom@1.0#bin"
+            .parse::<RawAtomUri>()
+            .unwrap()
+            .resolve(&map)
+            .unwrap();
+        let reparsed: AtomUri = resolved.to_string().parse().unwrap();
+        assert_eq!(reparsed.to_string(), resolved.to_string());
+        assert!(matches!(
+            "+gh/o/r::my-atom".parse::<AtomUri>(),
+            Err(UriError::AliasError(_))
+        ));
+    }
+
+    #[test]
+    fn requirement_parses_through_the_scheme() {
+        use atom_id::{NaturalOrderScheme, VersionScheme};
+
+        let scheme = NaturalOrderScheme;
+        let uri: AtomUri = "src::my-atom@>=1.2".parse().unwrap();
+        let req = uri.requirement(&scheme).unwrap().unwrap();
+        let version = scheme
+            .parse_version(&RawVersion::new("1.10".into()))
+            .unwrap();
+        assert!(scheme.matches(&version, &req));
+
+        let bare: AtomUri = "my-atom".parse().unwrap();
+        assert!(bare.requirement(&scheme).unwrap().is_none());
+    }
+
+    #[test]
+    fn relative_path_sources_resolve_against_base_dir() {
+        let uri: AtomUri = "../sets/core::atom@1".parse().unwrap();
+        assert_eq!(uri.source_kind(), Some(SourceKind::RelativePath));
+        let pinned = uri.canonicalize_path(Path::new("/work/project")).unwrap();
+        assert_eq!(pinned.to_string(), "/work/sets/core::atom@1");
+        assert_eq!(pinned.source_kind(), Some(SourceKind::AbsolutePath));
+
+        let remote: AtomUri = "git@github.com:o/r::atom".parse().unwrap();
+        assert_eq!(remote.source_kind(), Some(SourceKind::Scp));
+        let schemeless: AtomUri = "github.com/o/r::atom".parse().unwrap();
+        assert_eq!(schemeless.source_kind(), Some(SourceKind::Schemeless));
+        let url: AtomUri = "https://github.com/o/r::atom".parse().unwrap();
+        assert_eq!(url.source_kind(), Some(SourceKind::Url));
+        let same = remote.canonicalize_path(Path::new("/work")).unwrap();
+        assert_eq!(same.to_string(), remote.to_string());
+
+        let bare: AtomUri = "atom".parse().unwrap();
+        assert_eq!(bare.source_kind(), None);
+    }
+
+    #[test]
+    fn windows_path_sources_are_absolute() {
+        let uri: AtomUri = r"C:\atoms\set::my-atom@1".parse().unwrap();
+        assert_eq!(uri.source_url(), Some(r"C:\atoms\set"));
+        assert_eq!(uri.label().to_string(), "my-atom");
+        assert_eq!(uri.source_kind(), Some(SourceKind::AbsolutePath));
+        let same = uri.canonicalize_path(Path::new("/work")).unwrap();
+        assert_eq!(same.to_string(), uri.to_string());
+
+        let unc: AtomUri = r"\\server\share\set::my-atom".parse().unwrap();
+        assert_eq!(unc.source_url(), Some(r"\\server\share\set"));
+        assert_eq!(unc.source_kind(), Some(SourceKind::AbsolutePath));
+    }
+
+    #[test]
+    fn display_with_recompresses_aliases() {
+        let map = aliases(&[("gh", "github.com"), ("work", "+gh/myorg")]);
+        let uri = "+gh/owner/repo::atom@1.0"
+            .parse::<RawAtomUri>()
+            .unwrap()
+            .resolve(&map)
+            .unwrap();
+        assert_eq!(uri.to_string(), "github.com/owner/repo::atom@1.0");
+        assert_eq!(uri.display_with(&map), "+gh/owner/repo::atom@1.0");
+
+        let typed_out: AtomUri = "github.com/myorg/core::atom#lib".parse().unwrap();
+        assert_eq!(typed_out.display_with(&map), "+work/core::atom#lib");
+
+        let other: AtomUri = "/srv/sets::atom".parse().unwrap();
+        assert_eq!(other.display_with(&map), "/srv/sets::atom");
+        let bare: AtomUri = "atom@2".parse().unwrap();
+        assert_eq!(bare.display_with(&map), "atom@2");
+    }
+
+    #[test]
+    fn borrowed_parse_matches_owned() {
+        let input = "git@github.com:o/r::{core,lib}@^1.0#src/main.rs";
+        let borrowed = RawAtomUriRef::parse(input).unwrap();
+        assert_eq!(borrowed.source(), Some("git@github.com:o/r"));
+        assert_eq!(borrowed.label(), "{core,lib}");
+        assert_eq!(borrowed.version(), Some("^1.0"));
+        assert_eq!(borrowed.fragment(), Some("src/main.rs"));
+        assert_eq!(
+            borrowed.into_owned().unwrap(),
+            input.parse::<RawAtomUri>().unwrap()
+        );
+
+        let bare = RawAtomUriRef::parse("atom").unwrap();
+        assert_eq!((bare.source(), bare.version()), (None, None));
+
+        assert!(matches!(
+            RawAtomUriRef::parse("src::atom@"),
+            Err(UriError::EmptyVersion)
+        ));
+        assert!(matches!(
+            RawAtomUriRef::parse("src::@1"),
+            Err(UriError::MissingLabel)
+        ));
+        let unchecked = RawAtomUriRef::parse("src::not a label").unwrap();
+        assert!(matches!(
+            unchecked.into_owned(),
+            Err(UriError::InvalidLabel(_))
+        ));
+    }
+
+    #[test]
+    fn parse_with_rejects_empty_source_by_default() {
+        assert!(matches!(
+            RawAtomUri::parse_with("::atom", ParseOptions::default()),
+            Err(UriError::EmptySource)
+        ));
+        let lenient = ParseOptions {
+            allow_empty_source: true,
+        };
+        let uri = RawAtomUri::parse_with("::atom", lenient).unwrap();
+        assert_eq!(uri.source(), Some(""));
+        assert_eq!(uri, "::atom".parse().unwrap());
+
+        let strict = RawAtomUri::parse_with("src::atom@1", ParseOptions::default()).unwrap();
+        assert_eq!(strict.source(), Some("src"));
+        assert!(
+            RawAtomUri::parse_with("atom", ParseOptions::default())
+                .unwrap()
+                .source()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn equality_follows_the_resolved_url() {
+        use std::collections::HashSet;
+
+        let map = aliases(&[("gh", "github.com")]);
+        let expanded = "+gh/o/r::atom@1"
+            .parse::<RawAtomUri>()
+            .unwrap()
+            .resolve(&map)
+            .unwrap();
+        let raw: AtomUri = "github.com/o/r::atom@1".parse().unwrap();
+        assert!(matches!(
+            expanded.source(),
+            Some(AliasedUrl::Expanded { .. })
+        ));
+        assert_eq!(expanded, raw);
+        assert_eq!(HashSet::from([expanded, raw]).len(), 1);
+
+        let uri = |s: &str| s.parse::<AtomUri>().unwrap();
+        assert_ne!(uri("src::atom@1"), uri("src::atom@2"));
+        assert_ne!(uri("src::atom"), uri("other::atom"));
+        assert_ne!(uri("src::atom"), uri("atom"));
+        assert_ne!(uri("src::atom#a"), uri("src::atom#b"));
+    }
+
+    #[test]
+    fn os_str_conversions() {
+        let uri = RawAtomUri::try_from(OsStr::new("src::atom@1")).unwrap();
+        assert_eq!(uri, "src::atom@1".parse().unwrap());
+        let owned = RawAtomUri::try_from(OsString::from("atom")).unwrap();
+        assert_eq!(owned.label().to_string(), "atom");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn non_utf8_os_str_is_rejected() {
+        use std::os::unix::ffi::OsStringExt;
+
+        let arg = OsString::from_vec(b"src::at\xffom".to_vec());
+        assert!(matches!(
+            RawAtomUri::try_from(arg),
+            Err(UriError::InvalidUnicode)
+        ));
+    }
+
+    #[test]
+    fn same_atom_ignores_selection_and_source_spelling() {
+        let uri = |s: &str| s.parse::<AtomUri>().unwrap();
+        let a = uri("GitHub.com/owner/repo/::core@1.0");
+        assert!(a.same_atom(&uri("github.com/owner/repo.git::core:beta@^2#lib")));
+        assert!(a.same_source(&uri("github.com/owner/repo::other")));
+        assert!(!a.same_atom(&uri("github.com/owner/repo::other@1.0")));
+        assert!(!a.same_source(&uri("github.com/owner/fork::core@1.0")));
+        assert!(uri("core@1").same_atom(&uri("core@2")));
+        assert!(!uri("core").same_source(&a));
+
+        let map = aliases(&[("gh", "github.com")]);
+        let aliased = "+gh/owner/repo::core".parse::<RawAtomUri>().unwrap();
+        assert!(aliased.resolve(&map).unwrap().same_atom(&a));
+    }
+
+    #[test]
+    fn into_raw_keeps_the_aliased_spelling() {
+        let map = aliases(&[("gh", "github.com")]);
+        let raw: RawAtomUri = "+gh/owner/repo::core:beta@^1.0#lib".parse().unwrap();
+        let resolved = raw.resolve(&map).unwrap();
+        assert_eq!(resolved.as_raw_source(), Some("+gh/owner/repo"));
+        assert_eq!(resolved.clone().into_raw(), raw);
+
+        assert_eq!(
+            resolved.canonicalize().as_raw_source(),
+            Some("github.com/owner/repo")
+        );
+        let plain: AtomUri = "github.com/owner/repo::core".parse().unwrap();
+        assert_eq!(plain.as_raw_source(), Some("github.com/owner/repo"));
+        assert_eq!(plain.into_raw().to_string(), "github.com/owner/repo::core");
+        let bare: AtomUri = "core".parse().unwrap();
+        assert_eq!(bare.as_raw_source(), None);
+    }
+
+    #[test]
+    fn anchor_qualified_uris_carry_an_atom_id() {
+        let anchor = Anchor::new(vec![7; 32]);
+        let text = format!("{anchor}::core@1.0");
+        let uri: AtomUri = text.parse().unwrap();
+        assert_eq!(uri.anchor(), Some(&anchor));
+        assert_eq!(uri.source_url(), None);
+        assert_eq!(uri.source_kind(), None);
+        let id = uri.atom_id().unwrap();
+        assert_eq!(id.to_string(), format!("{anchor}::core"));
+        assert_eq!(uri.to_string(), text);
+        assert_eq!(uri.canonicalize(), uri);
+        assert_eq!(uri.as_raw_source(), Some(anchor.to_b64().as_str()));
+        assert!(!uri.same_source(&"core".parse().unwrap()));
+
+        // Not czd-sized, or not pure base64url: an ordinary path source.
+        let short: AtomUri = format!("{}::core", Anchor::new(vec![7; 20]))
+            .parse()
+            .unwrap();
+        assert_eq!(short.anchor(), None);
+        let path: AtomUri = format!("./{anchor}::core").parse().unwrap();
+        assert_eq!(path.anchor(), None);
+        assert_eq!(path.source_kind(), Some(SourceKind::RelativePath));
+    }
+
+    #[test]
+    fn canonicalize_folds_source_spellings() {
+        let map = aliases(&[("gh", "GitHub.com")]);
+        let a = "+gh/owner/repo.git/::my-atom@1.0"
+            .parse::<RawAtomUri>()
+            .unwrap()
+            .resolve(&map)
+            .unwrap()
+            .canonicalize();
+        let b: AtomUri = "github.com/owner/repo::my-atom@1.0".parse().unwrap();
+        assert_eq!(a.to_string(), b.canonicalize().to_string());
+        assert!(matches!(a.source(), Some(AliasedUrl::Raw(_))));
+        assert_eq!(a.to_string(), "github.com/owner/repo::my-atom@1.0");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_uses_the_string_form() {
+        let raw: RawAtomUri = "+gh/o/r::my-atom@^1".parse().unwrap();
+        let json = serde_json::to_string(&raw).unwrap();
+        assert_eq!(json, r#""+gh/o/r::my-atom@^1""#);
+        assert_eq!(serde_json::from_str::<RawAtomUri>(&json).unwrap(), raw);
+
+        let resolved: AtomUri = "github.com/o/r::my-atom@^1".parse().unwrap();
+        let json = serde_json::to_string(&resolved).unwrap();
+        assert_eq!(json, r#""github.com/o/r::my-atom@^1""#);
+        let back: AtomUri = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.to_string(), resolved.to_string());
+
+        assert!(serde_json::from_str::<RawAtomUri>(r#""source::""#).is_err());
+    }
+
     #[cfg(test)]
     mod proptests {
         use proptest::prelude::*;
@@ -523,22 +1827,34 @@ mod tests {
             (start, cont).prop_map(|(s, c)| format!("{}{}", s, c))
         }
 
+        // Sources, tags, versions and fragments all draw on the delimiter
+        // characters, so the round-trip law covers the escaping.
         fn arb_source() -> impl Strategy<Value = String> {
-            let chars = "[a-zA-Z0-9_./+-]";
-            proptest::collection::vec(chars, 1..50).prop_map(|v| v.join(""))
+            let chars = "[a-zA-Z0-9_./+:@#-]";
+            proptest::collection::vec(chars, 0..50).prop_map(|v| v.join(""))
+        }
+
+        fn arb_tag() -> impl Strategy<Value = Tag> {
+            "[a-z][a-z0-9:.]{0,12}".prop_filter_map("valid tag", |s| Tag::try_from(s).ok())
         }
 
         fn arb_version() -> impl Strategy<Value = String> {
-            let chars = "[a-zA-Z0-9_.-]";
+            let chars = "[a-zA-Z0-9_.:@#%-]";
             proptest::collection::vec(chars, 1..20).prop_map(|v| v.join(""))
         }
 
+        fn arb_fragment() -> impl Strategy<Value = String> {
+            proptest::collection::vec("[a-z0-9:@#%]{1,8}", 1..4).prop_map(|v| v.join("/"))
+        }
+
         proptest! {
             #[test]
             fn test_raw_atom_uri_roundtrip(
                 src in proptest::option::of(arb_source()),
                 lbl_str in arb_label(),
+                tag in proptest::option::of(arb_tag()),
                 ver in proptest::option::of(arb_version()),
+                fragment in proptest::option::of(arb_fragment()),
             ) {
                 let label = Label::try_from(lbl_str.as_str()).unwrap();
                 let version = ver.map(RawVersion::new);
@@ -546,7 +1862,10 @@ mod tests {
                 let original = RawAtomUri {
                     source: src,
                     label,
+                    group: Vec::new(),
+                    tag,
                     version,
+                    fragment,
                 };
 
                 let serialized = original.to_string();
@@ -572,14 +1891,7 @@ mod tests {
                 .with_type::<(Option<String>, String, Option<String>)>()
                 .for_each(|(src, lbl_str, ver)| {
                     if let Ok(label) = Label::try_from(lbl_str.as_str()) {
-                        if let Some(s) = &src
-                            && (s.contains("::") || s.is_empty())
-                        {
-                            return;
-                        }
-                        if let Some(v) = &ver
-                            && (v.contains('@') || v.contains("::") || v.is_empty())
-                        {
+                        if ver.as_ref().is_some_and(String::is_empty) {
                             return;
                         }
 
@@ -587,7 +1899,10 @@ mod tests {
                         let original = RawAtomUri {
                             source: src.clone(),
                             label: label.clone(),
+                            group: Vec::new(),
+                            tag: None,
                             version: version.clone(),
+                            fragment: None,
                         };
 
                         let serialized = original.to_string();
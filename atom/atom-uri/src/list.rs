@@ -0,0 +1,156 @@
+//! URI list files: [`parse_uri_list`].
+
+use std::fmt;
+use std::io::{self, BufRead};
+
+use crate::{RawAtomUri, UriError};
+
+/// A line of a URI list that failed to parse.
+#[derive(Debug)]
+pub struct LineError {
+    /// The line number, counting from 1.
+    pub line: usize,
+    /// Why the line's URI is invalid.
+    pub error: UriError,
+}
+
+impl fmt::Display for LineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.error)
+    }
+}
+
+impl std::error::Error for LineError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// Errors from [`parse_uri_list`].
+#[derive(Debug)]
+pub enum ListError {
+    /// Reading the input failed.
+    Io(io::Error),
+    /// One or more lines are not valid atom URIs — all of them, in order,
+    /// so a requirements file can be fixed in one pass.
+    Invalid(Vec<LineError>),
+}
+
+impl fmt::Display for ListError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "failed to read URI list: {e}"),
+            Self::Invalid(errors) => {
+                write!(f, "invalid URI list")?;
+                for error in errors {
+                    write!(f, "\n  {error}")?;
+                }
+                Ok(())
+            },
+        }
+    }
+}
+
+impl std::error::Error for ListError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            Self::Invalid(errors) => errors
+                .first()
+                .map(|e| e as &(dyn std::error::Error + 'static)),
+        }
+    }
+}
+
+impl From<io::Error> for ListError {
+    fn from(e: io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+/// Parse a requirements-style list of atom URIs: one per line, with blank
+/// lines and `#` comments ignored.
+///
+/// A comment runs from a `#` at the start of a line, or after whitespace,
+/// to the end of the line; a `#` directly after a label starts a fragment
+/// (`atom#lib`), since a URI never contains whitespace. Surrounding
+/// whitespace is trimmed.
+///
+/// ```text
+/// # build inputs
+/// +gh/owner/repo::core@^1.0
+/// ../sets/local::tools   # pinned by path
+/// ```
+///
+/// # Errors
+///
+/// [`ListError::Io`] if reading fails, or [`ListError::Invalid`] listing
+/// every line that does not parse, with its line number.
+pub fn parse_uri_list(reader: impl BufRead) -> Result<Vec<RawAtomUri>, ListError> {
+    let mut uris = Vec::new();
+    let mut errors = Vec::new();
+    for (index, line) in reader.lines().enumerate() {
+        let line = line?;
+        let entry = strip_comment(&line).trim();
+        if entry.is_empty() {
+            continue;
+        }
+        match entry.parse() {
+            Ok(uri) => uris.push(uri),
+            Err(error) => errors.push(LineError {
+                line: index + 1,
+                error,
+            }),
+        }
+    }
+    if errors.is_empty() {
+        Ok(uris)
+    } else {
+        Err(ListError::Invalid(errors))
+    }
+}
+
+/// The line up to its comment, if any.
+fn strip_comment(line: &str) -> &str {
+    let mut prev_is_space = true;
+    for (i, c) in line.char_indices() {
+        if c == '#' && prev_is_space {
+            return &line[..i];
+        }
+        prev_is_space = c.is_whitespace();
+    }
+    line
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn skips_blanks_and_comments() {
+        let input =
+            "# build inputs\n\n  +gh/o/r::core@^1.0\n../sets::tools   # by path\natom#lib/x.nix\n";
+        let uris = parse_uri_list(input.as_bytes()).unwrap();
+        let rendered: Vec<String> = uris.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            rendered,
+            ["+gh/o/r::core@^1.0", "../sets::tools", "atom#lib/x.nix"]
+        );
+    }
+
+    #[test]
+    fn reports_every_bad_line() {
+        let input = "good\nsrc::atom@\n# fine\nbad label\n";
+        let Err(ListError::Invalid(errors)) = parse_uri_list(input.as_bytes()) else {
+            panic!("expected line errors");
+        };
+        let lines: Vec<usize> = errors.iter().map(|e| e.line).collect();
+        assert_eq!(lines, [2, 4]);
+        assert!(matches!(errors[0].error, UriError::EmptyVersion));
+        assert!(errors[1].to_string().starts_with("line 4: "));
+    }
+}
@@ -0,0 +1,218 @@
+//! Lockfile entries: [`LockedAtomRef`].
+
+use std::fmt;
+use std::str::FromStr;
+
+use atom_id::{AtomDigest, RawVersion};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{AtomUri, UriError};
+
+/// An atom pinned for reproducible builds: the URI as requested, the exact
+/// version it resolved to, the source revision that version was read at,
+/// and the [`AtomDigest`] of its content.
+///
+/// This is the lockfile entry format every backend writes and reads. Its
+/// text form is the four parts separated by single spaces, URI first:
+///
+/// ```text
+/// github.com/owner/repo::core@^1.0 1.2.3 9fceb02d0ae598e95dc970b74767f19372d61af8 blake3:<hex>
+/// ```
+///
+/// The version, revision and digest never contain whitespace, so the URI is
+/// whatever precedes the last three spaces — even a path source with spaces
+/// in it. With the `serde` feature the entry is a map with the fields
+/// `uri`, `version`, `rev` and `digest`, each in its string form.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "LockedFields"))]
+pub struct LockedAtomRef {
+    uri: AtomUri,
+    version: RawVersion,
+    rev: String,
+    digest: AtomDigest,
+}
+
+impl LockedAtomRef {
+    /// Pin `uri` at `version`, read from the source at revision `rev`,
+    /// with content digest `digest`.
+    ///
+    /// # Errors
+    ///
+    /// [`UriError::InvalidLockEntry`] if the version or revision is empty
+    /// or contains whitespace, which the text form cannot carry.
+    pub fn new(
+        uri: AtomUri,
+        version: RawVersion,
+        rev: impl Into<String>,
+        digest: AtomDigest,
+    ) -> Result<Self, UriError> {
+        let rev = rev.into();
+        for (field, value) in [("version", version.as_str()), ("rev", rev.as_str())] {
+            if value.is_empty() || value.contains(char::is_whitespace) {
+                return Err(UriError::InvalidLockEntry(format!(
+                    "{field} must be non-empty with no whitespace: '{value}'"
+                )));
+            }
+        }
+        Ok(Self {
+            uri,
+            version,
+            rev,
+            digest,
+        })
+    }
+
+    /// The URI as requested, version requirement included.
+    #[must_use]
+    pub fn uri(&self) -> &AtomUri {
+        &self.uri
+    }
+
+    /// The exact version the URI resolved to.
+    #[must_use]
+    pub fn version(&self) -> &RawVersion {
+        &self.version
+    }
+
+    /// The source revision the version was read at — for a git source,
+    /// the commit id.
+    #[must_use]
+    pub fn rev(&self) -> &str {
+        &self.rev
+    }
+
+    /// The digest of the atom's content.
+    pub fn digest(&self) -> &AtomDigest {
+        &self.digest
+    }
+}
+
+/// The serde fields, checked through [`LockedAtomRef::new`] on the way in.
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct LockedFields {
+    uri: AtomUri,
+    version: RawVersion,
+    rev: String,
+    digest: AtomDigest,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<LockedFields> for LockedAtomRef {
+    type Error = UriError;
+
+    fn try_from(fields: LockedFields) -> Result<Self, Self::Error> {
+        Self::new(fields.uri, fields.version, fields.rev, fields.digest)
+    }
+}
+
+impl fmt::Display for LockedAtomRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} {}",
+            self.uri, self.version, self.rev, self.digest
+        )
+    }
+}
+
+impl FromStr for LockedAtomRef {
+    type Err = UriError;
+
+    /// Parse the text form written by [`Display`](fmt::Display).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.rsplitn(4, ' ');
+        let (Some(digest), Some(rev), Some(version), Some(uri)) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(UriError::InvalidLockEntry(format!(
+                "expected '<uri> <version> <rev> <digest>', got '{s}'"
+            )));
+        };
+        let digest = digest
+            .parse()
+            .map_err(|e| UriError::InvalidLockEntry(format!("invalid digest: {e}")))?;
+        Self::new(
+            uri.parse()?,
+            RawVersion::new(version.to_owned()),
+            rev,
+            digest,
+        )
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DIGEST: &str = "blake3:af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262";
+
+    fn locked() -> LockedAtomRef {
+        LockedAtomRef::new(
+            "github.com/owner/repo::core@^1.0".parse().unwrap(),
+            RawVersion::new("1.2.3".into()),
+            "9fceb02d0ae598e95dc970b74767f19372d61af8",
+            DIGEST.parse().unwrap(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn text_form_round_trips() {
+        let entry = locked();
+        let text = entry.to_string();
+        assert_eq!(
+            text,
+            format!(
+                "github.com/owner/repo::core@^1.0 1.2.3 9fceb02d0ae598e95dc970b74767f19372d61af8 \
+                 {DIGEST}"
+            )
+        );
+        assert_eq!(text.parse::<LockedAtomRef>().unwrap(), entry);
+
+        let spaced = format!("/home/me/My Sets::core 0.1 r1 {DIGEST}");
+        let entry: LockedAtomRef = spaced.parse().unwrap();
+        assert_eq!(entry.uri().source_url(), Some("/home/me/My Sets"));
+        assert_eq!(entry.to_string(), spaced);
+    }
+
+    #[test]
+    fn rejects_malformed_entries() {
+        assert!(matches!(
+            "core 1.0 r1".parse::<LockedAtomRef>(),
+            Err(UriError::InvalidLockEntry(_))
+        ));
+        assert!(matches!(
+            "core 1.0 r1 sha256:nope".parse::<LockedAtomRef>(),
+            Err(UriError::InvalidLockEntry(_))
+        ));
+        let uri: AtomUri = "core".parse().unwrap();
+        let digest: AtomDigest = DIGEST.parse().unwrap();
+        assert!(LockedAtomRef::new(uri, RawVersion::new("1 0".into()), "r1", digest).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_uses_named_string_fields() {
+        let entry = locked();
+        let json = serde_json::to_value(&entry).unwrap();
+        assert_eq!(json["uri"], "github.com/owner/repo::core@^1.0");
+        assert_eq!(json["version"], "1.2.3");
+        assert_eq!(json["rev"], "9fceb02d0ae598e95dc970b74767f19372d61af8");
+        assert_eq!(json["digest"], DIGEST);
+        assert_eq!(
+            serde_json::from_value::<LockedAtomRef>(json.clone()).unwrap(),
+            entry
+        );
+
+        let mut bad = json;
+        bad["rev"] = "two words".into();
+        assert!(serde_json::from_value::<LockedAtomRef>(bad).is_err());
+    }
+}
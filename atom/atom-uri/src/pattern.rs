@@ -0,0 +1,146 @@
+//! Label patterns for discovery queries: [`AtomPattern`].
+
+use std::fmt;
+use std::str::FromStr;
+
+use atom_id::Label;
+
+use crate::UriError;
+
+/// A discovery query: an optional source and a glob over labels, written
+/// `[source::] glob` — e.g. `+gh/owner/repo::prefix-*`.
+///
+/// `*` matches any run of characters, including none, and `?` matches
+/// exactly one. Everything else matches itself, case-sensitively. This is
+/// the query syntax for `AtomSource::discover`: parsing it here keeps
+/// every backend's search syntax the same, and validated the same.
+///
+/// A pattern is deliberately not a [`RawAtomUri`](crate::RawAtomUri): it
+/// names no tag, version or fragment, and it can only be searched with, not
+/// resolved.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AtomPattern {
+    source: Option<String>,
+    glob: String,
+}
+
+impl AtomPattern {
+    /// The source component, if present (before `::` delimiter).
+    #[must_use]
+    pub fn source(&self) -> Option<&str> {
+        self.source.as_deref()
+    }
+
+    /// The label glob.
+    #[must_use]
+    pub fn glob(&self) -> &str {
+        &self.glob
+    }
+
+    /// Whether the glob has no wildcards, so matches exactly one label.
+    #[must_use]
+    pub fn is_literal(&self) -> bool {
+        !self.glob.contains(['*', '?'])
+    }
+
+    /// Whether `label` matches the glob.
+    #[must_use]
+    pub fn matches(&self, label: &Label) -> bool {
+        label.matches_glob(&self.glob)
+    }
+}
+
+impl FromStr for AtomPattern {
+    type Err = UriError;
+
+    /// Parse `[source::] glob`.
+    ///
+    /// The glob is validated by standing a letter in for each wildcard:
+    /// the result must be a valid [`Label`], so a pattern can only match
+    /// strings that are themselves labels.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (source, glob) = match s.rsplit_once("::") {
+            Some((src, rest)) => (Some(src.to_owned()), rest),
+            None => (None, s),
+        };
+        if glob.is_empty() {
+            return Err(UriError::MissingLabel);
+        }
+        let stand_in: String = glob
+            .chars()
+            .map(|c| if matches!(c, '*' | '?') { 'a' } else { c })
+            .collect();
+        if Label::try_from(stand_in.as_str()).is_err() {
+            return Err(UriError::InvalidPattern(glob.to_owned()));
+        }
+        Ok(Self {
+            source,
+            glob: glob.to_owned(),
+        })
+    }
+}
+
+impl fmt::Display for AtomPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(src) = &self.source {
+            write!(f, "{src}::")?;
+        }
+        f.write_str(&self.glob)
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn label(s: &str) -> Label {
+        Label::try_from(s).unwrap()
+    }
+
+    #[test]
+    fn parses_source_and_glob() {
+        let pattern: AtomPattern = "+gh/owner/repo::prefix-*".parse().unwrap();
+        assert_eq!(pattern.source(), Some("+gh/owner/repo"));
+        assert_eq!(pattern.glob(), "prefix-*");
+        assert!(!pattern.is_literal());
+        assert_eq!(pattern.to_string(), "+gh/owner/repo::prefix-*");
+
+        let bare: AtomPattern = "exact".parse().unwrap();
+        assert_eq!(bare.source(), None);
+        assert!(bare.is_literal());
+    }
+
+    #[test]
+    fn rejects_what_no_label_could_match() {
+        assert!(matches!(
+            "src::".parse::<AtomPattern>(),
+            Err(UriError::MissingLabel)
+        ));
+        for bad in ["atom@1.0", "atom#lib", "a b*", "{a,b}", "*/x"] {
+            assert!(
+                matches!(bad.parse::<AtomPattern>(), Err(UriError::InvalidPattern(_))),
+                "{bad}"
+            );
+        }
+    }
+
+    #[test]
+    fn glob_semantics() {
+        let pattern = |s: &str| s.parse::<AtomPattern>().unwrap();
+        assert!(pattern("prefix-*").matches(&label("prefix-core")));
+        assert!(pattern("prefix-*").matches(&label("prefix-")));
+        assert!(!pattern("prefix-*").matches(&label("other")));
+        assert!(pattern("*-core").matches(&label("lib-core")));
+        assert!(pattern("*").matches(&label("anything")));
+        assert!(pattern("a*b*c").matches(&label("aXbYbZc")));
+        assert!(!pattern("a*b*c").matches(&label("aXbYbZ")));
+        assert!(pattern("pkg-?").matches(&label("pkg-e")));
+        assert!(!pattern("pkg-?").matches(&label("pkg-ee")));
+        assert!(pattern("exact").matches(&label("exact")));
+        assert!(!pattern("exact").matches(&label("Exact")));
+    }
+}
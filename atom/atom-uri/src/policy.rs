@@ -0,0 +1,206 @@
+//! Transport restrictions on resolved sources: [`SourcePolicy`], checked by
+//! [`RawAtomUri::resolve_checked`](crate::RawAtomUri::resolve_checked).
+
+use std::fmt;
+
+use crate::source::{self, SourceKind};
+use crate::{AtomUri, UriError};
+
+/// Which sources a deployment will fetch from, by shape and URL scheme.
+///
+/// A policy is checked against the source *after* alias expansion, so an
+/// alias cannot smuggle in a transport the policy forbids. Bare labels and
+/// anchor-qualified URIs name nothing to fetch from, and always pass.
+///
+/// Start from [`deny_all`](Self::deny_all) and allow what is wanted, or
+/// from [`encrypted`](Self::encrypted):
+///
+/// ```
+/// use atom_uri::{AliasMap, RawAtomUri, SourcePolicy};
+///
+/// let policy = SourcePolicy::encrypted();
+/// let uri: RawAtomUri = "http://example.com/repo::atom".parse().unwrap();
+/// assert!(uri.resolve_checked(&AliasMap::new(), &policy).is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourcePolicy {
+    /// Allowed `scheme://` schemes, lowercase.
+    schemes: Vec<String>,
+    scp: bool,
+    schemeless: bool,
+    paths: bool,
+}
+
+impl SourcePolicy {
+    /// A policy that allows no source.
+    #[must_use]
+    pub fn deny_all() -> Self {
+        Self {
+            schemes: Vec::new(),
+            scp: false,
+            schemeless: false,
+            paths: false,
+        }
+    }
+
+    /// Only transports that never send plaintext: `https://`, `ssh://`,
+    /// `git+ssh://` and `file://` URLs, SCP-style addresses (which are
+    /// SSH), and filesystem paths.
+    ///
+    /// Schemeless sources (`github.com/owner/repo`) are not allowed: the
+    /// transport is the backend's choice, so nothing here can vouch for
+    /// it. Write the scheme, or [`allow_schemeless`](Self::allow_schemeless)
+    /// if the backend is known to use one of the above.
+    #[must_use]
+    pub fn encrypted() -> Self {
+        ["https", "ssh", "git+ssh", "file"]
+            .into_iter()
+            .fold(Self::deny_all(), Self::allow_scheme)
+            .allow_scp()
+            .allow_paths()
+    }
+
+    /// Allow `scheme://` URLs. Schemes are compared case-insensitively.
+    #[must_use]
+    pub fn allow_scheme(mut self, scheme: &str) -> Self {
+        let scheme = scheme.to_ascii_lowercase();
+        if !self.schemes.contains(&scheme) {
+            self.schemes.push(scheme);
+        }
+        self
+    }
+
+    /// Allow SCP-style `[user@]host:path` addresses.
+    #[must_use]
+    pub fn allow_scp(mut self) -> Self {
+        self.scp = true;
+        self
+    }
+
+    /// Allow schemeless `host/path` URLs.
+    #[must_use]
+    pub fn allow_schemeless(mut self) -> Self {
+        self.schemeless = true;
+        self
+    }
+
+    /// Allow absolute and relative filesystem paths.
+    #[must_use]
+    pub fn allow_paths(mut self) -> Self {
+        self.paths = true;
+        self
+    }
+
+    /// Check a resolved URI's source against the policy.
+    ///
+    /// # Errors
+    ///
+    /// [`PolicyError`] naming the source if the policy does not allow it.
+    pub fn check(&self, uri: &AtomUri) -> Result<(), PolicyError> {
+        let Some(url) = uri.source_url() else {
+            return Ok(());
+        };
+        let kind = SourceKind::of(url);
+        let allowed = match kind {
+            SourceKind::Url => source::scheme(url)
+                .is_some_and(|scheme| self.schemes.iter().any(|s| s.eq_ignore_ascii_case(scheme))),
+            SourceKind::Schemeless => self.schemeless,
+            SourceKind::Scp => self.scp,
+            SourceKind::AbsolutePath | SourceKind::RelativePath => self.paths,
+        };
+        if allowed {
+            Ok(())
+        } else {
+            Err(PolicyError {
+                url: url.to_owned(),
+                kind,
+            })
+        }
+    }
+}
+
+/// A resolved source that a [`SourcePolicy`] does not allow.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PolicyError {
+    /// The source, after alias expansion.
+    pub url: String,
+    /// The source's shape.
+    pub kind: SourceKind,
+}
+
+impl PolicyError {
+    /// The source's `scheme://` scheme, if it has one.
+    #[must_use]
+    pub fn scheme(&self) -> Option<&str> {
+        source::scheme(&self.url)
+    }
+}
+
+impl fmt::Display for PolicyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            SourceKind::Url => write!(f, "'{}'", self.scheme().unwrap_or_default())?,
+            SourceKind::Schemeless => f.write_str("schemeless")?,
+            SourceKind::Scp => f.write_str("SCP-style")?,
+            SourceKind::AbsolutePath | SourceKind::RelativePath => f.write_str("path")?,
+        }
+        write!(f, " sources are not allowed by policy: '{}'", self.url)
+    }
+}
+
+impl std::error::Error for PolicyError {}
+
+impl From<PolicyError> for UriError {
+    fn from(e: PolicyError) -> Self {
+        Self::Policy(e)
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(policy: &SourcePolicy, uri: &str) -> Result<(), PolicyError> {
+        policy.check(&uri.parse().unwrap())
+    }
+
+    #[test]
+    fn encrypted_forbids_plaintext_and_unknown_transports() {
+        let policy = SourcePolicy::encrypted();
+        for ok in [
+            "https://example.com/repo::a",
+            "HTTPS://example.com/repo::a",
+            "ssh://git@example.com/repo::a",
+            "git@github.com:owner/repo::a",
+            "/srv/atoms::a",
+            "../sets::a",
+            "bare",
+        ] {
+            assert!(check(&policy, ok).is_ok(), "{ok}");
+        }
+        let err = check(&policy, "http://example.com/repo::a").unwrap_err();
+        assert_eq!(err.scheme(), Some("http"));
+        assert_eq!(
+            err.to_string(),
+            "'http' sources are not allowed by policy: 'http://example.com/repo'"
+        );
+        let err = check(&policy, "github.com/owner/repo::a").unwrap_err();
+        assert_eq!(err.kind, SourceKind::Schemeless);
+        assert_eq!(err.scheme(), None);
+        assert!(check(&policy.allow_schemeless(), "github.com/owner/repo::a").is_ok());
+    }
+
+    #[test]
+    fn deny_all_allows_only_what_is_added() {
+        let policy = SourcePolicy::deny_all();
+        assert!(check(&policy, "bare").is_ok());
+        assert!(check(&policy, "/srv/atoms::a").is_err());
+        let policy = policy.allow_scheme("Git");
+        assert!(check(&policy, "git://example.com/repo::a").is_ok());
+        assert!(check(&policy, "git@github.com:o/r::a").is_err());
+    }
+}
@@ -0,0 +1,345 @@
+//! Source strings: what shape they have ([`SourceKind`]) and their
+//! canonical forms, for [`AtomUri::canonicalize`] and
+//! [`AtomUri::canonicalize_path`].
+//!
+//! [`AtomUri::canonicalize`]: crate::AtomUri::canonicalize
+//! [`AtomUri::canonicalize_path`]: crate::AtomUri::canonicalize_path
+
+use std::borrow::Cow;
+use std::ops::Range;
+use std::path::{Component, Path, PathBuf};
+
+use atom_id::Anchor;
+
+/// Byte lengths of a czd, and so of an anchor: SHA-256, SHA-384, SHA-512.
+const ANCHOR_LENGTHS: [usize; 3] = [32, 48, 64];
+
+/// The shape of a resolved source, which decides how it is fetched.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SourceKind {
+    /// A URL with a scheme, `scheme://…`.
+    Url,
+    /// A schemeless URL, `host/path`, whose first segment contains a `.`
+    /// (`github.com/owner/repo`). The transport is the backend's choice.
+    Schemeless,
+    /// An SCP-style address, `[user@]host:path`.
+    Scp,
+    /// An absolute filesystem path: `/…`, or on Windows a drive-letter
+    /// (`C:\…`, `C:/…`) or UNC (`\\server\share\…`) path.
+    AbsolutePath,
+    /// A relative filesystem path, resolved against a working directory.
+    /// The empty source (`::label`) is the working directory itself.
+    RelativePath,
+}
+
+impl SourceKind {
+    /// Classify a source string.
+    pub(crate) fn of(src: &str) -> Self {
+        if scheme(src).is_some() {
+            Self::Url
+        } else if src.starts_with('/') || is_windows_path(src) {
+            Self::AbsolutePath
+        } else if scp_split(src).is_some() {
+            Self::Scp
+        } else if is_host(split_at_slash(src).0) {
+            Self::Schemeless
+        } else {
+            Self::RelativePath
+        }
+    }
+}
+
+/// The anchor a source spells, if it is one: base64url-unpadded text
+/// decoding to a czd-sized value. A relative path that happens to look
+/// like one can be written `./name`.
+pub(crate) fn anchor(src: &str) -> Option<Anchor> {
+    if !src
+        .bytes()
+        .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_'))
+    {
+        return None;
+    }
+    Anchor::from_b64(src)
+        .ok()
+        .filter(|anchor| ANCHOR_LENGTHS.contains(&anchor.as_bytes().len()))
+}
+
+/// The scheme of a `scheme://…` source, as written.
+pub(crate) fn scheme(src: &str) -> Option<&str> {
+    src.split_once("://")
+        .map(|(scheme, _)| scheme)
+        .filter(|scheme| is_scheme(scheme))
+}
+
+/// RFC 3986 scheme: a letter, then letters, digits, `+`, `-` or `.`.
+fn is_scheme(s: &str) -> bool {
+    let mut chars = s.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+}
+
+/// Whether `src` is a Windows drive-letter (`C:\`, `C:/`) or UNC
+/// (`\\server\share`) path — checked before the SCP split, which would
+/// otherwise read `C:\atoms` as host `C`.
+fn is_windows_path(src: &str) -> bool {
+    let bytes = src.as_bytes();
+    let drive = bytes.len() >= 3
+        && bytes[0].is_ascii_alphabetic()
+        && bytes[1] == b':'
+        && matches!(bytes[2], b'\\' | b'/');
+    drive || src.starts_with(r"\\")
+}
+
+/// Whether a schemeless source's first segment reads as a host.
+fn is_host(first: &str) -> bool {
+    first.contains('.') && !first.starts_with('.')
+}
+
+/// Canonicalize a resolved source: lowercase the scheme and host, drop
+/// trailing slashes, and, for remote sources, a trailing `.git`.
+///
+/// Three shapes are recognised, mirroring how sources are written:
+/// `scheme://[userinfo@]host[:port]/path`, SCP-style `[user@]host:path`,
+/// and schemeless `host/path` (the first segment contains a `.`). Anything
+/// else is a local path: only trailing slashes are dropped, and a `.git`
+/// suffix — which names a bare repository on disk — is kept.
+pub(crate) fn canonical_source(src: &str) -> String {
+    match SourceKind::of(src) {
+        SourceKind::Url => {
+            let (scheme, rest) = src.split_once("://").expect("classified as a URL");
+            let scheme = scheme.to_ascii_lowercase();
+            let (authority, path) = split_at_slash(rest);
+            let remote = scheme != "file";
+            format!(
+                "{scheme}://{}{}",
+                lowercase_host(authority),
+                trim_path(path, remote)
+            )
+        },
+        SourceKind::Schemeless => {
+            let (host, path) = split_at_slash(src);
+            format!("{}{}", host.to_ascii_lowercase(), trim_path(path, true))
+        },
+        SourceKind::Scp => {
+            let (host, path) = scp_split(src).expect("classified as SCP");
+            format!("{}:{}", lowercase_host(host), trim_path(path, true))
+        },
+        SourceKind::AbsolutePath if is_windows_path(src) => trim_windows_path(src).to_owned(),
+        SourceKind::AbsolutePath | SourceKind::RelativePath => trim_path(src, false).to_owned(),
+    }
+}
+
+/// Write a Unicode host in ASCII: each label with a non-ASCII character
+/// is lowercased and Punycode-encoded with an `xn--` prefix
+/// (`bücher.example` → `xn--bcher-kva.example`). Userinfo, port and path
+/// are untouched, as are paths and all-ASCII hosts.
+///
+/// This is the Punycode step of IDNA, not the full UTS #46 mapping: hosts
+/// are expected in NFC, as typed on any current system. `None` if a label
+/// is too long to encode.
+pub(crate) fn ascii_host(src: &str) -> Option<Cow<'_, str>> {
+    let Some(range) = host_range(src).filter(|r| !src[r.clone()].is_ascii()) else {
+        return Some(Cow::Borrowed(src));
+    };
+    let mut labels = Vec::new();
+    for label in src[range.clone()].split('.') {
+        labels.push(if label.is_ascii() {
+            label.to_owned()
+        } else {
+            format!("xn--{}", crate::punycode::encode(&label.to_lowercase())?)
+        });
+    }
+    Some(Cow::Owned(format!(
+        "{}{}{}",
+        &src[..range.start],
+        labels.join("."),
+        &src[range.end..]
+    )))
+}
+
+/// Where the host lies in a remote source, without userinfo or port.
+fn host_range(src: &str) -> Option<Range<usize>> {
+    let (start, end) = match SourceKind::of(src) {
+        SourceKind::Url => {
+            let start = src.find("://")? + 3;
+            (start, start + split_at_slash(&src[start..]).0.len())
+        },
+        SourceKind::Schemeless => (0, split_at_slash(src).0.len()),
+        SourceKind::Scp => (0, src.find(':')?),
+        SourceKind::AbsolutePath | SourceKind::RelativePath => return None,
+    };
+    let host_start = src[start..end].rfind('@').map_or(start, |i| start + i + 1);
+    let host_end = src[host_start..end]
+        .find(':')
+        .map_or(end, |i| host_start + i);
+    Some(host_start..host_end)
+}
+
+/// Resolve a path source against `base_dir` and normalize it lexically —
+/// `.` segments dropped, `..` folded into its parent — without touching
+/// the filesystem, so the result does not depend on what exists on disk.
+///
+/// A Windows path is already absolute; off Windows, where `std::path`
+/// cannot split it, it is returned as written.
+pub(crate) fn absolute_path(src: &str, base_dir: &Path) -> PathBuf {
+    if !cfg!(windows) && is_windows_path(src) {
+        return PathBuf::from(src);
+    }
+    let mut out = PathBuf::new();
+    for component in base_dir.join(src).components() {
+        match component {
+            Component::CurDir => {},
+            Component::ParentDir => {
+                out.pop();
+            },
+            other => out.push(other),
+        }
+    }
+    out
+}
+
+/// Lowercase the host of `[userinfo@]host[:port]`, keeping the userinfo.
+fn lowercase_host(authority: &str) -> String {
+    match authority.rsplit_once('@') {
+        Some((userinfo, host)) => format!("{userinfo}@{}", host.to_ascii_lowercase()),
+        None => authority.to_ascii_lowercase(),
+    }
+}
+
+/// Split at the first `/`, keeping it on the right.
+fn split_at_slash(s: &str) -> (&str, &str) {
+    s.find('/').map_or((s, ""), |i| s.split_at(i))
+}
+
+/// Split SCP-style `[user@]host:path` — a `:` before any `/`.
+fn scp_split(src: &str) -> Option<(&str, &str)> {
+    let colon = src.find(':')?;
+    let slash = src.find('/').unwrap_or(src.len());
+    (colon > 0 && colon < slash).then(|| (&src[..colon], &src[colon + 1..]))
+}
+
+/// Drop trailing slashes and, for remote sources, one `.git` suffix.
+/// A path made only of slashes keeps one.
+fn trim_path(path: &str, remote: bool) -> &str {
+    let mut trimmed = path.trim_end_matches('/');
+    if remote && let Some(stripped) = trimmed.strip_suffix(".git") {
+        trimmed = stripped.trim_end_matches('/');
+    }
+    if trimmed.is_empty() && path.starts_with('/') {
+        return &path[..1];
+    }
+    trimmed
+}
+
+/// Drop trailing `\` and `/` from a Windows path, keeping the one after
+/// a drive letter (`C:\`).
+fn trim_windows_path(path: &str) -> &str {
+    let trimmed = path.trim_end_matches(['\\', '/']);
+    if trimmed.len() == 2 && trimmed.ends_with(':') {
+        return &path[..3];
+    }
+    trimmed
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remote_forms_fold_together() {
+        for src in [
+            "github.com/owner/repo",
+            "GitHub.com/owner/repo/",
+            "github.com/owner/repo.git",
+            "GITHUB.COM/owner/repo.git/",
+        ] {
+            assert_eq!(canonical_source(src), "github.com/owner/repo", "{src}");
+        }
+        assert_eq!(
+            canonical_source("HTTPS://User@Example.COM:8080/Repo.git/"),
+            "https://User@example.com:8080/Repo"
+        );
+        assert_eq!(
+            canonical_source("git@GitHub.com:Owner/Repo.git"),
+            "git@github.com:Owner/Repo"
+        );
+    }
+
+    #[test]
+    fn unicode_hosts_become_punycode() {
+        for (src, ascii) in [
+            (
+                "https://Bücher.example/Repo",
+                "https://xn--bcher-kva.example/Repo",
+            ),
+            (
+                "https://me@bücher.example:8443/r",
+                "https://me@xn--bcher-kva.example:8443/r",
+            ),
+            (
+                "bücher.example/owner/repo",
+                "xn--bcher-kva.example/owner/repo",
+            ),
+            (
+                "git@例え.テスト:owner/repo",
+                "git@xn--r8jz45g.xn--zckzah:owner/repo",
+            ),
+            ("github.com/ünïcode/path", "github.com/ünïcode/path"),
+            ("./bücher", "./bücher"),
+        ] {
+            assert_eq!(ascii_host(src).unwrap(), ascii, "{src}");
+        }
+    }
+
+    #[test]
+    fn classifies_each_shape() {
+        for (src, kind) in [
+            ("https://example.com/repo", SourceKind::Url),
+            ("file:///srv/repo", SourceKind::Url),
+            ("github.com/owner/repo", SourceKind::Schemeless),
+            ("git@github.com:owner/repo", SourceKind::Scp),
+            ("host:repo", SourceKind::Scp),
+            ("/srv/repo", SourceKind::AbsolutePath),
+            (r"C:\atoms\set", SourceKind::AbsolutePath),
+            ("c:/atoms/set", SourceKind::AbsolutePath),
+            (r"\\server\share\set", SourceKind::AbsolutePath),
+            (r"..\sets\core", SourceKind::RelativePath),
+            ("../sets/core", SourceKind::RelativePath),
+            ("foo/bar", SourceKind::RelativePath),
+            ("", SourceKind::RelativePath),
+        ] {
+            assert_eq!(SourceKind::of(src), kind, "{src}");
+        }
+    }
+
+    #[test]
+    fn absolute_path_normalizes_lexically() {
+        let base = Path::new("/work/project");
+        assert_eq!(
+            absolute_path("../sets/core", base),
+            Path::new("/work/sets/core")
+        );
+        assert_eq!(
+            absolute_path("./a/./b/..", base),
+            Path::new("/work/project/a")
+        );
+        assert_eq!(absolute_path("", base), Path::new("/work/project"));
+        assert_eq!(absolute_path("/abs/x/../y", base), Path::new("/abs/y"));
+    }
+
+    #[test]
+    fn local_paths_keep_case_and_git_suffix() {
+        assert_eq!(canonical_source("/srv/Repo.git/"), "/srv/Repo.git");
+        assert_eq!(canonical_source("foo/Bar/"), "foo/Bar");
+        assert_eq!(canonical_source("/"), "/");
+        assert_eq!(canonical_source("file:///srv/r.git/"), "file:///srv/r.git");
+        assert_eq!(canonical_source(""), "");
+        assert_eq!(canonical_source(r"C:\Atoms\Set.git\"), r"C:\Atoms\Set.git");
+        assert_eq!(canonical_source(r"C:\"), r"C:\");
+        assert_eq!(canonical_source(r"\\Server\Share\"), r"\\Server\Share");
+    }
+}